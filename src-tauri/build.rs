@@ -1,7 +1,8 @@
 //! Build script for Omni-Glass Tauri app.
 //!
 //! Platform-conditional build:
-//! 1. Tauri build (generates Tauri-specific code) — all platforms
+//! 1. Tauri build (generates Tauri-specific code, plus the permissions of
+//!    the app-local "agent" plugin) — all platforms
 //! 2. macOS: swift-bridge FFI glue, compile Swift OCR bridge, link frameworks
 //! 3. Windows: no extra build steps (windows-rs WinRT bindings are auto-generated)
 //!
@@ -14,8 +15,13 @@
 //! isn't a build-dependency on those platforms.
 
 fn main() {
-    // Phase 1: Tauri (all platforms)
-    tauri_build::build();
+    // Phase 1: Tauri (all platforms). The agent commands are registered by
+    // an app-local plugin (pipeline_agent::init), which needs its own ACL.
+    let agent = tauri_build::InlinedPlugin::new()
+        .commands(&["run_agent_command", "continue_agent", "cancel_agent"])
+        .default_permission(tauri_build::DefaultPermissionRule::AllowAllCommands);
+    tauri_build::try_build(tauri_build::Attributes::new().plugin("agent", agent))
+        .expect("Tauri build failed");
 
     // Phase 2: macOS-only Swift OCR bridge
     #[cfg(target_os = "macos")]
@@ -0,0 +1,264 @@
+//! Hash chain — pure logic for sealing and verifying audit entries.
+//!
+//! Each entry stores the hash of its predecessor, and its own hash covers
+//! every field plus that link. Editing, reordering, or deleting an entry
+//! breaks the chain at that point. Truncating the tail is caught by the
+//! separately stored `ChainHead` anchor.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// `prev_hash` of the very first entry in a log.
+pub const GENESIS_HASH: &str = "sha256:genesis";
+
+/// What kind of event an audit entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A shell command run via `run_confirmed_command` (or blocked there).
+    Command,
+    /// A `tools/call` dispatched to a plugin's MCP server.
+    PluginCall,
+    /// A user approval or denial of a plugin.
+    PluginApproval,
+    /// A file written on the user's behalf.
+    FileWrite,
+    /// A plugin's `sampling/createMessage` request sent to the LLM provider.
+    PluginSampling,
+    /// A plugin installed, upgraded or uninstalled through the installer.
+    PluginInstall,
+    /// A `tools/call` received by Omni-Glass's own MCP server (`mcp_server`).
+    ServerCall,
+}
+
+/// One sealed line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Position in the chain, starting at 0.
+    pub seq: u64,
+    /// Unix timestamp (seconds) when the entry was recorded.
+    pub timestamp: u64,
+    pub kind: AuditKind,
+    /// Primary subject: the command, "plugin_id:tool", plugin id, or file path.
+    pub subject: String,
+    /// Event-specific structured details.
+    pub details: serde_json::Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// The last sealed entry, persisted outside the log to detect truncation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainHead {
+    pub seq: u64,
+    pub hash: String,
+}
+
+/// Outcome of verifying a log against its head anchor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Verification {
+    pub valid: bool,
+    pub entry_count: usize,
+    /// Sequence number of the first entry that failed verification.
+    pub first_invalid_seq: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Verification {
+    fn ok(entry_count: usize) -> Self {
+        Self {
+            valid: true,
+            entry_count,
+            first_invalid_seq: None,
+            error: None,
+        }
+    }
+
+    pub fn failed(entry_count: usize, seq: Option<u64>, error: String) -> Self {
+        Self {
+            valid: false,
+            entry_count,
+            first_invalid_seq: seq,
+            error: Some(error),
+        }
+    }
+}
+
+/// Build the next entry in the chain after `prev` (or the genesis entry).
+pub fn seal(
+    prev: Option<&ChainHead>,
+    timestamp: u64,
+    kind: AuditKind,
+    subject: &str,
+    details: serde_json::Value,
+) -> AuditEntry {
+    let (seq, prev_hash) = match prev {
+        Some(head) => (head.seq + 1, head.hash.clone()),
+        None => (0, GENESIS_HASH.to_string()),
+    };
+    let mut entry = AuditEntry {
+        seq,
+        timestamp,
+        kind,
+        subject: subject.to_string(),
+        details,
+        prev_hash,
+        hash: String::new(),
+    };
+    entry.hash = compute_hash(&entry);
+    entry
+}
+
+/// SHA-256 over every field except `hash` itself.
+pub fn compute_hash(entry: &AuditEntry) -> String {
+    let payload = serde_json::json!([
+        entry.seq,
+        entry.timestamp,
+        entry.kind,
+        entry.subject,
+        entry.details,
+        entry.prev_hash,
+    ]);
+    let digest = Sha256::digest(payload.to_string().as_bytes());
+    format!("sha256:{:x}", digest)
+}
+
+impl From<&AuditEntry> for ChainHead {
+    fn from(entry: &AuditEntry) -> Self {
+        ChainHead {
+            seq: entry.seq,
+            hash: entry.hash.clone(),
+        }
+    }
+}
+
+/// Walk the chain and check every link, then compare the tail to `head`.
+///
+/// Detects edited entries (hash mismatch), deleted or reordered entries
+/// (broken `prev_hash` link or sequence gap), and tail truncation (last
+/// entry does not match the head anchor).
+pub fn verify(entries: &[AuditEntry], head: Option<&ChainHead>) -> Verification {
+    let mut expected_prev = GENESIS_HASH.to_string();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let fail = |msg: String| Verification::failed(entries.len(), Some(entry.seq), msg);
+        if entry.seq != index as u64 {
+            return fail(format!("expected seq {}, found {}", index, entry.seq));
+        }
+        if entry.prev_hash != expected_prev {
+            return fail(format!(
+                "entry {} does not link to its predecessor",
+                entry.seq
+            ));
+        }
+        if compute_hash(entry) != entry.hash {
+            return fail(format!(
+                "entry {} was modified after it was written",
+                entry.seq
+            ));
+        }
+        expected_prev = entry.hash.clone();
+    }
+
+    match (entries.last(), head) {
+        (None, None) => Verification::ok(0),
+        (Some(_), None) => {
+            Verification::failed(entries.len(), None, "head anchor is missing".to_string())
+        }
+        (None, Some(h)) => Verification::failed(
+            0,
+            Some(0),
+            format!("log is empty but head records {} entries", h.seq + 1),
+        ),
+        (Some(last), Some(h)) if last.seq != h.seq || last.hash != h.hash => Verification::failed(
+            entries.len(),
+            Some(last.seq + 1),
+            format!(
+                "log ends at entry {} but head records entry {} (truncated?)",
+                last.seq, h.seq
+            ),
+        ),
+        (Some(_), Some(_)) => Verification::ok(entries.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_chain(n: usize) -> (Vec<AuditEntry>, ChainHead) {
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        for i in 0..n {
+            let prev = entries.last().map(ChainHead::from);
+            entries.push(seal(
+                prev.as_ref(),
+                1_700_000_000 + i as u64,
+                AuditKind::Command,
+                &format!("echo {}", i),
+                serde_json::json!({"status": "success"}),
+            ));
+        }
+        let head = ChainHead::from(entries.last().unwrap());
+        (entries, head)
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let (entries, head) = build_chain(5);
+        let v = verify(&entries, Some(&head));
+        assert!(v.valid, "{:?}", v.error);
+        assert_eq!(v.entry_count, 5);
+    }
+
+    #[test]
+    fn empty_log_without_head_is_valid() {
+        assert!(verify(&[], None).valid);
+    }
+
+    #[test]
+    fn detects_edited_entry() {
+        let (mut entries, head) = build_chain(4);
+        entries[2].subject = "rm -rf ~/evidence".to_string();
+        let v = verify(&entries, Some(&head));
+        assert!(!v.valid);
+        assert_eq!(v.first_invalid_seq, Some(2));
+    }
+
+    #[test]
+    fn detects_deleted_entry() {
+        let (mut entries, head) = build_chain(4);
+        entries.remove(1);
+        let v = verify(&entries, Some(&head));
+        assert!(!v.valid);
+        assert_eq!(v.first_invalid_seq, Some(2));
+    }
+
+    #[test]
+    fn detects_tail_truncation() {
+        let (mut entries, head) = build_chain(4);
+        entries.truncate(2);
+        let v = verify(&entries, Some(&head));
+        assert!(!v.valid);
+        assert!(v.error.unwrap().contains("truncated"));
+    }
+
+    #[test]
+    fn detects_rehashed_edit() {
+        // An attacker who recomputes the edited entry's hash still breaks the next link.
+        let (mut entries, head) = build_chain(3);
+        entries[1].details = serde_json::json!({"status": "failed"});
+        entries[1].hash = compute_hash(&entries[1]);
+        let v = verify(&entries, Some(&head));
+        assert!(!v.valid);
+        assert_eq!(v.first_invalid_seq, Some(2));
+    }
+
+    #[test]
+    fn missing_head_is_reported() {
+        let (entries, _) = build_chain(2);
+        assert!(!verify(&entries, None).valid);
+    }
+}
@@ -0,0 +1,94 @@
+//! Tauri commands for querying and verifying the audit log.
+
+use crate::audit::chain::{AuditEntry, AuditKind, Verification};
+use crate::audit::store;
+
+/// Entries returned by `get_audit_log` when no limit is given.
+const DEFAULT_LIMIT: usize = 200;
+
+/// Return the most recent audit entries, oldest first.
+///
+/// `kind` filters by event kind (`command`, `plugin_call`, `plugin_approval`,
+/// `file_write`, `plugin_sampling`, `plugin_install`, `server_call`); `limit` caps how many of the newest matches are returned.
+#[tauri::command]
+pub fn get_audit_log(
+    limit: Option<usize>,
+    kind: Option<String>,
+) -> Result<Vec<AuditEntry>, String> {
+    let kind = kind.map(|k| parse_kind(&k)).transpose()?;
+    let dir = store::audit_dir().ok_or("Could not find config directory")?;
+    let entries = store::read_entries(&dir)?;
+    Ok(select_recent(entries, kind, limit.unwrap_or(DEFAULT_LIMIT)))
+}
+
+/// Verify the audit log's hash chain and head anchor.
+#[tauri::command]
+pub fn verify_audit_log() -> Result<Verification, String> {
+    let dir = store::audit_dir().ok_or("Could not find config directory")?;
+    let result = store::verify(&dir);
+    if !result.valid {
+        log::warn!(
+            "[AUDIT] Verification failed: {}",
+            result.error.as_deref().unwrap_or("unknown")
+        );
+    }
+    Ok(result)
+}
+
+fn parse_kind(raw: &str) -> Result<AuditKind, String> {
+    serde_json::from_value(serde_json::Value::String(raw.to_string()))
+        .map_err(|_| format!("Unknown audit kind: {}", raw))
+}
+
+fn select_recent(
+    entries: Vec<AuditEntry>,
+    kind: Option<AuditKind>,
+    limit: usize,
+) -> Vec<AuditEntry> {
+    let matching: Vec<AuditEntry> = entries
+        .into_iter()
+        .filter(|e| kind.is_none_or(|k| e.kind == k))
+        .collect();
+    let skip = matching.len().saturating_sub(limit);
+    matching.into_iter().skip(skip).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::chain::{seal, ChainHead};
+
+    fn sample() -> Vec<AuditEntry> {
+        let kinds = [
+            AuditKind::Command,
+            AuditKind::PluginCall,
+            AuditKind::Command,
+        ];
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        for (i, kind) in kinds.iter().enumerate() {
+            let prev = entries.last().map(ChainHead::from);
+            entries.push(seal(
+                prev.as_ref(),
+                i as u64,
+                *kind,
+                "x",
+                serde_json::json!({}),
+            ));
+        }
+        entries
+    }
+
+    #[test]
+    fn select_recent_filters_and_limits() {
+        let picked = select_recent(sample(), Some(AuditKind::Command), 1);
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].seq, 2);
+        assert_eq!(select_recent(sample(), None, 10).len(), 3);
+    }
+
+    #[test]
+    fn parse_kind_accepts_snake_case() {
+        assert_eq!(parse_kind("plugin_call").unwrap(), AuditKind::PluginCall);
+        assert!(parse_kind("bogus").is_err());
+    }
+}
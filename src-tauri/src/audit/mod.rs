@@ -0,0 +1,105 @@
+//! Audit log — tamper-evident record of every side effect Omni-Glass performs.
+//!
+//! Executed (and blocked) shell commands, plugin tool calls, plugin approval
+//! decisions, file writes, plugin sampling requests, and calls to Omni-Glass's own MCP
+//! server are appended to a hash-chained JSONL log.
+//! Recording never fails the caller: I/O errors are logged and swallowed so
+//! an unwritable config dir can't block the user's action.
+//! Each append syncs the log to disk, so async callers use `record_async`
+//! to keep that off the runtime's worker threads.
+
+pub mod chain;
+pub mod commands;
+pub mod store;
+
+pub use chain::{AuditEntry, AuditKind, Verification};
+
+use crate::safety::redact::redact_sensitive_data;
+
+/// Long string values (command output, file contents) are cut to this many chars.
+const MAX_STRING_CHARS: usize = 2000;
+
+/// Append an event to the audit log in the default config directory.
+///
+/// String values in `details` are redacted and truncated before sealing.
+pub fn record(kind: AuditKind, subject: &str, details: serde_json::Value) {
+    let Some(dir) = store::audit_dir() else {
+        log::warn!("[AUDIT] No config directory — event not recorded");
+        return;
+    };
+    let subject = sanitize_str(subject);
+    if let Err(e) = store::append(&dir, kind, &subject, sanitize(details)) {
+        log::error!("[AUDIT] Failed to record {:?} '{}': {}", kind, subject, e);
+    }
+}
+
+/// `record` on the blocking pool, finished before this returns.
+pub async fn record_async(kind: AuditKind, subject: String, details: serde_json::Value) {
+    let recorded = tokio::task::spawn_blocking(move || record(kind, &subject, details)).await;
+    if let Err(e) = recorded {
+        log::error!("[AUDIT] Recording {:?} failed: {}", kind, e);
+    }
+}
+
+/// Record a file written on the user's behalf: path, size and content hash.
+pub fn record_file_write(path: &str, content: &[u8]) {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(content);
+    record(
+        AuditKind::FileWrite,
+        path,
+        serde_json::json!({
+            "bytes": content.len(),
+            "sha256": format!("sha256:{:x}", digest),
+        }),
+    );
+}
+
+/// Recursively redact and truncate every string in a JSON value.
+pub fn sanitize(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(s) => Value::String(sanitize_str(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(sanitize).collect()),
+        Value::Object(map) => {
+            Value::Object(map.into_iter().map(|(k, v)| (k, sanitize(v))).collect())
+        }
+        other => other,
+    }
+}
+
+fn sanitize_str(s: &str) -> String {
+    let cleaned = redact_sensitive_data(s).cleaned_text;
+    if cleaned.chars().count() <= MAX_STRING_CHARS {
+        return cleaned;
+    }
+    let mut cut: String = cleaned.chars().take(MAX_STRING_CHARS).collect();
+    cut.push_str("…[truncated]");
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_redacts_nested_strings() {
+        let details = serde_json::json!({
+            "arguments": {"token": "sk-ant-REDACTED"},
+            "exitCode": 0,
+        });
+        let out = sanitize(details);
+        assert_eq!(out["arguments"]["token"], "[REDACTED:api_key]");
+        assert_eq!(out["exitCode"], 0);
+    }
+
+    #[test]
+    fn sanitize_truncates_long_strings() {
+        let out = sanitize_str(&"a".repeat(MAX_STRING_CHARS + 50));
+        assert!(out.ends_with("…[truncated]"));
+        assert_eq!(
+            out.chars().count(),
+            MAX_STRING_CHARS + "…[truncated]".chars().count()
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Append-only audit log storage.
+//!
+//! Entries are stored as JSON lines in `~/.config/omni-glass/audit-log.jsonl`
+//! (macOS: `~/Library/Application Support/omni-glass/audit-log.jsonl`).
+//! The hash of the last sealed entry is kept in `audit-log.head` so that
+//! deleting lines from the end of the log is detectable.
+
+use crate::audit::chain::{self, AuditEntry, AuditKind, ChainHead, Verification};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const LOG_FILE: &str = "audit-log.jsonl";
+const HEAD_FILE: &str = "audit-log.head";
+
+/// Serializes appends so concurrent commands can't fork the chain.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Directory holding the audit log and its head anchor.
+pub fn audit_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass"))
+}
+
+/// Seal and append one entry to the log in `dir`, then advance the head.
+///
+/// The new entry links to the head anchor rather than the last line of the
+/// file, so a truncated log stays visibly broken after further appends.
+pub fn append(
+    dir: &Path,
+    kind: AuditKind,
+    subject: &str,
+    details: serde_json::Value,
+) -> Result<AuditEntry, String> {
+    let _guard = APPEND_LOCK.lock().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create audit dir: {}", e))?;
+
+    let prev = match load_head(dir) {
+        Some(head) => Some(head),
+        None => read_entries(dir)?.last().map(ChainHead::from),
+    };
+    let entry = chain::seal(prev.as_ref(), unix_now(), kind, subject, details);
+
+    let mut line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(LOG_FILE))
+        .map_err(|e| format!("Failed to open audit log: {}", e))?;
+    file.write_all(line.as_bytes())
+        .and_then(|_| file.sync_data())
+        .map_err(|e| format!("Failed to write audit log: {}", e))?;
+
+    save_head(dir, &ChainHead::from(&entry))?;
+    Ok(entry)
+}
+
+/// Read every entry from the log in `dir`. A missing log is an empty log.
+///
+/// Fails on the first line that isn't a valid entry, naming the line number.
+pub fn read_entries(dir: &Path) -> Result<Vec<AuditEntry>, String> {
+    let raw = match std::fs::read_to_string(dir.join(LOG_FILE)) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read audit log: {}", e)),
+    };
+
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("Audit log line {} is not a valid entry: {}", i + 1, e))
+        })
+        .collect()
+}
+
+/// Verify the whole log in `dir` against its head anchor.
+pub fn verify(dir: &Path) -> Verification {
+    match read_entries(dir) {
+        Ok(entries) => chain::verify(&entries, load_head(dir).as_ref()),
+        Err(e) => Verification::failed(0, None, e),
+    }
+}
+
+fn load_head(dir: &Path) -> Option<ChainHead> {
+    let raw = std::fs::read_to_string(dir.join(HEAD_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Write the head via a temp file + rename so it is never half-written.
+fn save_head(dir: &Path, head: &ChainHead) -> Result<(), String> {
+    let json = serde_json::to_string(head).map_err(|e| e.to_string())?;
+    let tmp = dir.join(format!("{}.tmp", HEAD_FILE));
+    std::fs::write(&tmp, json)
+        .and_then(|_| std::fs::rename(&tmp, dir.join(HEAD_FILE)))
+        .map_err(|e| format!("Failed to update audit head: {}", e))
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("og-audit-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn append_n(dir: &Path, n: usize) {
+        for i in 0..n {
+            append(
+                dir,
+                AuditKind::FileWrite,
+                &format!("file{}.csv", i),
+                serde_json::json!({"bytes": i}),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn appended_entries_verify() {
+        let dir = fresh_dir("roundtrip");
+        append_n(&dir, 3);
+        let entries = read_entries(&dir).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].seq, 2);
+        assert!(verify(&dir).valid);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detects_edit_on_disk() {
+        let dir = fresh_dir("edit");
+        append_n(&dir, 3);
+        let path = dir.join(LOG_FILE);
+        let raw = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, raw.replace("file1.csv", "other.csv")).unwrap();
+        let v = verify(&dir);
+        assert!(!v.valid);
+        assert_eq!(v.first_invalid_seq, Some(1));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn truncation_survives_later_appends() {
+        let dir = fresh_dir("truncate");
+        append_n(&dir, 3);
+        let path = dir.join(LOG_FILE);
+        let raw = std::fs::read_to_string(&path).unwrap();
+        let first_line = raw.lines().next().unwrap();
+        std::fs::write(&path, format!("{}\n", first_line)).unwrap();
+        assert!(!verify(&dir).valid);
+
+        // A new append links to the head anchor, so the gap stays visible.
+        append_n(&dir, 1);
+        assert!(!verify(&dir).valid);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn garbage_line_is_reported() {
+        let dir = fresh_dir("garbage");
+        append_n(&dir, 1);
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join(LOG_FILE))
+            .unwrap();
+        writeln!(f, "not json").unwrap();
+        let v = verify(&dir);
+        assert!(!v.valid);
+        assert!(v.error.unwrap().contains("line 2"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,214 @@
+//! Headless command-line front end for the snip-to-action pipeline.
+//!
+//! OCRs image files (or reads text from stdin), classifies them, and
+//! optionally executes an action, printing one JSON `SnipReport` per input.
+//! No display is needed, so it runs on CI and over SSH. `mcp` serves the
+//! same pipeline to MCP hosts (see `mcp_server`); `test-plugin` runs one
+//! plugin through `mcp::harness`.
+//!
+//! Usage:
+//!   omni-glass-cli [OPTIONS] <INPUT>...
+//!   omni-glass-cli validate <PLUGIN_DIR>...
+//!   omni-glass-cli mcp [--socket <PATH>] [--allow-capture]
+//!   omni-glass-cli test-plugin <PLUGIN_DIR> [--tool <NAME> (--args <JSON> | --text <TEXT>)]
+//!
+//! Examples:
+//!   omni-glass-cli screenshots/*.png
+//!   pbpaste | omni-glass-cli --action explain_error -
+//!   omni-glass-cli validate plugins/my-plugin
+//!   omni-glass-cli mcp --socket ~/.omni-glass/mcp.sock
+//!   omni-glass-cli test-plugin . --tool echo_text --args '{"text":"hi"}'
+
+mod args;
+
+use args::{parse_args, CliArgs, USAGE};
+use omni_glass_lib::headless::{self, SnipReport};
+use omni_glass_lib::mcp::harness::{self, HarnessRequest};
+use omni_glass_lib::mcp::{self, manifest_check, ToolRegistry};
+use omni_glass_lib::mcp_server::{self, ServerContext, ServerPolicy};
+use omni_glass_lib::ocr::RecognitionLevel;
+use std::io::Read;
+use std::path::Path;
+
+/// Extract text and OCR confidence from one input.
+fn read_input(input: &str, level: RecognitionLevel) -> Result<(String, f64), String> {
+    if input == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        // Piped text is exact, not recognized.
+        return Ok((text, 1.0));
+    }
+    headless::ocr_image(Path::new(input), level)
+}
+
+async fn process(
+    args: &CliArgs,
+    registry: &ToolRegistry,
+    input: &str,
+) -> Result<SnipReport, String> {
+    let level = if args.accurate {
+        RecognitionLevel::Accurate
+    } else {
+        RecognitionLevel::Fast
+    };
+    let (text, confidence) = read_input(input, level)?;
+    let source = if input == "-" { "stdin" } else { input };
+    let mut report = headless::analyze(registry, source, &text, confidence).await;
+    if let Some(action_id) = &args.action {
+        headless::run_action(registry, &mut report, action_id).await;
+    }
+    Ok(report)
+}
+
+/// Check each plugin directory's manifest, printing every problem.
+fn validate(dirs: &[String]) -> i32 {
+    let mut exit_code = 0;
+    for dir in dirs {
+        match manifest_check::check_manifest(Path::new(dir)) {
+            Ok(manifest) => println!("{}: ok ({} {})", dir, manifest.id, manifest.version),
+            Err(errors) => {
+                println!("{}: {} problem(s)", dir, errors.len());
+                for error in errors {
+                    println!("  {}", error);
+                }
+                exit_code = 1;
+            }
+        }
+    }
+    exit_code
+}
+
+/// Built-in actions plus, unless `--no-plugins`, approved plugins.
+async fn build_registry(args: &CliArgs) -> ToolRegistry {
+    let registry = ToolRegistry::new();
+    mcp::builtins::register_builtins(&registry).await;
+    if !args.no_plugins {
+        // Only already-approved plugins load; nothing is approved headlessly.
+        let pending = mcp::loader::PendingApprovals::new();
+        mcp::loader::load_plugins(&registry, &pending).await;
+    }
+    registry
+}
+
+/// Serve MCP until stdin closes (stdio) or the process is stopped (socket).
+async fn serve_mcp(args: CliArgs) -> i32 {
+    let ctx = std::sync::Arc::new(ServerContext {
+        registry: build_registry(&args).await,
+        policy: ServerPolicy {
+            allow_capture: args.allow_capture,
+        },
+    });
+    let served = match &args.socket {
+        Some(path) => {
+            let path = mcp::runtime::expand_home(path);
+            mcp_server::serve_socket(ctx.clone(), Path::new(&path)).await
+        }
+        None => mcp_server::serve_stdio(&ctx).await,
+    };
+    ctx.registry.shutdown_all().await;
+    match served {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("omni-glass-cli: {}", e);
+            1
+        }
+    }
+}
+
+/// Run one plugin through the harness and print its report.
+async fn test_plugin(args: &CliArgs) -> Result<bool, String> {
+    let arguments = args
+        .tool_args
+        .as_deref()
+        .map(|json| serde_json::from_str(json).map_err(|e| format!("--args is not JSON: {}", e)))
+        .transpose()?;
+    let text = match args.text.as_deref() {
+        Some("-") => Some(read_input("-", RecognitionLevel::Fast)?.0),
+        other => other.map(str::to_string),
+    };
+    let request = HarnessRequest {
+        plugin_dir: args.inputs[0].clone().into(),
+        tool: args.tool.clone(),
+        arguments,
+        text,
+    };
+    let report = harness::run(&request).await?;
+    let json = if args.pretty {
+        serde_json::to_string_pretty(&report)
+    } else {
+        serde_json::to_string(&report)
+    };
+    println!("{}", json.map_err(|e| e.to_string())?);
+    Ok(report.passed())
+}
+
+async fn run(args: CliArgs) -> i32 {
+    if args.mcp {
+        return serve_mcp(args).await;
+    }
+    if args.test_plugin {
+        return match test_plugin(&args).await {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                eprintln!("omni-glass-cli: {}", e);
+                1
+            }
+        };
+    }
+    let registry = build_registry(&args).await;
+
+    let mut exit_code = 0;
+    for input in &args.inputs {
+        match process(&args, &registry, input).await {
+            Ok(report) => {
+                let json = if args.pretty {
+                    serde_json::to_string_pretty(&report)
+                } else {
+                    serde_json::to_string(&report)
+                };
+                println!(
+                    "{}",
+                    json.unwrap_or_else(|e| format!("{{\"error\":\"{}\"}}", e))
+                );
+            }
+            Err(e) => {
+                eprintln!("omni-glass-cli: {}: {}", input, e);
+                exit_code = 1;
+            }
+        }
+    }
+    registry.shutdown_all().await;
+    exit_code
+}
+
+fn main() {
+    env_logger::init();
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) if args.help => {
+            println!("{}", USAGE);
+            return;
+        }
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("omni-glass-cli: {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.validate {
+        std::process::exit(validate(&args.inputs));
+    }
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("omni-glass-cli: failed to start async runtime: {}", e);
+            std::process::exit(1);
+        }
+    };
+    std::process::exit(runtime.block_on(run(args)));
+}
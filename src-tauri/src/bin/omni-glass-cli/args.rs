@@ -0,0 +1,203 @@
+//! Command-line parsing for `omni-glass-cli`: the usage text, the parsed
+//! options, and which options each subcommand accepts.
+
+pub const USAGE: &str = "\
+Usage: omni-glass-cli [OPTIONS] <INPUT>...
+       omni-glass-cli validate <PLUGIN_DIR>...
+       omni-glass-cli mcp [--socket <PATH>] [--allow-capture] [--no-plugins]
+       omni-glass-cli test-plugin <PLUGIN_DIR> [--tool <NAME> (--args <JSON> | --text <TEXT>)]
+
+Runs OCR, classify and (optionally) one action, printing one JSON report
+per input. INPUT is an image file, or '-' to read text from stdin.
+
+'validate' checks each plugin's omni-glass.plugin.json and lists every
+problem found. Exits 1 if any manifest is invalid.
+
+'mcp' runs Omni-Glass as an MCP server on stdio, or on a Unix socket with
+--socket. Tools: capture_screen, ocr_image, classify_text, run_action.
+
+'test-plugin' starts the plugin in PLUGIN_DIR as the app would (no
+approval needed), lists its tools and, with --tool, calls one. Arguments
+are given with --args or built from OCR text with --text ('-' reads
+stdin). Prints a JSON report with timings and the plugin's logs. Exits 1
+if the plugin fails to start or the call returns an error.
+
+Options:
+  -a, --action <ID>   Execute this action on every input
+      --accurate      Use accurate OCR (slower)
+      --no-plugins    Don't load installed plugins
+      --pretty        Pretty-print JSON
+      --socket <PATH> mcp: listen on a Unix socket instead of stdio
+      --allow-capture mcp: let hosts capture the screen
+      --tool <NAME>   test-plugin: tool to call
+      --args <JSON>   test-plugin: arguments, sent as given
+      --text <TEXT>   test-plugin: OCR text for the args bridge
+  -h, --help          Show this help
+
+Environment:
+  ANTHROPIC_API_KEY   Required for classify and execute
+  ANTHROPIC_BASE_URL  Override the API host (e.g. a mock server)";
+
+/// Parsed command-line options.
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    pub inputs: Vec<String>,
+    pub action: Option<String>,
+    pub accurate: bool,
+    pub no_plugins: bool,
+    pub pretty: bool,
+    pub help: bool,
+    /// `validate` subcommand: `inputs` are plugin directories.
+    pub validate: bool,
+    /// `mcp` subcommand: serve MCP instead of processing inputs.
+    pub mcp: bool,
+    pub socket: Option<String>,
+    pub allow_capture: bool,
+    /// `test-plugin` subcommand: `inputs` is one plugin directory.
+    pub test_plugin: bool,
+    pub tool: Option<String>,
+    pub tool_args: Option<String>,
+    pub text: Option<String>,
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, String> {
+    let mut parsed = CliArgs::default();
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("validate") => parsed.validate = true,
+        Some("mcp") => parsed.mcp = true,
+        Some("test-plugin") => parsed.test_plugin = true,
+        _ => {}
+    }
+    if parsed.validate || parsed.mcp || parsed.test_plugin {
+        args.next();
+    }
+    while let Some(arg) = args.next() {
+        parse_arg(&mut parsed, arg, &mut args)?;
+    }
+    check_combination(&parsed)?;
+    Ok(parsed)
+}
+
+/// Apply one argument, taking its value from `rest` when it has one.
+fn parse_arg(
+    parsed: &mut CliArgs,
+    arg: String,
+    rest: &mut impl Iterator<Item = String>,
+) -> Result<(), String> {
+    let mut value = |error: &str| rest.next().ok_or_else(|| error.to_string());
+    match arg.as_str() {
+        "-a" | "--action" => parsed.action = Some(value("--action needs an action id")?),
+        "--accurate" => parsed.accurate = true,
+        "--no-plugins" => parsed.no_plugins = true,
+        "--pretty" => parsed.pretty = true,
+        "--socket" => parsed.socket = Some(value("--socket needs a path")?),
+        "--allow-capture" => parsed.allow_capture = true,
+        "--tool" => parsed.tool = Some(value("--tool needs a tool name")?),
+        "--args" => parsed.tool_args = Some(value("--args needs JSON")?),
+        "--text" => parsed.text = Some(value("--text needs text or '-'")?),
+        "-h" | "--help" => parsed.help = true,
+        flag if flag.starts_with('-') && flag != "-" => {
+            return Err(format!("Unknown option: {}", flag));
+        }
+        _ => parsed.inputs.push(arg),
+    }
+    Ok(())
+}
+
+/// Reject options that don't belong to the chosen subcommand.
+fn check_combination(parsed: &CliArgs) -> Result<(), String> {
+    if parsed.mcp {
+        if !parsed.inputs.is_empty() || parsed.action.is_some() {
+            return Err("mcp takes no inputs or --action".to_string());
+        }
+        return Ok(());
+    }
+    if parsed.socket.is_some() || parsed.allow_capture {
+        return Err("--socket and --allow-capture are for mcp only".to_string());
+    }
+    if parsed.test_plugin {
+        if parsed.inputs.len() != 1 || parsed.action.is_some() {
+            return Err("test-plugin takes one plugin directory".to_string());
+        }
+        return Ok(());
+    }
+    if parsed.tool.is_some() || parsed.tool_args.is_some() || parsed.text.is_some() {
+        return Err("--tool, --args and --text are for test-plugin only".to_string());
+    }
+    if parsed.inputs.is_empty() && !parsed.help {
+        return Err("No input given".to_string());
+    }
+    if parsed.validate && (parsed.action.is_some() || parsed.inputs.iter().any(|i| i == "-")) {
+        return Err("validate takes plugin directories only".to_string());
+    }
+    if parsed.inputs.iter().filter(|i| *i == "-").count() > 1 {
+        return Err("stdin ('-') can only be read once".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_inputs_and_flags() {
+        let args = parse(&["-a", "explain_error", "--pretty", "a.png", "-"]).unwrap();
+        assert_eq!(args.inputs, vec!["a.png", "-"]);
+        assert_eq!(args.action.as_deref(), Some("explain_error"));
+        assert!(args.pretty && !args.accurate && !args.no_plugins);
+    }
+
+    #[test]
+    fn rejects_bad_invocations() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--action"]).is_err());
+        assert!(parse(&["--bogus", "a.png"]).is_err());
+        assert!(parse(&["-", "-"]).is_err());
+        assert!(parse(&["--help"]).unwrap().help);
+    }
+
+    #[test]
+    fn parses_validate_subcommand() {
+        let args = parse(&["validate", "plugins/a", "plugins/b"]).unwrap();
+        assert!(args.validate);
+        assert_eq!(args.inputs, vec!["plugins/a", "plugins/b"]);
+        assert!(!parse(&["a.png", "validate"]).unwrap().validate);
+        assert!(parse(&["validate"]).is_err());
+        assert!(parse(&["validate", "-a", "x", "plugins/a"]).is_err());
+    }
+
+    #[test]
+    fn parses_mcp_subcommand() {
+        let args = parse(&["mcp"]).unwrap();
+        assert!(args.mcp && args.socket.is_none() && !args.allow_capture);
+        let args = parse(&["mcp", "--socket", "/tmp/og.sock", "--allow-capture"]).unwrap();
+        assert_eq!(args.socket.as_deref(), Some("/tmp/og.sock"));
+        assert!(args.allow_capture);
+        assert!(parse(&["mcp", "a.png"]).is_err());
+        assert!(parse(&["mcp", "--socket"]).is_err());
+        assert!(parse(&["--allow-capture", "a.png"]).is_err());
+    }
+
+    #[test]
+    fn parses_test_plugin_subcommand() {
+        let args = parse(&["test-plugin", "p", "--tool", "echo", "--args", "{}"]).unwrap();
+        assert!(args.test_plugin);
+        assert_eq!(args.inputs, vec!["p"]);
+        assert_eq!(args.tool.as_deref(), Some("echo"));
+        assert_eq!(args.tool_args.as_deref(), Some("{}"));
+        assert!(parse(&["test-plugin", "p", "--text", "-"])
+            .unwrap()
+            .text
+            .is_some());
+        assert!(parse(&["test-plugin"]).is_err());
+        assert!(parse(&["test-plugin", "p", "q"]).is_err());
+        assert!(parse(&["test-plugin", "p", "--tool"]).is_err());
+        assert!(parse(&["--tool", "echo", "a.png"]).is_err());
+    }
+}
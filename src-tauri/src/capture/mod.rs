@@ -6,33 +6,71 @@
 mod region;
 mod screenshot;
 
-pub use region::crop_to_png_bytes;
-pub use screenshot::capture_primary_monitor;
+pub use region::{crop_to_png_bytes, map_selection, Selection};
+pub use screenshot::{capture_all_monitors, capture_window, list_windows, Capture, CaptureSource};
 
 use image::DynamicImage;
 use std::sync::Mutex;
 
-/// Info needed by the overlay to display the screenshot.
+/// Info needed by an overlay to display its screenshot.
 /// Stored in CaptureState so the overlay can fetch it via a Tauri command
 /// (eliminates the race condition where an event fires before JS loads).
 #[derive(Clone, serde::Serialize)]
 pub struct CaptureInfo {
     pub image_path: String,
     pub click_epoch_ms: f64,
+    /// Index into `CaptureState::captures` — one overlay per capture.
+    pub index: usize,
+    pub source: CaptureSource,
 }
 
-/// Thread-safe storage for the current full-screen capture.
-/// Held between capture and crop so the user can draw a rectangle.
+/// One captured monitor or window, held until the user finishes selecting.
+pub struct StoredCapture {
+    pub image: DynamicImage,
+    pub info: CaptureInfo,
+}
+
+/// Thread-safe storage for the current captures (one per monitor, or a
+/// single window). Held between capture and crop so the user can draw a
+/// rectangle on any of them.
 pub struct CaptureState {
-    pub screenshot: Mutex<Option<DynamicImage>>,
-    pub capture_info: Mutex<Option<CaptureInfo>>,
+    pub captures: Mutex<Vec<StoredCapture>>,
 }
 
 impl CaptureState {
     pub fn new() -> Self {
         Self {
-            screenshot: Mutex::new(None),
-            capture_info: Mutex::new(None),
+            captures: Mutex::new(Vec::new()),
         }
     }
 }
+
+/// Window label of the overlay showing capture `index`.
+///
+/// The first overlay keeps the plain "overlay" label so single-monitor
+/// behaviour is unchanged.
+pub fn overlay_label(index: usize) -> String {
+    if index == 0 {
+        "overlay".to_string()
+    } else {
+        format!("overlay-{}", index)
+    }
+}
+
+/// True for any overlay window label produced by `overlay_label`.
+pub fn is_overlay_label(label: &str) -> bool {
+    label == "overlay" || label.starts_with("overlay-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_labels_round_trip() {
+        assert_eq!(overlay_label(0), "overlay");
+        assert_eq!(overlay_label(2), "overlay-2");
+        assert!(is_overlay_label(&overlay_label(3)));
+        assert!(!is_overlay_label("action-menu"));
+    }
+}
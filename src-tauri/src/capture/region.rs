@@ -49,6 +49,74 @@ pub fn crop_to_png_bytes(
     Ok(png_bytes)
 }
 
+/// A selection rectangle in the overlay's CSS (logical) pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogicalRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A user's selection as sent by an overlay: which capture it was drawn on,
+/// the rectangle in CSS pixels, and the overlay's CSS viewport size.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Selection {
+    #[serde(default)]
+    pub capture_index: usize,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+}
+
+impl Selection {
+    pub fn rect(&self) -> LogicalRect {
+        LogicalRect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn viewport(&self) -> (f64, f64) {
+        (self.viewport_width, self.viewport_height)
+    }
+}
+
+/// Maps an overlay selection to pixel coordinates in the captured image.
+///
+/// The scale is derived from the image size over the overlay viewport size
+/// rather than a global DPI, so each monitor's own scale factor is honoured
+/// on mixed-DPI setups. The result is clamped to the image bounds.
+pub fn map_selection(
+    rect: LogicalRect,
+    viewport: (f64, f64),
+    image_size: (u32, u32),
+) -> Result<(u32, u32, u32, u32), CropError> {
+    let (vw, vh) = viewport;
+    if vw <= 0.0 || vh <= 0.0 || rect.width <= 0.0 || rect.height <= 0.0 {
+        return Err(CropError::ZeroDimension);
+    }
+    let (iw, ih) = (image_size.0 as f64, image_size.1 as f64);
+    let (sx, sy) = (iw / vw, ih / vh);
+
+    let left = (rect.x * sx).round().clamp(0.0, iw);
+    let top = (rect.y * sy).round().clamp(0.0, ih);
+    let right = ((rect.x + rect.width) * sx).round().clamp(0.0, iw);
+    let bottom = ((rect.y + rect.height) * sy).round().clamp(0.0, ih);
+
+    let (width, height) = ((right - left) as u32, (bottom - top) as u32);
+    if width == 0 || height == 0 {
+        return Err(CropError::ZeroDimension);
+    }
+    Ok((left as u32, top as u32, width, height))
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CropError {
     #[error("Crop rectangle has zero width or height")]
@@ -90,6 +158,36 @@ mod tests {
         assert!(matches!(result, Err(CropError::ZeroDimension)));
     }
 
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> LogicalRect {
+        LogicalRect { x, y, width, height }
+    }
+
+    #[test]
+    fn map_selection_retina() {
+        // 1440x900 points captured at 2880x1800 pixels
+        let mapped = map_selection(rect(10.0, 20.0, 100.0, 50.0), (1440.0, 900.0), (2880, 1800));
+        assert_eq!(mapped.unwrap(), (20, 40, 200, 100));
+    }
+
+    #[test]
+    fn map_selection_fractional_scale() {
+        // 150% scaling on a secondary display: 1280x720 logical, 1920x1080 pixels
+        let mapped = map_selection(rect(100.0, 100.0, 200.0, 100.0), (1280.0, 720.0), (1920, 1080));
+        assert_eq!(mapped.unwrap(), (150, 150, 300, 150));
+    }
+
+    #[test]
+    fn map_selection_clamps_to_image() {
+        let mapped = map_selection(rect(-5.0, 90.0, 50.0, 50.0), (100.0, 100.0), (100, 100));
+        assert_eq!(mapped.unwrap(), (0, 90, 45, 10));
+    }
+
+    #[test]
+    fn map_selection_outside_image_fails() {
+        let mapped = map_selection(rect(120.0, 0.0, 10.0, 10.0), (100.0, 100.0), (100, 100));
+        assert!(matches!(mapped, Err(CropError::ZeroDimension)));
+    }
+
     #[test]
     fn crop_out_of_bounds_fails() {
         let img = DynamicImage::ImageRgba8(RgbaImage::new(100, 100));
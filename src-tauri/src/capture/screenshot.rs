@@ -1,34 +1,155 @@
-//! Full-screen capture using the `xcap` crate.
+//! Monitor and window capture using the `xcap` crate.
 //!
 //! This is the infrastructure layer — it talks to the OS.
 //! If xcap fails on macOS 26.3, this file is the one we replace
 //! with a ScreenCaptureKit FFI implementation.
 
 use image::DynamicImage;
-use xcap::Monitor;
+use serde::Serialize;
+use xcap::{Monitor, Window};
 
-/// Captures the primary monitor's screen as a `DynamicImage`.
+/// Where a capture came from and where it sits on the desktop.
 ///
-/// Returns the full-screen screenshot including all pixels.
-/// The caller is responsible for cropping to the user's selection.
-pub fn capture_primary_monitor() -> Result<DynamicImage, CaptureError> {
-    let monitors = Monitor::all().map_err(|e| CaptureError::MonitorEnumeration(e.to_string()))?;
+/// `x`/`y`/`width`/`height` are as reported by xcap: points on macOS,
+/// physical pixels elsewhere. Use `logical_origin()` for window placement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CaptureSource {
+    /// "monitor" or "window".
+    pub kind: String,
+    pub id: u32,
+    /// Monitor name, or "App — Title" for windows.
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f32,
+}
+
+impl CaptureSource {
+    /// Top-left corner in logical (Tauri window) coordinates.
+    pub fn logical_origin(&self) -> (f64, f64) {
+        if cfg!(target_os = "macos") {
+            (self.x as f64, self.y as f64)
+        } else {
+            let scale = self.scale_factor.max(0.1) as f64;
+            (self.x as f64 / scale, self.y as f64 / scale)
+        }
+    }
+
+    /// Size in logical (Tauri window) coordinates.
+    pub fn logical_size(&self) -> (f64, f64) {
+        if cfg!(target_os = "macos") {
+            (self.width as f64, self.height as f64)
+        } else {
+            let scale = self.scale_factor.max(0.1) as f64;
+            (self.width as f64 / scale, self.height as f64 / scale)
+        }
+    }
+}
+
+/// A captured image plus the monitor or window it came from.
+pub struct Capture {
+    pub image: DynamicImage,
+    pub source: CaptureSource,
+}
+
+/// Captures every connected monitor, primary first.
+///
+/// Monitors that fail to capture are skipped with a warning; the call only
+/// fails if no monitor could be captured at all.
+pub fn capture_all_monitors() -> Result<Vec<Capture>, CaptureError> {
+    let mut monitors =
+        Monitor::all().map_err(|e| CaptureError::MonitorEnumeration(e.to_string()))?;
+    monitors.sort_by_key(|m| !m.is_primary().unwrap_or(false));
+
+    let mut captures = Vec::new();
+    for monitor in monitors {
+        match capture_monitor(&monitor) {
+            Ok(capture) => captures.push(capture),
+            Err(e) => log::warn!("[CAPTURE] Skipping monitor: {}", e),
+        }
+    }
+
+    if captures.is_empty() {
+        return Err(CaptureError::NoMonitor);
+    }
+    Ok(captures)
+}
+
+fn capture_monitor(monitor: &Monitor) -> Result<Capture, CaptureError> {
+    let image = monitor
+        .capture_image()
+        .map_err(|e| CaptureError::CaptureFailed(e.to_string()))?;
+    let source = CaptureSource {
+        kind: "monitor".to_string(),
+        id: monitor.id().unwrap_or(0),
+        name: monitor.name().unwrap_or_default(),
+        x: monitor.x().unwrap_or(0),
+        y: monitor.y().unwrap_or(0),
+        width: monitor.width().unwrap_or(image.width()),
+        height: monitor.height().unwrap_or(image.height()),
+        scale_factor: monitor.scale_factor().unwrap_or(1.0),
+    };
+    Ok(Capture {
+        image: DynamicImage::ImageRgba8(image),
+        source,
+    })
+}
 
-    let primary = monitors
+/// Lists windows that can be snipped: visible, titled, and not our own.
+pub fn list_windows() -> Result<Vec<CaptureSource>, CaptureError> {
+    let windows = Window::all().map_err(|e| CaptureError::WindowEnumeration(e.to_string()))?;
+    Ok(windows
+        .iter()
+        .filter(|w| !w.is_minimized().unwrap_or(true))
+        .filter(|w| w.width().unwrap_or(0) > 0 && w.height().unwrap_or(0) > 0)
+        .filter(|w| w.app_name().map(|a| a != "Omni-Glass").unwrap_or(false))
+        .filter_map(window_source)
+        .collect())
+}
+
+/// Captures a single window by id, including parts hidden behind others.
+pub fn capture_window(window_id: u32) -> Result<Capture, CaptureError> {
+    let windows = Window::all().map_err(|e| CaptureError::WindowEnumeration(e.to_string()))?;
+    let window = windows
         .into_iter()
-        .find(|m| m.is_primary().unwrap_or(false))
-        .or_else(|| {
-            // Fallback: if no monitor reports as primary, use the first one
-            let all = Monitor::all().ok()?;
-            all.into_iter().next()
-        })
-        .ok_or(CaptureError::NoPrimaryMonitor)?;
-
-    let image = primary
+        .find(|w| w.id().ok() == Some(window_id))
+        .ok_or(CaptureError::WindowNotFound(window_id))?;
+    let source = window_source(&window).ok_or(CaptureError::WindowNotFound(window_id))?;
+    let image = window
         .capture_image()
         .map_err(|e| CaptureError::CaptureFailed(e.to_string()))?;
+    Ok(Capture {
+        image: DynamicImage::ImageRgba8(image),
+        source,
+    })
+}
 
-    Ok(DynamicImage::ImageRgba8(image))
+fn window_source(window: &Window) -> Option<CaptureSource> {
+    let title = window.title().unwrap_or_default();
+    let app = window.app_name().unwrap_or_default();
+    if title.is_empty() && app.is_empty() {
+        return None;
+    }
+    let name = match (app.is_empty(), title.is_empty()) {
+        (false, false) => format!("{} — {}", app, title),
+        (false, true) => app,
+        _ => title,
+    };
+    Some(CaptureSource {
+        kind: "window".to_string(),
+        id: window.id().ok()?,
+        name,
+        x: window.x().unwrap_or(0),
+        y: window.y().unwrap_or(0),
+        width: window.width().unwrap_or(0),
+        height: window.height().unwrap_or(0),
+        scale_factor: window
+            .current_monitor()
+            .and_then(|m| m.scale_factor())
+            .unwrap_or(1.0),
+    })
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -36,8 +157,14 @@ pub enum CaptureError {
     #[error("Failed to enumerate monitors: {0}")]
     MonitorEnumeration(String),
 
-    #[error("No primary monitor found")]
-    NoPrimaryMonitor,
+    #[error("Failed to enumerate windows: {0}")]
+    WindowEnumeration(String),
+
+    #[error("No monitor could be captured")]
+    NoMonitor,
+
+    #[error("Window {0} not found")]
+    WindowNotFound(u32),
 
     #[error("Screen capture failed: {0}")]
     CaptureFailed(String),
@@ -0,0 +1,190 @@
+//! Capture Tauri commands and overlay window management.
+//!
+//! Handles:
+//! - Opening one overlay per captured monitor (or one over a captured window)
+//! - Closing every overlay when a snip finishes or is cancelled
+//! - Window capture mode: listing windows and snipping a specific one
+//! - Window picker lifecycle
+
+use crate::capture::{
+    self, Capture, CaptureInfo, CaptureSource, CaptureState, Selection, StoredCapture,
+};
+use image::DynamicImage;
+use tauri::{AppHandle, Manager};
+
+/// Store captures and open an overlay for each one.
+///
+/// Each screenshot is saved to its own temp PNG for the overlay to load via
+/// Tauri's asset protocol. Monitor overlays go fullscreen on their monitor;
+/// a window overlay is placed exactly over the captured window.
+pub fn open_overlays(
+    app: &AppHandle,
+    captures: Vec<Capture>,
+    click_epoch_ms: f64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    close_overlays(app);
+
+    let mut stored = Vec::with_capacity(captures.len());
+    for (index, capture) in captures.into_iter().enumerate() {
+        let temp_path = std::env::temp_dir().join(format!("omni-glass-capture-{}.png", index));
+        capture
+            .image
+            .save(&temp_path)
+            .map_err(|e| format!("PNG save failed: {}", e))?;
+        // Canonicalize to resolve /var → /private/var symlink on macOS.
+        let temp_path = std::fs::canonicalize(&temp_path).unwrap_or(temp_path);
+        stored.push(StoredCapture {
+            image: capture.image,
+            info: CaptureInfo {
+                image_path: temp_path.to_string_lossy().to_string(),
+                click_epoch_ms,
+                index,
+                source: capture.source,
+            },
+        });
+    }
+
+    let sources: Vec<CaptureSource> = stored.iter().map(|c| c.info.source.clone()).collect();
+    *app.state::<CaptureState>().captures.lock().unwrap() = stored;
+
+    for (index, source) in sources.iter().enumerate() {
+        build_overlay(app, index, source)?;
+    }
+    Ok(())
+}
+
+fn build_overlay(
+    app: &AppHandle,
+    index: usize,
+    source: &CaptureSource,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (x, y) = source.logical_origin();
+    let (width, height) = source.logical_size();
+    let is_monitor = source.kind == "monitor";
+
+    tauri::WebviewWindowBuilder::new(
+        app,
+        capture::overlay_label(index),
+        tauri::WebviewUrl::App("index.html".into()),
+    )
+    .position(x, y)
+    .inner_size(width, height)
+    .fullscreen(is_monitor)
+    .transparent(true)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .title("Omni-Glass Overlay")
+    .build()?;
+
+    log::info!(
+        "[CAPTURE] Overlay {} on {} '{}' at ({}, {}) {}x{} scale={}",
+        index, source.kind, source.name, x, y, width, height, source.scale_factor
+    );
+    Ok(())
+}
+
+/// Crop the capture a selection was drawn on.
+///
+/// The pixel scale is derived from the overlay's CSS viewport so each
+/// monitor's own DPI is honoured. Returns the cropped image and the
+/// monitor or window it came from.
+pub fn crop_selection(
+    state: &CaptureState,
+    selection: &Selection,
+) -> Result<(DynamicImage, CaptureSource), String> {
+    let captures = state.captures.lock().map_err(|e| e.to_string())?;
+    let capture = captures
+        .get(selection.capture_index)
+        .ok_or("No screenshot available — capture first")?;
+    let image = &capture.image;
+    let (x, y, width, height) = capture::map_selection(
+        selection.rect(),
+        selection.viewport(),
+        (image.width(), image.height()),
+    )
+    .map_err(|e| e.to_string())?;
+    log::info!(
+        "[CAPTURE] Selection on capture {} → pixels ({}, {}) {}x{} of {}x{}",
+        selection.capture_index,
+        x,
+        y,
+        width,
+        height,
+        image.width(),
+        image.height()
+    );
+    Ok((image.crop_imm(x, y, width, height), capture.info.source.clone()))
+}
+
+/// Close every open overlay window.
+pub fn close_overlays(app: &AppHandle) {
+    for (label, window) in app.webview_windows() {
+        if capture::is_overlay_label(&label) {
+            let _ = window.destroy();
+        }
+    }
+}
+
+/// Tauri command: list windows that can be snipped individually.
+#[tauri::command]
+pub fn list_capture_windows() -> Result<Vec<CaptureSource>, String> {
+    capture::list_windows().map_err(|e| e.to_string())
+}
+
+/// Tauri command: capture one window and open an overlay over it.
+///
+/// The window is captured directly, so parts hidden behind other windows
+/// are still visible in the snip.
+#[tauri::command]
+pub fn start_window_snip(app: AppHandle, window_id: u32) -> Result<(), String> {
+    if let Some(picker) = app.get_webview_window("window-picker") {
+        let _ = picker.close();
+    }
+    let click_epoch_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as f64;
+
+    let capture = capture::capture_window(window_id).map_err(|e| e.to_string())?;
+    log::info!(
+        "[CAPTURE] Window '{}' captured at {}x{}",
+        capture.source.name,
+        capture.image.width(),
+        capture.image.height()
+    );
+    open_overlays(&app, vec![capture], click_epoch_ms).map_err(|e| e.to_string())
+}
+
+/// Open (or focus) the window picker used by "Snip Window...".
+pub fn open_window_picker(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("window-picker") {
+        let _ = window.set_focus();
+        return;
+    }
+    match tauri::WebviewWindowBuilder::new(
+        app,
+        "window-picker",
+        tauri::WebviewUrl::App("window-picker.html".into()),
+    )
+    .title("Snip Window")
+    .inner_size(360.0, 420.0)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .center()
+    .build()
+    {
+        Ok(_) => log::info!("[CAPTURE] Window picker opened"),
+        Err(e) => log::error!("[CAPTURE] Failed to open window picker: {}", e),
+    }
+}
+
+/// Tauri command: close the window picker.
+#[tauri::command]
+pub fn close_window_picker(app: AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("window-picker") {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
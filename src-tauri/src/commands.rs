@@ -8,30 +8,30 @@
 use crate::capture::CaptureState;
 use crate::llm;
 use crate::mcp;
-use crate::safety;
 use tauri::Manager;
 
-/// Tauri command: crop the stored screenshot to the given rectangle.
+/// Tauri command: crop a stored capture to the given selection.
 ///
-/// Called by the frontend overlay when the user releases the mouse.
+/// The selection is in the overlay's CSS pixels and is mapped onto the
+/// capture's pixels using the overlay's viewport size.
 /// Returns base64-encoded PNG of the cropped region.
 #[tauri::command]
 pub fn crop_region(
     state: tauri::State<'_, CaptureState>,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+    selection: crate::capture::Selection,
 ) -> Result<String, String> {
     let start = std::time::Instant::now();
 
-    let guard = state.screenshot.lock().map_err(|e| e.to_string())?;
-    let screenshot = guard
-        .as_ref()
-        .ok_or("No screenshot available — capture first")?;
+    let (cropped, _source) = crate::capture_commands::crop_selection(&state, &selection)?;
 
-    let png_bytes = crate::capture::crop_to_png_bytes(screenshot, x, y, width, height)
-        .map_err(|e| e.to_string())?;
+    let png_bytes = crate::capture::crop_to_png_bytes(
+        &cropped,
+        0,
+        0,
+        cropped.width(),
+        cropped.height(),
+    )
+    .map_err(|e| e.to_string())?;
 
     let base64_png = base64::Engine::encode(
         &base64::engine::general_purpose::STANDARD,
@@ -40,8 +40,8 @@ pub fn crop_region(
 
     let crop_ms = start.elapsed().as_millis();
     log::info!(
-        "Cropped region ({}x{} at {},{}) in {}ms — {} bytes",
-        width, height, x, y, crop_ms, png_bytes.len()
+        "Cropped region ({}x{}) in {}ms — {} bytes",
+        cropped.width(), cropped.height(), crop_ms, png_bytes.len()
     );
 
     Ok(base64_png)
@@ -49,15 +49,17 @@ pub fn crop_region(
 
 /// Tauri command: get capture info (screenshot path + click timestamp).
 ///
-/// Called by the overlay on load. This replaces the event-based approach
-/// which raced — the event fired before JS was ready to listen.
+/// Called by each overlay on load with its capture index. This replaces the
+/// event-based approach which raced — the event fired before JS was ready.
 #[tauri::command]
 pub fn get_capture_info(
     state: tauri::State<'_, CaptureState>,
+    index: Option<usize>,
 ) -> Result<crate::capture::CaptureInfo, String> {
-    let guard = state.capture_info.lock().map_err(|e| e.to_string())?;
+    let guard = state.captures.lock().map_err(|e| e.to_string())?;
     guard
-        .clone()
+        .get(index.unwrap_or(0))
+        .map(|c| c.info.clone())
         .ok_or("No capture info available".to_string())
 }
 
@@ -88,12 +90,11 @@ pub fn copy_to_clipboard(text: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Tauri command: close the overlay and clean up capture state.
+/// Tauri command: close every overlay and clean up capture state.
 #[tauri::command]
 pub fn close_overlay(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("overlay") {
-        window.close().map_err(|e| e.to_string())?;
-    }
+    crate::capture_commands::close_overlays(&app);
+    app.state::<CaptureState>().captures.lock().map_err(|e| e.to_string())?.clear();
     Ok(())
 }
 
@@ -128,44 +129,24 @@ pub fn get_action_menu(
         .ok_or("No action menu available".to_string())
 }
 
+/// Tauri command: plugin actions whose manifest triggers fired for the
+/// current snip. The skeleton menu shows them before classification ends.
+#[tauri::command]
+pub fn get_triggered_actions(
+    state: tauri::State<'_, llm::ActionMenuState>,
+) -> Result<Vec<llm::types::Action>, String> {
+    let guard = state.triggered.lock().map_err(|e| e.to_string())?;
+    Ok(guard.clone())
+}
+
 /// Tauri command: run a confirmed shell command.
 ///
 /// Only called after the user explicitly clicks "Run" in the confirmation
-/// dialog. Runs the command via the default shell and returns its output.
+/// dialog. Runs the command via the default shell and returns its output;
+/// see `shell_command` for the checks and the time limit.
 #[tauri::command]
 pub async fn run_confirmed_command(command: String) -> Result<String, String> {
-    // Double-check safety before executing
-    let check = safety::command_check::is_command_safe(&command);
-    if !check.safe {
-        return Err(format!(
-            "Command blocked by safety layer: {}",
-            check.reason.unwrap_or_else(|| "Unknown".to_string())
-        ));
-    }
-
-    log::info!("[EXECUTE] Running confirmed command: {}", command);
-
-    let output = std::process::Command::new("sh")
-        .arg("-c")
-        .arg(&command)
-        .output()
-        .map_err(|e| format!("Failed to run command: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    if output.status.success() {
-        log::info!("[EXECUTE] Command succeeded");
-        Ok(stdout)
-    } else if !stdout.trim().is_empty() {
-        // Non-zero exit but stdout has content (e.g. `find` with permission errors).
-        // Return the valid output rather than discarding it.
-        log::info!("[EXECUTE] Command exited non-zero but produced output");
-        Ok(stdout)
-    } else {
-        log::warn!("[EXECUTE] Command failed: {}", stderr);
-        Err(format!("Command failed:\n{}", stderr))
-    }
+    crate::shell_command::run_shell_command(&command).await
 }
 
 /// Tauri command: summarize raw shell output into a human-readable answer.
@@ -216,7 +197,7 @@ pub async fn summarize_command_output(
 
     let client = reqwest::Client::new();
     let resp = client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(crate::llm::provider::anthropic_messages_url())
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
@@ -283,27 +264,6 @@ async fn summarize_via_local(
     }
 }
 
-/// Tauri command: write file content to the user's Desktop.
-///
-/// Used for export_csv and other file-generating actions.
-/// Validates filename, writes to Desktop directory.
-#[tauri::command]
-pub fn write_to_desktop(filename: String, content: String) -> Result<String, String> {
-    if !safety::command_check::is_path_safe(&filename) {
-        return Err("Unsafe filename".to_string());
-    }
-
-    let desktop = dirs::desktop_dir().ok_or("Could not find Desktop directory")?;
-    let path = desktop.join(&filename);
-
-    std::fs::write(&path, &content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
-
-    let full_path = path.to_string_lossy().to_string();
-    log::info!("[EXECUTE] Wrote file: {}", full_path);
-    Ok(full_path)
-}
-
 /// Tauri command: close the text launcher window.
 #[tauri::command]
 pub fn close_text_launcher(app: tauri::AppHandle) -> Result<(), String> {
@@ -356,37 +316,3 @@ pub fn open_text_launcher(app: tauri::AppHandle) -> Result<(), String> {
     crate::show_text_launcher(&app);
     Ok(())
 }
-
-/// Tauri command: write file to a user-chosen path (from save dialog).
-///
-/// The frontend shows a native save-file picker and passes the chosen path here.
-#[tauri::command]
-pub fn write_file_to_path(file_path: String, content: String) -> Result<String, String> {
-    if !safety::command_check::is_path_safe(&file_path) {
-        return Err("Unsafe file path".to_string());
-    }
-
-    std::fs::write(&file_path, &content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
-
-    log::info!("[EXPORT] Wrote file: {}", file_path);
-    Ok(file_path)
-}
-
-/// Tauri command: open a file with the system default application.
-///
-/// Uses macOS `open` command. Only allows paths under $HOME.
-#[tauri::command]
-pub fn open_file(file_path: String) -> Result<(), String> {
-    if !safety::command_check::is_path_safe(&file_path) {
-        return Err("Unsafe file path".to_string());
-    }
-
-    std::process::Command::new("open")
-        .arg(&file_path)
-        .spawn()
-        .map_err(|e| format!("Failed to open file: {}", e))?;
-
-    log::info!("[EXPORT] Opened file: {}", file_path);
-    Ok(())
-}
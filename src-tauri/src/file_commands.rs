@@ -0,0 +1,95 @@
+//! File Tauri commands: save action output to the Desktop or a chosen
+//! path, and open a saved file.
+//!
+//! Every path is checked with `safety::command_check::is_path_safe` and
+//! every write is recorded in the audit log.
+
+use crate::audit;
+use crate::safety;
+
+/// Tauri command: write file content to the user's Desktop.
+///
+/// Used for export_csv and other file-generating actions.
+/// Validates filename, writes to Desktop directory. When `data` (base64)
+/// is given, the decoded bytes are written instead of `content`.
+#[tauri::command]
+pub fn write_to_desktop(
+    filename: String,
+    content: String,
+    data: Option<String>,
+) -> Result<String, String> {
+    if !safety::command_check::is_path_safe(&filename) {
+        return Err("Unsafe filename".to_string());
+    }
+    let bytes = match data {
+        Some(data) => decode_base64(&data)?,
+        None => content.into_bytes(),
+    };
+
+    let desktop = dirs::desktop_dir().ok_or("Could not find Desktop directory")?;
+    let path = desktop.join(&filename);
+
+    std::fs::write(&path, &bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    let full_path = path.to_string_lossy().to_string();
+    audit::record_file_write(&full_path, &bytes);
+    log::info!("[EXECUTE] Wrote file: {}", full_path);
+    Ok(full_path)
+}
+
+/// Tauri command: write file to a user-chosen path (from save dialog).
+///
+/// The frontend shows a native save-file picker and passes the chosen path here.
+#[tauri::command]
+pub fn write_file_to_path(file_path: String, content: String) -> Result<String, String> {
+    if !safety::command_check::is_path_safe(&file_path) {
+        return Err("Unsafe file path".to_string());
+    }
+
+    std::fs::write(&file_path, &content).map_err(|e| format!("Failed to write file: {}", e))?;
+    audit::record_file_write(&file_path, content.as_bytes());
+
+    log::info!("[EXPORT] Wrote file: {}", file_path);
+    Ok(file_path)
+}
+
+/// Tauri command: write base64-encoded bytes to a user-chosen path.
+///
+/// Binary counterpart of `write_file_to_path`, used to save images and
+/// blob resources returned by plugins.
+#[tauri::command]
+pub fn write_binary_file_to_path(file_path: String, data: String) -> Result<String, String> {
+    if !safety::command_check::is_path_safe(&file_path) {
+        return Err("Unsafe file path".to_string());
+    }
+    let bytes = decode_base64(&data)?;
+
+    std::fs::write(&file_path, &bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    audit::record_file_write(&file_path, &bytes);
+
+    log::info!("[EXPORT] Wrote {} bytes: {}", bytes.len(), file_path);
+    Ok(file_path)
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, String> {
+    base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data)
+        .map_err(|e| format!("Invalid base64 data: {}", e))
+}
+
+/// Tauri command: open a file with the system default application.
+///
+/// Uses macOS `open` command. Only allows paths under $HOME.
+#[tauri::command]
+pub fn open_file(file_path: String) -> Result<(), String> {
+    if !safety::command_check::is_path_safe(&file_path) {
+        return Err("Unsafe file path".to_string());
+    }
+
+    std::process::Command::new("open")
+        .arg(&file_path)
+        .spawn()
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    log::info!("[EXPORT] Opened file: {}", file_path);
+    Ok(())
+}
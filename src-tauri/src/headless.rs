@@ -0,0 +1,251 @@
+//! Headless snip-to-action pipeline — no windows, no Tauri state.
+//!
+//! Runs the same OCR, heuristics, classify and execute code as
+//! `pipeline.rs`, minus the overlay and action menu windows. Used by the
+//! `omni-glass-cli` binary for batch processing and end-to-end tests, and
+//! by `mcp_server` to answer MCP hosts.
+//!
+//! Classify and execute use the provider the app would
+//! (`settings_commands::resolve_provider`); the local model is loaded on
+//! first use. With Anthropic, point `ANTHROPIC_BASE_URL` at a mock server
+//! to run without network access.
+
+#[cfg(feature = "local-llm")]
+use crate::llm::local_state::LocalLlmState;
+use crate::llm::{self, ActionMenu, ActionResult};
+use crate::mcp::{confirm, relevance, tool_prefs, ToolRegistry};
+use crate::ocr;
+use crate::safety::injection::{self, InjectionReport};
+use crate::settings_commands::resolve_provider;
+use serde::Serialize;
+use std::path::Path;
+
+/// Everything the pipeline learned about one input, printed by the CLI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipReport {
+    /// Image path, or "stdin" for piped text.
+    pub source: String,
+    pub ocr_text: String,
+    pub ocr_confidence: f64,
+    pub has_table: bool,
+    pub has_code: bool,
+    pub injection: InjectionReport,
+    pub menu: ActionMenu,
+    /// Present when an action was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ActionResult>,
+}
+
+/// OCR an image file. Any format the `image` crate reads is accepted.
+///
+/// Returns the recognized text and its confidence (0.0–1.0).
+pub fn ocr_image(path: &Path, level: ocr::RecognitionLevel) -> Result<(String, f64), String> {
+    let image =
+        image::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    ocr_decoded(&image, level, &path.display().to_string())
+}
+
+/// OCR encoded image bytes (PNG, JPEG, ...), e.g. a base64 payload.
+pub fn ocr_image_bytes(
+    bytes: &[u8],
+    level: ocr::RecognitionLevel,
+) -> Result<(String, f64), String> {
+    let image =
+        image::load_from_memory(bytes).map_err(|e| format!("Unreadable image data: {}", e))?;
+    ocr_decoded(&image, level, "image data")
+}
+
+/// OCR an already-decoded image (e.g. a fresh capture).
+pub fn ocr_decoded(
+    image: &image::DynamicImage,
+    level: ocr::RecognitionLevel,
+    label: &str,
+) -> Result<(String, f64), String> {
+    let png_bytes = encode_png(image)?;
+    let output = ocr::recognize_text_from_bytes(png_bytes, level);
+    log::info!(
+        "[OCR] {}: {} chars in {:.0}ms",
+        label,
+        output.char_count,
+        output.latency_ms
+    );
+    Ok((output.text, output.confidence))
+}
+
+/// Encode an image as PNG.
+pub fn encode_png(image: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let mut png_bytes = Vec::new();
+    image
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| format!("PNG encode failed: {}", e))?;
+    Ok(png_bytes)
+}
+
+/// Run heuristics, the injection scan, and classify on extracted text.
+///
+/// Installed plugin tools are offered to the classifier exactly as in the
+/// app (through the same relevance filter), and their content triggers are
+/// merged into the menu, so plugin actions can appear in it. The CLI
+/// doesn't add to the app's relevance metrics.
+pub async fn analyze(
+    registry: &ToolRegistry,
+    source: &str,
+    text: &str,
+    confidence: f64,
+) -> SnipReport {
+    let has_table = ocr::heuristics::detect_table_structure(text);
+    let has_code = ocr::heuristics::detect_code_structure(text);
+    let injection = injection::scan_for_injection(text);
+    if injection.adversarial {
+        log::warn!(
+            "[SAFETY] {} looks adversarial (score={}, signals={:?})",
+            source,
+            injection.score,
+            injection.signals
+        );
+    }
+    let prefs = tool_prefs::load_prefs();
+    let query = relevance::Query::from_snip(text, has_table, has_code);
+    let triggered = registry.triggered_actions(text, &query, &prefs).await;
+    let (plugin_tools, _) = registry.tools_for_snip(&prefs, &query).await;
+    let menu = classify(text, has_table, has_code, confidence, &plugin_tools).await;
+    let menu = registry.merge_triggered(menu, &triggered, &prefs).await;
+
+    SnipReport {
+        source: source.to_string(),
+        ocr_text: text.to_string(),
+        ocr_confidence: confidence,
+        has_table,
+        has_code,
+        injection,
+        menu,
+        result: None,
+    }
+}
+
+/// Classify on the active provider, without a window to stream into.
+async fn classify(
+    text: &str,
+    has_table: bool,
+    has_code: bool,
+    confidence: f64,
+    plugin_tools: &str,
+) -> ActionMenu {
+    match resolve_provider().as_str() {
+        "gemini" => {
+            llm::classify_streaming_gemini(
+                None,
+                text,
+                has_table,
+                has_code,
+                confidence,
+                plugin_tools,
+            )
+            .await
+        }
+        #[cfg(feature = "local-llm")]
+        "local" => {
+            let state = local_state().await;
+            llm::local::classify_local(
+                None,
+                text,
+                has_table,
+                has_code,
+                confidence,
+                plugin_tools,
+                state,
+            )
+            .await
+        }
+        _ => llm::classify(text, has_table, has_code, confidence, plugin_tools).await,
+    }
+}
+
+/// Run a built-in action on the active provider, as `pipeline::execute_builtin`.
+async fn execute_builtin(action_id: &str, text: &str) -> ActionResult {
+    match resolve_provider().as_str() {
+        #[cfg(feature = "local-llm")]
+        "local" => llm::local::execute_action_local(action_id, text, local_state().await).await,
+        _ => llm::execute_action_anthropic(action_id, text).await,
+    }
+}
+
+/// The CLI's local model, loaded the first time it is needed.
+#[cfg(feature = "local-llm")]
+async fn local_state() -> &'static LocalLlmState {
+    static STATE: tokio::sync::OnceCell<LocalLlmState> = tokio::sync::OnceCell::const_new();
+    STATE
+        .get_or_init(|| async {
+            let state = LocalLlmState::new();
+            if let Err(e) = state.load_default().await {
+                log::warn!("[LOCAL_LLM] {}", e);
+            }
+            state
+        })
+        .await
+}
+
+/// Execute `action_id` on an analyzed snip and store the result.
+pub async fn run_action(registry: &ToolRegistry, report: &mut SnipReport, action_id: &str) {
+    let result = execute(
+        registry,
+        action_id,
+        &report.ocr_text,
+        report.injection.adversarial,
+    )
+    .await;
+    report.result = Some(result);
+}
+
+/// Execute `action_id` on `text`.
+///
+/// Plugin actions go to their MCP server (which scans its own output);
+/// everything else goes to the LLM, and results for adversarial text
+/// are flagged just like in the app. A plugin tool the app would hold for
+/// argument review is refused: there is no window to review it in.
+pub async fn execute(
+    registry: &ToolRegistry,
+    action_id: &str,
+    text: &str,
+    adversarial: bool,
+) -> ActionResult {
+    if let Err(e) = check_unreviewed(registry, action_id).await {
+        log::warn!("[EXECUTE] {}", e);
+        return ActionResult::error(action_id, &e);
+    }
+    let plugin_result = crate::mcp::run_plugin_action(registry, action_id, text, None).await;
+    let result = match plugin_result {
+        Some(result) => result,
+        None => {
+            let mut result = execute_builtin(action_id, text).await;
+            if adversarial {
+                result.flag_untrusted(injection::INJECTION_WARNING);
+            }
+            result
+        }
+    };
+    log::info!(
+        "[EXECUTE] Complete: status={}, type={}",
+        result.status,
+        result.result.result_type
+    );
+    result
+}
+
+/// Err when `action_id` is a plugin tool whose arguments the user reviews.
+async fn check_unreviewed(registry: &ToolRegistry, action_id: &str) -> Result<(), String> {
+    if !registry.is_plugin_action(action_id).await {
+        return Ok(());
+    }
+    match registry.tool_for_action(action_id).await {
+        Some(tool) if confirm::needs_review(&tool, confirm::pref_for(&tool)) => Err(format!(
+            "'{}' needs the user to review its arguments; run it from the Omni-Glass app",
+            action_id
+        )),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,114 @@
+//! Tauri commands for browsing, re-running, and clearing snip history.
+
+use crate::history::record::{HistorySettings, SnipRecord};
+use crate::history::store;
+use crate::{llm, mcp, recipes, safety};
+use tauri::Manager;
+
+/// Results returned by `search_history` when no limit is given.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Full-text search over past snips. An empty query lists the newest.
+#[tauri::command]
+pub fn search_history(query: Option<String>, limit: Option<usize>) -> Vec<SnipRecord> {
+    store::search(
+        &store::history_dir(),
+        query.as_deref().unwrap_or(""),
+        limit.unwrap_or(DEFAULT_LIMIT),
+    )
+}
+
+/// Fetch a single history entry.
+#[tauri::command]
+pub fn get_history_entry(id: String) -> Result<SnipRecord, String> {
+    store::load_record(&store::history_dir(), &id)
+}
+
+/// Delete a single history entry and its thumbnail.
+#[tauri::command]
+pub fn delete_history_entry(id: String) -> Result<(), String> {
+    store::delete_record(&store::history_dir(), &id)?;
+    log::info!("[HISTORY] Deleted {}", id);
+    Ok(())
+}
+
+/// Re-run an action against a past snip's OCR text.
+///
+/// Runs on the stored record only; the snip open in the action menu is
+/// left alone. The text is re-scanned for prompt injection (there is no
+/// crop, so no accurate re-OCR) and the new result is appended to the
+/// same history entry.
+#[tauri::command]
+pub async fn rerun_history_action(
+    app: tauri::AppHandle,
+    id: String,
+    action_id: String,
+    registry: tauri::State<'_, mcp::ToolRegistry>,
+) -> Result<llm::ActionResult, String> {
+    if action_id.starts_with(recipes::ACTION_PREFIX) {
+        return Err("Recipes can't be re-run from history".to_string());
+    }
+    let snip = store::load_record(&store::history_dir(), &id)?;
+    log::info!("[HISTORY] Re-running '{}' on {}", action_id, id);
+    let text = snip.ocr_text;
+    let result = match mcp::run_plugin_action(&registry, &action_id, &text, Some(&app)).await {
+        Some(result) => result,
+        None => {
+            let adversarial = safety::injection::scan_for_injection(&text).adversarial;
+            crate::pipeline::execute_builtin(&app, &action_id, &text, adversarial).await
+        }
+    };
+    if result.result.result_type != mcp::confirm::REVIEW_RESULT_TYPE {
+        crate::history::spawn_record_action(id, result.clone());
+    }
+    Ok(result)
+}
+
+/// Current history settings (privacy switch and retention limits).
+#[tauri::command]
+pub fn get_history_settings() -> HistorySettings {
+    store::load_settings()
+}
+
+/// Save history settings. Turning history off also purges it, and new
+/// limits are applied immediately.
+#[tauri::command]
+pub fn set_history_settings(settings: HistorySettings) -> Result<(), String> {
+    store::save_settings(&settings)?;
+    if settings.enabled {
+        crate::history::apply_retention(&settings);
+    } else {
+        let removed = store::purge(&store::history_dir())?;
+        log::info!("[HISTORY] Disabled — purged {} entries", removed);
+    }
+    Ok(())
+}
+
+/// Delete every history entry. Returns how many were removed.
+#[tauri::command]
+pub fn purge_history() -> Result<usize, String> {
+    let removed = store::purge(&store::history_dir())?;
+    log::info!("[HISTORY] Purged {} entries", removed);
+    Ok(removed)
+}
+
+/// Open (or focus) the history browser window.
+pub fn open_history_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("history") {
+        let _ = window.set_focus();
+        return;
+    }
+    match tauri::WebviewWindowBuilder::new(
+        app,
+        "history",
+        tauri::WebviewUrl::App("history.html".into()),
+    )
+    .title("Omni-Glass History")
+    .inner_size(560.0, 620.0)
+    .resizable(true)
+    .build()
+    {
+        Ok(_) => log::info!("[HISTORY] History window opened"),
+        Err(e) => log::error!("[HISTORY] Failed to open history window: {}", e),
+    }
+}
@@ -0,0 +1,157 @@
+//! In-memory full-text index over snip history.
+//!
+//! Each record's searchable text is split into lowercase words, and an
+//! inverted index maps every word to the records containing it. A query
+//! term narrows the candidates to records with a word containing that term;
+//! `record::search` then ranks only those, so results are the same as a
+//! full scan. No I/O here: `store.rs` builds the index once and keeps it
+//! current as records are saved and deleted.
+
+use crate::history::record::{self, HistorySettings, SnipRecord};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Records by id plus a word → record ids index over their text.
+#[derive(Debug, Default)]
+pub struct HistoryIndex {
+    records: HashMap<String, SnipRecord>,
+    words: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Lowercase alphanumeric runs of `text`.
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+impl HistoryIndex {
+    pub fn build(records: Vec<SnipRecord>) -> Self {
+        let mut index = Self::default();
+        for record in records {
+            index.insert(record);
+        }
+        index
+    }
+
+    /// Add or replace a record.
+    pub fn insert(&mut self, record: SnipRecord) {
+        self.remove(&record.id);
+        for word in words(&record::searchable_text(&record)) {
+            self.words
+                .entry(word)
+                .or_default()
+                .insert(record.id.clone());
+        }
+        self.records.insert(record.id.clone(), record);
+    }
+
+    /// Drop a record. Returns it if it was indexed.
+    pub fn remove(&mut self, id: &str) -> Option<SnipRecord> {
+        let record = self.records.remove(id)?;
+        for word in words(&record::searchable_text(&record)) {
+            if let Some(ids) = self.words.get_mut(&word) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.words.remove(&word);
+                }
+            }
+        }
+        Some(record)
+    }
+
+    pub fn ids(&self) -> Vec<String> {
+        self.records.keys().cloned().collect()
+    }
+
+    /// Ranked matches for `query`, as `record::search` over every record.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SnipRecord> {
+        match self.candidates(query) {
+            Some(ids) => record::search(
+                ids.into_iter().filter_map(|id| self.records.get(id)),
+                query,
+                limit,
+            ),
+            None => record::search(self.records.values(), query, limit),
+        }
+    }
+
+    /// Ids of records outside the retention limits.
+    pub fn expired_ids(&self, settings: &HistorySettings, now: u64) -> Vec<String> {
+        record::expired_ids(self.records.values(), settings, now)
+    }
+
+    /// Records that contain every word of the query somewhere in a word of
+    /// their own text, or `None` when the query has no words to narrow by.
+    fn candidates(&self, query: &str) -> Option<BTreeSet<&str>> {
+        let mut candidates: Option<BTreeSet<&str>> = None;
+        for part in words(query) {
+            let matching: BTreeSet<&str> = self
+                .words
+                .iter()
+                .filter(|(word, _)| word.contains(part.as_str()))
+                .flat_map(|(_, ids)| ids.iter().map(String::as_str))
+                .collect();
+            candidates = Some(match candidates {
+                Some(found) => found.intersection(&matching).copied().collect(),
+                None => matching,
+            });
+        }
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, timestamp: u64, text: &str) -> SnipRecord {
+        SnipRecord {
+            id: id.to_string(),
+            timestamp,
+            thumbnail_path: None,
+            ocr_text: text.to_string(),
+            content_type: "prose".to_string(),
+            summary: String::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn search_matches_a_full_scan() {
+        let records = vec![
+            record("a", 1, "TypeError: undefined is not a function"),
+            record("b", 2, "undefined reference to main()"),
+            record("c", 3, "cargo build --release"),
+        ];
+        let index = HistoryIndex::build(records.clone());
+        for query in [
+            "undef",
+            "UNDEFINED function",
+            "main()",
+            "--release",
+            "",
+            "absent",
+        ] {
+            assert_eq!(
+                index.search(query, 10),
+                record::search(&records, query, 10),
+                "query {:?}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn replaced_and_removed_records_leave_the_index() {
+        let mut index = HistoryIndex::build(vec![record("a", 1, "alpha")]);
+        index.insert(record("a", 1, "beta"));
+        assert!(index.search("alpha", 10).is_empty());
+        assert_eq!(index.search("beta", 10).len(), 1);
+
+        assert!(index.remove("a").is_some());
+        assert!(index.search("beta", 10).is_empty());
+        assert!(index.words.is_empty());
+        assert!(index.ids().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+//! Snip history — a local, searchable record of past snips.
+//!
+//! Each snip stores a thumbnail, its OCR text, the classified content type
+//! and summary, and every action executed on it with the result. OCR text
+//! and action output are redacted before they touch disk. Recording never
+//! fails the caller and is skipped entirely when the user turns history off.
+
+pub mod commands;
+pub mod index;
+pub mod record;
+pub mod store;
+
+pub use record::{ActionRecord, HistorySettings, SnipRecord};
+
+use crate::llm::ActionResult;
+use crate::safety::redact::redact_sensitive_data;
+use image::DynamicImage;
+use std::sync::Mutex;
+
+fn now() -> std::time::Duration {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Serializes load-modify-save of a record, so the snip's classification
+/// and an action finishing at the same time can't overwrite each other.
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Start a history entry for a new snip, before its menu opens, so actions
+/// clicked early have somewhere to go. Returns its id, or `None` when
+/// history is disabled or the record couldn't be written.
+pub fn start_snip(ocr_text: &str) -> Option<String> {
+    if !store::load_settings().enabled {
+        return None;
+    }
+    let id = record::new_id(now().as_millis());
+    let snip = SnipRecord {
+        id: id.clone(),
+        timestamp: now().as_secs(),
+        thumbnail_path: None,
+        ocr_text: redacted(ocr_text),
+        content_type: String::new(),
+        summary: String::new(),
+        actions: Vec::new(),
+    };
+    if let Err(e) = store::save_record(&store::history_dir(), &snip) {
+        log::error!("[HISTORY] Failed to record snip: {}", e);
+        return None;
+    }
+    log::info!("[HISTORY] Recorded {}", id);
+    Some(id)
+}
+
+/// Complete a started entry with its thumbnail and classification, then
+/// apply retention.
+pub fn finish_snip(id: &str, image: &DynamicImage, content_type: &str, summary: &str) {
+    let settings = store::load_settings();
+    if !settings.enabled {
+        return;
+    }
+    let dir = store::history_dir();
+    let thumbnail_path = store::save_thumbnail(&dir, id, image)
+        .map_err(|e| log::warn!("[HISTORY] {}", e))
+        .ok();
+    let updated = update(id, |snip| {
+        snip.thumbnail_path = thumbnail_path;
+        snip.content_type = content_type.to_string();
+        snip.summary = summary.to_string();
+    });
+    if let Err(e) = updated {
+        log::error!("[HISTORY] Failed to finish snip: {}", e);
+    }
+    apply_retention(&settings);
+}
+
+/// Append an executed action and its result to a snip's history entry.
+pub fn record_action(id: &str, result: &ActionResult) {
+    if !store::load_settings().enabled {
+        return;
+    }
+    let action = ActionRecord {
+        action_id: result.action_id.clone(),
+        timestamp: now().as_secs(),
+        status: result.status.clone(),
+        result_type: result.result.result_type.clone(),
+        text: result.result.text.as_deref().map(redacted),
+        command: result.result.command.as_deref().map(redacted),
+        file_path: result.result.file_path.clone(),
+    };
+    if let Err(e) = update(id, |snip| snip.actions.push(action)) {
+        log::warn!("[HISTORY] Cannot append action: {}", e);
+    }
+}
+
+/// `record_action` on the blocking pool, without waiting for it.
+pub fn spawn_record_action(id: String, result: ActionResult) {
+    tokio::task::spawn_blocking(move || record_action(&id, &result));
+}
+
+/// Load a record, change it and save it back, under `UPDATE_LOCK`.
+fn update(id: &str, change: impl FnOnce(&mut SnipRecord)) -> Result<(), String> {
+    let _guard = UPDATE_LOCK.lock().map_err(|e| e.to_string())?;
+    let dir = store::history_dir();
+    let mut snip = store::load_record(&dir, id)?;
+    change(&mut snip);
+    store::save_record(&dir, &snip)
+}
+
+/// `text` with secrets replaced, as it may be stored.
+fn redacted(text: &str) -> String {
+    redact_sensitive_data(text).cleaned_text
+}
+
+/// Delete entries that fall outside the configured retention limits.
+pub fn apply_retention(settings: &HistorySettings) {
+    let dir = store::history_dir();
+    for id in store::expired_ids(&dir, settings, now().as_secs()) {
+        if let Err(e) = store::delete_record(&dir, &id) {
+            log::warn!("[HISTORY] Retention cleanup failed for {}: {}", id, e);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Snip history records — pure types, search, and retention logic.
+//!
+//! No I/O here: `store.rs` loads and saves records, this module decides
+//! what matches a query and what has aged out. `index.rs` narrows which
+//! records a query needs to look at.
+
+use serde::{Deserialize, Serialize};
+
+/// One past snip: what was captured and what the user did with it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipRecord {
+    pub id: String,
+    /// Unix timestamp (seconds) of the snip.
+    pub timestamp: u64,
+    /// Absolute path of the PNG thumbnail, if one was saved.
+    pub thumbnail_path: Option<String>,
+    pub ocr_text: String,
+    pub content_type: String,
+    pub summary: String,
+    /// Actions executed on this snip, oldest first.
+    #[serde(default)]
+    pub actions: Vec<ActionRecord>,
+}
+
+/// An action executed on a snip and its outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionRecord {
+    pub action_id: String,
+    pub timestamp: u64,
+    pub status: String,
+    pub result_type: String,
+    pub text: Option<String>,
+    pub command: Option<String>,
+    pub file_path: Option<String>,
+}
+
+/// User-controlled history settings, including the privacy switch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistorySettings {
+    /// When false, nothing is recorded.
+    pub enabled: bool,
+    /// Keep at most this many snips (newest win).
+    pub max_entries: usize,
+    /// Drop snips older than this many days (0 = no age limit).
+    pub max_age_days: u64,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: 500,
+            max_age_days: 30,
+        }
+    }
+}
+
+/// Build a sortable, unique-enough id from a millisecond timestamp.
+pub fn new_id(timestamp_ms: u128) -> String {
+    format!("snip-{}", timestamp_ms)
+}
+
+/// Full-text search over OCR text, summary, content type and action results.
+///
+/// Every whitespace-separated term must appear (case-insensitive). Results
+/// are ranked by total term hits, then newest first. An empty query returns
+/// the most recent records.
+pub fn search<'a>(
+    records: impl IntoIterator<Item = &'a SnipRecord>,
+    query: &str,
+    limit: usize,
+) -> Vec<SnipRecord> {
+    let terms: Vec<String> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+
+    let mut scored: Vec<(usize, &SnipRecord)> = records
+        .into_iter()
+        .filter_map(|r| {
+            let haystack = searchable_text(r);
+            let mut hits = 0;
+            for term in &terms {
+                let count = haystack.matches(term.as_str()).count();
+                if count == 0 {
+                    return None;
+                }
+                hits += count;
+            }
+            Some((hits, r))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.timestamp.cmp(&a.1.timestamp)));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, r)| r.clone())
+        .collect()
+}
+
+/// Everything a query is matched against, lowercased.
+pub fn searchable_text(record: &SnipRecord) -> String {
+    let mut text = format!(
+        "{}\n{}\n{}",
+        record.ocr_text, record.summary, record.content_type
+    );
+    for action in &record.actions {
+        text.push('\n');
+        text.push_str(&action.action_id);
+        for part in [&action.text, &action.command, &action.file_path]
+            .into_iter()
+            .flatten()
+        {
+            text.push('\n');
+            text.push_str(part);
+        }
+    }
+    text.to_lowercase()
+}
+
+/// Ids of records that fall outside the retention limits.
+pub fn expired_ids<'a>(
+    records: impl IntoIterator<Item = &'a SnipRecord>,
+    settings: &HistorySettings,
+    now: u64,
+) -> Vec<String> {
+    let mut by_age: Vec<&SnipRecord> = records.into_iter().collect();
+    by_age.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+
+    let max_age_secs = settings.max_age_days * 24 * 60 * 60;
+    by_age
+        .into_iter()
+        .enumerate()
+        .filter(|(rank, r)| {
+            *rank >= settings.max_entries
+                || (settings.max_age_days > 0 && now.saturating_sub(r.timestamp) > max_age_secs)
+        })
+        .map(|(_, r)| r.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, timestamp: u64, text: &str) -> SnipRecord {
+        SnipRecord {
+            id: id.to_string(),
+            timestamp,
+            thumbnail_path: None,
+            ocr_text: text.to_string(),
+            content_type: "prose".to_string(),
+            summary: String::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn search_requires_all_terms() {
+        let records = vec![
+            record("a", 1, "TypeError: undefined is not a function"),
+            record("b", 2, "undefined reference to main"),
+        ];
+        let hits = search(&records, "UNDEFINED function", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "a");
+    }
+
+    #[test]
+    fn search_matches_action_results() {
+        let mut r = record("a", 1, "some table");
+        r.actions.push(ActionRecord {
+            action_id: "export_csv".to_string(),
+            timestamp: 2,
+            status: "success".to_string(),
+            result_type: "file".to_string(),
+            text: None,
+            command: None,
+            file_path: Some("invoices.csv".to_string()),
+        });
+        assert_eq!(search(&[r], "invoices", 10).len(), 1);
+    }
+
+    #[test]
+    fn empty_query_returns_newest_first() {
+        let records = vec![record("old", 1, "x"), record("new", 5, "y")];
+        let hits = search(&records, "  ", 1);
+        assert_eq!(hits[0].id, "new");
+    }
+
+    #[test]
+    fn retention_drops_excess_and_old_entries() {
+        let day = 24 * 60 * 60;
+        let now = 100 * day;
+        let records = vec![
+            record("fresh", now - 10, "a"),
+            record("recent", now - day, "b"),
+            record("stale", now - 40 * day, "c"),
+        ];
+        let settings = HistorySettings {
+            enabled: true,
+            max_entries: 1,
+            max_age_days: 30,
+        };
+        let mut expired = expired_ids(&records, &settings, now);
+        expired.sort();
+        assert_eq!(expired, vec!["recent".to_string(), "stale".to_string()]);
+    }
+
+    #[test]
+    fn settings_default_missing_fields() {
+        let s: HistorySettings = serde_json::from_str(r#"{"enabled": false}"#).unwrap();
+        assert!(!s.enabled);
+        assert_eq!(s.max_entries, 500);
+    }
+}
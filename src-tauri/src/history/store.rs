@@ -0,0 +1,238 @@
+//! Snip history storage — one JSON file plus one PNG thumbnail per snip.
+//!
+//! Records live in `~/.config/omni-glass/history/{id}.json` with the
+//! thumbnail next to it as `{id}.png`. Settings are stored separately in
+//! `~/.config/omni-glass/history-settings.json` so purging history keeps
+//! the user's privacy choice.
+//!
+//! Search and retention go through a `HistoryIndex` built from the files the
+//! first time a directory is used, then updated by every save and delete, so
+//! recording a snip doesn't reload the whole history.
+
+use crate::history::index::HistoryIndex;
+use crate::history::record::{HistorySettings, SnipRecord};
+use image::DynamicImage;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+/// Longest edge of a stored thumbnail, in pixels.
+const THUMBNAIL_MAX_EDGE: u32 = 320;
+
+/// Directory holding history records and thumbnails.
+pub fn history_dir() -> PathBuf {
+    base_dir().join("history")
+}
+
+fn base_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("omni-glass")
+}
+
+/// One index per history directory, built on first use.
+static INDEXES: LazyLock<Mutex<HashMap<PathBuf, HistoryIndex>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Run `f` with the index for `dir`, holding it locked so file writes and
+/// index updates stay in step.
+fn with_index<T>(dir: &Path, f: impl FnOnce(&mut HistoryIndex) -> T) -> T {
+    let mut indexes = INDEXES.lock().unwrap_or_else(|e| e.into_inner());
+    let index = indexes
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| HistoryIndex::build(load_all(dir)));
+    f(index)
+}
+
+fn record_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+fn thumbnail_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.png", id))
+}
+
+/// Write (or overwrite) a record.
+pub fn save_record(dir: &Path, record: &SnipRecord) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create history dir: {}", e))?;
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize history record: {}", e))?;
+    with_index(dir, |index| {
+        std::fs::write(record_path(dir, &record.id), json)
+            .map_err(|e| format!("Failed to write history record: {}", e))?;
+        index.insert(record.clone());
+        Ok(())
+    })
+}
+
+/// Downscale and save a snip thumbnail. Returns its absolute path.
+pub fn save_thumbnail(dir: &Path, id: &str, image: &DynamicImage) -> Result<String, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create history dir: {}", e))?;
+    let path = thumbnail_path(dir, id);
+    image
+        .thumbnail(THUMBNAIL_MAX_EDGE, THUMBNAIL_MAX_EDGE)
+        .save(&path)
+        .map_err(|e| format!("Failed to save thumbnail: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Load one record by id.
+pub fn load_record(dir: &Path, id: &str) -> Result<SnipRecord, String> {
+    if id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid history id: {}", id));
+    }
+    let raw = std::fs::read_to_string(record_path(dir, id))
+        .map_err(|_| format!("History entry '{}' not found", id))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Corrupt history entry '{}': {}", id, e))
+}
+
+/// Ranked full-text search; an empty query lists the newest records.
+pub fn search(dir: &Path, query: &str, limit: usize) -> Vec<SnipRecord> {
+    with_index(dir, |index| index.search(query, limit))
+}
+
+/// Ids of records outside the retention limits.
+pub fn expired_ids(dir: &Path, settings: &HistorySettings, now: u64) -> Vec<String> {
+    with_index(dir, |index| index.expired_ids(settings, now))
+}
+
+/// Load every record from disk, newest first. Unreadable files are skipped.
+pub fn load_all(dir: &Path) -> Vec<SnipRecord> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut records: Vec<SnipRecord> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| {
+            let raw = std::fs::read_to_string(&p).ok()?;
+            match serde_json::from_str(&raw) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    log::warn!("[HISTORY] Skipping {}: {}", p.display(), e);
+                    None
+                }
+            }
+        })
+        .collect();
+    records.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+    records
+}
+
+/// Delete a record and its thumbnail.
+pub fn delete_record(dir: &Path, id: &str) -> Result<(), String> {
+    if id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid history id: {}", id));
+    }
+    with_index(dir, |index| {
+        let _ = std::fs::remove_file(thumbnail_path(dir, id));
+        match std::fs::remove_file(record_path(dir, id)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to delete history entry: {}", e)),
+        }
+        index.remove(id);
+        Ok(())
+    })
+}
+
+/// Delete all history records and thumbnails.
+pub fn purge(dir: &Path) -> Result<usize, String> {
+    let ids = with_index(dir, |index| index.ids());
+    for id in &ids {
+        delete_record(dir, id)?;
+    }
+    Ok(ids.len())
+}
+
+/// Load history settings. Missing or invalid → defaults (history on).
+pub fn load_settings() -> HistorySettings {
+    std::fs::read_to_string(base_dir().join("history-settings.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Persist history settings.
+pub fn save_settings(settings: &HistorySettings) -> Result<(), String> {
+    let dir = base_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize history settings: {}", e))?;
+    std::fs::write(dir.join("history-settings.json"), json)
+        .map_err(|e| format!("Failed to write history settings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("og-history-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn record(id: &str, timestamp: u64) -> SnipRecord {
+        SnipRecord {
+            id: id.to_string(),
+            timestamp,
+            thumbnail_path: None,
+            ocr_text: "hello".to_string(),
+            content_type: "prose".to_string(),
+            summary: "greeting".to_string(),
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn save_load_roundtrip_newest_first() {
+        let dir = fresh_dir("roundtrip");
+        save_record(&dir, &record("snip-1", 1)).unwrap();
+        save_record(&dir, &record("snip-2", 2)).unwrap();
+        let all = load_all(&dir);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, "snip-2");
+        assert_eq!(load_record(&dir, "snip-1").unwrap(), record("snip-1", 1));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn search_follows_saves_and_deletes() {
+        let dir = fresh_dir("index");
+        save_record(&dir, &record("snip-1", 1)).unwrap();
+        assert_eq!(search(&dir, "hello", 10).len(), 1);
+
+        let mut updated = record("snip-1", 1);
+        updated.ocr_text = "goodbye".to_string();
+        save_record(&dir, &updated).unwrap();
+        assert!(search(&dir, "hello", 10).is_empty());
+
+        delete_record(&dir, "snip-1").unwrap();
+        assert!(search(&dir, "goodbye", 10).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn thumbnail_is_downscaled_and_deleted_with_record() {
+        let dir = fresh_dir("thumb");
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::new(1280, 640));
+        let path = save_thumbnail(&dir, "snip-1", &img).unwrap();
+        let thumb = image::open(&path).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (320, 160));
+
+        save_record(&dir, &record("snip-1", 1)).unwrap();
+        assert_eq!(purge(&dir).unwrap(), 1);
+        assert!(!std::path::Path::new(&path).exists());
+        assert!(load_all(&dir).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_path_traversal_ids() {
+        let dir = fresh_dir("traversal");
+        assert!(load_record(&dir, "../secrets").is_err());
+        assert!(delete_record(&dir, "a/b").is_err());
+    }
+}
@@ -5,20 +5,39 @@
 //! registration, state management, and the command registry.
 //!
 //! Commands are split across:
-//!   - commands.rs           — simple one-step commands (crop, close, clipboard, file I/O)
+//!   - commands.rs           — simple one-step commands (crop, close, clipboard)
+//!   - file_commands.rs      — saving output to the Desktop or a chosen path, opening files
 //!   - pipeline.rs           — multi-step orchestration (process_snip, execute_action)
+//!   - pipeline_agent/       — text launcher agent mode, registered as the "agent" plugin
 //!   - settings_commands.rs  — settings panel + provider resolution
+//!   - capture_commands.rs   — overlays per monitor, window capture + picker
+//!   - audit/commands.rs     — audit log query + verification
+//!   - history/commands.rs   — snip history search, re-run, privacy controls
+//!   - recipes/commands.rs   — recipe editing, confirming a paused recipe step
+//!
+//! `headless.rs` runs the same pipeline without windows for the
+//! `omni-glass-cli` binary (src/bin/omni-glass-cli.rs); `mcp_server`
+//! serves it to MCP hosts (`omni-glass-cli mcp`).
 
+pub mod audit;
 mod capture;
+mod capture_commands;
 mod commands;
+mod file_commands;
+pub mod headless;
+pub mod history;
 pub mod llm;
 mod local_model_commands;
 pub mod mcp;
-mod ocr;
+pub mod mcp_server;
+pub mod ocr;
 mod pipeline;
+mod pipeline_agent;
 mod pipeline_text;
+pub mod recipes;
 pub mod safety;
 pub mod settings_commands;
+mod shell_command;
 mod tray;
 
 use capture::CaptureState;
@@ -51,6 +70,7 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(pipeline_agent::init())
         // Global shortcut plugin — available for opt-in hotkey binding.
         // Not registered by default; tray menu is the primary entry point.
         // .plugin(tauri_plugin_global_shortcut::Builder::new().build())
@@ -58,6 +78,8 @@ pub fn run() {
         .manage(llm::ActionMenuState::new())
         .manage(ToolRegistry::new())
         .manage(PendingApprovals::new())
+        .manage(mcp::dev::DevPlugins::new())
+        .manage(mcp::elicit::Elicitations::new())
         .invoke_handler(tauri::generate_handler![
             // Simple commands (commands.rs)
             commands::crop_region,
@@ -68,17 +90,24 @@ pub fn run() {
             commands::close_action_menu,
             commands::close_permission_prompt,
             commands::get_action_menu,
+            commands::get_triggered_actions,
             commands::run_confirmed_command,
             commands::summarize_command_output,
-            commands::write_to_desktop,
-            commands::write_file_to_path,
-            commands::open_file,
             commands::close_text_launcher,
             commands::close_tray_menu,
             commands::start_snip,
             commands::open_text_launcher,
             commands::get_plugin_names,
-            // Pipeline commands (pipeline.rs / pipeline_text.rs)
+            // File commands (file_commands.rs)
+            file_commands::write_to_desktop,
+            file_commands::write_file_to_path,
+            file_commands::write_binary_file_to_path,
+            file_commands::open_file,
+            // Capture commands (capture_commands.rs)
+            capture_commands::list_capture_windows,
+            capture_commands::start_window_snip,
+            capture_commands::close_window_picker,
+            // Pipeline commands (pipeline.rs / pipeline_text/; agent mode is a plugin)
             pipeline::process_snip,
             pipeline::execute_action,
             pipeline_text::execute_text_command,
@@ -94,6 +123,54 @@ pub fn run() {
             // MCP approval commands (approval_commands.rs)
             mcp::approval_commands::get_pending_approvals,
             mcp::approval_commands::approve_plugin,
+            // Plugin argument review (confirm_commands.rs)
+            mcp::confirm_commands::get_tool_review,
+            mcp::confirm_commands::confirm_plugin_call,
+            mcp::confirm_commands::get_tool_confirm_prefs,
+            mcp::confirm_commands::set_tool_confirm_pref,
+            // Tool enable/disable and pinning (tool_prefs_commands.rs)
+            mcp::tool_prefs_commands::get_tool_prefs,
+            mcp::tool_prefs_commands::set_tool_enabled,
+            mcp::tool_prefs_commands::set_tool_pinned,
+            mcp::tool_prefs_commands::get_relevance_stats,
+            // Plugin install / upgrade / uninstall (install_commands.rs)
+            mcp::install_commands::install_plugin,
+            mcp::install_commands::upgrade_plugin,
+            mcp::install_commands::uninstall_plugin,
+            // Trusted publishers (trust_commands.rs)
+            mcp::trust_commands::get_trust_store,
+            mcp::trust_commands::trust_publisher,
+            mcp::trust_commands::remove_publisher,
+            mcp::trust_commands::set_require_signed,
+            // Plugin configuration (config_commands.rs)
+            mcp::config_commands::get_plugin_configs,
+            mcp::config_commands::set_plugin_config,
+            // Plugin logs (log_commands.rs)
+            mcp::log_commands::get_plugin_logs,
+            // Plugin developer mode (dev_commands.rs)
+            mcp::dev_commands::get_dev_plugins,
+            mcp::dev_commands::set_dev_mode,
+            mcp::dev_commands::add_dev_plugin,
+            mcp::dev_commands::remove_dev_plugin,
+            // Plugin questions to the user (elicit_commands.rs)
+            mcp::elicit_commands::get_elicitation,
+            mcp::elicit_commands::respond_elicitation,
+            // Audit log (audit/commands.rs)
+            audit::commands::get_audit_log,
+            audit::commands::verify_audit_log,
+            // Snip history (history/commands.rs)
+            history::commands::search_history,
+            history::commands::get_history_entry,
+            history::commands::delete_history_entry,
+            history::commands::rerun_history_action,
+            history::commands::get_history_settings,
+            history::commands::set_history_settings,
+            history::commands::purge_history,
+            // Recipes (recipes/commands.rs)
+            recipes::commands::get_recipes,
+            recipes::commands::save_recipes,
+            recipes::commands::continue_recipe,
+            recipes::commands::cancel_recipe,
             // Local model management (local_model_commands.rs)
             local_model_commands::get_local_models,
             local_model_commands::download_local_model,
@@ -110,7 +187,10 @@ pub fn run() {
                 app.manage(llm::local_state::LocalLlmState::new());
                 let handle_llm = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
-                    auto_load_local_model(&handle_llm).await;
+                    let state = handle_llm.state::<llm::local_state::LocalLlmState>();
+                    if let Err(e) = state.load_default().await {
+                        log::warn!("[LOCAL_LLM] Model not preloaded: {}", e);
+                    }
                 });
             }
 
@@ -130,23 +210,18 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 let registry = handle.state::<ToolRegistry>();
                 let pending = handle.state::<PendingApprovals>();
+                registry.set_host(std::sync::Arc::new(mcp::elicit::AppHost::new(
+                    handle.clone(),
+                )));
                 mcp::builtins::register_builtins(&registry).await;
                 mcp::loader::load_plugins(&registry, &pending).await;
+                mcp::dev::start(&handle);
 
                 // If any plugins are queued for approval, open the prompt window
                 let has_pending = !pending.queue.lock().await.is_empty();
                 if has_pending {
                     log::info!("[MCP] Opening permission prompt for pending plugins");
-                    let _ = tauri::WebviewWindowBuilder::new(
-                        &handle,
-                        "permission-prompt",
-                        tauri::WebviewUrl::App("permission-prompt.html".into()),
-                    )
-                    .title("Plugin Permissions")
-                    .inner_size(460.0, 380.0)
-                    .resizable(false)
-                    .center()
-                    .build();
+                    show_permission_prompt(&handle);
                 }
             });
 
@@ -157,40 +232,25 @@ pub fn run() {
         .expect("Error running Omni-Glass");
 }
 
-/// Auto-load the default local model if one is downloaded.
+/// Open (or focus) the plugin permission prompt.
 ///
-/// Called at startup when LLM_PROVIDER=local. Scans the model registry
-/// for the first downloaded model and loads it into the LocalLlmState.
-#[cfg(feature = "local-llm")]
-async fn auto_load_local_model(app: &tauri::AppHandle) {
-    use llm::{model_manager, model_registry};
-
-    // Find the first downloaded model (prefer default)
-    let default = model_registry::default_model();
-    let model_to_load = if model_manager::is_model_downloaded(default) {
-        Some(default)
-    } else {
-        model_registry::available_models()
-            .iter()
-            .find(|m| model_manager::is_model_downloaded(m))
-    };
-
-    let model = match model_to_load {
-        Some(m) => m,
-        None => {
-            eprintln!("[LOCAL_LLM] No downloaded model found — download one in Settings");
-            return;
-        }
-    };
-
-    let path = model_manager::model_path(model);
-    eprintln!("[LOCAL_LLM] Auto-loading model: {} from {}", model.id, path.display());
-
-    let state = app.state::<llm::local_state::LocalLlmState>();
-    match state.load(&path, model.id).await {
-        Ok(()) => eprintln!("[LOCAL_LLM] Model loaded successfully: {}", model.id),
-        Err(e) => eprintln!("[LOCAL_LLM] Failed to auto-load model: {}", e),
+/// An open prompt re-reads the pending queue after each decision, so
+/// plugins queued while it is showing are picked up without reopening it.
+pub(crate) fn show_permission_prompt(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("permission-prompt") {
+        let _ = window.set_focus();
+        return;
     }
+    let _ = tauri::WebviewWindowBuilder::new(
+        app,
+        "permission-prompt",
+        tauri::WebviewUrl::App("permission-prompt.html".into()),
+    )
+    .title("Plugin Permissions")
+    .inner_size(460.0, 380.0)
+    .resizable(false)
+    .center()
+    .build();
 }
 
 /// Open (or focus) the text launcher window.
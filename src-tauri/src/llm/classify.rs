@@ -1,19 +1,19 @@
 //! Anthropic Claude CLASSIFY pipeline — streaming SSE.
 //!
-//! Streams the response and emits Tauri events as data becomes available:
-//! - "action-menu-skeleton" at TTFT (~300ms) with contentType + summary
-//! - "action-menu-complete" when the full ActionMenu JSON is parsed
+//! Streams the response and emits "action-menu-skeleton" at TTFT (~300ms)
+//! with contentType + summary. The pipeline emits "action-menu-complete"
+//! once it has added triggered actions to the returned menu.
 
 use super::prompts::{self, CLASSIFY_SYSTEM_PROMPT, MAX_TOKENS, MODEL};
 use super::streaming;
 use super::types::{Action, ActionMenu, ActionMenuSkeleton};
+use crate::mcp::tool_prefs::{self, ToolPrefs};
 use tauri::Emitter;
 
 /// Call Claude API with streaming to classify OCR text.
 ///
-/// Emits Tauri events as the response streams:
-/// - "action-menu-skeleton" when contentType + summary are available
-/// - "action-menu-complete" when the full ActionMenu is parsed
+/// Emits "action-menu-skeleton" as soon as contentType + summary are
+/// available; the caller emits the finished menu.
 ///
 /// Always returns a valid ActionMenu (fallback on any error).
 pub async fn classify_streaming(
@@ -32,25 +32,19 @@ pub async fn classify_streaming(
         Ok(_) => {
             eprintln!("[CLASSIFY] ANTHROPIC_API_KEY is set but EMPTY");
             log::warn!("[LLM] No ANTHROPIC_API_KEY set — returning fallback actions");
-            let menu = ActionMenu::fallback();
-            let _ = app.emit("action-menu-complete", &menu);
-            return menu;
+            return ActionMenu::fallback();
         }
         Err(e) => {
             eprintln!("[CLASSIFY] ANTHROPIC_API_KEY not in env: {}", e);
             log::warn!("[LLM] No ANTHROPIC_API_KEY set — returning fallback actions");
-            let menu = ActionMenu::fallback();
-            let _ = app.emit("action-menu-complete", &menu);
-            return menu;
+            return ActionMenu::fallback();
         }
     };
 
     if text.trim().is_empty() {
         eprintln!("[CLASSIFY] OCR text is EMPTY — fallback");
         log::warn!("[LLM] Empty OCR text — returning fallback actions");
-        let menu = ActionMenu::fallback();
-        let _ = app.emit("action-menu-complete", &menu);
-        return menu;
+        return ActionMenu::fallback();
     }
 
     eprintln!("[CLASSIFY] OCR text: {} chars, starting API call...", text.len());
@@ -64,7 +58,7 @@ pub async fn classify_streaming(
 
     let client = reqwest::Client::new();
     let mut response = match client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(super::provider::anthropic_messages_url())
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
@@ -87,9 +81,7 @@ pub async fn classify_streaming(
         Err(e) => {
             eprintln!("[CLASSIFY] HTTP request FAILED: {}", e);
             log::error!("[LLM] HTTP request failed: {}", e);
-            let menu = ActionMenu::fallback();
-            let _ = app.emit("action-menu-complete", &menu);
-            return menu;
+            return ActionMenu::fallback();
         }
     };
 
@@ -98,9 +90,7 @@ pub async fn classify_streaming(
         let body = response.text().await.unwrap_or_default();
         eprintln!("[CLASSIFY] API error {}: {}", status, body);
         log::error!("[LLM] API returned {}: {}", status, body);
-        let menu = ActionMenu::fallback();
-        let _ = app.emit("action-menu-complete", &menu);
-        return menu;
+        return ActionMenu::fallback();
     }
 
     eprintln!("[CLASSIFY] API returned 200, streaming...");
@@ -216,17 +206,16 @@ pub async fn classify_streaming(
         }
     };
 
-    let menu = ensure_required_actions(menu, has_table);
-    let _ = app.emit("action-menu-complete", &menu);
-    menu
+    ensure_required_actions(menu, has_table, &tool_prefs::load_prefs())
 }
 
 /// Post-process: guarantee certain actions exist for specific content types.
 ///
 /// The LLM is non-deterministic — it might omit "Export CSV" for table content,
 /// or classify a spreadsheet as "mixed" instead of "table". We use both the
-/// LLM content_type AND the OCR has_table hint to decide.
-fn ensure_required_actions(mut menu: ActionMenu, has_table: bool) -> ActionMenu {
+/// LLM content_type AND the OCR has_table hint to decide. Actions the user
+/// disabled are never injected; the user's tool preferences are applied last.
+fn ensure_required_actions(mut menu: ActionMenu, has_table: bool, prefs: &ToolPrefs) -> ActionMenu {
     // Check specifically for CSV-related action IDs (not broad "export" match)
     let has_csv_action = menu.actions.iter().any(|a| {
         a.id.contains("csv") || a.id == "export_csv" || a.id == "export_to_csv" || a.id == "extract_to_csv"
@@ -236,7 +225,7 @@ fn ensure_required_actions(mut menu: ActionMenu, has_table: bool) -> ActionMenu
 
     // Inject CSV export if: content is table/kv_pairs OR OCR detected table structure
     let needs_csv = matches!(menu.content_type.as_str(), "table" | "kv_pairs") || has_table;
-    if needs_csv && !has_csv_action {
+    if needs_csv && !has_csv_action && prefs.is_enabled("export_csv") {
         let next_priority = menu.actions.len() as u8 + 1;
         menu.actions.push(Action {
             id: "export_csv".to_string(),
@@ -251,7 +240,7 @@ fn ensure_required_actions(mut menu: ActionMenu, has_table: bool) -> ActionMenu
 
     // Error content must always have explain_error and suggest_fix
     if menu.content_type == "error" {
-        if !has_explain {
+        if !has_explain && prefs.is_enabled("explain_error") {
             let next_priority = menu.actions.len() as u8 + 1;
             menu.actions.push(Action {
                 id: "explain_error".to_string(),
@@ -262,7 +251,7 @@ fn ensure_required_actions(mut menu: ActionMenu, has_table: bool) -> ActionMenu
                 requires_execution: true,
             });
         }
-        if !has_fix {
+        if !has_fix && prefs.is_enabled("suggest_fix") {
             let next_priority = menu.actions.len() as u8 + 1;
             menu.actions.push(Action {
                 id: "suggest_fix".to_string(),
@@ -275,7 +264,7 @@ fn ensure_required_actions(mut menu: ActionMenu, has_table: bool) -> ActionMenu
         }
     }
 
-    menu
+    tool_prefs::apply(menu, prefs)
 }
 
 /// Extract the text delta from an Anthropic content_block_delta SSE data payload.
@@ -306,7 +295,7 @@ pub async fn classify(
     let start = std::time::Instant::now();
 
     let response = reqwest::Client::new()
-        .post("https://api.anthropic.com/v1/messages")
+        .post(super::provider::anthropic_messages_url())
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
@@ -348,5 +337,34 @@ pub async fn classify(
         log::warn!("[LLM] Failed to parse ActionMenu: {}", e);
         ActionMenu::fallback()
     });
-    ensure_required_actions(menu, has_table)
+    ensure_required_actions(menu, has_table, &tool_prefs::load_prefs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_menu() -> ActionMenu {
+        ActionMenu {
+            content_type: "error".to_string(),
+            actions: Vec::new(),
+            ..ActionMenu::fallback()
+        }
+    }
+
+    #[test]
+    fn required_actions_are_injected_unless_disabled() {
+        let ids = |menu: &ActionMenu| -> Vec<String> {
+            menu.actions.iter().map(|a| a.id.clone()).collect()
+        };
+        let menu = ensure_required_actions(error_menu(), true, &ToolPrefs::default());
+        assert_eq!(ids(&menu), ["export_csv", "explain_error", "suggest_fix"]);
+
+        let prefs = ToolPrefs {
+            disabled: vec!["export_csv".to_string(), "suggest_fix".to_string()],
+            pinned: Vec::new(),
+        };
+        let menu = ensure_required_actions(error_menu(), true, &prefs);
+        assert_eq!(ids(&menu), ["explain_error"]);
+    }
 }
@@ -29,12 +29,21 @@ pub struct ActionResult {
 #[serde(rename_all = "camelCase")]
 pub struct ActionResultBody {
     #[serde(rename = "type")]
-    pub result_type: String, // "text" | "file" | "command" | "clipboard"
+    pub result_type: String, // "text" | "file" | "command" | "clipboard" | "image" | "link" | "tool_args"
     pub text: Option<String>,
     pub file_path: Option<String>,
     pub command: Option<String>,
     pub clipboard_content: Option<String>,
     pub mime_type: Option<String>,
+    /// Base64 payload for "image" results and binary "file" results.
+    #[serde(default)]
+    pub data: Option<String>,
+    /// Target of a "link" result (http/https only).
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Generated plugin tool arguments awaiting user review ("tool_args").
+    #[serde(default)]
+    pub arguments: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +51,12 @@ pub struct ActionResultBody {
 pub struct ActionResultMetadata {
     pub tokens_used: Option<u32>,
     pub processing_note: Option<String>,
+    /// The resource limit a plugin ran into, for error results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<crate::mcp::limits::LimitViolation>,
+    /// The recipe step this result came from, when run as part of a recipe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe: Option<crate::recipes::RecipeProgress>,
 }
 
 impl ActionResult {
@@ -57,6 +72,9 @@ impl ActionResult {
                 command: None,
                 clipboard_content: None,
                 mime_type: None,
+                data: None,
+                url: None,
+                arguments: None,
             },
             metadata: None,
         }
@@ -75,10 +93,33 @@ impl ActionResult {
                 command: None,
                 clipboard_content: None,
                 mime_type: None,
+                data: None,
+                url: None,
+                arguments: None,
             },
             metadata: None,
         }
     }
+
+    /// Mark a result produced from adversarial-looking input.
+    ///
+    /// Command suggestions are downgraded to confirm-only and the warning is
+    /// attached as a processing note so the UI can show it next to the result.
+    pub fn flag_untrusted(&mut self, warning: &str) {
+        if self.result.result_type == "command" && self.status != "error" {
+            self.status = "needs_confirmation".to_string();
+        }
+        let metadata = self.metadata.get_or_insert(ActionResultMetadata {
+            tokens_used: None,
+            processing_note: None,
+            limit: None,
+            recipe: None,
+        });
+        metadata.processing_note = Some(match metadata.processing_note.take() {
+            Some(note) => format!("{} {}", warning, note),
+            None => warning.to_string(),
+        });
+    }
 }
 
 // ── Pipeline ───────────────────────────────────────────────────────
@@ -120,7 +161,7 @@ pub async fn execute_action_anthropic(
     let model = super::prompts::MODEL;
     let client = reqwest::Client::new();
     let resp = client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(super::provider::anthropic_messages_url())
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
@@ -258,6 +299,9 @@ fn salvage_text_from_json(raw: &str, action_id: &str) -> Option<ActionResult> {
             command,
             clipboard_content: None,
             mime_type: None,
+            data: None,
+            url: None,
+            arguments: None,
         },
         metadata: None,
     })
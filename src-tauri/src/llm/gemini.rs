@@ -2,7 +2,7 @@
 //!
 //! Mirrors the Anthropic streaming implementation in classify.rs:
 //! - "action-menu-skeleton" emitted when contentType + summary are parsed
-//! - "action-menu-complete" emitted when full ActionMenu JSON is available
+//! - the parsed ActionMenu is returned; the pipeline emits "action-menu-complete"
 //!
 //! Key differences from Anthropic:
 //! - API key in URL query param, not header
@@ -28,11 +28,12 @@ const OUTPUT_COST_PER_MILLION: f64 = 0.40;
 /// Stream a CLASSIFY request through Gemini Flash.
 ///
 /// Same contract as `classify_streaming` in classify.rs:
-/// - Emits "action-menu-skeleton" as soon as contentType + summary are available
-/// - Emits "action-menu-complete" when the full JSON is parsed
+/// - Emits "action-menu-skeleton" as soon as contentType + summary are
+///   available, when there is an `app` to emit to (the CLI has none)
+/// - Returns the menu for the pipeline to finish and emit
 /// - Always returns a valid ActionMenu (fallback on any error)
 pub async fn classify_streaming_gemini(
-    app: &tauri::AppHandle,
+    app: Option<&tauri::AppHandle>,
     text: &str,
     has_table: bool,
     has_code: bool,
@@ -43,17 +44,13 @@ pub async fn classify_streaming_gemini(
         Ok(key) if !key.is_empty() => key,
         _ => {
             log::warn!("[LLM] No GEMINI_API_KEY set — returning fallback actions");
-            let menu = ActionMenu::fallback();
-            let _ = app.emit("action-menu-complete", &menu);
-            return menu;
+            return ActionMenu::fallback();
         }
     };
 
     if text.trim().is_empty() {
         log::warn!("[LLM] Empty OCR text — returning fallback actions");
-        let menu = ActionMenu::fallback();
-        let _ = app.emit("action-menu-complete", &menu);
-        return menu;
+        return ActionMenu::fallback();
     }
 
     let user_message = super::prompts::build_classify_message(text, confidence, has_table, has_code, plugin_tools);
@@ -103,9 +100,7 @@ pub async fn classify_streaming_gemini(
         Ok(resp) => resp,
         Err(e) => {
             log::error!("[LLM] HTTP request failed: {}", e);
-            let menu = ActionMenu::fallback();
-            let _ = app.emit("action-menu-complete", &menu);
-            return menu;
+            return ActionMenu::fallback();
         }
     };
 
@@ -113,9 +108,7 @@ pub async fn classify_streaming_gemini(
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
         log::error!("[LLM] Gemini API returned {}: {}", status, body);
-        let menu = ActionMenu::fallback();
-        let _ = app.emit("action-menu-complete", &menu);
-        return menu;
+        return ActionMenu::fallback();
     }
 
     let ttfb_ms = start.elapsed().as_millis();
@@ -161,7 +154,9 @@ pub async fn classify_streaming_gemini(
                                     "[LLM] Skeleton emitted at {}ms",
                                     start.elapsed().as_millis()
                                 );
-                                let _ = app.emit("action-menu-skeleton", &skeleton);
+                                if let Some(app) = app {
+                                    let _ = app.emit("action-menu-skeleton", &skeleton);
+                                }
                                 skeleton_emitted = true;
                             }
                         }
@@ -222,8 +217,7 @@ pub async fn classify_streaming_gemini(
         }
     };
 
-    let _ = app.emit("action-menu-complete", &menu);
-    menu
+    crate::mcp::tool_prefs::apply(menu, &crate::mcp::tool_prefs::load_prefs())
 }
 
 /// Extract text content from a Gemini SSE data payload.
@@ -16,10 +16,11 @@ use tauri::Emitter;
 
 /// CLASSIFY: local LLM version.
 ///
-/// Emits skeleton event, generates ActionMenu JSON via grammar-constrained
-/// local inference, then emits the complete event.
+/// Emits skeleton event (when there is an `app`), then generates
+/// ActionMenu JSON via local inference. The pipeline emits the complete
+/// event.
 pub async fn classify_local(
-    app: &tauri::AppHandle,
+    app: Option<&tauri::AppHandle>,
     text: &str,
     has_table: bool,
     has_code: bool,
@@ -30,13 +31,15 @@ pub async fn classify_local(
     let start = std::time::Instant::now();
 
     // Emit skeleton immediately so the UI shows a loading state
-    let _ = app.emit(
-        "action-menu-skeleton",
-        ActionMenuSkeleton {
-            content_type: "unknown".to_string(),
-            summary: "Analyzing with local model...".to_string(),
-        },
-    );
+    if let Some(app) = app {
+        let _ = app.emit(
+            "action-menu-skeleton",
+            ActionMenuSkeleton {
+                content_type: "unknown".to_string(),
+                summary: "Analyzing with local model...".to_string(),
+            },
+        );
+    }
 
     let prompt = prompts_local::build_local_classify_prompt(
         text, confidence, has_table, has_code, plugin_tools,
@@ -71,10 +74,7 @@ pub async fn classify_local(
         }
     };
 
-    // Emit completion event
-    let _ = app.emit("action-menu-complete", &menu);
-
-    menu
+    crate::mcp::tool_prefs::apply(menu, &crate::mcp::tool_prefs::load_prefs())
 }
 
 /// EXECUTE: local LLM version.
@@ -131,23 +131,19 @@ pub async fn generate_plugin_args_local(
     tool_description: &str,
     input_schema: &serde_json::Value,
     extracted_text: &str,
+    hints: &super::plugin_args::ArgsHints,
     state: &LocalLlmState,
-) -> Result<serde_json::Value, String> {
+) -> Result<String, String> {
     let schema_str = serde_json::to_string_pretty(input_schema).unwrap_or_default();
     let prompt = prompts_execute_local::build_local_args_prompt(
-        tool_name, tool_description, &schema_str, extracted_text,
+        tool_name, tool_description, &schema_str, extracted_text, &hints.render(),
     );
 
     // No strict grammar for args — schemas vary per plugin.
-    // Rely on the model's JSON instruction following.
-    let json_text = state.generate(&prompt, 512, None).await?;
-    let clean = streaming::strip_code_fences(&json_text);
-
-    let args: serde_json::Value = serde_json::from_str(&clean)
-        .map_err(|e| format!("Local model args parse failed: {}", e))?;
-
+    // Rely on the model's JSON instruction following; mcp::args validates.
+    let raw = state.generate(&prompt, 512, None).await?;
     log::info!("[LOCAL_ARGS] Generated args for '{}'", tool_name);
-    Ok(args)
+    Ok(raw)
 }
 
 /// TEXT_CMD: local LLM version.
@@ -239,6 +235,9 @@ fn salvage_flat_result(action_id: &str, raw: &str) -> ActionResult {
                     command: Some(cmd.to_string()),
                     clipboard_content: None,
                     mime_type: None,
+                    data: None,
+                    url: None,
+                    arguments: None,
                 },
                 metadata: None,
             };
@@ -257,6 +256,9 @@ fn salvage_flat_result(action_id: &str, raw: &str) -> ActionResult {
                     command: None,
                     clipboard_content: v.get("clipboardContent").and_then(|s| s.as_str()).map(|s| s.to_string()),
                     mime_type: None,
+                    data: None,
+                    url: None,
+                    arguments: None,
                 },
                 metadata: None,
             };
@@ -278,6 +280,9 @@ fn salvage_flat_result(action_id: &str, raw: &str) -> ActionResult {
                             command: Some(cmd),
                             clipboard_content: None,
                             mime_type: None,
+                            data: None,
+                            url: None,
+                            arguments: None,
                         },
                         metadata: None,
                     };
@@ -301,6 +306,9 @@ fn salvage_flat_result(action_id: &str, raw: &str) -> ActionResult {
                     command: Some(cmd),
                     clipboard_content: None,
                     mime_type: None,
+                    data: None,
+                    url: None,
+                    arguments: None,
                 },
                 metadata: None,
             };
@@ -83,6 +83,28 @@ impl LocalLlmState {
         Ok(())
     }
 
+    /// Load the default model if it is downloaded, otherwise the first
+    /// downloaded one. Fails when no model has been downloaded.
+    pub async fn load_default(&self) -> Result<(), String> {
+        use super::{model_manager, model_registry};
+        let default = model_registry::default_model();
+        let model = if model_manager::is_model_downloaded(default) {
+            Some(default)
+        } else {
+            model_registry::available_models()
+                .iter()
+                .find(|m| model_manager::is_model_downloaded(m))
+        };
+        let model = model.ok_or("No downloaded model found — download one in Settings")?;
+        let path = model_manager::model_path(model);
+        log::info!(
+            "[LOCAL_LLM] Loading model: {} from {}",
+            model.id,
+            path.display()
+        );
+        self.load(&path, model.id).await
+    }
+
     /// Check if a model is currently loaded.
     pub async fn is_loaded(&self) -> bool {
         self.model.lock().await.is_some()
@@ -11,6 +11,9 @@
 //! Shared:
 //!   - streaming.rs — SSE parsing + partial JSON extraction
 //!   - provider.rs  — provider metadata + configuration checks
+//!   - sampling.rs  — plain chat completion for plugin sampling requests
+//!   - tool_use.rs  — native tool calling (launcher agent mode)
+//!   - tool_use_gemini.rs — native tool calling on Gemini (routing + agent mode)
 
 mod classify;
 pub mod execute;
@@ -32,14 +35,18 @@ pub mod prompts_execute_local;
 #[cfg(feature = "local-llm")]
 pub mod prompts_local;
 pub mod prompts_text_command;
+pub mod sampling;
 pub mod streaming;
+pub mod tool_use;
+pub mod tool_use_gemini;
 pub mod types;
 
 pub use classify::{classify, classify_streaming};
 pub use execute::{execute_action_anthropic, ActionResult};
-pub use gemini::classify_streaming_gemini;
+pub use gemini::{classify_streaming_gemini, GEMINI_MODEL};
 pub use types::{ActionMenu, ActionMenuSkeleton};
 
+use crate::safety::injection::InjectionReport;
 use std::sync::Mutex;
 
 /// Thread-safe storage for the current ActionMenu result + OCR text.
@@ -49,6 +56,16 @@ pub struct ActionMenuState {
     pub ocr_text: Mutex<Option<String>>,
     /// Cropped PNG bytes — stored so execute_action can re-OCR with .accurate.
     pub crop_png: Mutex<Option<Vec<u8>>>,
+    /// Prompt-injection scan of the current snip's OCR text.
+    pub injection: Mutex<Option<InjectionReport>>,
+    /// History entry of the current snip, if history is enabled.
+    pub history_id: Mutex<Option<String>>,
+    /// Plugin tools the relevance filter offered the classifier for this snip.
+    pub offered_tools: Mutex<Option<Vec<String>>>,
+    /// Plugin actions whose manifest triggers fired for this snip.
+    pub triggered: Mutex<Vec<types::Action>>,
+    /// Recipe waiting for the user to review a step's arguments.
+    pub recipe_run: Mutex<Option<crate::recipes::runner::PendingRun>>,
 }
 
 impl ActionMenuState {
@@ -57,6 +74,11 @@ impl ActionMenuState {
             menu: Mutex::new(None),
             ocr_text: Mutex::new(None),
             crop_png: Mutex::new(None),
+            injection: Mutex::new(None),
+            history_id: Mutex::new(None),
+            offered_tools: Mutex::new(None),
+            triggered: Mutex::new(Vec::new()),
+            recipe_run: Mutex::new(None),
         }
     }
 }
@@ -4,8 +4,12 @@
 //! we need an LLM call to transform the raw OCR text into properly structured
 //! arguments. For example, a GitHub Issues tool expects `{title, body, repo}`
 //! — this module generates those from free-form screen text.
+//!
+//! Generation returns the model's raw output; `mcp::args` parses it,
+//! validates it against the schema and asks for a repair if needed.
 
 use crate::llm::streaming;
+use serde_json::{Map, Value};
 
 const ARGS_SYSTEM_PROMPT: &str = r#"You generate JSON arguments for a tool call. Given the tool's input schema and user-provided text, extract the relevant information and produce a JSON object that matches the schema exactly.
 
@@ -19,41 +23,95 @@ const ARGS_SYSTEM_PROMPT: &str = r#"You generate JSON arguments for a tool call.
 
 const ARGS_MAX_TOKENS: u32 = 512;
 
-/// Generate structured arguments for a plugin tool call.
+/// Extra context for argument generation beyond the schema and text.
+#[derive(Debug, Default)]
+pub struct ArgsHints {
+    /// Values from the plugin's saved config, keyed by schema property.
+    pub known_values: Map<String, Value>,
+    /// Set on a repair round-trip.
+    pub repair: Option<ArgsRepair>,
+}
+
+/// A previous attempt and why it was rejected.
+#[derive(Debug)]
+pub struct ArgsRepair {
+    pub previous_output: String,
+    pub errors: Vec<String>,
+}
+
+impl ArgsHints {
+    /// Prompt sections appended after the user text (empty if no hints).
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if !self.known_values.is_empty() {
+            out.push_str(&format!(
+                "\n\nSaved plugin settings (use these values unless the text clearly names others):\n{}",
+                Value::Object(self.known_values.clone())
+            ));
+        }
+        if let Some(repair) = &self.repair {
+            out.push_str(&format!(
+                "\n\nYour previous output was rejected:\n{}\n\nValidation errors:\n- {}\n\nReturn the corrected JSON object only.",
+                repair.previous_output,
+                repair.errors.join("\n- ")
+            ));
+        }
+        out
+    }
+}
+
+/// Build the user message shared by the Anthropic and local args prompts.
+pub fn build_args_message(
+    tool_name: &str,
+    tool_description: &str,
+    input_schema: &Value,
+    extracted_text: &str,
+    hints: &ArgsHints,
+) -> String {
+    let schema_str = serde_json::to_string_pretty(input_schema).unwrap_or_default();
+    format!(
+        "Tool: {}\nDescription: {}\n\nInput schema:\n{}\n\nUser text:\n{}{}",
+        tool_name,
+        tool_description,
+        schema_str,
+        extracted_text,
+        hints.render(),
+    )
+}
+
+/// Ask the model for arguments for a plugin tool call.
 ///
-/// Uses the LLM to transform free-form OCR text into a JSON object
-/// matching the tool's input schema. Falls back to `{text}` on failure.
+/// Returns the raw model output; parse it with `parse_args`.
 pub async fn generate_plugin_args(
     tool_name: &str,
     tool_description: &str,
-    input_schema: &serde_json::Value,
+    input_schema: &Value,
     extracted_text: &str,
-) -> Result<serde_json::Value, String> {
+    hints: &ArgsHints,
+) -> Result<String, String> {
     let api_key = std::env::var("ANTHROPIC_API_KEY")
         .map_err(|_| "No API key configured".to_string())?;
     if api_key.is_empty() {
         return Err("No API key configured".to_string());
     }
 
-    let schema_str = serde_json::to_string_pretty(input_schema).unwrap_or_default();
-    let user_message = format!(
-        "Tool: {}\nDescription: {}\n\nInput schema:\n{}\n\nUser text:\n{}",
-        tool_name, tool_description, schema_str, extracted_text,
-    );
+    let user_message =
+        build_args_message(tool_name, tool_description, input_schema, extracted_text, hints);
 
     log::info!(
-        "[ARGS_BRIDGE] Generating args for tool '{}' (schema has {} properties)",
+        "[ARGS_BRIDGE] Generating args for tool '{}' (schema has {} properties, repair={})",
         tool_name,
         input_schema
             .get("properties")
             .and_then(|p| p.as_object())
             .map(|m| m.len())
-            .unwrap_or(0)
+            .unwrap_or(0),
+        hints.repair.is_some()
     );
 
     let client = reqwest::Client::new();
     let resp = client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(super::provider::anthropic_messages_url())
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
@@ -76,22 +134,24 @@ pub async fn generate_plugin_args(
     }
 
     let body = resp.text().await.map_err(|e| e.to_string())?;
-    let response_text = extract_text_content(&body)?;
-    let json_text = streaming::strip_code_fences(&response_text);
-
-    let args: serde_json::Value = serde_json::from_str(&json_text)
-        .map_err(|e| format!("Failed to parse generated args: {}", e))?;
-
-    validate_required_fields(&args, input_schema)?;
+    extract_text_content(&body)
+}
 
-    log::info!("[ARGS_BRIDGE] Generated args for '{}': {}", tool_name, args);
+/// Parse model output (optionally code-fenced) into a JSON object.
+pub fn parse_args(raw: &str) -> Result<Value, String> {
+    let json_text = streaming::strip_code_fences(raw);
+    let args: Value = serde_json::from_str(&json_text)
+        .map_err(|e| format!("Output is not valid JSON: {}", e))?;
+    if !args.is_object() {
+        return Err("Output must be a JSON object".to_string());
+    }
     Ok(args)
 }
 
 /// Check if a tool's input schema is trivial (just `{text: string}` or empty).
 ///
 /// Trivial schemas don't need an LLM call — we pass `{text: ocr_text}` directly.
-pub fn is_trivial_schema(schema: &serde_json::Value) -> bool {
+pub fn is_trivial_schema(schema: &Value) -> bool {
     let props = schema.get("properties").and_then(|p| p.as_object());
     match props {
         None => true,
@@ -100,8 +160,8 @@ pub fn is_trivial_schema(schema: &serde_json::Value) -> bool {
 }
 
 /// Extract text content from an Anthropic Messages API response body.
-fn extract_text_content(body: &str) -> Result<String, String> {
-    let parsed: serde_json::Value =
+pub(crate) fn extract_text_content(body: &str) -> Result<String, String> {
+    let parsed: Value =
         serde_json::from_str(body).map_err(|e| format!("Invalid API response: {}", e))?;
     let content = parsed
         .get("content")
@@ -117,23 +177,31 @@ fn extract_text_content(body: &str) -> Result<String, String> {
     Err("No text content in response".to_string())
 }
 
-/// Validate that all required fields from the schema are present in the args.
-fn validate_required_fields(
-    args: &serde_json::Value,
-    schema: &serde_json::Value,
-) -> Result<(), String> {
-    let required = schema.get("required").and_then(|r| r.as_array());
-    if let Some(required_fields) = required {
-        let args_obj = args
-            .as_object()
-            .ok_or("Generated args must be a JSON object")?;
-        for field in required_fields {
-            if let Some(name) = field.as_str() {
-                if !args_obj.contains_key(name) {
-                    return Err(format!("Missing required field: {}", name));
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn hints_render_config_and_repair_sections() {
+        assert_eq!(ArgsHints::default().render(), "");
+        let hints = ArgsHints {
+            known_values: Map::from_iter([("repo".to_string(), json!("acme/app"))]),
+            repair: Some(ArgsRepair {
+                previous_output: "{\"title\": 3}".to_string(),
+                errors: vec!["/title: expected \"string\", got number".to_string()],
+            }),
+        };
+        let message = build_args_message("gh:create_issue", "Create", &json!({}), "crash", &hints);
+        assert!(message.contains("acme/app"));
+        assert!(message.contains("{\"title\": 3}"));
+        assert!(message.contains("- /title: expected"));
+    }
+
+    #[test]
+    fn parse_args_requires_an_object() {
+        assert!(parse_args("```json\n{\"a\": 1}\n```").is_ok());
+        assert!(parse_args("[1, 2]").is_err());
+        assert!(parse_args("sure! here you go").is_err());
     }
-    Ok(())
 }
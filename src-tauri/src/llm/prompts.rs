@@ -79,6 +79,7 @@ Respond with ONLY this JSON structure. No other text.
 /// Format from LLM Integration PRD Section 3.
 /// If `plugin_tools` is non-empty, it's injected as an `<available_plugins>`
 /// block so the LLM can include plugin actions in the ActionMenu.
+/// The OCR text is fenced as untrusted so on-screen instructions stay data.
 pub fn build_classify_message(
     text: &str,
     confidence: f64,
@@ -86,6 +87,7 @@ pub fn build_classify_message(
     has_code: bool,
     plugin_tools: &str,
 ) -> String {
+    let text = crate::safety::injection::fence_untrusted(text);
     let plugins_block = if plugin_tools.is_empty() {
         String::new()
     } else {
@@ -5,6 +5,8 @@
 //! Now we send the OCR text + action-specific instructions and get
 //! a structured result back.
 
+use crate::safety::injection::fence_untrusted;
+
 pub const EXECUTE_MAX_TOKENS: u32 = 2048;
 
 /// EXECUTE system prompt — instructs the LLM to perform a specific action
@@ -209,6 +211,7 @@ Summarize this output to directly answer the user's question."#
 
 /// Build the user message for an EXECUTE call by selecting the
 /// appropriate action template and filling in placeholders.
+/// The OCR text is fenced as untrusted so on-screen instructions stay data.
 pub fn build_execute_message(
     action_id: &str,
     extracted_text: &str,
@@ -225,7 +228,7 @@ pub fn build_execute_message(
     };
 
     template
-        .replace("{extracted_text}", extracted_text)
+        .replace("{extracted_text}", &fence_untrusted(extracted_text))
         .replace("{platform}", platform)
         .replace("{detected_shell}", "zsh")
         .replace("{target_language}", "English")
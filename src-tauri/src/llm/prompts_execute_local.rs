@@ -1,7 +1,10 @@
 //! ChatML-format EXECUTE prompts for local Qwen-2.5 models.
 //!
 //! Compact versions of the Anthropic EXECUTE prompts, optimized for
-//! smaller context windows and GBNF grammar enforcement.
+//! smaller context windows and GBNF grammar enforcement. Screen text is
+//! fenced as untrusted, as in the Anthropic prompts.
+
+use crate::safety::injection::fence_untrusted;
 
 /// EXECUTE system prompt — compact version for Qwen-2.5.
 /// Includes JSON example since grammar enforcement is disabled.
@@ -49,6 +52,7 @@ pub fn build_local_execute_prompt(
         }
     };
 
+    let extracted_text = fence_untrusted(extracted_text);
     let user_content = format!(
         "Action: {action_id}\nPlatform: {platform}\n\n{action_instruction}\n\nExtracted text:\n{extracted_text}"
     );
@@ -89,11 +93,13 @@ pub fn build_local_args_prompt(
     tool_description: &str,
     input_schema: &str,
     extracted_text: &str,
+    hints: &str,
 ) -> String {
     let system = "You generate JSON arguments for a tool call. Given the tool's input schema and user text, produce a JSON object matching the schema exactly. Output ONLY valid JSON.";
 
+    let extracted_text = fence_untrusted(extracted_text);
     let user_content = format!(
-        "Tool: {tool_name}\nDescription: {tool_description}\n\nInput schema:\n{input_schema}\n\nUser text:\n{extracted_text}"
+        "Tool: {tool_name}\nDescription: {tool_description}\n\nInput schema:\n{input_schema}\n\nUser text:\n{extracted_text}{hints}"
     );
 
     format!(
@@ -135,6 +141,7 @@ mod tests {
         let prompt = build_local_execute_prompt("explain_error", "NameError: foo", "macos");
         assert!(prompt.starts_with("<|im_start|>system\n"));
         assert!(prompt.contains("NameError: foo"));
+        assert!(prompt.contains("<untrusted_text>"));
         assert!(prompt.ends_with("<|im_start|>assistant\n"));
     }
 
@@ -147,7 +154,7 @@ mod tests {
 
     #[test]
     fn args_prompt_includes_schema() {
-        let prompt = build_local_args_prompt("create_issue", "Creates a GitHub issue", "{}", "error text");
+        let prompt = build_local_args_prompt("create_issue", "Creates a GitHub issue", "{}", "error text", "");
         assert!(prompt.contains("create_issue"));
         assert!(prompt.contains("error text"));
     }
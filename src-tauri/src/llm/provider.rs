@@ -63,3 +63,15 @@ pub fn is_provider_configured(provider_id: &str) -> bool {
 fn env_key_set(key: &str) -> bool {
     std::env::var(key).map(|k| !k.is_empty()).unwrap_or(false)
 }
+
+/// Anthropic Messages API endpoint.
+///
+/// `ANTHROPIC_BASE_URL` overrides the host (e.g. a proxy, or a mock server
+/// for end-to-end tests); defaults to `https://api.anthropic.com`.
+pub fn anthropic_messages_url() -> String {
+    let base = std::env::var("ANTHROPIC_BASE_URL")
+        .ok()
+        .filter(|b| !b.trim().is_empty())
+        .unwrap_or_else(|| "https://api.anthropic.com".to_string());
+    format!("{}/v1/messages", base.trim_end_matches('/'))
+}
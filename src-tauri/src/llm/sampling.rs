@@ -0,0 +1,180 @@
+//! Plain chat completion for plugin sampling requests.
+//!
+//! Plugins that hold the `sampling` permission can ask the app's model for a
+//! completion instead of shipping their own API key. `mcp::sampling` parses
+//! and redacts the request; this file only talks to the provider
+//! (Anthropic, Gemini, or a ChatML prompt for the local model).
+
+use serde_json::Value;
+
+/// One turn of a sampling conversation (text only).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    /// "user" or "assistant".
+    pub role: String,
+    pub text: String,
+}
+
+/// Run a completion on Anthropic Claude. Returns the reply text.
+pub async fn complete_anthropic(
+    system: Option<&str>,
+    messages: &[ChatMessage],
+    max_tokens: u32,
+) -> Result<String, String> {
+    let api_key = std::env::var("ANTHROPIC_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or("No API key configured")?;
+
+    let mut body = serde_json::json!({
+        "model": super::prompts::MODEL,
+        "max_tokens": max_tokens,
+        "messages": messages
+            .iter()
+            .map(|m| serde_json::json!({ "role": m.role, "content": m.text }))
+            .collect::<Vec<Value>>(),
+    });
+    if let Some(system) = system {
+        body["system"] = Value::String(system.to_string());
+    }
+
+    let resp = reqwest::Client::new()
+        .post(super::provider::anthropic_messages_url())
+        .header("x-api-key", &api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Sampling API call failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!(
+            "Sampling API error: {}",
+            &body[..200.min(body.len())]
+        ));
+    }
+
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    super::plugin_args::extract_text_content(&body)
+}
+
+/// Gemini `generateContent` body for a sampling conversation. Gemini calls
+/// the assistant role "model".
+pub fn gemini_request_body(
+    system: Option<&str>,
+    messages: &[ChatMessage],
+    max_tokens: u32,
+) -> Value {
+    let contents: Vec<Value> = messages
+        .iter()
+        .map(|m| {
+            let role = if m.role == "assistant" {
+                "model"
+            } else {
+                "user"
+            };
+            serde_json::json!({ "role": role, "parts": [{ "text": m.text }] })
+        })
+        .collect();
+    let mut body = serde_json::json!({
+        "contents": contents,
+        "generationConfig": { "maxOutputTokens": max_tokens },
+    });
+    if let Some(system) = system {
+        body["systemInstruction"] = serde_json::json!({ "parts": [{ "text": system }] });
+    }
+    body
+}
+
+/// Run a completion on Gemini Flash. Returns the reply text.
+pub async fn complete_gemini(
+    system: Option<&str>,
+    messages: &[ChatMessage],
+    max_tokens: u32,
+) -> Result<String, String> {
+    let api_key = std::env::var("GEMINI_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or("No API key configured")?;
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        super::gemini::GEMINI_MODEL,
+        api_key
+    );
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .header("content-type", "application/json")
+        .json(&gemini_request_body(system, messages, max_tokens))
+        .send()
+        .await
+        .map_err(|e| format!("Sampling API call failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!(
+            "Sampling API error: {}",
+            &body[..200.min(body.len())]
+        ));
+    }
+
+    let body: Value = resp.json().await.map_err(|e| e.to_string())?;
+    let parts = body
+        .pointer("/candidates/0/content/parts")
+        .and_then(|p| p.as_array())
+        .ok_or("No content in response")?;
+    Ok(parts
+        .iter()
+        .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+        .collect::<Vec<_>>()
+        .join(""))
+}
+
+/// Format a sampling conversation as a ChatML prompt for the local model.
+pub fn build_chatml_prompt(system: Option<&str>, messages: &[ChatMessage]) -> String {
+    let mut prompt = String::new();
+    if let Some(system) = system {
+        prompt.push_str(&format!("<|im_start|>system\n{}<|im_end|>\n", system));
+    }
+    for m in messages {
+        prompt.push_str(&format!("<|im_start|>{}\n{}<|im_end|>\n", m.role, m.text));
+    }
+    prompt.push_str("<|im_start|>assistant\n");
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chatml_prompt_ends_with_assistant_turn() {
+        let messages = vec![ChatMessage {
+            role: "user".to_string(),
+            text: "Hi".to_string(),
+        }];
+        let prompt = build_chatml_prompt(Some("Be brief."), &messages);
+        assert!(prompt.starts_with("<|im_start|>system\nBe brief.<|im_end|>\n"));
+        assert!(prompt.contains("<|im_start|>user\nHi<|im_end|>\n"));
+        assert!(prompt.ends_with("<|im_start|>assistant\n"));
+    }
+
+    #[test]
+    fn gemini_body_maps_roles_and_system() {
+        let messages = vec![
+            ChatMessage {
+                role: "user".to_string(),
+                text: "Hi".to_string(),
+            },
+            ChatMessage {
+                role: "assistant".to_string(),
+                text: "Hello".to_string(),
+            },
+        ];
+        let body = gemini_request_body(Some("Be brief."), &messages, 64);
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 64);
+    }
+}
@@ -0,0 +1,271 @@
+//! Native tool calling on the Anthropic and Gemini APIs.
+//!
+//! Tools go to the model as native definitions with their input schemas,
+//! and the reply's tool calls come back as `ToolCall`s with their arguments
+//! already structured — no routing JSON to parse out of text.
+//!
+//! - Anthropic (`complete_with_tools`): `tools` / `tool_use` blocks. The
+//!   conversation is kept as raw Messages API turns so assistant content
+//!   (including `tool_use` blocks) can be sent back verbatim.
+//! - Gemini (`tool_use_gemini`, re-exported as `complete_with_tools_gemini`):
+//!   `functionDeclarations` / `functionCall` parts over the same message
+//!   history, converted to Gemini `contents` per request.
+//!   Schemas are cut down to the OpenAPI subset Gemini accepts.
+//!
+//! Wire names must match `^[a-zA-Z0-9_-]{1,64}$`, which qualified plugin
+//! names ("com.example.gh:close_issue") don't; `wire_name` maps them.
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+pub use super::tool_use_gemini::complete_with_tools_gemini;
+
+/// Longest tool name the API accepts.
+const MAX_WIRE_NAME: usize = 64;
+
+/// One tool offered to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolSpec {
+    /// Name on the wire (see `wire_name`).
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the arguments; always an object schema.
+    pub input_schema: Value,
+}
+
+/// A tool the model asked to call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    /// `tool_use` block id, echoed in the matching `tool_result`.
+    pub id: String,
+    pub name: String,
+    pub input: Value,
+}
+
+/// One model reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelTurn {
+    /// Text blocks, joined.
+    pub text: String,
+    pub calls: Vec<ToolCall>,
+    /// The reply's content blocks, to append as the assistant turn.
+    pub content: Value,
+}
+
+/// A wire-safe name for `qualified`, unique among `taken`.
+pub fn wire_name(qualified: &str, taken: &HashSet<String>) -> String {
+    let base: String = qualified
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_WIRE_NAME)
+        .collect();
+    let base = if base.is_empty() {
+        "tool".to_string()
+    } else {
+        base
+    };
+    if !taken.contains(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!("_{}", n);
+            let keep = base.len().min(MAX_WIRE_NAME - suffix.len());
+            format!("{}{}", &base[..keep], suffix)
+        })
+        .find(|name| !taken.contains(name))
+        .unwrap_or(base)
+}
+
+/// The schema for tools that take the text as is — the shape the args
+/// bridge falls back to for tools without an input schema.
+pub fn text_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": { "text": { "type": "string" } },
+        "required": ["text"]
+    })
+}
+
+/// `tools` array for the request body.
+pub fn tool_definitions(tools: &[ToolSpec]) -> Value {
+    Value::Array(
+        tools
+            .iter()
+            .map(|t| {
+                json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "input_schema": t.input_schema,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// A `tool_result` block answering `tool_use_id`.
+pub fn tool_result(tool_use_id: &str, content: &str, is_error: bool) -> Value {
+    let mut block = json!({
+        "type": "tool_result",
+        "tool_use_id": tool_use_id,
+        "content": content,
+    });
+    if is_error {
+        block["is_error"] = Value::Bool(true);
+    }
+    block
+}
+
+/// Parse a Messages API response body.
+pub fn parse_turn(body: &str) -> Result<ModelTurn, String> {
+    let parsed: Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid response: {}", e))?;
+    let content = parsed
+        .get("content")
+        .and_then(|c| c.as_array())
+        .ok_or("No content in response")?;
+
+    let mut texts = Vec::new();
+    let mut calls = Vec::new();
+    for block in content {
+        match block.get("type").and_then(|t| t.as_str()) {
+            Some("text") => {
+                if let Some(t) = block.get("text").and_then(|t| t.as_str()) {
+                    texts.push(t.to_string());
+                }
+            }
+            Some("tool_use") => {
+                let field = |name: &str| {
+                    block
+                        .get(name)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                calls.push(ToolCall {
+                    id: field("id"),
+                    name: field("name"),
+                    input: block.get("input").cloned().unwrap_or_else(|| json!({})),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(ModelTurn {
+        text: texts.join("\n").trim().to_string(),
+        calls,
+        content: Value::Array(content.clone()),
+    })
+}
+
+/// Run one turn on Anthropic Claude with `tools` available. With
+/// `allow_tools` false the tools stay defined (the history may contain
+/// `tool_use` blocks) but the model must answer in text.
+pub async fn complete_with_tools(
+    system: &str,
+    messages: &[Value],
+    tools: &[ToolSpec],
+    allow_tools: bool,
+    max_tokens: u32,
+) -> Result<ModelTurn, String> {
+    let api_key = std::env::var("ANTHROPIC_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or("No API key configured")?;
+
+    let mut body = json!({
+        "model": super::prompts::MODEL,
+        "max_tokens": max_tokens,
+        "system": system,
+        "messages": messages,
+    });
+    if !tools.is_empty() {
+        body["tools"] = tool_definitions(tools);
+        body["tool_choice"] = json!({ "type": if allow_tools { "auto" } else { "none" } });
+    }
+
+    let resp = reqwest::Client::new()
+        .post(super::provider::anthropic_messages_url())
+        .header("x-api-key", &api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("API call failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("API error: {}", &body[..200.min(body.len())]));
+    }
+
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    parse_turn(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_names_are_sanitized_and_unique() {
+        let mut taken = HashSet::new();
+        let first = wire_name("com.example.gh:close_issue", &taken);
+        assert_eq!(first, "com_example_gh_close_issue");
+        taken.insert(first);
+        assert_eq!(
+            wire_name("com.example-gh:close.issue", &taken),
+            "com_example-gh_close_issue"
+        );
+        assert_eq!(
+            wire_name("com.example:gh.close_issue", &taken),
+            "com_example_gh_close_issue_2"
+        );
+
+        let long = "x".repeat(80);
+        let name = wire_name(&long, &taken);
+        assert_eq!(name.len(), MAX_WIRE_NAME);
+        taken.insert(name);
+        let again = wire_name(&long, &taken);
+        assert_eq!(again.len(), MAX_WIRE_NAME);
+        assert!(again.ends_with("_2"));
+    }
+
+    #[test]
+    fn parse_turn_splits_text_and_tool_calls() {
+        let body = r#"{"content":[
+            {"type":"text","text":"Checking the issue."},
+            {"type":"tool_use","id":"toolu_1","name":"gh_get_issue","input":{"number":7}}
+        ],"stop_reason":"tool_use"}"#;
+        let turn = parse_turn(body).unwrap();
+        assert_eq!(turn.text, "Checking the issue.");
+        assert_eq!(
+            turn.calls,
+            [ToolCall {
+                id: "toolu_1".to_string(),
+                name: "gh_get_issue".to_string(),
+                input: json!({"number": 7}),
+            }]
+        );
+        assert_eq!(turn.content.as_array().unwrap().len(), 2);
+
+        let answer = parse_turn(r#"{"content":[{"type":"text","text":"Done."}]}"#).unwrap();
+        assert!(answer.calls.is_empty());
+        assert!(parse_turn(r#"{"error":"x"}"#).is_err());
+    }
+
+    #[test]
+    fn tool_results_mark_errors_only_when_set() {
+        let ok = tool_result("toolu_1", "42", false);
+        assert!(ok.get("is_error").is_none());
+        let err = tool_result("toolu_1", "blocked", true);
+        assert_eq!(err["is_error"], json!(true));
+        assert_eq!(err["tool_use_id"], json!("toolu_1"));
+    }
+}
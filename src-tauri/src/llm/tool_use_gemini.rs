@@ -0,0 +1,299 @@
+//! Native tool calling on the Gemini API, for launcher routing and agent
+//! mode.
+//!
+//! Tools go out as `functionDeclarations` and calls come back as
+//! `functionCall` parts, mapped onto the same `ToolSpec` / `ToolCall` /
+//! `ModelTurn` types as `tool_use`. The conversation stays in Messages API
+//! shape and is converted per request (`gemini_contents`).
+
+use super::tool_use::{ModelTurn, ToolCall, ToolSpec};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Schema keywords Gemini's function declarations accept.
+const GEMINI_SCHEMA_KEYS: &[&str] = &[
+    "type",
+    "format",
+    "description",
+    "nullable",
+    "enum",
+    "properties",
+    "required",
+    "items",
+    "minItems",
+    "maxItems",
+    "minimum",
+    "maximum",
+];
+
+/// `schema` in the OpenAPI subset Gemini accepts: unsupported keywords are
+/// dropped and `["string", "null"]` types become `nullable`. Arguments are
+/// still validated against the full schema afterwards.
+pub fn gemini_schema(schema: &Value) -> Value {
+    let Some(obj) = schema.as_object() else {
+        return schema.clone();
+    };
+    let mut out = serde_json::Map::new();
+    for (key, value) in obj {
+        if !GEMINI_SCHEMA_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let value = match key.as_str() {
+            "type" => match value.as_array() {
+                Some(types) => nullable_type(types, &mut out),
+                None => value.clone(),
+            },
+            "properties" => Value::Object(
+                value
+                    .as_object()
+                    .map(|props| {
+                        props
+                            .iter()
+                            .map(|(name, prop)| (name.clone(), gemini_schema(prop)))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+            "items" => gemini_schema(value),
+            _ => value.clone(),
+        };
+        out.insert(key.clone(), value);
+    }
+    Value::Object(out)
+}
+
+/// The non-null type of a `["integer", "null"]` union, marking `out`
+/// nullable when the union allowed null.
+fn nullable_type(types: &[Value], out: &mut serde_json::Map<String, Value>) -> Value {
+    if types.iter().any(|t| t == "null") {
+        out.insert("nullable".to_string(), Value::Bool(true));
+    }
+    types
+        .iter()
+        .find(|t| *t != "null")
+        .cloned()
+        .unwrap_or_else(|| json!("string"))
+}
+
+/// `tools` array for a Gemini request body.
+pub fn gemini_tool_definitions(tools: &[ToolSpec]) -> Value {
+    let declarations: Vec<Value> = tools
+        .iter()
+        .map(|t| {
+            json!({
+                "name": t.name,
+                "description": t.description,
+                "parameters": gemini_schema(&t.input_schema),
+            })
+        })
+        .collect();
+    json!([{ "functionDeclarations": declarations }])
+}
+
+/// Parse a Gemini `generateContent` response body. Function calls carry no
+/// id, so they are numbered in order.
+pub fn parse_gemini_turn(body: &str) -> Result<ModelTurn, String> {
+    let parsed: Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid response: {}", e))?;
+    let parts = parsed
+        .pointer("/candidates/0/content/parts")
+        .and_then(|p| p.as_array())
+        .ok_or("No content in response")?;
+
+    let mut texts = Vec::new();
+    let mut calls = Vec::new();
+    for part in parts {
+        if let Some(t) = part.get("text").and_then(|t| t.as_str()) {
+            texts.push(t.to_string());
+        }
+        if let Some(call) = part.get("functionCall") {
+            calls.push(ToolCall {
+                id: format!("call_{}", calls.len() + 1),
+                name: call
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                input: call.get("args").cloned().unwrap_or_else(|| json!({})),
+            });
+        }
+    }
+    Ok(ModelTurn {
+        text: texts.join("\n").trim().to_string(),
+        calls,
+        content: Value::Array(parts.clone()),
+    })
+}
+
+/// Anthropic-shaped `messages` as Gemini `contents`. Assistant turns
+/// already hold Gemini parts; a `tool_result` becomes a `functionResponse`
+/// named after the call it answers in the model turn before it.
+pub fn gemini_contents(messages: &[Value]) -> Vec<Value> {
+    let mut names = HashMap::new();
+    messages
+        .iter()
+        .map(|message| {
+            let content = &message["content"];
+            if message["role"] == "assistant" {
+                names = call_names(content);
+                return json!({ "role": "model", "parts": content });
+            }
+            let parts: Vec<Value> = match content.as_array() {
+                Some(blocks) => blocks
+                    .iter()
+                    .map(|b| function_response(b, &names))
+                    .collect(),
+                None => vec![json!({ "text": content.as_str().unwrap_or_default() })],
+            };
+            json!({ "role": "user", "parts": parts })
+        })
+        .collect()
+}
+
+/// Call id → function name for a model turn's parts, numbered as
+/// `parse_gemini_turn` numbers them.
+fn call_names(parts: &Value) -> HashMap<String, String> {
+    parts
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part.pointer("/functionCall/name")?.as_str())
+        .enumerate()
+        .map(|(i, name)| (format!("call_{}", i + 1), name.to_string()))
+        .collect()
+}
+
+/// A `tool_result` block as a `functionResponse` part.
+fn function_response(block: &Value, names: &HashMap<String, String>) -> Value {
+    let id = block["tool_use_id"].as_str().unwrap_or_default();
+    json!({ "functionResponse": {
+        "name": names.get(id).map(String::as_str).unwrap_or(id),
+        "response": { "content": block["content"], "isError": block["is_error"] == true },
+    } })
+}
+
+/// Run one turn on Gemini Flash with `tools` available; the Gemini
+/// counterpart of `tool_use::complete_with_tools`.
+pub async fn complete_with_tools_gemini(
+    system: &str,
+    messages: &[Value],
+    tools: &[ToolSpec],
+    allow_tools: bool,
+    max_tokens: u32,
+) -> Result<ModelTurn, String> {
+    let api_key = std::env::var("GEMINI_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or("No API key configured")?;
+
+    let mut body = json!({
+        "systemInstruction": { "parts": [{ "text": system }] },
+        "contents": gemini_contents(messages),
+        "generationConfig": { "maxOutputTokens": max_tokens, "temperature": 0.1 },
+    });
+    if !tools.is_empty() {
+        let mode = if allow_tools { "AUTO" } else { "NONE" };
+        body["tools"] = gemini_tool_definitions(tools);
+        body["toolConfig"] = json!({ "functionCallingConfig": { "mode": mode } });
+    }
+
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        super::gemini::GEMINI_MODEL,
+        api_key
+    );
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("API call failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("API error: {}", &body[..200.min(body.len())]));
+    }
+
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    parse_gemini_turn(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gemini_schema_keeps_the_supported_subset() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "additionalProperties": false,
+            "required": ["title"],
+            "properties": {
+                "title": { "type": "string", "minLength": 1, "description": "Issue title" },
+                "labels": { "type": "array", "items": { "type": "string", "pattern": "^[a-z]+$" } },
+                "milestone": { "type": ["integer", "null"] }
+            }
+        });
+        assert_eq!(
+            gemini_schema(&schema),
+            json!({
+                "type": "object",
+                "required": ["title"],
+                "properties": {
+                    "title": { "type": "string", "description": "Issue title" },
+                    "labels": { "type": "array", "items": { "type": "string" } },
+                    "milestone": { "type": "integer", "nullable": true }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_gemini_turn_numbers_function_calls() {
+        let body = r#"{"candidates":[{"content":{"role":"model","parts":[
+            {"functionCall":{"name":"translate_text","args":{"text":"hola"}}}
+        ]}}]}"#;
+        let turn = parse_gemini_turn(body).unwrap();
+        assert_eq!(turn.text, "");
+        assert_eq!(
+            turn.calls,
+            [ToolCall {
+                id: "call_1".to_string(),
+                name: "translate_text".to_string(),
+                input: json!({"text": "hola"}),
+            }]
+        );
+
+        let answer =
+            parse_gemini_turn(r#"{"candidates":[{"content":{"parts":[{"text":"Paris."}]}}]}"#)
+                .unwrap();
+        assert_eq!(answer.text, "Paris.");
+        assert!(answer.calls.is_empty());
+        assert!(parse_gemini_turn(r#"{"candidates":[]}"#).is_err());
+    }
+
+    #[test]
+    fn gemini_contents_name_function_responses() {
+        let messages = [
+            json!({ "role": "user", "content": "Translate hola" }),
+            json!({ "role": "assistant", "content": [
+                { "functionCall": { "name": "translate_text", "args": { "text": "hola" } } }
+            ] }),
+            json!({ "role": "user", "content": [
+                { "type": "tool_result", "tool_use_id": "call_1", "content": "hello" }
+            ] }),
+        ];
+        let contents = gemini_contents(&messages);
+        assert_eq!(
+            contents[0],
+            json!({ "role": "user", "parts": [{ "text": "Translate hola" }] })
+        );
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"],
+            json!({ "name": "translate_text", "response": { "content": "hello", "isError": false } })
+        );
+    }
+}
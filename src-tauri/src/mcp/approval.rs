@@ -5,7 +5,8 @@
 //! `~/Library/Application Support/omni-glass/plugin-approvals.json`).
 //!
 //! Re-prompts the user when a plugin's permissions change (detected by
-//! comparing SHA-256 hashes of the serialized permissions).
+//! comparing SHA-256 hashes of the serialized permissions) or when its entry
+//! file's content changes (SHA-256 of the file, pinned at approval).
 
 use crate::mcp::manifest::{Permissions, PluginManifest};
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,8 @@ pub enum ApprovalStatus {
     Denied,
     NeedsApproval,
     PermissionsChanged,
+    /// Same permissions, but the entry file differs from the approved one.
+    CodeChanged,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -36,6 +39,10 @@ pub struct ApprovalRecord {
     pub version: String,
     pub permissions_hash: String,
     pub approved_at: String,
+    /// `sha256:<hex>` of the entry file when approved. Missing in records
+    /// written before code pinning; the loader fills it in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,7 +81,13 @@ pub fn save_approvals(store: &ApprovalStore) -> Result<(), String> {
 }
 
 /// Check whether a plugin is approved, denied, or needs a prompt.
-pub fn check_approval(store: &ApprovalStore, manifest: &PluginManifest) -> ApprovalStatus {
+///
+/// `entry_hash` is the current `signing::entry_hash` of the plugin.
+pub fn check_approval(
+    store: &ApprovalStore,
+    manifest: &PluginManifest,
+    entry_hash: &str,
+) -> ApprovalStatus {
     // Check denied first
     if store.denied.contains_key(&manifest.id) {
         return ApprovalStatus::Denied;
@@ -83,17 +96,20 @@ pub fn check_approval(store: &ApprovalStore, manifest: &PluginManifest) -> Appro
     // Check approved
     if let Some(record) = store.approved.get(&manifest.id) {
         let current_hash = hash_permissions(&manifest.permissions);
-        if record.permissions_hash == current_hash {
-            return ApprovalStatus::Approved;
+        if record.permissions_hash != current_hash {
+            return ApprovalStatus::PermissionsChanged;
         }
-        return ApprovalStatus::PermissionsChanged;
+        if record.entry_hash.as_deref().is_some_and(|h| h != entry_hash) {
+            return ApprovalStatus::CodeChanged;
+        }
+        return ApprovalStatus::Approved;
     }
 
     ApprovalStatus::NeedsApproval
 }
 
-/// Record an approval decision.
-pub fn record_approval(store: &mut ApprovalStore, manifest: &PluginManifest) {
+/// Record an approval decision, pinning the approved entry file.
+pub fn record_approval(store: &mut ApprovalStore, manifest: &PluginManifest, entry_hash: &str) {
     store.denied.remove(&manifest.id);
     store.approved.insert(
         manifest.id.clone(),
@@ -101,10 +117,23 @@ pub fn record_approval(store: &mut ApprovalStore, manifest: &PluginManifest) {
             version: manifest.version.clone(),
             permissions_hash: hash_permissions(&manifest.permissions),
             approved_at: chrono_now(),
+            entry_hash: Some(entry_hash.to_string()),
         },
     );
 }
 
+/// Pin the entry hash on an approval made before code pinning existed.
+/// Returns whether the store changed.
+pub fn pin_entry_hash(store: &mut ApprovalStore, plugin_id: &str, entry_hash: &str) -> bool {
+    match store.approved.get_mut(plugin_id) {
+        Some(record) if record.entry_hash.is_none() => {
+            record.entry_hash = Some(entry_hash.to_string());
+            true
+        }
+        _ => false,
+    }
+}
+
 /// Record a denial decision.
 pub fn record_denial(store: &mut ApprovalStore, plugin_id: &str) {
     store.approved.remove(plugin_id);
@@ -116,6 +145,12 @@ pub fn record_denial(store: &mut ApprovalStore, plugin_id: &str) {
     );
 }
 
+/// Forget any decision about a plugin, so it is prompted for again.
+pub fn forget(store: &mut ApprovalStore, plugin_id: &str) {
+    store.approved.remove(plugin_id);
+    store.denied.remove(plugin_id);
+}
+
 /// SHA-256 hash of the serialized permissions. Deterministic because
 /// Permissions is a struct (not HashMap), so field order is stable.
 pub fn hash_permissions(permissions: &Permissions) -> String {
@@ -125,7 +160,7 @@ pub fn hash_permissions(permissions: &Permissions) -> String {
 }
 
 /// ISO 8601 timestamp (simplified — no chrono crate dependency).
-fn chrono_now() -> String {
+pub(crate) fn chrono_now() -> String {
     let dur = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default();
@@ -136,16 +171,16 @@ fn chrono_now() -> String {
 mod tests {
     use super::*;
 
+    const HASH: &str = "sha256:00";
+
     fn test_manifest(perms: Permissions) -> PluginManifest {
         PluginManifest {
             id: "com.test.plugin".to_string(),
             name: "Test".to_string(),
             version: "1.0.0".to_string(),
-            description: String::new(),
-            runtime: crate::mcp::manifest::Runtime::Node,
             entry: "index.js".to_string(),
             permissions: perms,
-            configuration: None,
+            ..Default::default()
         }
     }
 
@@ -153,22 +188,22 @@ mod tests {
     fn new_plugin_needs_approval() {
         let store = ApprovalStore::default();
         let manifest = test_manifest(Permissions::default());
-        assert_eq!(check_approval(&store, &manifest), ApprovalStatus::NeedsApproval);
+        assert_eq!(check_approval(&store, &manifest, HASH), ApprovalStatus::NeedsApproval);
     }
 
     #[test]
     fn approved_plugin_loads_silently() {
         let mut store = ApprovalStore::default();
         let manifest = test_manifest(Permissions::default());
-        record_approval(&mut store, &manifest);
-        assert_eq!(check_approval(&store, &manifest), ApprovalStatus::Approved);
+        record_approval(&mut store, &manifest, HASH);
+        assert_eq!(check_approval(&store, &manifest, HASH), ApprovalStatus::Approved);
     }
 
     #[test]
     fn permission_change_triggers_reprompt() {
         let mut store = ApprovalStore::default();
         let manifest = test_manifest(Permissions::default());
-        record_approval(&mut store, &manifest);
+        record_approval(&mut store, &manifest, HASH);
 
         // Change permissions
         let new_manifest = test_manifest(Permissions {
@@ -176,7 +211,7 @@ mod tests {
             ..Default::default()
         });
         assert_eq!(
-            check_approval(&store, &new_manifest),
+            check_approval(&store, &new_manifest, HASH),
             ApprovalStatus::PermissionsChanged
         );
     }
@@ -186,7 +221,7 @@ mod tests {
         let mut store = ApprovalStore::default();
         record_denial(&mut store, "com.test.plugin");
         let manifest = test_manifest(Permissions::default());
-        assert_eq!(check_approval(&store, &manifest), ApprovalStatus::Denied);
+        assert_eq!(check_approval(&store, &manifest, HASH), ApprovalStatus::Denied);
     }
 
     #[test]
@@ -197,4 +232,49 @@ mod tests {
         assert_eq!(h1, h2);
         assert!(h1.starts_with("sha256:"));
     }
+
+    #[test]
+    fn requesting_sampling_needs_reapproval() {
+        let mut store = ApprovalStore::default();
+        let manifest = test_manifest(Permissions::default());
+        record_approval(&mut store, &manifest, HASH);
+        // Absent `sampling` serializes as before, so old approvals still match
+        assert!(!serde_json::to_string(&manifest.permissions)
+            .unwrap()
+            .contains("sampling"));
+        let sampling = test_manifest(Permissions { sampling: true, ..Default::default() });
+        assert_eq!(
+            check_approval(&store, &sampling, HASH),
+            ApprovalStatus::PermissionsChanged
+        );
+    }
+
+    #[test]
+    fn changed_entry_file_triggers_reprompt() {
+        let mut store = ApprovalStore::default();
+        let manifest = test_manifest(Permissions::default());
+        record_approval(&mut store, &manifest, HASH);
+        assert_eq!(
+            check_approval(&store, &manifest, "sha256:ff"),
+            ApprovalStatus::CodeChanged
+        );
+    }
+
+    #[test]
+    fn legacy_approvals_get_pinned() {
+        let mut store = ApprovalStore::default();
+        let manifest = test_manifest(Permissions::default());
+        record_approval(&mut store, &manifest, HASH);
+        store.approved.get_mut("com.test.plugin").unwrap().entry_hash = None;
+        assert_eq!(
+            check_approval(&store, &manifest, "sha256:ff"),
+            ApprovalStatus::Approved
+        );
+        assert!(pin_entry_hash(&mut store, "com.test.plugin", "sha256:ff"));
+        assert!(!pin_entry_hash(&mut store, "com.test.plugin", "sha256:ee"));
+        assert_eq!(
+            check_approval(&store, &manifest, "sha256:ee"),
+            ApprovalStatus::CodeChanged
+        );
+    }
 }
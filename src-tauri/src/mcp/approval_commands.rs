@@ -3,11 +3,14 @@
 //! These commands are called by the permission-prompt.ts frontend
 //! to display pending plugin approvals and record user decisions.
 
+use crate::audit::AuditKind;
 use crate::mcp::approval;
 use crate::mcp::loader::PendingApprovals;
 use crate::mcp::manifest::PluginManifest;
 use crate::mcp::registry::ToolRegistry;
 use crate::mcp::sandbox::risk::{self, RiskLevel};
+use crate::mcp::signing::{self, Publisher};
+use crate::mcp::trust;
 use serde::Serialize;
 
 /// Plugin info sent to the permission prompt UI.
@@ -20,7 +23,9 @@ pub struct PendingPlugin {
     pub description: String,
     pub permissions: crate::mcp::manifest::Permissions,
     pub risk_level: RiskLevel,
-    pub is_update: bool, // true if PermissionsChanged (not NeedsApproval)
+    pub is_update: bool, // true if PermissionsChanged / CodeChanged (not NeedsApproval)
+    /// Signature status; the name is only trustworthy when `Verified`.
+    pub publisher: Publisher,
 }
 
 /// Get all plugins awaiting user approval.
@@ -29,9 +34,10 @@ pub async fn get_pending_approvals(
     state: tauri::State<'_, PendingApprovals>,
 ) -> Result<Vec<PendingPlugin>, String> {
     let queue = state.queue.lock().await;
+    let trust = trust::load_trust();
     Ok(queue
         .iter()
-        .map(|(manifest, _dir, is_update)| PendingPlugin {
+        .map(|(manifest, dir, is_update)| PendingPlugin {
             id: manifest.id.clone(),
             name: manifest.name.clone(),
             version: manifest.version.clone(),
@@ -39,6 +45,8 @@ pub async fn get_pending_approvals(
             permissions: manifest.permissions.clone(),
             risk_level: risk::calculate_risk(&manifest.permissions),
             is_update: *is_update,
+            // Files that fail verification are refused again on approval
+            publisher: signing::verify(manifest, dir, &trust).unwrap_or(Publisher::Unsigned),
         })
         .collect())
 }
@@ -69,16 +77,39 @@ pub async fn approve_plugin(
 
     if approved {
         if let Some((manifest, plugin_dir, _)) = entry {
-            approval::record_approval(&mut store, &manifest);
+            // Re-check the files: they may have changed while the prompt was open
+            let trust = trust::load_trust();
+            let publisher = signing::verify(&manifest, &plugin_dir, &trust)?;
+            trust.check_policy(&manifest.id, &publisher)?;
+            let entry_hash = signing::entry_hash(&manifest, &plugin_dir)?;
+            approval::record_approval(&mut store, &manifest, &entry_hash);
             approval::save_approvals(&store)?;
 
             // Load the plugin now
+            crate::audit::record_async(
+                AuditKind::PluginApproval,
+                plugin_id.clone(),
+                serde_json::json!({
+                    "decision": "approved",
+                    "version": manifest.version,
+                    "permissionsHash": approval::hash_permissions(&manifest.permissions),
+                    "entryHash": entry_hash,
+                    "publisher": publisher,
+                }),
+            )
+            .await;
             crate::mcp::loader::load_approved_plugin(&manifest, &plugin_dir, &registry).await?;
             log::info!("[APPROVAL] Plugin '{}' approved and loaded", plugin_id);
         }
     } else {
         approval::record_denial(&mut store, &plugin_id);
         approval::save_approvals(&store)?;
+        crate::audit::record_async(
+            AuditKind::PluginApproval,
+            plugin_id.clone(),
+            serde_json::json!({"decision": "denied"}),
+        )
+        .await;
         log::info!("[APPROVAL] Plugin '{}' denied", plugin_id);
     }
 
@@ -96,6 +127,7 @@ impl From<&PluginManifest> for PendingPlugin {
             permissions: m.permissions.clone(),
             risk_level: risk::calculate_risk(&m.permissions),
             is_update: false,
+            publisher: Publisher::Unsigned,
         }
     }
 }
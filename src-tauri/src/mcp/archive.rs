@@ -0,0 +1,129 @@
+//! Plugin archive extraction (`.tar.gz`, `.tgz`, `.zip`).
+//!
+//! Uses the system `tar` (and `unzip` for zip files when present; bsdtar on
+//! macOS and Windows reads zip too) rather than pulling in archive crates.
+//! Entry names are listed and checked before anything is extracted, and
+//! symbolic links are refused afterwards, so an archive can't write or point
+//! outside its staging directory.
+
+use crate::mcp::integrity::is_contained;
+use std::path::Path;
+use std::process::Command;
+
+/// Archive formats the installer accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    TarGz,
+    Zip,
+}
+
+/// Detect an archive by file name.
+pub fn kind_of(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+/// Run a tool and return its stdout, or its stderr as the error.
+pub(crate) fn run(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run {:?}: {}", command.get_program(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{:?} failed: {}",
+            command.get_program(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Extract `archive` into `dest` (which must exist and be empty).
+pub fn extract(archive: &Path, dest: &Path) -> Result<(), String> {
+    let kind = kind_of(archive)
+        .ok_or_else(|| format!("{} is not a .tar.gz or .zip file", archive.display()))?;
+    let use_unzip = kind == ArchiveKind::Zip && which::which("unzip").is_ok();
+
+    let listing = if use_unzip {
+        run(Command::new("unzip").arg("-Z1").arg(archive))?
+    } else {
+        run(Command::new("tar").arg("-tf").arg(archive))?
+    };
+    for name in listing.lines().filter(|l| !l.is_empty()) {
+        if !is_contained(name.trim_end_matches('/')) {
+            return Err(format!(
+                "Archive entry '{}' escapes the plugin directory",
+                name
+            ));
+        }
+    }
+
+    if use_unzip {
+        run(Command::new("unzip")
+            .arg("-q")
+            .arg(archive)
+            .arg("-d")
+            .arg(dest))?;
+    } else {
+        run(Command::new("tar")
+            .arg("-xf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dest))?;
+    }
+    reject_links(dest)
+}
+
+/// Fail if `dir` contains a symbolic link anywhere below it.
+pub fn reject_links(dir: &Path) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Cannot stat {}: {}", entry.path().display(), e))?;
+        if file_type.is_symlink() {
+            return Err(format!(
+                "Plugin contains a symbolic link: {}",
+                entry.path().display()
+            ));
+        }
+        if file_type.is_dir() {
+            reject_links(&entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_archive_kinds_by_name() {
+        assert_eq!(kind_of(Path::new("p-1.0.tar.gz")), Some(ArchiveKind::TarGz));
+        assert_eq!(kind_of(Path::new("P.TGZ")), Some(ArchiveKind::TarGz));
+        assert_eq!(kind_of(Path::new("p.zip")), Some(ArchiveKind::Zip));
+        assert_eq!(kind_of(Path::new("p.tar")), None);
+        assert_eq!(kind_of(Path::new("plugin-dir")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_symlinks() {
+        let dir = std::env::temp_dir().join("og-archive-links-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::write(dir.join("lib/a.js"), "").unwrap();
+        assert!(reject_links(&dir).is_ok());
+        std::os::unix::fs::symlink("/etc/passwd", dir.join("lib/passwd")).unwrap();
+        assert!(reject_links(&dir).unwrap_err().contains("symbolic link"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,244 @@
+//! Build validated `tools/call` arguments for a plugin tool.
+//!
+//! Trivial schemas get `{text}` directly. Otherwise the active LLM provider
+//! generates arguments, saved plugin config fills any gaps (e.g.
+//! `default_repo` → `repo`), and the result is validated against the
+//! tool's input schema. Invalid output gets one repair round-trip with the
+//! validation errors; if it is still invalid the call is refused rather
+//! than sending malformed arguments to the plugin.
+//!
+//! Arguments a cloud model produced through native tool calling skip
+//! generation: `accept_arguments` applies the same config prefill and
+//! schema validation to them.
+
+use crate::llm::plugin_args::{self, ArgsHints, ArgsRepair};
+use crate::mcp::registry::RegisteredTool;
+use crate::mcp::{config_store, schema};
+use serde_json::{Map, Value};
+
+/// Repair round-trips after the first generation attempt.
+const MAX_REPAIRS: usize = 1;
+
+/// Produce schema-valid arguments for `tool` from the snip text.
+pub async fn build_arguments(
+    action_id: &str,
+    tool: Option<&RegisteredTool>,
+    input_text: &str,
+    app: Option<&tauri::AppHandle>,
+) -> Result<Value, String> {
+    let text_args = serde_json::json!({ "text": input_text });
+    let (tool, input_schema) = match tool {
+        Some(tool) => match &tool.input_schema {
+            Some(s) if !plugin_args::is_trivial_schema(s) => (tool, s),
+            _ => return Ok(text_args),
+        },
+        None => return Ok(text_args),
+    };
+
+    let mut hints = ArgsHints {
+        known_values: schema::prefill_values(
+            input_schema,
+            &config_store::load_config(&tool.plugin_id),
+        ),
+        repair: None,
+    };
+
+    for attempt in 0..=MAX_REPAIRS {
+        let raw = match generate_args_for_provider(action_id, tool, input_text, &hints, app).await {
+            Ok(raw) => raw,
+            Err(e) => return fallback(action_id, input_schema, &hints.known_values, input_text, e),
+        };
+        let (args, errors) = check(&raw, input_schema, &hints.known_values);
+        if let (Some(args), true) = (args, errors.is_empty()) {
+            log::info!(
+                "[ARGS_BRIDGE] Valid args for '{}' (attempt {})",
+                action_id,
+                attempt + 1
+            );
+            return Ok(args);
+        }
+        log::warn!(
+            "[ARGS_BRIDGE] Args for '{}' invalid (attempt {}): {:?}",
+            action_id,
+            attempt + 1,
+            errors
+        );
+        hints.repair = Some(ArgsRepair {
+            previous_output: raw,
+            errors,
+        });
+    }
+
+    let errors = hints.repair.map(|r| r.errors).unwrap_or_default();
+    Err(format!(
+        "Generated arguments don't match the tool's schema: {}",
+        errors.join("; ")
+    ))
+}
+
+/// Prefill and validate arguments from a native tool call.
+///
+/// Returns the arguments ready to send, or the validation errors.
+pub fn accept_arguments(tool: &RegisteredTool, mut arguments: Value) -> Result<Value, Vec<String>> {
+    let Some(input_schema) = &tool.input_schema else {
+        return Ok(arguments);
+    };
+    let known_values =
+        schema::prefill_values(input_schema, &config_store::load_config(&tool.plugin_id));
+    schema::apply_prefill(&mut arguments, &known_values);
+    let errors = schema::validate(input_schema, &arguments);
+    if errors.is_empty() {
+        Ok(arguments)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse, prefill and validate one model output.
+///
+/// Returns the arguments (if they parsed) and the validation errors.
+pub fn check(
+    raw: &str,
+    input_schema: &Value,
+    known_values: &Map<String, Value>,
+) -> (Option<Value>, Vec<String>) {
+    match plugin_args::parse_args(raw) {
+        Ok(mut args) => {
+            schema::apply_prefill(&mut args, known_values);
+            let errors = schema::validate(input_schema, &args);
+            (Some(args), errors)
+        }
+        Err(e) => (None, vec![e]),
+    }
+}
+
+/// No model available: send `{text}` plus saved config, but only if valid.
+fn fallback(
+    action_id: &str,
+    input_schema: &Value,
+    known_values: &Map<String, Value>,
+    input_text: &str,
+    cause: String,
+) -> Result<Value, String> {
+    let mut args = serde_json::json!({ "text": input_text });
+    schema::apply_prefill(&mut args, known_values);
+    let errors = schema::validate(input_schema, &args);
+    if errors.is_empty() {
+        log::warn!(
+            "[ARGS_BRIDGE] Args bridge failed for '{}', using {{text}}: {}",
+            action_id,
+            cause
+        );
+        Ok(args)
+    } else {
+        Err(format!("Could not generate arguments: {}", cause))
+    }
+}
+
+/// Route args generation to the active provider (Anthropic or local).
+async fn generate_args_for_provider(
+    action_id: &str,
+    tool: &RegisteredTool,
+    input_text: &str,
+    hints: &ArgsHints,
+    #[allow(unused_variables)] app: Option<&tauri::AppHandle>,
+) -> Result<String, String> {
+    let schema = tool.input_schema.as_ref().unwrap_or(&Value::Null);
+
+    #[cfg(feature = "local-llm")]
+    if let Some(app_handle) = app {
+        let provider = crate::settings_commands::resolve_provider();
+        if provider == "local" {
+            use tauri::Manager;
+            let state = app_handle.state::<crate::llm::local_state::LocalLlmState>();
+            return crate::llm::local::generate_plugin_args_local(
+                action_id,
+                &tool.description,
+                schema,
+                input_text,
+                hints,
+                &state,
+            )
+            .await;
+        }
+    }
+
+    plugin_args::generate_plugin_args(action_id, &tool.description, schema, input_text, hints).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["title", "repo"],
+            "properties": {
+                "title": { "type": "string", "minLength": 1 },
+                "repo": { "type": "string", "pattern": "^[\\w.-]+/[\\w.-]+$" },
+                "priority": { "enum": ["low", "high"] }
+            }
+        })
+    }
+
+    #[test]
+    fn check_fills_gaps_from_config_before_validating() {
+        let known = Map::from_iter([("repo".to_string(), json!("acme/app"))]);
+        let (args, errors) = check(
+            "```json\n{\"title\": \"Crash\"}\n```",
+            &issue_schema(),
+            &known,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(args.unwrap()["repo"], "acme/app");
+    }
+
+    #[test]
+    fn check_reports_parse_and_schema_errors() {
+        let (args, errors) = check("not json", &issue_schema(), &Map::new());
+        assert!(args.is_none());
+        assert!(errors[0].contains("not valid JSON"));
+
+        let (_, errors) = check(
+            r#"{"title": "Crash", "repo": "acme", "priority": "urgent"}"#,
+            &issue_schema(),
+            &Map::new(),
+        );
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+    }
+
+    #[test]
+    fn accept_arguments_validates_native_calls() {
+        let tool = RegisteredTool {
+            plugin_id: "test.args-unconfigured".to_string(),
+            name: "create_issue".to_string(),
+            display_name: "Create Issue".to_string(),
+            description: String::new(),
+            input_schema: Some(issue_schema()),
+            output_schema: None,
+        };
+        let args = json!({ "title": "Crash", "repo": "acme/app" });
+        assert_eq!(accept_arguments(&tool, args.clone()).unwrap(), args);
+
+        let errors = accept_arguments(&tool, json!({ "title": "Crash" })).unwrap_err();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+
+        let untyped = RegisteredTool {
+            input_schema: None,
+            ..tool
+        };
+        assert!(accept_arguments(&untyped, json!({})).is_ok());
+    }
+
+    #[test]
+    fn fallback_only_when_text_args_are_valid() {
+        let text_schema = json!({ "type": "object", "required": ["text"],
+            "properties": { "text": { "type": "string" }, "lang": { "type": "string" } } });
+        let args = fallback("t", &text_schema, &Map::new(), "hi", "no key".into()).unwrap();
+        assert_eq!(args, json!({ "text": "hi" }));
+
+        assert!(fallback("t", &issue_schema(), &Map::new(), "hi", "no key".into()).is_err());
+    }
+}
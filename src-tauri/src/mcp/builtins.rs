@@ -18,6 +18,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Copy Text".to_string(),
             description: "Copy the extracted text to the clipboard".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -25,6 +26,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Search Web".to_string(),
             description: "Search the web for the extracted text".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -32,6 +34,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Explain Error".to_string(),
             description: "Explain what this error means and why it occurred".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -39,6 +42,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Suggest Fix".to_string(),
             description: "Analyze the error and suggest a fix (code or command)".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -46,6 +50,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Export CSV".to_string(),
             description: "Extract tabular data and export as CSV file".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -53,6 +58,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Explain This".to_string(),
             description: "Explain this content clearly and concisely".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -60,6 +66,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Translate".to_string(),
             description: "Translate the text to English (or another target language)".to_string(),
             input_schema: None,
+            output_schema: None,
         },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
@@ -67,6 +74,7 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             display_name: "Run Command".to_string(),
             description: "Execute a user request as a macOS shell command (change settings, open apps, manage files, install software, adjust display, etc.)".to_string(),
             input_schema: None,
+            output_schema: None,
         },
     ];
 
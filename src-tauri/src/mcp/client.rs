@@ -1,105 +1,119 @@
 //! MCP client — JSON-RPC 2.0 over stdio (newline-delimited JSON).
 //!
-//! Spawns a child process implementing the MCP server protocol,
-//! communicates via NDJSON on stdin/stdout, and provides typed
-//! methods for the initialize → tools/list → tools/call lifecycle.
+//! Talks to a child process implementing the MCP server protocol (started
+//! by `spawn.rs`) via NDJSON on stdin/stdout, and provides typed methods
+//! for the initialize → tools/list → tools/call lifecycle.
+//!
+//! Requests the server sends while we wait for a response (elicitation,
+//! sampling, ping) are answered inline through `server_requests::answer`,
+//! within the pending request's `CallBudget`.
+//!
+//! The transport is any reader/writer pair, so in-process servers (WASM
+//! plugins) use the same framing through `McpServer::from_io`.
+//!
+//! `Limits` cap the size of each message read and the time a `tools/call`
+//! may take; the limit hit last is kept for `take_violation`.
+//!
+//! The child's stderr and any `notifications/message` the server sends go
+//! to `plugin_logs`.
 
-use crate::mcp::types::*;
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
-use tokio::process::{Child, ChildStdin, ChildStdout};
+mod rpc;
 
-/// Default timeout for any single JSON-RPC request (seconds).
-const REQUEST_TIMEOUT_SECS: u64 = 15;
+use crate::mcp::host::ClientHost;
+use crate::mcp::limits::{LimitViolation, Limits};
+use crate::mcp::plugin_logs::LogLevel;
+use crate::mcp::types::*;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, OnceLock};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
+use tokio::process::Child;
+use tokio::task::JoinHandle;
+
+/// What runs an MCP server.
+pub enum ServerProcess {
+    /// A child process speaking MCP on its stdio.
+    Child(Child),
+    /// An in-process task; it ends when its stdin is closed. The cell is
+    /// set to the limit that stopped it, if any, before its stdout closes.
+    Task(JoinHandle<()>, Arc<OnceLock<LimitViolation>>),
+}
 
 /// An active connection to an MCP server process.
 pub struct McpServer {
     pub plugin_id: String,
-    child: Child,
-    stdin: BufWriter<ChildStdin>,
-    stdout: BufReader<ChildStdout>,
+    process: ServerProcess,
+    stdin: BufWriter<Box<dyn AsyncWrite + Send + Unpin>>,
+    stdout: BufReader<Box<dyn AsyncRead + Send + Unpin>>,
     next_id: AtomicU64,
+    /// Answers elicitation/sampling requests; `None` in headless use.
+    host: Option<Arc<dyn ClientHost>>,
+    /// Whether the manifest grants the `sampling` permission.
+    sampling_allowed: bool,
+    /// Configuration values sent with `initialize`.
+    configuration: Option<serde_json::Map<String, serde_json::Value>>,
+    limits: Limits,
+    /// The limit the last failed request ran into, if any.
+    violation: Option<LimitViolation>,
+    /// Minimum level requested with `logging/setLevel`.
+    log_level: LogLevel,
 }
 
 impl McpServer {
-    /// Spawn a child process and prepare stdio pipes.
-    ///
-    /// Does NOT perform the initialize handshake — call `initialize()` after.
-    /// `cwd` sets the working directory (important: sandboxed plugins wall
-    /// off /Users, so CWD must be inside an allowed path).
-    pub fn spawn(
+    /// Wrap an already-running server's transport. `stdin` carries our
+    /// requests to the server, `stdout` its NDJSON replies.
+    pub fn from_io(
         plugin_id: &str,
-        command: &str,
-        args: &[&str],
-        env: HashMap<String, String>,
-        cwd: Option<&std::path::Path>,
-    ) -> Result<Self, String> {
-        let mut cmd = tokio::process::Command::new(command);
-        cmd.args(args)
-            .envs(env)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .kill_on_drop(true);
-        if let Some(dir) = cwd {
-            cmd.current_dir(dir);
-        }
-
-        let mut child = cmd.spawn().map_err(|e| {
-            format!("Failed to spawn MCP server '{}' ({}): {}", plugin_id, command, e)
-        })?;
-
-        let stdin = child
-            .stdin
-            .take()
-            .ok_or_else(|| format!("No stdin for MCP server '{}'", plugin_id))?;
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| format!("No stdout for MCP server '{}'", plugin_id))?;
-
-        Ok(Self {
+        process: ServerProcess,
+        stdin: Box<dyn AsyncWrite + Send + Unpin>,
+        stdout: Box<dyn AsyncRead + Send + Unpin>,
+    ) -> Self {
+        Self {
             plugin_id: plugin_id.to_string(),
-            child,
+            process,
             stdin: BufWriter::new(stdin),
             stdout: BufReader::new(stdout),
             next_id: AtomicU64::new(1),
-        })
+            host: None,
+            sampling_allowed: false,
+            configuration: None,
+            limits: Limits::default(),
+            violation: None,
+            log_level: LogLevel::Info,
+        }
     }
 
-    /// Spawn a child process inside a macOS sandbox-exec sandbox.
-    ///
-    /// Wraps the command in `sandbox-exec -f {profile_path}` so the kernel
-    /// enforces the profile's restrictions. CWD is set to `plugin_dir` because
-    /// the sandbox walls off /Users — Node.js calls getcwd() at startup.
-    #[cfg(target_os = "macos")]
-    pub fn spawn_sandboxed(
-        plugin_id: &str,
-        command: &str,
-        args: &[&str],
-        env: HashMap<String, String>,
-        sandbox_profile_path: &std::path::Path,
-        plugin_dir: &std::path::Path,
-    ) -> Result<Self, String> {
-        let profile_str = sandbox_profile_path.to_str()
-            .ok_or("Invalid sandbox profile path")?;
-        let mut sandbox_args = vec!["-f", profile_str, command];
-        sandbox_args.extend(args);
-        Self::spawn(plugin_id, "sandbox-exec", &sandbox_args, env, Some(plugin_dir))
+    /// Set the message size and `tools/call` budget. OS limits are set at
+    /// spawn and can't be changed here.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// The limit the last failed request ran into, if any.
+    pub fn take_violation(&mut self) -> Option<LimitViolation> {
+        self.violation.take()
+    }
+
+    /// Set the minimum level of log messages to ask the server for. Call
+    /// before `initialize()`.
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+    }
+
+    /// Set the configuration sent to the server in `initialize()`.
+    pub fn set_configuration(&mut self, configuration: serde_json::Map<String, serde_json::Value>) {
+        self.configuration = Some(configuration);
+    }
+
+    /// Attach the host that answers server requests. Call before `initialize()`
+    /// so the declared capabilities match.
+    pub fn set_host(&mut self, host: Option<Arc<dyn ClientHost>>, sampling_allowed: bool) {
+        self.host = host;
+        self.sampling_allowed = sampling_allowed;
     }
 
     /// Send the initialize handshake and notifications/initialized notification.
     pub async fn initialize(&mut self) -> Result<ServerInfo, String> {
-        let params = InitializeParams {
-            protocol_version: "2024-11-05".to_string(),
-            capabilities: ClientCapabilities {},
-            client_info: ClientInfo {
-                name: "omni-glass".to_string(),
-                version: env!("CARGO_PKG_VERSION").to_string(),
-            },
-        };
-
+        let params = self.initialize_params();
         let resp = self
             .request("initialize", Some(serde_json::to_value(&params).unwrap()))
             .await?;
@@ -116,6 +130,13 @@ impl McpServer {
         // Send initialized notification (no response expected)
         self.notify("notifications/initialized", None).await?;
 
+        if resp.pointer("/capabilities/logging").is_some() {
+            let level = serde_json::json!({ "level": self.log_level.as_str() });
+            if let Err(e) = self.request("logging/setLevel", Some(level)).await {
+                log::warn!("{}", e);
+            }
+        }
+
         log::info!(
             "[MCP] Initialized '{}' — server: {} v{}",
             self.plugin_id,
@@ -126,6 +147,24 @@ impl McpServer {
         Ok(server_info)
     }
 
+    /// What we send with `initialize`: capabilities follow the host and
+    /// the sampling permission.
+    fn initialize_params(&self) -> InitializeParams {
+        InitializeParams {
+            protocol_version: "2025-06-18".to_string(),
+            capabilities: ClientCapabilities {
+                elicitation: self.host.as_ref().map(|_| serde_json::json!({})),
+                sampling: (self.host.is_some() && self.sampling_allowed)
+                    .then(|| serde_json::json!({})),
+            },
+            client_info: ClientInfo {
+                name: "omni-glass".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            configuration: self.configuration.clone(),
+        }
+    }
+
     /// Discover tools via tools/list.
     pub async fn list_tools(&mut self) -> Result<Vec<McpTool>, String> {
         let resp = self.request("tools/list", None).await?;
@@ -157,7 +196,11 @@ impl McpServer {
             "arguments": arguments,
         });
 
-        let resp = self.request("tools/call", Some(params)).await?;
+        self.violation = None;
+        let budget = self.limits.call_timeout_secs;
+        let resp = self
+            .request_within("tools/call", Some(params), budget)
+            .await?;
         let result: ToolResult =
             serde_json::from_value(resp).map_err(|e| format!("Bad tools/call result: {}", e))?;
 
@@ -170,131 +213,64 @@ impl McpServer {
         let _ = self.stdin.shutdown().await;
 
         // Give the process a moment to exit gracefully
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(3),
-            self.child.wait(),
-        )
-        .await
-        {
-            Ok(Ok(status)) => {
-                log::info!("[MCP] '{}' exited: {}", self.plugin_id, status);
-            }
-            _ => {
-                log::warn!("[MCP] '{}' did not exit gracefully, killing", self.plugin_id);
-                let _ = self.child.kill().await;
+        let grace = std::time::Duration::from_secs(3);
+        match &mut self.process {
+            ServerProcess::Child(child) => match tokio::time::timeout(grace, child.wait()).await {
+                Ok(Ok(status)) => {
+                    log::info!("[MCP] '{}' exited: {}", self.plugin_id, status);
+                }
+                _ => {
+                    log::warn!(
+                        "[MCP] '{}' did not exit gracefully, killing",
+                        self.plugin_id
+                    );
+                    let _ = child.kill().await;
+                }
+            },
+            ServerProcess::Task(task, _) => {
+                if tokio::time::timeout(grace, &mut *task).await.is_err() {
+                    log::warn!(
+                        "[MCP] '{}' did not exit gracefully, aborting",
+                        self.plugin_id
+                    );
+                    task.abort();
+                } else {
+                    log::info!("[MCP] '{}' exited", self.plugin_id);
+                }
             }
         }
     }
+}
 
-    // ── Internal: JSON-RPC framing ──────────────────────────────────
-
-    /// Send a JSON-RPC request and wait for the matching response.
-    async fn request(
-        &mut self,
-        method: &str,
-        params: Option<serde_json::Value>,
-    ) -> Result<serde_json::Value, String> {
-        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
-        let req = JsonRpcRequest::new(id, method, params);
-
-        self.send_message(&serde_json::to_value(&req).unwrap())
-            .await?;
-
-        let resp = self.read_response(id).await?;
-
-        if let Some(err) = resp.error {
-            return Err(format!("[MCP] '{}' {}: {}", self.plugin_id, method, err));
-        }
-
-        resp.result.ok_or_else(|| {
-            format!(
-                "[MCP] '{}' {}: response had neither result nor error",
-                self.plugin_id, method
-            )
-        })
-    }
-
-    /// Send a JSON-RPC notification (no id, no response expected).
-    async fn notify(
-        &mut self,
-        method: &str,
-        params: Option<serde_json::Value>,
-    ) -> Result<(), String> {
-        let notif = JsonRpcNotification::new(method, params);
-        self.send_message(&serde_json::to_value(&notif).unwrap())
-            .await
-    }
-
-    /// Write a single NDJSON line to the child's stdin.
-    async fn send_message(&mut self, value: &serde_json::Value) -> Result<(), String> {
-        let mut line = serde_json::to_string(value)
-            .map_err(|e| format!("JSON serialize failed: {}", e))?;
-        line.push('\n');
-
-        self.stdin
-            .write_all(line.as_bytes())
-            .await
-            .map_err(|e| format!("[MCP] '{}' stdin write failed: {}", self.plugin_id, e))?;
-        self.stdin
-            .flush()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn slow_tool_call_reports_its_budget() {
+        // The server never answers
+        let (client_in, _server_in) = tokio::io::duplex(1024);
+        let (_server_out, client_out) = tokio::io::duplex(1024);
+        let task = tokio::spawn(std::future::pending::<()>());
+        let mut server = McpServer::from_io(
+            "com.test.slow",
+            ServerProcess::Task(task, Default::default()),
+            Box::new(client_in),
+            Box::new(client_out),
+        );
+        server.set_limits(Limits {
+            call_timeout_secs: 1,
+            ..Limits::DEFAULT
+        });
+        let err = server
+            .call_tool("slow", serde_json::json!({}))
             .await
-            .map_err(|e| format!("[MCP] '{}' stdin flush failed: {}", self.plugin_id, e))?;
-
-        Ok(())
-    }
-
-    /// Read lines from stdout until we find a response matching the given id.
-    /// Skips notifications and other non-matching messages.
-    async fn read_response(&mut self, expected_id: u64) -> Result<JsonRpcResponse, String> {
-        let timeout = std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS);
-
-        tokio::time::timeout(timeout, async {
-            let mut line = String::new();
-            loop {
-                line.clear();
-                let n = self
-                    .stdout
-                    .read_line(&mut line)
-                    .await
-                    .map_err(|e| format!("[MCP] '{}' stdout read failed: {}", self.plugin_id, e))?;
-
-                if n == 0 {
-                    return Err(format!(
-                        "[MCP] '{}' stdout closed (process exited?)",
-                        self.plugin_id
-                    ));
-                }
-
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-
-                // Try to parse as a JSON-RPC response
-                match serde_json::from_str::<JsonRpcResponse>(trimmed) {
-                    Ok(resp) if resp.id == Some(expected_id) => return Ok(resp),
-                    Ok(_) => {
-                        // Non-matching id or notification — skip
-                        continue;
-                    }
-                    Err(_) => {
-                        // Not valid JSON-RPC — could be server log output, skip
-                        log::debug!(
-                            "[MCP] '{}' ignoring non-JSON line: {}",
-                            self.plugin_id,
-                            &trimmed[..trimmed.len().min(100)]
-                        );
-                        continue;
-                    }
-                }
-            }
-        })
-        .await
-        .map_err(|_| {
-            format!(
-                "[MCP] '{}' request timed out after {}s",
-                self.plugin_id, REQUEST_TIMEOUT_SECS
-            )
-        })?
+            .unwrap_err();
+        assert!(err.contains("1s"));
+        assert_eq!(
+            server.take_violation(),
+            Some(LimitViolation::CallTimeout { limit_seconds: 1 })
+        );
+        assert_eq!(server.take_violation(), None);
     }
 }
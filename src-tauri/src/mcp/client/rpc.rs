@@ -0,0 +1,223 @@
+//! JSON-RPC framing for `McpServer`: requests, notifications, and the
+//! read loop that waits for a response.
+//!
+//! While waiting, the loop logs `notifications/message`, skips other
+//! notifications and non-JSON output, and answers server requests within
+//! the call's `CallBudget`. Each line is read up to `maxMessageBytes`.
+
+use super::{McpServer, ServerProcess};
+use crate::mcp::limits::LimitViolation;
+use crate::mcp::line_reader::{read_line_limited, LineRead};
+use crate::mcp::plugin_logs::{self, LogEntry};
+use crate::mcp::sandbox::process_limits;
+use crate::mcp::server_requests::{self, CallBudget};
+use crate::mcp::types::*;
+use std::sync::atomic::Ordering;
+use tokio::io::AsyncWriteExt;
+
+/// Timeout for requests other than `tools/call` (seconds).
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+impl McpServer {
+    /// Send a JSON-RPC request and wait for the matching response.
+    pub(super) async fn request(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_within(method, params, REQUEST_TIMEOUT_SECS)
+            .await
+    }
+
+    /// `request` with its own timeout.
+    pub(super) async fn request_within(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        timeout_secs: u64,
+    ) -> Result<serde_json::Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let req = JsonRpcRequest::new(id, method, params);
+
+        self.send_message(&serde_json::to_value(&req).unwrap())
+            .await?;
+
+        let resp = self.read_response(id, timeout_secs).await?;
+
+        if let Some(err) = resp.error {
+            return Err(format!("[MCP] '{}' {}: {}", self.plugin_id, method, err));
+        }
+
+        resp.result.ok_or_else(|| {
+            format!(
+                "[MCP] '{}' {}: response had neither result nor error",
+                self.plugin_id, method
+            )
+        })
+    }
+
+    /// Send a JSON-RPC notification (no id, no response expected).
+    pub(super) async fn notify(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<(), String> {
+        let notif = JsonRpcNotification::new(method, params);
+        self.send_message(&serde_json::to_value(&notif).unwrap())
+            .await
+    }
+
+    /// Write a single NDJSON line to the child's stdin.
+    async fn send_message(&mut self, value: &serde_json::Value) -> Result<(), String> {
+        let mut line =
+            serde_json::to_string(value).map_err(|e| format!("JSON serialize failed: {}", e))?;
+        line.push('\n');
+
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("[MCP] '{}' stdin write failed: {}", self.plugin_id, e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("[MCP] '{}' stdin flush failed: {}", self.plugin_id, e))?;
+
+        Ok(())
+    }
+
+    /// Read lines from stdout until we find a response matching the given id.
+    /// Logs `notifications/message`, skips other notifications and non-JSON
+    /// output; answers server requests.
+    ///
+    /// The timeout covers everything but the user filling in elicitation
+    /// forms, which is itself capped; see `server_requests`.
+    async fn read_response(
+        &mut self,
+        expected_id: u64,
+        timeout_secs: u64,
+    ) -> Result<JsonRpcResponse, String> {
+        let mut budget = CallBudget::new(timeout_secs);
+        loop {
+            let Some(message) = self.read_message(&budget).await? else {
+                continue;
+            };
+            if message.get("method").and_then(|m| m.as_str()) == Some("notifications/message") {
+                let params = message.get("params").cloned().unwrap_or_default();
+                plugin_logs::record(&self.plugin_id, LogEntry::from_notification(&params));
+                continue;
+            }
+            if message.get("method").is_some() {
+                // Server request (has an id) or notification (no id, skipped)
+                if let Ok(request) = serde_json::from_value::<JsonRpcServerRequest>(message) {
+                    self.answer_server_request(request, &mut budget).await?;
+                }
+                continue;
+            }
+
+            match serde_json::from_value::<JsonRpcResponse>(message) {
+                Ok(resp) if resp.id == Some(expected_id) => return Ok(resp),
+                // Non-matching id — skip
+                _ => continue,
+            }
+        }
+    }
+
+    /// Read one line within the budget. `None` for blank or non-JSON lines.
+    async fn read_message(
+        &mut self,
+        budget: &CallBudget,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let max_bytes = self.limits.max_message_bytes as usize;
+        let mut buf = Vec::new();
+        let read = tokio::time::timeout_at(
+            budget.deadline(),
+            read_line_limited(&mut self.stdout, &mut buf, max_bytes),
+        )
+        .await;
+        let read = match read {
+            Ok(read) => {
+                read.map_err(|e| format!("[MCP] '{}' stdout read failed: {}", self.plugin_id, e))?
+            }
+            Err(_) => {
+                return Err(self.violated(LimitViolation::CallTimeout {
+                    limit_seconds: budget.limit_seconds,
+                }))
+            }
+        };
+        match read {
+            LineRead::Line => Ok(self.parse_line(&buf)),
+            LineRead::TooLong => Err(self.violated(LimitViolation::MessageSize {
+                limit_bytes: max_bytes as u64,
+            })),
+            LineRead::Eof => match self.exit_violation().await {
+                Some(violation) => Err(self.violated(violation)),
+                None => Err(format!(
+                    "[MCP] '{}' stdout closed (process exited?)",
+                    self.plugin_id
+                )),
+            },
+        }
+    }
+
+    /// Parse a line as JSON; blank lines and server log output are `None`.
+    fn parse_line(&self, buf: &[u8]) -> Option<serde_json::Value> {
+        let line = String::from_utf8_lossy(buf);
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let parsed = serde_json::from_str(trimmed);
+        if parsed.is_err() {
+            log::debug!(
+                "[MCP] '{}' ignoring non-JSON line: {}",
+                self.plugin_id,
+                &trimmed[..trimmed.len().min(100)]
+            );
+        }
+        parsed.ok()
+    }
+
+    /// Record a violation and describe it.
+    fn violated(&mut self, violation: LimitViolation) -> String {
+        let message = format!("[MCP] '{}' {}", self.plugin_id, violation);
+        log::warn!("{}", message);
+        self.violation = Some(violation);
+        message
+    }
+
+    /// After stdout closes: the limit that ended the process, if any.
+    async fn exit_violation(&mut self) -> Option<LimitViolation> {
+        let wait = std::time::Duration::from_millis(500);
+        match &mut self.process {
+            ServerProcess::Child(child) => {
+                let status = tokio::time::timeout(wait, child.wait()).await.ok()?.ok()?;
+                process_limits::exit_violation(&status, &self.limits)
+            }
+            ServerProcess::Task(_, stopped_by) => stopped_by.get().cloned(),
+        }
+    }
+
+    /// Handle one server → client request and write the reply. Fails with
+    /// a timeout if the call's budget runs out while answering.
+    async fn answer_server_request(
+        &mut self,
+        request: JsonRpcServerRequest,
+        budget: &mut CallBudget,
+    ) -> Result<(), String> {
+        let reply = server_requests::answer(
+            self.host.as_deref(),
+            &self.plugin_id,
+            self.sampling_allowed,
+            request,
+            budget,
+        )
+        .await;
+        let Some(reply) = reply else {
+            return Err(self.violated(LimitViolation::CallTimeout {
+                limit_seconds: budget.limit_seconds,
+            }));
+        };
+        self.send_message(&serde_json::to_value(&reply).unwrap())
+            .await
+    }
+}
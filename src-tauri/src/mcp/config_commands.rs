@@ -0,0 +1,111 @@
+//! Tauri commands for the plugin configuration section of Settings.
+//!
+//! `get_plugin_configs` lists every installed plugin with the current values
+//! of the `configuration` fields it declares; the panel also offers upgrade
+//! and uninstall for each one. Secret values never leave the backend — the
+//! UI only learns whether one is set. `set_plugin_config`
+//! saves changes and restarts the plugin if it is running, so the new values
+//! reach it in the next `initialize`.
+
+use crate::mcp::config_store;
+use crate::mcp::loader;
+use crate::mcp::registry::ToolRegistry;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// One configuration field as shown in the settings panel.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConfigField {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub label: String,
+    pub placeholder: Option<String>,
+    pub description: Option<String>,
+    /// Current value; always `null` for secrets.
+    pub value: Value,
+    /// Whether a value is stored (the only thing shown for secrets).
+    pub has_value: bool,
+}
+
+/// An installed plugin and its configuration form (empty `fields` when it
+/// declares none).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConfigView {
+    pub plugin_id: String,
+    pub name: String,
+    pub version: String,
+    pub fields: Vec<PluginConfigField>,
+}
+
+/// List installed plugins with their configuration fields.
+#[tauri::command]
+pub fn get_plugin_configs() -> Vec<PluginConfigView> {
+    loader::installed_plugins()
+        .into_iter()
+        .map(|(manifest, _)| {
+            let fields = manifest.configuration.clone().unwrap_or_default();
+            let config = config_store::load_config(&manifest.id);
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            let fields = keys
+                .into_iter()
+                .map(|key| {
+                    let field = &fields[key];
+                    let (value, has_value) = if field.field_type == "secret" {
+                        let stored = config_store::load_secret(&manifest.id, key).is_some();
+                        (Value::Null, stored)
+                    } else {
+                        let value = config.get(key).cloned().unwrap_or(Value::Null);
+                        let stored = !value.is_null();
+                        (value, stored)
+                    };
+                    PluginConfigField {
+                        key: key.clone(),
+                        field_type: field.field_type.clone(),
+                        label: field.label.clone(),
+                        placeholder: field.placeholder.clone(),
+                        description: field.description.clone(),
+                        value,
+                        has_value,
+                    }
+                })
+                .collect();
+            PluginConfigView {
+                plugin_id: manifest.id.clone(),
+                name: manifest.name.clone(),
+                version: manifest.version.clone(),
+                fields,
+            }
+        })
+        .collect()
+}
+
+/// Save configuration values for a plugin. `null` clears a field.
+///
+/// Values are validated against the manifest before anything is written.
+/// A running plugin is restarted so it receives the new configuration.
+#[tauri::command]
+pub async fn set_plugin_config(
+    plugin_id: String,
+    values: Map<String, Value>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), String> {
+    let (manifest, dir) = loader::find_plugin(&plugin_id)
+        .ok_or_else(|| format!("Plugin '{}' is not installed", plugin_id))?;
+    config_store::set_values(&manifest, &values)?;
+    log::info!(
+        "[CONFIG] Updated {} field(s) for '{}'",
+        values.len(),
+        plugin_id
+    );
+
+    if registry.server_handle(&plugin_id).await.is_some() {
+        registry.remove_plugin(&plugin_id).await;
+        loader::load_approved_plugin(&manifest, &dir, &registry).await?;
+        log::info!("[CONFIG] Restarted '{}' with new configuration", plugin_id);
+    }
+    Ok(())
+}
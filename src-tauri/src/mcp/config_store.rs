@@ -1,15 +1,24 @@
 //! Plugin configuration persistence.
 //!
 //! Each plugin can declare a `configuration` field in its manifest with
-//! typed fields (string, number, boolean). User values are stored in
-//! `~/.config/omni-glass/plugin-config/{plugin_id}.json`.
+//! typed fields (string, number, boolean, secret). User values are stored in
+//! `~/.config/omni-glass/plugin-config/{plugin_id}.json`; `secret` fields go
+//! to the OS keychain instead and never touch that file.
 //!
-//! This module handles loading, saving, and querying per-plugin config.
+//! This module handles loading, saving, and querying per-plugin config, and
+//! builds the `configuration` object sent to the plugin at initialize.
 
-use serde_json::Value;
+use crate::mcp::manifest::{ConfigField, PluginManifest};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Keychain service name, shared with provider API keys.
+const KEYCHAIN_SERVICE: &str = "omni-glass";
+
+/// Field types a manifest may declare.
+pub const FIELD_TYPES: [&str; 4] = ["string", "number", "boolean", "secret"];
+
 /// Directory where plugin configs are stored.
 fn config_dir() -> PathBuf {
     dirs::config_dir()
@@ -60,10 +69,177 @@ pub fn get_config_value(plugin_id: &str, key: &str) -> Option<Value> {
     load_config(plugin_id).get(key).cloned()
 }
 
+/// Keychain account for one secret field.
+fn secret_account(plugin_id: &str, key: &str) -> String {
+    format!("plugin:{}:{}", plugin_id, key)
+}
+
+/// Read a secret field from the OS keychain.
+pub fn load_secret(plugin_id: &str, key: &str) -> Option<String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &secret_account(plugin_id, key))
+        .ok()?
+        .get_password()
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
+/// Store (or with `None`, delete) a secret field in the OS keychain.
+pub fn save_secret(plugin_id: &str, key: &str, secret: Option<&str>) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, &secret_account(plugin_id, key))
+        .map_err(|e| format!("Keyring error: {}", e))?;
+    match secret {
+        Some(secret) => entry
+            .set_password(secret)
+            .map_err(|e| format!("Failed to save secret: {}", e)),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete secret: {}", e)),
+        },
+    }
+}
+
+/// Check a value against its declared field type. `null` clears the field.
+pub fn check_value(key: &str, field: &ConfigField, value: &Value) -> Result<(), String> {
+    let ok = match field.field_type.as_str() {
+        _ if value.is_null() => true,
+        "string" | "secret" => value.is_string(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        _ => false,
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("'{}' must be a {}", key, field.field_type))
+    }
+}
+
+/// Apply settings-panel updates: plain fields go to the JSON file, secrets
+/// to the keychain. Keys the manifest doesn't declare are rejected.
+pub fn set_values(manifest: &PluginManifest, updates: &Map<String, Value>) -> Result<(), String> {
+    let fields = manifest.configuration.clone().unwrap_or_default();
+    for (key, value) in updates {
+        let field = fields
+            .get(key)
+            .ok_or_else(|| format!("'{}' is not a configuration field of {}", key, manifest.id))?;
+        check_value(key, field, value)?;
+    }
+
+    let mut config = load_config(&manifest.id);
+    for (key, value) in updates {
+        if fields[key].field_type == "secret" {
+            save_secret(&manifest.id, key, value.as_str())?;
+        } else if value.is_null() {
+            config.remove(key);
+        } else {
+            config.insert(key.clone(), value.clone());
+        }
+    }
+    save_config(&manifest.id, &config)
+}
+
+/// Delete a plugin's saved configuration: the JSON file and every secret
+/// field its manifest declares.
+pub fn clear_config(manifest: &PluginManifest) -> Result<(), String> {
+    for (key, field) in manifest.configuration.iter().flatten() {
+        if field.field_type == "secret" {
+            save_secret(&manifest.id, key, None)?;
+        }
+    }
+    match std::fs::remove_file(config_path(&manifest.id)) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove config: {}", e)),
+    }
+}
+
+/// After an upgrade, drop saved values for fields the new manifest no
+/// longer declares, or whose type changed.
+pub fn prune_config(old: &PluginManifest, new: &PluginManifest) -> Result<(), String> {
+    let new_fields = new.configuration.clone().unwrap_or_default();
+    let kept = |key: &str, field: &ConfigField| {
+        new_fields
+            .get(key)
+            .is_some_and(|f| f.field_type == field.field_type)
+    };
+    for (key, field) in old.configuration.iter().flatten() {
+        if field.field_type == "secret" && !kept(key, field) {
+            save_secret(&old.id, key, None)?;
+        }
+    }
+    let mut config = load_config(&old.id);
+    let before = config.len();
+    config.retain(|key, value| {
+        new_fields
+            .get(key)
+            .is_some_and(|f| f.field_type != "secret" && check_value(key, f, value).is_ok())
+    });
+    if config.len() != before {
+        save_config(&old.id, &config)?;
+    }
+    Ok(())
+}
+
+/// Everything the plugin is configured with, secrets included, keyed by
+/// field name. Sent once in the `initialize` request.
+pub fn plugin_configuration(manifest: &PluginManifest) -> Map<String, Value> {
+    let Some(fields) = &manifest.configuration else {
+        return Map::new();
+    };
+    let config = load_config(&manifest.id);
+    let mut out = Map::new();
+    for (key, field) in fields {
+        let value = if field.field_type == "secret" {
+            load_secret(&manifest.id, key).map(Value::String)
+        } else {
+            config.get(key).cloned()
+        };
+        if let Some(value) = value {
+            out.insert(key.clone(), value);
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn field(field_type: &str) -> ConfigField {
+        ConfigField {
+            field_type: field_type.to_string(),
+            label: "Field".to_string(),
+            placeholder: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn values_must_match_field_type() {
+        assert!(check_value("repo", &field("string"), &Value::String("a/b".into())).is_ok());
+        assert!(check_value("token", &field("secret"), &Value::String("x".into())).is_ok());
+        assert!(check_value("count", &field("number"), &Value::from(3)).is_ok());
+        assert!(check_value("count", &field("number"), &Value::String("3".into())).is_err());
+        assert!(check_value("on", &field("boolean"), &Value::Null).is_ok());
+    }
+
+    #[test]
+    fn unknown_keys_and_bad_types_are_rejected_before_saving() {
+        let manifest = PluginManifest {
+            id: "com.example.config-test-reject".to_string(),
+            name: "Test".to_string(),
+            version: "1.0.0".to_string(),
+            entry: "index.js".to_string(),
+            configuration: Some(HashMap::from([("repo".to_string(), field("string"))])),
+            ..Default::default()
+        };
+        let unknown = Map::from_iter([("token".to_string(), Value::from("x"))]);
+        assert!(set_values(&manifest, &unknown).unwrap_err().contains("not a configuration field"));
+        let wrong = Map::from_iter([("repo".to_string(), Value::from(1))]);
+        assert!(set_values(&manifest, &wrong).is_err());
+        assert!(!config_path(&manifest.id).exists());
+    }
+
     #[test]
     fn load_missing_config_returns_empty() {
         let config = load_config("com.example.nonexistent-plugin-test");
@@ -0,0 +1,177 @@
+//! Per-tool argument review preferences.
+//!
+//! Before a plugin tool runs from the UI, the user can review and edit the
+//! arguments the args bridge generated. Whether that review step appears is
+//! decided per tool:
+//!
+//! - `always`: every call is reviewed
+//! - `never`: calls run directly
+//! - no preference: reviewed when the tool has a non-trivial input schema
+//!   (i.e. the LLM generated the arguments), otherwise run directly
+//!
+//! Preferences live in `tool-confirm-prefs.json` next to
+//! `plugin-approvals.json`, keyed by qualified tool name.
+
+use crate::llm::execute::{ActionResult, ActionResultBody};
+use crate::mcp::registry::{qualified_name, RegisteredTool};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const PREFS_FILE: &str = "tool-confirm-prefs.json";
+
+/// Result type for a tool call waiting on argument review.
+pub const REVIEW_RESULT_TYPE: &str = "tool_args";
+
+/// The user's review preference for one tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmPref {
+    Always,
+    Never,
+}
+
+fn prefs_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(PREFS_FILE))
+}
+
+/// Load all review preferences. Returns an empty map if none are saved.
+pub fn load_prefs() -> HashMap<String, ConfirmPref> {
+    prefs_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Set (or with `None`, clear) the preference for a qualified tool name.
+pub fn set_pref(tool: &str, pref: Option<ConfirmPref>) -> Result<(), String> {
+    let path = prefs_path().ok_or("Could not determine config directory")?;
+    let mut prefs = load_prefs();
+    match pref {
+        Some(pref) => prefs.insert(tool.to_string(), pref),
+        None => prefs.remove(tool),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&prefs)
+        .map_err(|e| format!("Failed to serialize confirm prefs: {}", e))?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    log::info!("[MCP] Review preference for '{}' set to {:?}", tool, pref);
+    Ok(())
+}
+
+/// Saved preference for a tool, if any.
+pub fn pref_for(tool: &RegisteredTool) -> Option<ConfirmPref> {
+    load_prefs()
+        .get(&qualified_name(&tool.plugin_id, &tool.name))
+        .copied()
+}
+
+/// Whether a call to `tool` should stop for argument review.
+pub fn needs_review(tool: &RegisteredTool, pref: Option<ConfirmPref>) -> bool {
+    match pref {
+        Some(ConfirmPref::Always) => true,
+        Some(ConfirmPref::Never) => false,
+        None => tool
+            .input_schema
+            .as_ref()
+            .is_some_and(|s| !crate::llm::plugin_args::is_trivial_schema(s)),
+    }
+}
+
+/// Accept the user's reviewed arguments for `tool`: check them against its
+/// input schema (errors joined one per line, for the form), then save
+/// `remember` as its review preference.
+pub fn accept_reviewed(
+    tool: &RegisteredTool,
+    arguments: &serde_json::Value,
+    remember: Option<ConfirmPref>,
+) -> Result<(), String> {
+    if let Some(input_schema) = &tool.input_schema {
+        let errors = crate::mcp::schema::validate(input_schema, arguments);
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+    }
+    if let Some(pref) = remember {
+        set_pref(&qualified_name(&tool.plugin_id, &tool.name), Some(pref))?;
+    }
+    Ok(())
+}
+
+/// A `needs_confirmation` result carrying the arguments to review.
+pub fn review_result(action_id: &str, arguments: serde_json::Value) -> ActionResult {
+    ActionResult {
+        status: "needs_confirmation".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: REVIEW_RESULT_TYPE.to_string(),
+            text: None,
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            data: None,
+            url: None,
+            arguments: Some(arguments),
+        },
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(input_schema: Option<serde_json::Value>) -> RegisteredTool {
+        RegisteredTool {
+            plugin_id: "com.example.gh".to_string(),
+            name: "create_issue".to_string(),
+            display_name: "Create Issue".to_string(),
+            description: String::new(),
+            input_schema,
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn default_reviews_only_generated_arguments() {
+        let generated = tool(Some(json!({ "properties": { "title": {}, "body": {} } })));
+        let text_only = tool(Some(json!({ "properties": { "text": {} } })));
+        assert!(needs_review(&generated, None));
+        assert!(!needs_review(&text_only, None));
+        assert!(!needs_review(&tool(None), None));
+    }
+
+    #[test]
+    fn explicit_preference_wins() {
+        let generated = tool(Some(json!({ "properties": { "title": {}, "body": {} } })));
+        assert!(!needs_review(&generated, Some(ConfirmPref::Never)));
+        assert!(needs_review(&tool(None), Some(ConfirmPref::Always)));
+    }
+
+    #[test]
+    fn reviewed_arguments_must_match_the_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "title": { "type": "string" } },
+            "required": ["title"]
+        });
+        let generated = tool(Some(schema));
+        assert!(accept_reviewed(&generated, &json!({ "title": "Bug" }), None).is_ok());
+        assert!(accept_reviewed(&generated, &json!({}), None).is_err());
+        assert!(accept_reviewed(&tool(None), &json!({}), None).is_ok());
+    }
+
+    #[test]
+    fn prefs_serialize_lowercase() {
+        let prefs = HashMap::from([("a:b".to_string(), ConfirmPref::Always)]);
+        assert_eq!(
+            serde_json::to_string(&prefs).unwrap(),
+            r#"{"a:b":"always"}"#
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Tauri commands for the plugin argument review step.
+//!
+//! The action menu and text launcher call these when a plugin action comes
+//! back as a `tool_args` result: fetch the tool's schema to render the
+//! form, then send the (possibly edited) arguments for the actual call.
+
+use crate::llm::{self, ActionResult};
+use crate::mcp::confirm::{self, ConfirmPref};
+use crate::mcp::registry::{qualified_name, ToolRegistry};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Tool metadata the review form is rendered from.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolReview {
+    /// Qualified tool name ("plugin_id:tool_name").
+    pub tool_id: String,
+    pub display_name: String,
+    pub description: String,
+    pub input_schema: Option<serde_json::Value>,
+    pub pref: Option<ConfirmPref>,
+}
+
+/// Get the schema and current review preference for a plugin action.
+#[tauri::command]
+pub async fn get_tool_review(
+    action_id: String,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<ToolReview, String> {
+    let tool = registry
+        .tool_for_action(&action_id)
+        .await
+        .ok_or_else(|| format!("Unknown plugin action: {}", action_id))?;
+    Ok(ToolReview {
+        tool_id: qualified_name(&tool.plugin_id, &tool.name),
+        pref: confirm::pref_for(&tool),
+        display_name: tool.display_name,
+        description: tool.description,
+        input_schema: tool.input_schema,
+    })
+}
+
+/// Run a reviewed plugin call with the user's final arguments.
+///
+/// The arguments are validated against the tool's schema again (the user
+/// may have edited them); errors are returned so the form can show them.
+/// `remember` updates the tool's review preference. `record_history` is
+/// set by the action menu so the call is logged against the current snip.
+#[tauri::command]
+pub async fn confirm_plugin_call(
+    action_id: String,
+    arguments: serde_json::Value,
+    remember: Option<ConfirmPref>,
+    record_history: bool,
+    state: tauri::State<'_, llm::ActionMenuState>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<ActionResult, String> {
+    let tool = registry
+        .tool_for_action(&action_id)
+        .await
+        .ok_or_else(|| format!("Unknown plugin action: {}", action_id))?;
+    confirm::accept_reviewed(&tool, &arguments, remember)?;
+
+    log::info!("[MCP] User approved arguments for '{}'", action_id);
+    let result =
+        crate::mcp::call_with_arguments(&registry, &action_id, arguments, Some(&tool)).await;
+    if record_history {
+        crate::pipeline::record_in_history(&state, &result);
+    }
+    Ok(result)
+}
+
+/// Get every saved review preference, keyed by qualified tool name.
+#[tauri::command]
+pub fn get_tool_confirm_prefs() -> HashMap<String, ConfirmPref> {
+    confirm::load_prefs()
+}
+
+/// Set or clear (`pref: null`) the review preference for a tool.
+#[tauri::command]
+pub fn set_tool_confirm_pref(tool_id: String, pref: Option<ConfirmPref>) -> Result<(), String> {
+    confirm::set_pref(&tool_id, pref)
+}
@@ -0,0 +1,240 @@
+//! Developer mode — run plugins straight from their working directory.
+//!
+//! Settings live in `~/.config/omni-glass/dev-plugins.json`:
+//!
+//! ```json
+//! { "enabled": true, "paths": ["/Users/me/src/my-plugin"] }
+//! ```
+//!
+//! With developer mode on, each listed directory is loaded without the
+//! approval prompt — adding the path in Settings is the approval, and it
+//! covers that path only. The plugin still runs with its declared
+//! permissions, sandbox and limits. A watcher polls the directory and
+//! restarts the plugin once its files have changed and settled. A dev
+//! plugin replaces an installed plugin with the same id while it runs, but
+//! starts without that plugin's configuration and secrets; the installed
+//! copy is loaded again when the path stops being watched.
+
+use crate::mcp::dev_snapshot::snapshot;
+use crate::mcp::{launch, loader};
+use crate::mcp::manifest::PluginManifest;
+use crate::mcp::manifest_check;
+use crate::mcp::registry::ToolRegistry;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+
+pub use crate::mcp::dev_settings::{load_settings, save_settings, DevSettings};
+
+/// How often watched directories are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a watched directory is doing, for the settings panel.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DevStatus {
+    /// Id of the plugin currently running from this path.
+    pub plugin_id: Option<String>,
+    pub tool_count: u32,
+    /// Why the last (re)load failed.
+    pub error: Option<String>,
+    /// Loads so far, the first one included.
+    pub loads: u32,
+    /// Unix time of the last load attempt.
+    pub loaded_at: Option<u64>,
+}
+
+/// Running watchers and their status. Managed as Tauri state.
+pub struct DevPlugins {
+    watchers: Mutex<HashMap<PathBuf, JoinHandle<()>>>,
+    status: Arc<Mutex<HashMap<PathBuf, DevStatus>>>,
+}
+
+impl DevPlugins {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+            status: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Status of a watched path (default if it isn't watched).
+    pub fn status(&self, path: &Path) -> DevStatus {
+        self.status
+            .lock()
+            .ok()
+            .and_then(|s| s.get(path).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Load `path` and restart it on change. Does nothing if already watched.
+    pub fn watch(&self, app: &AppHandle, path: PathBuf) {
+        let Ok(mut watchers) = self.watchers.lock() else {
+            return;
+        };
+        if watchers.contains_key(&path) {
+            return;
+        }
+        log::info!("[DEV] Watching {}", path.display());
+        let task =
+            tauri::async_runtime::spawn(watch_loop(app.clone(), path.clone(), self.status.clone()));
+        watchers.insert(path, task);
+    }
+
+    /// Stop watching `path` and unload the plugin it was running.
+    pub async fn unwatch(&self, registry: &ToolRegistry, path: &Path) {
+        if let Some(task) = self.watchers.lock().ok().and_then(|mut w| w.remove(path)) {
+            task.abort();
+        }
+        let status = self.status.lock().ok().and_then(|mut s| s.remove(path));
+        if let Some(plugin_id) = status.and_then(|s| s.plugin_id) {
+            registry.remove_plugin(&plugin_id).await;
+            log::info!("[DEV] Stopped '{}' ({})", plugin_id, path.display());
+            match crate::mcp::load_installed(registry, &plugin_id).await {
+                Ok(Some(_)) => log::info!("[DEV] Reloaded the installed '{}'", plugin_id),
+                Ok(None) => {}
+                Err(e) => log::warn!(
+                    "[DEV] Failed to reload the installed '{}': {}",
+                    plugin_id,
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Stop every watcher (developer mode turned off).
+    pub async fn unwatch_all(&self, registry: &ToolRegistry) {
+        let paths: Vec<PathBuf> = self
+            .watchers
+            .lock()
+            .map(|w| w.keys().cloned().collect())
+            .unwrap_or_default();
+        for path in paths {
+            self.unwatch(registry, &path).await;
+        }
+    }
+}
+
+impl Default for DevPlugins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Start watchers for the saved paths if developer mode is on. Called at
+/// startup after the installed plugins have loaded.
+pub fn start(app: &AppHandle) {
+    let settings = load_settings();
+    if !settings.enabled {
+        return;
+    }
+    let devs = app.state::<DevPlugins>();
+    for path in settings.paths {
+        devs.watch(app, PathBuf::from(path));
+    }
+}
+
+/// Load the plugin, then reload it each time its files change and settle.
+async fn watch_loop(app: AppHandle, dir: PathBuf, status: Arc<Mutex<HashMap<PathBuf, DevStatus>>>) {
+    let registry = app.state::<ToolRegistry>();
+    let mut loaded = snapshot(&dir);
+    let mut running = reload(&registry, &dir, None, &status).await;
+    let mut previous = loaded.clone();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let current = snapshot(&dir);
+        // Restart once a change has held still for one poll, so a save
+        // that touches several files restarts the plugin once
+        if current != loaded && current == previous {
+            log::info!("[DEV] {} changed, restarting", dir.display());
+            running = reload(&registry, &dir, running.as_deref(), &status).await;
+            loaded = current.clone();
+        }
+        previous = current;
+    }
+}
+
+/// Stop `running` (if any) and load the plugin in `dir` again. Returns the
+/// id now running from `dir`.
+async fn reload(
+    registry: &ToolRegistry,
+    dir: &Path,
+    running: Option<&str>,
+    status: &Mutex<HashMap<PathBuf, DevStatus>>,
+) -> Option<String> {
+    if let Some(plugin_id) = running {
+        registry.remove_plugin(plugin_id).await;
+    }
+    let outcome = match manifest_check::check_manifest(dir) {
+        Ok(manifest) => load_dev_plugin(registry, &manifest, dir)
+            .await
+            .map(|tools| (manifest.id, tools)),
+        Err(errors) => Err(errors.join("; ")),
+    };
+
+    let mut entry = status
+        .lock()
+        .ok()
+        .and_then(|s| s.get(dir).cloned())
+        .unwrap_or_default();
+    entry.loads += 1;
+    entry.loaded_at = Some(unix_now());
+    let running = match outcome {
+        Ok((plugin_id, tools)) => {
+            log::info!(
+                "[DEV] Loaded '{}' from {} ({} tools)",
+                plugin_id,
+                dir.display(),
+                tools
+            );
+            entry.plugin_id = Some(plugin_id.clone());
+            entry.tool_count = tools;
+            entry.error = None;
+            Some(plugin_id)
+        }
+        Err(e) => {
+            // Nothing is registered until a load succeeds
+            log::warn!("[DEV] Failed to load {}: {}", dir.display(), e);
+            entry.plugin_id = None;
+            entry.tool_count = 0;
+            entry.error = Some(e);
+            None
+        }
+    };
+    if let Ok(mut s) = status.lock() {
+        s.insert(dir.to_path_buf(), entry);
+    }
+    running
+}
+
+/// Start and register the plugin in `dir`. One that shares an installed
+/// plugin's id replaces it but gets no configuration, so it never sees the
+/// installed plugin's settings or secrets.
+async fn load_dev_plugin(
+    registry: &ToolRegistry,
+    manifest: &PluginManifest,
+    dir: &Path,
+) -> Result<u32, String> {
+    let mut server = launch::start_plugin(manifest, dir, registry.host()).await?;
+    if loader::find_plugin(&manifest.id).is_some() {
+        log::info!(
+            "[DEV] '{}' from {} replaces the installed copy, without its configuration",
+            manifest.id,
+            dir.display()
+        );
+        registry.remove_plugin(&manifest.id).await;
+        server.set_configuration(serde_json::Map::new());
+    }
+    loader::register_plugin(manifest, server, registry).await
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,108 @@
+//! Tauri commands for the developer mode section of Settings.
+//!
+//! Adding a path is recorded in the audit log as an approval for that
+//! path, since its plugin then runs without the permission prompt.
+
+use crate::audit::AuditKind;
+use crate::mcp::dev::{self, DevPlugins, DevStatus};
+use crate::mcp::manifest_check;
+use crate::mcp::registry::ToolRegistry;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A watched plugin directory and what it is doing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevPluginView {
+    pub path: String,
+    #[serde(flatten)]
+    pub status: DevStatus,
+}
+
+/// Developer mode as shown in Settings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevModeView {
+    pub enabled: bool,
+    pub plugins: Vec<DevPluginView>,
+}
+
+/// Developer mode settings and the status of each path.
+#[tauri::command]
+pub fn get_dev_plugins(devs: tauri::State<'_, DevPlugins>) -> DevModeView {
+    let settings = dev::load_settings();
+    let plugins = settings
+        .paths
+        .into_iter()
+        .map(|path| DevPluginView {
+            status: devs.status(&PathBuf::from(&path)),
+            path,
+        })
+        .collect();
+    DevModeView {
+        enabled: settings.enabled,
+        plugins,
+    }
+}
+
+/// Turn developer mode on (load and watch every path) or off (stop them).
+#[tauri::command]
+pub async fn set_dev_mode(
+    enabled: bool,
+    app: tauri::AppHandle,
+    devs: tauri::State<'_, DevPlugins>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), String> {
+    let mut settings = dev::load_settings();
+    settings.enabled = enabled;
+    dev::save_settings(&settings)?;
+    log::info!("[DEV] Developer mode: {}", enabled);
+    if enabled {
+        dev::start(&app);
+    } else {
+        devs.unwatch_all(&registry).await;
+    }
+    Ok(())
+}
+
+/// Add a plugin directory. Its manifest must be valid; the plugin starts
+/// right away if developer mode is on.
+#[tauri::command]
+pub fn add_dev_plugin(
+    path: String,
+    app: tauri::AppHandle,
+    devs: tauri::State<'_, DevPlugins>,
+) -> Result<(), String> {
+    let dir = std::fs::canonicalize(&path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+    let manifest = manifest_check::check_manifest(&dir).map_err(|errors| errors.join("\n"))?;
+
+    let mut settings = dev::load_settings();
+    let display = dir.display().to_string();
+    if !settings.paths.contains(&display) {
+        settings.paths.push(display.clone());
+        dev::save_settings(&settings)?;
+        crate::audit::record(
+            AuditKind::PluginApproval,
+            &manifest.id,
+            serde_json::json!({ "decision": "developerMode", "path": display }),
+        );
+    }
+    if settings.enabled {
+        devs.watch(&app, dir);
+    }
+    Ok(())
+}
+
+/// Remove a plugin directory and stop its plugin.
+#[tauri::command]
+pub async fn remove_dev_plugin(
+    path: String,
+    devs: tauri::State<'_, DevPlugins>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), String> {
+    let mut settings = dev::load_settings();
+    settings.paths.retain(|p| *p != path);
+    dev::save_settings(&settings)?;
+    devs.unwatch(&registry, &PathBuf::from(&path)).await;
+    Ok(())
+}
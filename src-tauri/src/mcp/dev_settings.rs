@@ -0,0 +1,56 @@
+//! Developer mode settings in `~/.config/omni-glass/dev-plugins.json`.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const DEV_FILE: &str = "dev-plugins.json";
+
+/// Developer mode settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DevSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Plugin directories, absolute.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+fn settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(DEV_FILE))
+}
+
+/// Load developer mode settings. Off with no paths if there is no file.
+pub fn load_settings() -> DevSettings {
+    settings_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Save developer mode settings.
+pub fn save_settings(settings: &DevSettings) -> Result<(), String> {
+    let path = settings_path().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize dev settings: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_default_to_off() {
+        let settings: DevSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, DevSettings::default());
+        let settings: DevSettings =
+            serde_json::from_str(r#"{ "enabled": true, "paths": ["/src/p"] }"#).unwrap();
+        assert!(settings.enabled);
+        assert_eq!(settings.paths, ["/src/p"]);
+    }
+}
@@ -0,0 +1,87 @@
+//! File snapshots for the developer mode watcher.
+//!
+//! The watcher polls instead of using OS file events: comparing the size
+//! and modification time of every file is cheap for plugin-sized folders
+//! and needs no dependency.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directories that never trigger a restart (dependencies, VCS, caches).
+const IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "target",
+];
+
+/// Files looked at per directory; past this, changes go unnoticed.
+const MAX_WATCHED_FILES: usize = 5000;
+
+/// Every watched file in `dir` with its size and modification time.
+/// Comparing two snapshots tells whether anything was added, removed or
+/// changed.
+pub fn snapshot(dir: &Path) -> BTreeMap<PathBuf, (u64, SystemTime)> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                let ignored = entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| IGNORED_DIRS.contains(&name));
+                if !ignored {
+                    pending.push(path);
+                }
+            } else if file_type.is_file() {
+                if files.len() >= MAX_WATCHED_FILES {
+                    return files;
+                }
+                if let Ok(meta) = entry.metadata() {
+                    let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.insert(path, (meta.len(), modified));
+                }
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_sees_changes_but_not_dependencies() {
+        let dir = std::env::temp_dir().join("og-dev-snapshot-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("node_modules/dep")).unwrap();
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::write(dir.join("index.js"), "one").unwrap();
+        std::fs::write(dir.join("lib/util.js"), "x").unwrap();
+        let before = snapshot(&dir);
+        assert_eq!(before.len(), 2);
+
+        std::fs::write(dir.join("node_modules/dep/index.js"), "dep").unwrap();
+        assert_eq!(snapshot(&dir), before);
+
+        std::fs::write(dir.join("index.js"), "three").unwrap();
+        assert_ne!(snapshot(&dir), before);
+
+        std::fs::write(dir.join("index.js"), "one").unwrap();
+        std::fs::remove_file(dir.join("lib/util.js")).unwrap();
+        assert_eq!(snapshot(&dir).len(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
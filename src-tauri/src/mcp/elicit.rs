@@ -0,0 +1,251 @@
+//! `elicitation/create` — plugins asking the user a question mid-call.
+//!
+//! `AppHost` is the app's `ClientHost`. For an elicitation it opens a small
+//! `elicitation` window that renders the plugin's `requestedSchema` as a form
+//! and parks the plugin call on a oneshot channel until the user answers,
+//! closes the window, or `ELICIT_TIMEOUT_SECS` passes. Sampling requests are
+//! handed to `mcp::sampling`.
+
+use crate::mcp::host::{rpc_error, ClientHost, HostFuture, INTERNAL_ERROR};
+use crate::mcp::types::JsonRpcError;
+use crate::mcp::{host, sampling, schema};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::Manager;
+use tokio::sync::oneshot;
+
+/// How long a plugin waits for the user before the prompt is cancelled.
+pub const ELICIT_TIMEOUT_SECS: u64 = 300;
+
+/// Window label prefix; the frontend reads the prompt id from the label.
+pub const WINDOW_PREFIX: &str = "elicitation-";
+
+/// A question from a plugin, as shown in the elicitation window.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitationPrompt {
+    pub id: u64,
+    pub plugin_id: String,
+    pub message: String,
+    pub requested_schema: Value,
+}
+
+/// Open elicitation prompts. Managed as Tauri state.
+pub struct Elicitations {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, (ElicitationPrompt, oneshot::Sender<Value>)>>,
+}
+
+impl Elicitations {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a prompt; the receiver resolves with the MCP elicit result.
+    pub fn open(
+        &self,
+        plugin_id: &str,
+        params: &Value,
+    ) -> (ElicitationPrompt, oneshot::Receiver<Value>) {
+        let prompt = ElicitationPrompt {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+            plugin_id: plugin_id.to_string(),
+            message: host::message_of(params).to_string(),
+            requested_schema: params
+                .get("requestedSchema")
+                .cloned()
+                .unwrap_or(Value::Null),
+        };
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(prompt.id, (prompt.clone(), tx));
+        (prompt, rx)
+    }
+
+    /// The prompt with this id, if it is still open.
+    pub fn get(&self, id: u64) -> Option<ElicitationPrompt> {
+        self.pending
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|(p, _)| p.clone())
+    }
+
+    /// Validate the user's answer and hand it to the waiting plugin call.
+    pub fn answer(&self, id: u64, action: &str, content: Option<Value>) -> Result<(), String> {
+        let prompt = self.get(id).ok_or("This prompt is no longer open")?;
+        let result = to_result(&prompt, action, content)?;
+        if let Some((_, tx)) = self.pending.lock().unwrap().remove(&id) {
+            let _ = tx.send(result);
+        }
+        Ok(())
+    }
+
+    /// Drop a prompt; its plugin call sees the user cancel.
+    pub fn remove(&self, id: u64) {
+        self.pending.lock().unwrap().remove(&id);
+    }
+}
+
+impl Default for Elicitations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build an MCP elicit result. Accepted content must match the schema.
+pub fn to_result(
+    prompt: &ElicitationPrompt,
+    action: &str,
+    content: Option<Value>,
+) -> Result<Value, String> {
+    match action {
+        "accept" => {
+            let content = content.unwrap_or_else(|| json!({}));
+            let errors = schema::validate(&prompt.requested_schema, &content);
+            if !errors.is_empty() {
+                return Err(errors.join("\n"));
+            }
+            Ok(json!({ "action": "accept", "content": content }))
+        }
+        "decline" | "cancel" => Ok(json!({ "action": action })),
+        other => Err(format!("Unknown elicitation action: {}", other)),
+    }
+}
+
+/// The app's `ClientHost`: elicitation windows and provider-backed sampling.
+pub struct AppHost {
+    app: tauri::AppHandle,
+}
+
+impl AppHost {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl ClientHost for AppHost {
+    fn elicit<'a>(&'a self, plugin_id: &'a str, params: Value) -> HostFuture<'a> {
+        Box::pin(ask_user(&self.app, plugin_id, params))
+    }
+
+    fn sample<'a>(&'a self, plugin_id: &'a str, params: Value) -> HostFuture<'a> {
+        Box::pin(sampling::run(&self.app, plugin_id, params))
+    }
+}
+
+/// Show the prompt window and wait for the user's answer.
+async fn ask_user(
+    app: &tauri::AppHandle,
+    plugin_id: &str,
+    params: Value,
+) -> Result<Value, JsonRpcError> {
+    let state = app.state::<Elicitations>();
+    let (prompt, rx) = state.open(plugin_id, &params);
+    let label = format!("{}{}", WINDOW_PREFIX, prompt.id);
+
+    let window = tauri::WebviewWindowBuilder::new(
+        app,
+        &label,
+        tauri::WebviewUrl::App("elicitation.html".into()),
+    )
+    .title("Plugin Question")
+    .inner_size(420.0, 320.0)
+    .resizable(false)
+    .always_on_top(true)
+    .center()
+    .build()
+    .map_err(|e| {
+        state.remove(prompt.id);
+        rpc_error(
+            INTERNAL_ERROR,
+            format!("Could not open prompt window: {}", e),
+        )
+    })?;
+
+    // Closing the window without answering drops the sender → cancel.
+    let handle = app.clone();
+    let id = prompt.id;
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            handle.state::<Elicitations>().remove(id);
+        }
+    });
+
+    let wait = std::time::Duration::from_secs(ELICIT_TIMEOUT_SECS);
+    let result = match tokio::time::timeout(wait, rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => json!({ "action": "cancel" }),
+        Err(_) => {
+            log::info!("[MCP] Prompt from '{}' timed out", plugin_id);
+            state.remove(prompt.id);
+            json!({ "action": "cancel" })
+        }
+    };
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.close();
+    }
+    log::info!(
+        "[MCP] User answered '{}': {}",
+        plugin_id,
+        result["action"].as_str().unwrap_or("?")
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> ElicitationPrompt {
+        ElicitationPrompt {
+            id: 1,
+            plugin_id: "com.example.gh".to_string(),
+            message: "Which label?".to_string(),
+            requested_schema: json!({ "type": "object", "required": ["label"],
+                "properties": { "label": { "type": "string", "enum": ["bug", "docs"] } } }),
+        }
+    }
+
+    #[test]
+    fn accepted_content_is_validated() {
+        let ok = to_result(&prompt(), "accept", Some(json!({ "label": "bug" }))).unwrap();
+        assert_eq!(
+            ok,
+            json!({ "action": "accept", "content": { "label": "bug" } })
+        );
+        assert!(to_result(&prompt(), "accept", Some(json!({ "label": "other" }))).is_err());
+        assert!(to_result(&prompt(), "accept", None).is_err());
+    }
+
+    #[test]
+    fn decline_and_cancel_carry_no_content() {
+        let declined = to_result(&prompt(), "decline", Some(json!({ "label": "bug" }))).unwrap();
+        assert_eq!(declined, json!({ "action": "decline" }));
+        assert!(to_result(&prompt(), "later", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn answer_resolves_the_waiting_call() {
+        let state = Elicitations::new();
+        let params =
+            json!({ "message": "Which label?", "requestedSchema": prompt().requested_schema });
+        let (open, rx) = state.open("com.example.gh", &params);
+        assert!(state
+            .answer(open.id, "accept", Some(json!({ "label": "x" })))
+            .is_err());
+        state
+            .answer(open.id, "accept", Some(json!({ "label": "docs" })))
+            .unwrap();
+        assert_eq!(rx.await.unwrap()["content"]["label"], "docs");
+        assert!(state.get(open.id).is_none());
+    }
+}
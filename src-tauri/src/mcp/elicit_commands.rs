@@ -0,0 +1,31 @@
+//! Tauri commands for the plugin elicitation window.
+//!
+//! The window's label is `elicitation-<id>`; it loads its prompt with
+//! `get_elicitation` and sends the user's choice with `respond_elicitation`.
+
+use crate::mcp::elicit::{ElicitationPrompt, Elicitations};
+
+/// Get an open prompt by id.
+#[tauri::command]
+pub fn get_elicitation(
+    id: u64,
+    state: tauri::State<'_, Elicitations>,
+) -> Result<ElicitationPrompt, String> {
+    state
+        .get(id)
+        .ok_or_else(|| "This prompt is no longer open".to_string())
+}
+
+/// Answer a prompt: `action` is `accept` (with `content`), `decline` or `cancel`.
+///
+/// Accepted content is validated against the plugin's schema; errors are
+/// returned so the form can show them and the prompt stays open.
+#[tauri::command]
+pub fn respond_elicitation(
+    id: u64,
+    action: String,
+    content: Option<serde_json::Value>,
+    state: tauri::State<'_, Elicitations>,
+) -> Result<(), String> {
+    state.answer(id, &action, content)
+}
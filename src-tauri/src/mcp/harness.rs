@@ -0,0 +1,280 @@
+//! Plugin test harness — run one plugin the way the app does, outside it.
+//!
+//! `run` starts a plugin from its directory with the app's environment
+//! filter, sandbox and limits (no approval: the caller pointed at it), then
+//! times each step: spawn, `initialize`, `tools/list`, argument building and
+//! `tools/call`. Arguments are either given as JSON or built from OCR text
+//! by the args bridge, as for a snip, and the result goes through the same
+//! mapping and safety checks. There is no host, so elicitation and sampling
+//! requests are refused, as in the CLI.
+//!
+//! `omni-glass-cli test-plugin` prints the report as JSON.
+
+use crate::llm::execute::ActionResult;
+use crate::mcp::plugin_logs::{self, LogEntry, LogLevel};
+use crate::mcp::registry::{self, RegisteredTool, ToolRegistry};
+use crate::mcp::types::{McpTool, ServerInfo};
+use crate::mcp::{args, launch, manifest_check, schema};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Log entries included in the report.
+const REPORT_LOG_ENTRIES: usize = 100;
+
+/// What to run.
+#[derive(Debug, Clone, Default)]
+pub struct HarnessRequest {
+    pub plugin_dir: PathBuf,
+    /// Tool to call; without one the run stops after `tools/list`.
+    pub tool: Option<String>,
+    /// Arguments to send as-is.
+    pub arguments: Option<Value>,
+    /// OCR text to build arguments from with the args bridge.
+    pub text: Option<String>,
+}
+
+/// Where the call's arguments came from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArgumentsSource {
+    Given,
+    ArgsBridge,
+}
+
+/// Milliseconds per step.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timings {
+    pub spawn_ms: u64,
+    pub initialize_ms: u64,
+    pub list_tools_ms: u64,
+    pub arguments_ms: Option<u64>,
+    pub call_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+/// The tool call and what the app would have shown for it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallReport {
+    pub tool: String,
+    /// `null` if the args bridge failed.
+    pub arguments: Value,
+    pub arguments_from: ArgumentsSource,
+    /// Schema problems in given arguments. They are sent anyway, so the
+    /// plugin's own handling can be tested.
+    pub argument_errors: Vec<String>,
+    pub result: ActionResult,
+}
+
+/// Everything a run found out.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarnessReport {
+    pub plugin_id: String,
+    pub version: String,
+    pub server: ServerInfo,
+    pub tools: Vec<McpTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call: Option<CallReport>,
+    pub timings: Timings,
+    /// What the plugin logged (stderr and MCP log messages).
+    pub logs: Vec<LogEntry>,
+}
+
+impl HarnessReport {
+    /// Whether the tool call (if any) succeeded.
+    pub fn passed(&self) -> bool {
+        self.call
+            .as_ref()
+            .is_none_or(|call| call.result.status != "error")
+    }
+}
+
+/// Start the plugin, discover its tools, optionally call one, and stop it.
+pub async fn run(request: &HarnessRequest) -> Result<HarnessReport, String> {
+    let started = Instant::now();
+    let manifest = manifest_check::check_manifest(&request.plugin_dir)
+        .map_err(|errors| format!("Invalid manifest: {}", errors.join("; ")))?;
+    check_request(request)?;
+    let mut timings = Timings::default();
+
+    let step = Instant::now();
+    let mut server = launch::start_plugin(&manifest, &request.plugin_dir, None).await?;
+    timings.spawn_ms = elapsed_ms(step);
+
+    let step = Instant::now();
+    let server_info = server.initialize().await?;
+    timings.initialize_ms = elapsed_ms(step);
+
+    let step = Instant::now();
+    let tools = server.list_tools().await?;
+    timings.list_tools_ms = elapsed_ms(step);
+
+    let registry = ToolRegistry::new();
+    registry
+        .register_plugin_tools(&manifest.id, tools.clone())
+        .await;
+    registry.add_server(manifest.id.clone(), server).await;
+
+    let call = match &request.tool {
+        Some(name) => call_tool(&registry, &manifest.id, name, request, &mut timings).await,
+        None => Ok(None),
+    };
+    registry.shutdown_all().await;
+    timings.total_ms = elapsed_ms(started);
+
+    Ok(HarnessReport {
+        logs: plugin_logs::recent(&manifest.id, REPORT_LOG_ENTRIES, LogLevel::Debug),
+        plugin_id: manifest.id,
+        version: manifest.version,
+        server: server_info,
+        tools,
+        call: call?,
+        timings,
+    })
+}
+
+/// Refuse requests that can't make a call.
+fn check_request(request: &HarnessRequest) -> Result<(), String> {
+    match (&request.tool, &request.arguments, &request.text) {
+        (_, Some(_), Some(_)) => Err("Give arguments or text, not both".to_string()),
+        (None, Some(_), _) | (None, _, Some(_)) => {
+            Err("Arguments and text need a tool to call".to_string())
+        }
+        (Some(_), None, None) => Err("Give arguments or text for the call".to_string()),
+        _ => Ok(()),
+    }
+}
+
+async fn call_tool(
+    registry: &ToolRegistry,
+    plugin_id: &str,
+    name: &str,
+    request: &HarnessRequest,
+    timings: &mut Timings,
+) -> Result<Option<CallReport>, String> {
+    let action_id = registry::qualified_name(plugin_id, name);
+    let tool = match registry.get_tool(&action_id).await {
+        Some(tool) => tool,
+        None => {
+            let names: Vec<String> = registry
+                .all_tools()
+                .await
+                .into_iter()
+                .map(|t| t.name)
+                .collect();
+            return Err(format!(
+                "'{}' has no tool '{}' (it has: {})",
+                plugin_id,
+                name,
+                names.join(", ")
+            ));
+        }
+    };
+
+    let step = Instant::now();
+    let built = build_arguments(&action_id, &tool, request).await;
+    timings.arguments_ms = Some(elapsed_ms(step));
+    let (arguments, arguments_from, argument_errors) = match built {
+        Ok(built) => built,
+        Err(e) => {
+            return Ok(Some(CallReport {
+                tool: name.to_string(),
+                arguments: Value::Null,
+                arguments_from: ArgumentsSource::ArgsBridge,
+                argument_errors: Vec::new(),
+                result: ActionResult::error(&action_id, &e),
+            }))
+        }
+    };
+
+    let step = Instant::now();
+    let result =
+        crate::mcp::call_with_arguments(registry, &action_id, arguments.clone(), Some(&tool)).await;
+    timings.call_ms = Some(elapsed_ms(step));
+
+    Ok(Some(CallReport {
+        tool: name.to_string(),
+        arguments,
+        arguments_from,
+        argument_errors,
+        result,
+    }))
+}
+
+/// Given arguments with their schema problems, or arguments from the args
+/// bridge.
+async fn build_arguments(
+    action_id: &str,
+    tool: &RegisteredTool,
+    request: &HarnessRequest,
+) -> Result<(Value, ArgumentsSource, Vec<String>), String> {
+    if let Some(arguments) = &request.arguments {
+        let errors = tool
+            .input_schema
+            .as_ref()
+            .map(|s| schema::validate(s, arguments))
+            .unwrap_or_default();
+        return Ok((arguments.clone(), ArgumentsSource::Given, errors));
+    }
+    let text = request.text.as_deref().unwrap_or_default();
+    args::build_arguments(action_id, Some(tool), text, None)
+        .await
+        .map(|arguments| (arguments, ArgumentsSource::ArgsBridge, Vec::new()))
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(tool: Option<&str>, arguments: Option<Value>, text: Option<&str>) -> HarnessRequest {
+        HarnessRequest {
+            plugin_dir: PathBuf::from("plugins/example"),
+            tool: tool.map(str::to_string),
+            arguments,
+            text: text.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn requests_need_one_input_per_call() {
+        let args = Some(serde_json::json!({ "text": "hi" }));
+        assert!(check_request(&request(None, None, None)).is_ok());
+        assert!(check_request(&request(Some("echo"), args.clone(), None)).is_ok());
+        assert!(check_request(&request(Some("echo"), None, Some("hi"))).is_ok());
+        assert!(check_request(&request(Some("echo"), None, None)).is_err());
+        assert!(check_request(&request(Some("echo"), args.clone(), Some("hi"))).is_err());
+        assert!(check_request(&request(None, args, None)).is_err());
+        assert!(check_request(&request(None, None, Some("hi"))).is_err());
+    }
+
+    #[tokio::test]
+    async fn given_arguments_report_schema_problems() {
+        let tool = RegisteredTool {
+            plugin_id: "com.test.harness".to_string(),
+            name: "echo".to_string(),
+            display_name: "Echo".to_string(),
+            description: String::new(),
+            input_schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            })),
+            output_schema: None,
+        };
+        let req = request(Some("echo"), Some(serde_json::json!({ "txt": 1 })), None);
+        let (arguments, from, errors) = build_arguments("com.test.harness:echo", &tool, &req)
+            .await
+            .unwrap();
+        assert_eq!(arguments, serde_json::json!({ "txt": 1 }));
+        assert_eq!(from, ArgumentsSource::Given);
+        assert!(!errors.is_empty());
+    }
+}
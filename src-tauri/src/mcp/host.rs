@@ -0,0 +1,169 @@
+//! Server-initiated requests — what a plugin may ask of Omni-Glass.
+//!
+//! While handling `tools/call`, an MCP server can send requests back over
+//! the same stdio channel:
+//!
+//! - `elicitation/create`: ask the user a question with a small form
+//! - `sampling/createMessage`: borrow the app's active LLM provider
+//! - `ping`: liveness check
+//!
+//! `McpServer` forwards these to `handle_request`, which checks what the
+//! plugin is allowed to do and delegates to a `ClientHost`. The app's host
+//! (`elicit::AppHost`) shows UI and calls providers; the headless CLI has no
+//! host, so plugins get a "method not found" error there.
+
+use crate::mcp::types::JsonRpcError;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
+
+/// JSON-RPC: the client does not implement this method.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC: malformed request parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC: the host failed while handling the request.
+pub const INTERNAL_ERROR: i64 = -32603;
+/// MCP: the request was refused (no permission, or the user declined).
+pub const REQUEST_REJECTED: i64 = -1;
+
+/// Boxed future returned by `ClientHost` methods.
+pub type HostFuture<'a> = Pin<Box<dyn Future<Output = Result<Value, JsonRpcError>> + Send + 'a>>;
+
+/// Answers elicitation and sampling requests on behalf of the user.
+pub trait ClientHost: Send + Sync {
+    /// Show `params.message` with a form for `params.requestedSchema`.
+    /// Resolves to an MCP elicit result: `{action, content?}`.
+    fn elicit<'a>(&'a self, plugin_id: &'a str, params: Value) -> HostFuture<'a>;
+
+    /// Run `params` (an MCP `createMessage` request) on the active provider.
+    /// Resolves to an MCP sampling result: `{role, content, model, stopReason}`.
+    fn sample<'a>(&'a self, plugin_id: &'a str, params: Value) -> HostFuture<'a>;
+}
+
+/// Build a JSON-RPC error object.
+pub fn rpc_error(code: i64, message: impl Into<String>) -> JsonRpcError {
+    JsonRpcError {
+        code,
+        message: message.into(),
+        data: None,
+    }
+}
+
+/// Dispatch one server → client request.
+///
+/// `sampling_allowed` comes from the plugin's manifest (`permissions.sampling`).
+pub async fn handle_request(
+    host: Option<&dyn ClientHost>,
+    plugin_id: &str,
+    sampling_allowed: bool,
+    method: &str,
+    params: Value,
+) -> Result<Value, JsonRpcError> {
+    match (method, host) {
+        ("ping", _) => Ok(json!({})),
+        ("elicitation/create", Some(host)) => {
+            check_requested_schema(params.get("requestedSchema"))
+                .map_err(|e| rpc_error(INVALID_PARAMS, e))?;
+            log::info!(
+                "[MCP] '{}' asks the user: {}",
+                plugin_id,
+                message_of(&params)
+            );
+            host.elicit(plugin_id, params).await
+        }
+        ("sampling/createMessage", Some(_)) if !sampling_allowed => {
+            log::warn!(
+                "[MCP] '{}' tried sampling without the permission",
+                plugin_id
+            );
+            Err(rpc_error(
+                REQUEST_REJECTED,
+                "Sampling is not allowed: add \"sampling\": true to the manifest permissions",
+            ))
+        }
+        ("sampling/createMessage", Some(host)) => host.sample(plugin_id, params).await,
+        _ => Err(rpc_error(
+            METHOD_NOT_FOUND,
+            format!("Method not supported by this client: {}", method),
+        )),
+    }
+}
+
+/// The prompt text of an elicitation request.
+pub fn message_of(params: &Value) -> &str {
+    params.get("message").and_then(|m| m.as_str()).unwrap_or("")
+}
+
+/// Check an elicitation `requestedSchema` is the flat form MCP allows:
+/// an object whose properties are strings, numbers, integers, booleans
+/// or string enums. Nested objects and arrays are rejected.
+pub fn check_requested_schema(schema: Option<&Value>) -> Result<(), String> {
+    let schema = schema.ok_or("requestedSchema is missing")?;
+    if schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+        return Err("requestedSchema must have type \"object\"".to_string());
+    }
+    let props = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .ok_or("requestedSchema must have properties")?;
+    for (name, prop) in props {
+        let kind = prop.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        if !matches!(kind, "string" | "number" | "integer" | "boolean") {
+            return Err(format!(
+                "requestedSchema property '{}' must be a string, number, integer or boolean",
+                name
+            ));
+        }
+        if let Some(options) = prop.get("enum") {
+            let all_strings = options
+                .as_array()
+                .is_some_and(|o| o.iter().all(|v| v.is_string()));
+            if kind != "string" || !all_strings {
+                return Err(format!(
+                    "requestedSchema property '{}' has a non-string enum",
+                    name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requested_schema_must_be_flat() {
+        let flat = json!({ "type": "object", "properties": {
+            "name": { "type": "string" },
+            "count": { "type": "integer", "minimum": 1 },
+            "size": { "type": "string", "enum": ["s", "m", "l"] },
+        }});
+        assert!(check_requested_schema(Some(&flat)).is_ok());
+
+        let nested = json!({ "type": "object", "properties": {
+            "address": { "type": "object", "properties": {} },
+        }});
+        assert!(check_requested_schema(Some(&nested)).is_err());
+        let bad_enum = json!({ "type": "object", "properties": {
+            "n": { "type": "integer", "enum": [1, 2] },
+        }});
+        assert!(check_requested_schema(Some(&bad_enum)).is_err());
+        assert!(check_requested_schema(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn without_host_only_ping_is_answered() {
+        assert_eq!(
+            handle_request(None, "p", true, "ping", json!({}))
+                .await
+                .unwrap(),
+            json!({})
+        );
+        let err = handle_request(None, "p", true, "sampling/createMessage", json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, METHOD_NOT_FOUND);
+    }
+}
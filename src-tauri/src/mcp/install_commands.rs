@@ -0,0 +1,149 @@
+//! Tauri commands for installing, upgrading and uninstalling plugins.
+//!
+//! `installer` does the file work; these commands handle everything around
+//! it. A new or upgraded plugin has its approval cleared and is queued for
+//! the permission prompt, so it never runs before the user has seen its
+//! permissions. Uninstalling stops the plugin and clears its approval,
+//! configuration (secrets included) and logs.
+
+use crate::audit::AuditKind;
+use crate::mcp::approval;
+use crate::mcp::config_store;
+use crate::mcp::installer::{self, InstallMode, InstalledPlugin};
+use crate::mcp::loader::{self, PendingApprovals};
+use crate::mcp::plugin_logs;
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::signing::Publisher;
+use crate::mcp::trust;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Result shown to the user after an install or upgrade.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallReport {
+    pub plugin_id: String,
+    pub name: String,
+    pub version: String,
+    pub previous_version: Option<String>,
+    /// Files checked against declared SHA-256 digests (0 = none declared).
+    pub verified_files: usize,
+    pub dependencies: Vec<String>,
+    pub publisher: Publisher,
+}
+
+fn plugins_dir() -> Result<PathBuf, String> {
+    loader::plugins_dir().ok_or_else(|| "Could not determine the plugins directory".to_string())
+}
+
+/// Install a plugin from a `.tar.gz` / `.zip` file or a local directory.
+#[tauri::command]
+pub async fn install_plugin(
+    path: String,
+    app: tauri::AppHandle,
+    pending: tauri::State<'_, PendingApprovals>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<InstallReport, String> {
+    install_from(path, InstallMode::Install, &app, &pending, &registry).await
+}
+
+/// Replace an installed plugin with a newer copy from an archive or directory.
+#[tauri::command]
+pub async fn upgrade_plugin(
+    path: String,
+    app: tauri::AppHandle,
+    pending: tauri::State<'_, PendingApprovals>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<InstallReport, String> {
+    install_from(path, InstallMode::Upgrade, &app, &pending, &registry).await
+}
+
+async fn install_from(
+    path: String,
+    mode: InstallMode,
+    app: &tauri::AppHandle,
+    pending: &PendingApprovals,
+    registry: &ToolRegistry,
+) -> Result<InstallReport, String> {
+    let dir = plugins_dir()?;
+    let source = PathBuf::from(&path);
+    let trust = trust::load_trust();
+    let installed: InstalledPlugin =
+        tokio::task::spawn_blocking(move || installer::install(&source, &dir, mode, &trust))
+            .await
+            .map_err(|e| format!("Install task failed: {}", e))??;
+    let id = installed.manifest.id.clone();
+
+    // The old version keeps running until here; stop it before re-approval.
+    registry.remove_plugin(&id).await;
+    if let Some(previous) = &installed.previous {
+        config_store::prune_config(previous, &installed.manifest)?;
+    }
+    let mut store = approval::load_approvals();
+    approval::forget(&mut store, &id);
+    approval::save_approvals(&store)?;
+
+    {
+        let mut queue = pending.queue.lock().await;
+        queue.retain(|(m, _, _)| m.id != id);
+        queue.push((
+            installed.manifest.clone(),
+            installed.dir.clone(),
+            installed.previous.is_some(),
+        ));
+    }
+    crate::audit::record_async(
+        AuditKind::PluginInstall,
+        id.clone(),
+        serde_json::json!({
+            "action": if installed.previous.is_some() { "upgrade" } else { "install" },
+            "version": installed.manifest.version,
+            "previousVersion": installed.previous.as_ref().map(|m| m.version.clone()),
+            "source": path,
+            "verifiedFiles": installed.verified_files,
+            "publisher": installed.publisher,
+        }),
+    )
+    .await;
+    crate::show_permission_prompt(app);
+
+    Ok(InstallReport {
+        plugin_id: id,
+        name: installed.manifest.name.clone(),
+        version: installed.manifest.version.clone(),
+        previous_version: installed.previous.map(|m| m.version),
+        verified_files: installed.verified_files,
+        dependencies: installed.dependencies,
+        publisher: installed.publisher,
+    })
+}
+
+/// Stop and delete a plugin, and clear its approval and configuration.
+#[tauri::command]
+pub async fn uninstall_plugin(
+    plugin_id: String,
+    pending: tauri::State<'_, PendingApprovals>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), String> {
+    registry.remove_plugin(&plugin_id).await;
+    let manifest = installer::uninstall(&plugin_id, &plugins_dir()?)?;
+    pending
+        .queue
+        .lock()
+        .await
+        .retain(|(m, _, _)| m.id != plugin_id);
+
+    let mut store = approval::load_approvals();
+    approval::forget(&mut store, &plugin_id);
+    approval::save_approvals(&store)?;
+    config_store::clear_config(&manifest)?;
+    plugin_logs::remove(&plugin_id);
+
+    crate::audit::record_async(
+        AuditKind::PluginInstall,
+        plugin_id.clone(),
+        serde_json::json!({ "action": "uninstall", "version": manifest.version }),
+    )
+    .await;
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! Plugin installer — archive or local directory → plugins directory.
+//!
+//! 1. Stage: extract the archive (or copy the directory, minus `.git`) into
+//!    `plugin-staging/` next to the plugins directory
+//! 2. Validate: `manifest::load_manifest`, declared SHA-256 digests, the
+//!    publisher signature and the signing policy
+//! 3. Dependencies: installed offline from what the plugin vendors —
+//!    `npm ci --offline --ignore-scripts` for a `package-lock.json` (Node or
+//!    Bun), pip from `wheels/` into a `.venv` for a `requirements.txt`
+//! 4. Move into `plugins/{id}/`, replacing the old copy on upgrade
+//!
+//! This module only touches files. Approvals, configuration and the running
+//! server are handled by `install_commands`.
+
+use crate::mcp::manifest::{self, PluginManifest, Runtime, MANIFEST_FILENAME};
+use crate::mcp::runtime::{self, PYTHON_VENV_DIR};
+use crate::mcp::signing::{self, Publisher};
+use crate::mcp::trust::TrustStore;
+use crate::mcp::{archive, integrity, loader};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Whether the plugin must be new or must already be installed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstallMode {
+    Install,
+    Upgrade,
+}
+
+/// A plugin that was installed or upgraded.
+#[derive(Debug, Clone)]
+pub struct InstalledPlugin {
+    pub manifest: PluginManifest,
+    pub dir: PathBuf,
+    /// The manifest that was replaced, on upgrade.
+    pub previous: Option<PluginManifest>,
+    /// Number of files checked against declared digests (0 = none declared).
+    pub verified_files: usize,
+    /// Dependency steps that ran, e.g. `"npm ci"`.
+    pub dependencies: Vec<String>,
+    pub publisher: Publisher,
+}
+
+/// Removes the staging directory when the install finishes or fails.
+struct Staging(PathBuf);
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Install or upgrade a plugin from `source` into `plugins_dir`.
+pub fn install(
+    source: &Path,
+    plugins_dir: &Path,
+    mode: InstallMode,
+    trust: &TrustStore,
+) -> Result<InstalledPlugin, String> {
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let staging = Staging(plugins_dir.with_file_name("plugin-staging").join(format!(
+        "{}-{}",
+        std::process::id(),
+        nonce
+    )));
+    let unpacked = staging.0.join("unpacked");
+    std::fs::create_dir_all(&unpacked)
+        .map_err(|e| format!("Failed to create staging dir: {}", e))?;
+
+    if source.is_dir() {
+        copy_dir(source, &unpacked)?;
+    } else {
+        archive::extract(source, &unpacked)?;
+    }
+    let root = plugin_root(&unpacked)?;
+    let manifest = manifest::load_manifest(&root)?;
+    check_id(&manifest.id)?;
+
+    let verified_files = match &manifest.integrity {
+        Some(declared) => integrity::verify(declared, &root)?,
+        None => {
+            log::warn!("[INSTALL] '{}' declares no file digests", manifest.id);
+            0
+        }
+    };
+    let publisher = signing::verify(&manifest, &root, trust)?;
+    trust.check_policy(&manifest.id, &publisher)?;
+
+    let existing = loader::scan_plugins(plugins_dir)
+        .into_iter()
+        .find(|(m, _)| m.id == manifest.id);
+    match (mode, &existing) {
+        (InstallMode::Install, Some((old, _))) => {
+            return Err(format!(
+                "{} v{} is already installed; upgrade it instead",
+                old.id, old.version
+            ))
+        }
+        (InstallMode::Upgrade, None) => {
+            return Err(format!("{} is not installed", manifest.id));
+        }
+        _ => {}
+    }
+    let dependencies = install_dependencies(&manifest, &root)?;
+
+    let target = existing
+        .as_ref()
+        .map(|(_, dir)| dir.clone())
+        .unwrap_or_else(|| plugins_dir.join(&manifest.id));
+    if existing.is_none() && target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+    std::fs::create_dir_all(plugins_dir)
+        .map_err(|e| format!("Failed to create plugins dir: {}", e))?;
+    if existing.is_some() {
+        let backup = staging.0.join("previous");
+        std::fs::rename(&target, &backup)
+            .map_err(|e| format!("Failed to move the old version aside: {}", e))?;
+        if let Err(e) = std::fs::rename(&root, &target) {
+            let _ = std::fs::rename(&backup, &target);
+            return Err(format!("Failed to install the new version: {}", e));
+        }
+    } else {
+        std::fs::rename(&root, &target).map_err(|e| format!("Failed to install: {}", e))?;
+    }
+
+    log::info!(
+        "[INSTALL] {} '{}' v{} into {}",
+        if existing.is_some() {
+            "Upgraded"
+        } else {
+            "Installed"
+        },
+        manifest.id,
+        manifest.version,
+        target.display()
+    );
+    Ok(InstalledPlugin {
+        manifest,
+        dir: target,
+        previous: existing.map(|(m, _)| m),
+        verified_files,
+        dependencies,
+        publisher,
+    })
+}
+
+/// Delete an installed plugin's directory. Returns its manifest.
+pub fn uninstall(plugin_id: &str, plugins_dir: &Path) -> Result<PluginManifest, String> {
+    let (manifest, dir) = loader::scan_plugins(plugins_dir)
+        .into_iter()
+        .find(|(m, _)| m.id == plugin_id)
+        .ok_or_else(|| format!("{} is not installed", plugin_id))?;
+    std::fs::remove_dir_all(&dir)
+        .map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+    log::info!("[INSTALL] Removed '{}' from {}", plugin_id, dir.display());
+    Ok(manifest)
+}
+
+/// Plugin ids become directory names, so keep them to a safe alphabet.
+fn check_id(id: &str) -> Result<(), String> {
+    let safe = id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if safe && !id.starts_with('.') {
+        Ok(())
+    } else {
+        Err(format!(
+            "Plugin id '{}' can only use letters, digits, '.', '-' and '_'",
+            id
+        ))
+    }
+}
+
+/// The directory holding the manifest: the staged root, or its only
+/// subdirectory (archives usually wrap everything in one folder).
+fn plugin_root(staged: &Path) -> Result<PathBuf, String> {
+    if staged.join(MANIFEST_FILENAME).exists() {
+        return Ok(staged.to_path_buf());
+    }
+    let children: Vec<PathBuf> = std::fs::read_dir(staged)
+        .map_err(|e| format!("Cannot read staged plugin: {}", e))?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    match children.as_slice() {
+        [only] if only.is_dir() && only.join(MANIFEST_FILENAME).exists() => Ok(only.clone()),
+        _ => Err(format!(
+            "No {} found at the top of the plugin",
+            MANIFEST_FILENAME
+        )),
+    }
+}
+
+/// Copy a plugin directory, skipping `.git` and `.venv` (virtualenvs hold
+/// absolute paths; the installer makes a fresh one) and refusing symlinks.
+fn copy_dir(src: &Path, dest: &Path) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(src).map_err(|e| format!("Cannot read {}: {}", src.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+        if path.is_symlink() {
+            return Err(format!(
+                "Plugin contains a symbolic link: {}",
+                path.display()
+            ));
+        }
+        if path.is_dir() {
+            if entry.file_name() == ".git" || entry.file_name() == PYTHON_VENV_DIR {
+                continue;
+            }
+            std::fs::create_dir_all(&target).map_err(|e| format!("Copy failed: {}", e))?;
+            copy_dir(&path, &target)?;
+        } else {
+            std::fs::copy(&path, &target)
+                .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Install vendored dependencies in `dir`. Nothing is downloaded and no
+/// install scripts run: they would execute outside the plugin sandbox.
+pub fn install_dependencies(manifest: &PluginManifest, dir: &Path) -> Result<Vec<String>, String> {
+    let mut steps = Vec::new();
+    match manifest.runtime {
+        Runtime::Node | Runtime::Bun
+            if dir.join("package-lock.json").exists() && !dir.join("node_modules").exists() =>
+        {
+            archive::run(
+                Command::new("npm")
+                    .args([
+                        "ci",
+                        "--offline",
+                        "--ignore-scripts",
+                        "--no-audit",
+                        "--no-fund",
+                    ])
+                    .current_dir(dir),
+            )?;
+            steps.push("npm ci".to_string());
+        }
+        Runtime::Python
+            if dir.join("requirements.txt").exists() && !dir.join(PYTHON_VENV_DIR).exists() =>
+        {
+            if !dir.join("wheels").is_dir() {
+                return Err(
+                    "requirements.txt needs its wheels vendored in wheels/ (packages are not downloaded)"
+                        .to_string(),
+                );
+            }
+            archive::run(
+                Command::new("python3")
+                    .args(["-m", "venv", PYTHON_VENV_DIR])
+                    .current_dir(dir),
+            )?;
+            let python = runtime::venv_python(dir)
+                .ok_or_else(|| format!("python3 -m venv did not create {}", PYTHON_VENV_DIR))?;
+            archive::run(
+                Command::new(python)
+                    .args(["-m", "pip", "install", "--no-index", "--only-binary=:all:"])
+                    .args(["--find-links", "wheels"])
+                    .args(["-r", "requirements.txt"])
+                    .current_dir(dir),
+            )?;
+            steps.push("venv + pip install".to_string());
+        }
+        _ => {}
+    }
+    Ok(steps)
+}
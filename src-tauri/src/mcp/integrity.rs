@@ -0,0 +1,141 @@
+//! Plugin file integrity — SHA-256 digests declared in the manifest.
+//!
+//! A manifest may list the digest of its files:
+//!
+//! ```json
+//! "integrity": { "files": { "index.js": "9f86d0…", "lib/api.js": "…" } }
+//! ```
+//!
+//! When present, the entry point must be listed, and the installer refuses a
+//! plugin whose files are missing or don't match. Files that aren't listed
+//! (e.g. `node_modules/` installed afterwards) are not checked.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// Declared digests, keyed by path relative to the plugin directory.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Integrity {
+    /// Lowercase hex SHA-256 per file.
+    #[serde(default)]
+    pub files: HashMap<String, String>,
+}
+
+/// Whether `path` is relative and stays inside the directory it's joined to.
+pub fn is_contained(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Check the declaration itself: contained paths, well-formed digests, and
+/// the entry point listed. Called from `manifest::validate`.
+pub fn check_declared(integrity: &Integrity, entry: &str) -> Result<(), String> {
+    for (path, digest) in &integrity.files {
+        if !is_contained(path) {
+            return Err(format!(
+                "integrity path '{}' must stay inside the plugin",
+                path
+            ));
+        }
+        let well_formed = digest.len() == 64
+            && digest
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+        if !well_formed {
+            return Err(format!(
+                "integrity digest for '{}' must be 64 lowercase hex characters",
+                path
+            ));
+        }
+    }
+    let entry = entry.trim_start_matches("./");
+    if !integrity.files.contains_key(entry) {
+        return Err(format!(
+            "integrity must include the entry point '{}'",
+            entry
+        ));
+    }
+    Ok(())
+}
+
+/// Lowercase hex SHA-256 of a file.
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    Ok(Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Verify every declared file under `dir`. Returns how many were checked.
+///
+/// All mismatches are reported together so a publisher can fix them at once.
+pub fn verify(integrity: &Integrity, dir: &Path) -> Result<usize, String> {
+    let mut errors = Vec::new();
+    let mut paths: Vec<&String> = integrity.files.keys().collect();
+    paths.sort();
+    for path in paths {
+        match sha256_file(&dir.join(path)) {
+            Ok(actual) if actual == integrity.files[path] => {}
+            Ok(actual) => errors.push(format!(
+                "{}: expected {}, got {}",
+                path, integrity.files[path], actual
+            )),
+            Err(_) => errors.push(format!("{}: missing", path)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(integrity.files.len())
+    } else {
+        Err(format!("Integrity check failed:\n{}", errors.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    fn declared(path: &str, digest: &str) -> Integrity {
+        Integrity {
+            files: HashMap::from([(path.to_string(), digest.to_string())]),
+        }
+    }
+
+    #[test]
+    fn declarations_must_be_contained_and_cover_the_entry() {
+        assert!(check_declared(&declared("index.js", HELLO_SHA256), "./index.js").is_ok());
+        assert!(check_declared(&declared("../index.js", HELLO_SHA256), "index.js").is_err());
+        assert!(check_declared(&declared("/etc/passwd", HELLO_SHA256), "index.js").is_err());
+        assert!(check_declared(&declared("index.js", "ABC"), "index.js").is_err());
+        assert!(
+            check_declared(&declared("lib.js", HELLO_SHA256), "index.js")
+                .unwrap_err()
+                .contains("entry point")
+        );
+    }
+
+    #[test]
+    fn verify_reports_mismatches_and_missing_files() {
+        let dir = std::env::temp_dir().join("og-integrity-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.js"), "hello").unwrap();
+
+        assert_eq!(verify(&declared("index.js", HELLO_SHA256), &dir), Ok(1));
+        std::fs::write(dir.join("index.js"), "hello!").unwrap();
+        let err = verify(&declared("index.js", HELLO_SHA256), &dir).unwrap_err();
+        assert!(err.contains("index.js: expected"));
+        let err = verify(&declared("gone.js", HELLO_SHA256), &dir).unwrap_err();
+        assert!(err.contains("gone.js: missing"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
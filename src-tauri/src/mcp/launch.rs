@@ -0,0 +1,129 @@
+//! Starting a plugin from its manifest, the way the app runs it: filtered
+//! environment, runtime command, resource limits and sandbox, then the host
+//! and configuration the handshake carries.
+//!
+//! Shared by the installed-plugin loader, developer mode and the test
+//! harness; approval is the caller's business.
+
+use crate::mcp::client::McpServer;
+use crate::mcp::host::ClientHost;
+use crate::mcp::limits::{self, Limits};
+use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::runtime;
+use crate::mcp::sandbox::env_filter;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Spawn a plugin the way the app runs it, ready for `initialize()`.
+///
+/// Applies the filtered environment, sandbox and resource limits, and sets
+/// the host and configuration the handshake carries.
+pub async fn start_plugin(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    host: Option<Arc<dyn ClientHost>>,
+) -> Result<McpServer, String> {
+    // 1. Filter environment variables (all platforms), then add what the
+    //    runtime needs (virtualenv, Deno cache)
+    let mut env = env_filter::filter_environment(&manifest.permissions, &manifest.id);
+    runtime::add_runtime_env(manifest, plugin_dir, &mut env);
+    let limits = limits::effective(manifest.limits.as_ref(), &limits::load_policy());
+
+    // 2. Spawn under the resource limits — WASM in-process; otherwise
+    //    sandboxed on macOS, filtered-env-only on other platforms
+    let mut server = if manifest.runtime == Runtime::Wasm {
+        spawn_wasm(manifest, plugin_dir, env, &limits).await?
+    } else {
+        let (command, args) = runtime::resolve_command(manifest, plugin_dir)?;
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        spawn_plugin(
+            &manifest.id,
+            manifest,
+            plugin_dir,
+            &command,
+            &args_refs,
+            env,
+            &limits,
+        )?
+    };
+    server.set_limits(limits);
+
+    // 3. Capabilities depend on the host and manifest; configuration
+    //    (secrets included) is delivered at initialize
+    server.set_host(host, manifest.permissions.sampling);
+    server.set_configuration(crate::mcp::config_store::plugin_configuration(manifest));
+    Ok(server)
+}
+
+/// Spawn a plugin process with platform-appropriate sandboxing.
+fn spawn_plugin(
+    plugin_id: &str,
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    command: &str,
+    args: &[&str],
+    env: std::collections::HashMap<String, String>,
+    limits: &Limits,
+) -> Result<McpServer, String> {
+    // Suppress unused warning on non-macOS platforms (manifest used in sandbox profile gen)
+    let _ = &manifest;
+
+    #[cfg(target_os = "macos")]
+    {
+        use crate::mcp::sandbox::macos;
+        match macos::generate_profile(manifest, plugin_dir) {
+            Ok(profile) => {
+                let profile_path = macos::write_profile(plugin_id, &profile)?;
+                log::info!(
+                    "[SANDBOX] Profile written for '{}': {}",
+                    plugin_id,
+                    profile_path.display()
+                );
+                return McpServer::spawn_sandboxed(
+                    plugin_id,
+                    command,
+                    args,
+                    env,
+                    &profile_path,
+                    plugin_dir,
+                    limits,
+                );
+            }
+            Err(e) => {
+                log::warn!(
+                    "[SANDBOX] Failed to generate profile for '{}': {} — loading without sandbox",
+                    plugin_id,
+                    e
+                );
+            }
+        }
+    }
+
+    // Fallback: spawn with filtered environment only (all platforms)
+    McpServer::spawn_limited(plugin_id, command, args, env, Some(plugin_dir), limits)
+}
+
+/// Run a WASI component in-process. Its capabilities come from the
+/// manifest alone, so no OS sandbox is involved.
+#[cfg(feature = "wasm-plugins")]
+async fn spawn_wasm(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    env: std::collections::HashMap<String, String>,
+    limits: &Limits,
+) -> Result<McpServer, String> {
+    crate::mcp::wasm::spawn(manifest, plugin_dir, env, limits).await
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+async fn spawn_wasm(
+    manifest: &PluginManifest,
+    _plugin_dir: &Path,
+    _env: std::collections::HashMap<String, String>,
+    _limits: &Limits,
+) -> Result<McpServer, String> {
+    Err(format!(
+        "'{}' is a WASM plugin; this build was made without the wasm-plugins feature",
+        manifest.id
+    ))
+}
@@ -0,0 +1,209 @@
+//! Plugin resource limits — declared in the manifest, capped by policy.
+//!
+//! A manifest may ask for limits under `"limits"`; anything it leaves out
+//! gets the policy default, and nothing may exceed the policy cap. The
+//! policy is `~/.config/omni-glass/plugin-limits.json`:
+//!
+//! ```json
+//! { "defaults": { "cpuSeconds": 300, "memoryMb": 1024 },
+//!   "caps":     { "cpuSeconds": 3600, "callTimeoutSecs": 300 } }
+//! ```
+//!
+//! Fields missing from the file keep the built-in values below.
+//! CPU, memory and open files are enforced by the OS at spawn
+//! (`sandbox::process_limits`); message size and the per-call budget are
+//! enforced by `McpServer`.
+
+use serde::{Deserialize, Serialize};
+
+const POLICY_FILE: &str = "plugin-limits.json";
+
+/// Limits as declared in a manifest or the policy file. `None` = not set.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimits {
+    /// Total CPU time over the plugin process's life; the plugin is
+    /// restarted with a fresh budget when it runs out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_seconds: Option<u64>,
+    /// Heap (data segment) size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// Open file descriptors, sockets included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_files: Option<u64>,
+    /// Largest JSON-RPC message the plugin may send.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_bytes: Option<u64>,
+    /// Wall-clock budget for one `tools/call`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_timeout_secs: Option<u64>,
+}
+
+/// Defaults and caps applied to every plugin.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitPolicy {
+    #[serde(default)]
+    pub defaults: ResourceLimits,
+    #[serde(default)]
+    pub caps: ResourceLimits,
+}
+
+/// The limits a plugin actually runs with.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Limits {
+    pub cpu_seconds: u64,
+    pub memory_mb: u64,
+    pub open_files: u64,
+    pub max_message_bytes: u64,
+    pub call_timeout_secs: u64,
+}
+
+impl Limits {
+    /// Built-in defaults, used when the policy file doesn't set one.
+    pub const DEFAULT: Limits = Limits {
+        cpu_seconds: 300,
+        memory_mb: 1024,
+        open_files: 256,
+        max_message_bytes: 8 * 1024 * 1024,
+        call_timeout_secs: 15,
+    };
+
+    /// Built-in caps, used when the policy file doesn't set one.
+    pub const CAP: Limits = Limits {
+        cpu_seconds: 3600,
+        memory_mb: 4096,
+        open_files: 4096,
+        max_message_bytes: 64 * 1024 * 1024,
+        call_timeout_secs: 300,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A limit a plugin ran into, reported to the UI with the error.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum LimitViolation {
+    /// The plugin used up its CPU time and was stopped.
+    #[serde(rename_all = "camelCase")]
+    CpuTime { limit_seconds: u64 },
+    /// A message from the plugin was larger than allowed.
+    #[serde(rename_all = "camelCase")]
+    MessageSize { limit_bytes: u64 },
+    /// A tool call ran past its budget.
+    #[serde(rename_all = "camelCase")]
+    CallTimeout { limit_seconds: u64 },
+}
+
+impl std::fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CpuTime { limit_seconds } => {
+                write!(f, "used its {}s of CPU time and was stopped", limit_seconds)
+            }
+            Self::MessageSize { limit_bytes } => {
+                write!(f, "sent a message larger than {} bytes", limit_bytes)
+            }
+            Self::CallTimeout { limit_seconds } => {
+                write!(f, "did not answer within {}s", limit_seconds)
+            }
+        }
+    }
+}
+
+fn policy_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(POLICY_FILE))
+}
+
+/// Load the limit policy. Returns the built-in policy if there is no file.
+pub fn load_policy() -> LimitPolicy {
+    policy_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// The limits for a manifest's declared `limits` under `policy`: declared
+/// or default, then capped.
+pub fn effective(declared: Option<&ResourceLimits>, policy: &LimitPolicy) -> Limits {
+    let declared = declared.cloned().unwrap_or_default();
+    let pick = |field: fn(&ResourceLimits) -> Option<u64>, default: u64, cap: u64| {
+        let cap = field(&policy.caps).unwrap_or(cap);
+        let value = field(&declared)
+            .or(field(&policy.defaults))
+            .unwrap_or(default);
+        value.min(cap).max(1)
+    };
+    let (d, c) = (Limits::DEFAULT, Limits::CAP);
+    Limits {
+        cpu_seconds: pick(|l| l.cpu_seconds, d.cpu_seconds, c.cpu_seconds),
+        memory_mb: pick(|l| l.memory_mb, d.memory_mb, c.memory_mb),
+        open_files: pick(|l| l.open_files, d.open_files, c.open_files),
+        max_message_bytes: pick(
+            |l| l.max_message_bytes,
+            d.max_message_bytes,
+            c.max_message_bytes,
+        ),
+        call_timeout_secs: pick(
+            |l| l.call_timeout_secs,
+            d.call_timeout_secs,
+            c.call_timeout_secs,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_policy_without_declarations() {
+        assert_eq!(effective(None, &LimitPolicy::default()), Limits::DEFAULT);
+    }
+
+    #[test]
+    fn declared_limits_are_capped() {
+        let declared = ResourceLimits {
+            cpu_seconds: Some(10),
+            memory_mb: Some(1_000_000),
+            call_timeout_secs: Some(60),
+            ..Default::default()
+        };
+        let limits = effective(Some(&declared), &LimitPolicy::default());
+        assert_eq!(limits.cpu_seconds, 10);
+        assert_eq!(limits.memory_mb, Limits::CAP.memory_mb);
+        assert_eq!(limits.call_timeout_secs, 60);
+        assert_eq!(limits.open_files, Limits::DEFAULT.open_files);
+    }
+
+    #[test]
+    fn policy_file_overrides_defaults_and_caps() {
+        let policy: LimitPolicy = serde_json::from_str(
+            r#"{ "defaults": { "openFiles": 64 }, "caps": { "callTimeoutSecs": 30 } }"#,
+        )
+        .unwrap();
+        let declared = ResourceLimits {
+            call_timeout_secs: Some(120),
+            ..Default::default()
+        };
+        let limits = effective(Some(&declared), &policy);
+        assert_eq!(limits.open_files, 64);
+        assert_eq!(limits.call_timeout_secs, 30);
+    }
+
+    #[test]
+    fn violations_serialize_with_kind() {
+        let json = serde_json::to_value(LimitViolation::CpuTime { limit_seconds: 5 }).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "cpuTime", "limitSeconds": 5 })
+        );
+    }
+}
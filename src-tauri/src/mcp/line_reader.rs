@@ -0,0 +1,88 @@
+//! Size-limited NDJSON line reads.
+//!
+//! Plugins, their stderr and MCP server peers all send newline-delimited
+//! text we don't control. `read_line_limited` keeps at most a fixed number
+//! of bytes per line, so a peer can't grow our memory by never sending `\n`.
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Outcome of `read_line_limited`.
+#[derive(Debug, PartialEq)]
+pub(crate) enum LineRead {
+    Line,
+    /// The line was longer than allowed; it was read to its end and dropped.
+    TooLong,
+    Eof,
+}
+
+/// Read one `\n`-terminated line into `buf`, holding at most `max_bytes`.
+/// A longer line is still consumed, so the next read starts on a fresh
+/// message, but none of it is kept.
+pub(crate) async fn read_line_limited<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max_bytes: usize,
+) -> std::io::Result<LineRead> {
+    let mut too_long = false;
+    let mut read_any = false;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Ok(match (too_long, read_any) {
+                (true, _) => LineRead::TooLong,
+                (false, true) => LineRead::Line,
+                (false, false) => LineRead::Eof,
+            });
+        }
+        read_any = true;
+        let (chunk, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(end) => (&available[..=end], true),
+            None => (available, false),
+        };
+        let len = chunk.len();
+        if !too_long {
+            if buf.len() + len > max_bytes {
+                too_long = true;
+                buf.clear();
+            } else {
+                buf.extend_from_slice(chunk);
+            }
+        }
+        reader.consume(len);
+        if done {
+            return Ok(if too_long {
+                LineRead::TooLong
+            } else {
+                LineRead::Line
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn long_lines_are_dropped_whole() {
+        let input = format!("{}\n{{\"ok\":1}}\n", "x".repeat(100));
+        let mut reader = BufReader::with_capacity(16, input.as_bytes());
+        let mut buf = Vec::new();
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 32).await.unwrap(),
+            LineRead::TooLong
+        );
+        assert!(buf.is_empty());
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 32).await.unwrap(),
+            LineRead::Line
+        );
+        assert_eq!(buf, b"{\"ok\":1}\n");
+        buf.clear();
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 32).await.unwrap(),
+            LineRead::Eof
+        );
+    }
+}
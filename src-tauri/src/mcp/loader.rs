@@ -1,4 +1,4 @@
-//! Plugin loader — scans plugin directory, spawns sandboxed MCP servers.
+//! Plugin loader — scans plugin directory, starts approved plugins (`launch`).
 //!
 //! Called once at startup from lib.rs `.setup()`. Each plugin is checked
 //! against the approval store before loading. Unapproved or permission-
@@ -6,9 +6,9 @@
 
 use crate::mcp::approval::{self, ApprovalStatus};
 use crate::mcp::client::McpServer;
-use crate::mcp::manifest::{self, PluginManifest, Runtime};
+use crate::mcp::manifest::{self, PluginManifest};
 use crate::mcp::registry::ToolRegistry;
-use crate::mcp::sandbox::env_filter;
+use crate::mcp::{launch, signing, trust};
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
 
@@ -26,10 +26,49 @@ impl PendingApprovals {
 }
 
 /// Default plugin directory: ~/.config/omni-glass/plugins/
-fn plugins_dir() -> Option<PathBuf> {
+pub fn plugins_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|c| c.join("omni-glass").join("plugins"))
 }
 
+/// Every installed plugin with a valid manifest.
+pub fn installed_plugins() -> Vec<(PluginManifest, PathBuf)> {
+    plugins_dir()
+        .map(|dir| scan_plugins(&dir))
+        .unwrap_or_default()
+}
+
+/// Every plugin directory under `dir` with a valid manifest, in directory
+/// order. Invalid manifests are logged and skipped.
+pub fn scan_plugins(dir: &Path) -> Vec<(PluginManifest, PathBuf)> {
+    if !dir.exists() {
+        return Vec::new();
+    }
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            log::warn!("[MCP] Failed to read plugins dir {}: {}", dir.display(), e);
+            return Vec::new();
+        }
+    };
+    let mut plugins = Vec::new();
+    for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        match manifest::load_manifest(&path) {
+            Ok(m) => plugins.push((m, path)),
+            Err(e) => log::warn!(
+                "[MCP] Failed to load manifest for '{}': {}",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                e
+            ),
+        }
+    }
+    plugins
+}
+
+/// Find an installed plugin by id.
+pub fn find_plugin(plugin_id: &str) -> Option<(PluginManifest, PathBuf)> {
+    installed_plugins().into_iter().find(|(m, _)| m.id == plugin_id)
+}
+
 /// Load all plugins from the plugins directory.
 ///
 /// For each valid plugin subdirectory:
@@ -54,41 +93,33 @@ pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
         return;
     }
 
-    let entries = match std::fs::read_dir(&dir) {
-        Ok(e) => e,
-        Err(e) => {
-            log::warn!("[MCP] Failed to read plugins dir {}: {}", dir.display(), e);
-            return;
-        }
-    };
-
-    let store = approval::load_approvals();
+    let mut store = approval::load_approvals();
+    let trust = trust::load_trust();
+    let mut store_changed = false;
     let mut loaded = 0u32;
     let mut total_tools = 0u32;
     let mut queued = 0u32;
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_dir() {
+    for (manifest, path) in installed_plugins() {
+        // Verify the signature (if any) and apply the signing policy
+        let publisher = signing::verify(&manifest, &path, &trust)
+            .and_then(|p| trust.check_policy(&manifest.id, &p).map(|_| p));
+        if let Err(e) = publisher {
+            log::warn!("[MCP] Not loading '{}': {}", manifest.id, e);
             continue;
         }
-
-        // Parse manifest first
-        let manifest = match manifest::load_manifest(&path) {
-            Ok(m) => m,
+        let entry_hash = match signing::entry_hash(&manifest, &path) {
+            Ok(h) => h,
             Err(e) => {
-                log::warn!(
-                    "[MCP] Failed to load manifest for '{}': {}",
-                    path.file_name().unwrap_or_default().to_string_lossy(),
-                    e
-                );
+                log::warn!("[MCP] Not loading '{}': {}", manifest.id, e);
                 continue;
             }
         };
 
         // Check approval status
-        match approval::check_approval(&store, &manifest) {
+        match approval::check_approval(&store, &manifest, &entry_hash) {
             ApprovalStatus::Approved => {
+                store_changed |= approval::pin_entry_hash(&mut store, &manifest.id, &entry_hash);
                 match load_approved_plugin(&manifest, &path, registry).await {
                     Ok(tool_count) => {
                         loaded += 1;
@@ -115,6 +146,19 @@ pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
                 pending.queue.lock().await.push((manifest, path, true));
                 queued += 1;
             }
+            ApprovalStatus::CodeChanged => {
+                log::info!(
+                    "[MCP] Plugin '{}' entry file changed since approval, queuing for re-approval",
+                    manifest.id
+                );
+                pending.queue.lock().await.push((manifest, path, true));
+                queued += 1;
+            }
+        }
+    }
+    if store_changed {
+        if let Err(e) = approval::save_approvals(&store) {
+            log::warn!("[MCP] Failed to pin entry hashes: {}", e);
         }
     }
 
@@ -142,23 +186,19 @@ pub async fn load_approved_plugin(
         manifest.id
     );
 
-    // 1. Filter environment variables (all platforms)
-    let env = env_filter::filter_environment(&manifest.permissions, &manifest.id);
-
-    // 2. Determine spawn command
-    let (command, args) = resolve_command(manifest, plugin_dir)?;
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-
-    // 3. Spawn — sandboxed on macOS, filtered-env-only on other platforms
-    let mut server = spawn_plugin(
-        &manifest.id,
-        manifest,
-        plugin_dir,
-        &command,
-        &args_refs,
-        env,
-    )?;
+    // 1-3. Environment, sandbox, limits, host and configuration
+    let server = launch::start_plugin(manifest, plugin_dir, registry.host()).await?;
+    register_plugin(manifest, server, registry).await
+}
 
+/// Initialize a started plugin, discover its tools and register them.
+/// Returns the tool count. Developer mode uses it to adjust the server
+/// before the handshake.
+pub async fn register_plugin(
+    manifest: &PluginManifest,
+    mut server: McpServer,
+    registry: &ToolRegistry,
+) -> Result<u32, String> {
     // 4. Initialize handshake
     server.initialize().await?;
 
@@ -168,74 +208,13 @@ pub async fn load_approved_plugin(
 
     // 6. Register tools and store server
     registry.register_plugin_tools(&manifest.id, tools).await;
+    if let Some(hints) = &manifest.hints {
+        registry.set_plugin_hints(&manifest.id, hints.clone()).await;
+    }
+    if let Some(triggers) = &manifest.triggers {
+        registry.set_plugin_triggers(&manifest.id, triggers).await;
+    }
     registry.add_server(manifest.id.clone(), server).await;
 
     Ok(tool_count)
 }
-
-/// Spawn a plugin process with platform-appropriate sandboxing.
-fn spawn_plugin(
-    plugin_id: &str,
-    manifest: &PluginManifest,
-    plugin_dir: &Path,
-    command: &str,
-    args: &[&str],
-    env: std::collections::HashMap<String, String>,
-) -> Result<McpServer, String> {
-    // Suppress unused warning on non-macOS platforms (manifest used in sandbox profile gen)
-    let _ = &manifest;
-
-    #[cfg(target_os = "macos")]
-    {
-        use crate::mcp::sandbox::macos;
-        match macos::generate_profile(manifest, plugin_dir) {
-            Ok(profile) => {
-                let profile_path = macos::write_profile(plugin_id, &profile)?;
-                log::info!("[SANDBOX] Profile written for '{}': {}", plugin_id, profile_path.display());
-                return McpServer::spawn_sandboxed(
-                    plugin_id, command, args, env, &profile_path, plugin_dir,
-                );
-            }
-            Err(e) => {
-                log::warn!(
-                    "[SANDBOX] Failed to generate profile for '{}': {} — loading without sandbox",
-                    plugin_id,
-                    e
-                );
-            }
-        }
-    }
-
-    // Fallback: spawn with filtered environment only (all platforms)
-    McpServer::spawn(plugin_id, command, args, env, Some(plugin_dir))
-}
-
-/// Determine the command + args to spawn based on runtime and entry point.
-fn resolve_command(
-    manifest: &PluginManifest,
-    plugin_dir: &Path,
-) -> Result<(String, Vec<String>), String> {
-    let entry_path = plugin_dir.join(&manifest.entry);
-    let entry_str = entry_path.to_string_lossy().to_string();
-
-    match manifest.runtime {
-        Runtime::Node => Ok(("node".to_string(), vec![entry_str])),
-        Runtime::Python => Ok(("python3".to_string(), vec![entry_str])),
-        Runtime::Binary => {
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Ok(meta) = std::fs::metadata(&entry_path) {
-                    let mode = meta.permissions().mode();
-                    if mode & 0o111 == 0 {
-                        return Err(format!(
-                            "Binary entry '{}' is not executable",
-                            manifest.entry
-                        ));
-                    }
-                }
-            }
-            Ok((entry_str, vec![]))
-        }
-    }
-}
@@ -0,0 +1,48 @@
+//! Tauri command for the plugin log viewer in Settings.
+//!
+//! `get_plugin_logs` returns the most recent entries a plugin wrote to
+//! stderr or sent as MCP log messages since the app started, plus the path
+//! of its log file for anything older.
+
+use crate::mcp::plugin_log_files;
+use crate::mcp::plugin_logs::{self, LogEntry, LogLevel};
+use serde::Serialize;
+
+/// Entries returned when the panel doesn't ask for a number.
+const DEFAULT_LIMIT: usize = 200;
+
+/// A plugin's recent log entries.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginLogsView {
+    pub plugin_id: String,
+    /// Oldest first.
+    pub entries: Vec<LogEntry>,
+    /// The current log file; older entries are in `{log_file}.1`.
+    pub log_file: Option<String>,
+}
+
+/// The last `limit` entries for a plugin at `min_level` or above.
+#[tauri::command]
+pub fn get_plugin_logs(
+    plugin_id: String,
+    limit: Option<usize>,
+    min_level: Option<LogLevel>,
+) -> PluginLogsView {
+    let entries = plugin_logs::recent(
+        &plugin_id,
+        limit
+            .unwrap_or(DEFAULT_LIMIT)
+            .min(plugin_logs::RING_CAPACITY),
+        min_level.unwrap_or(LogLevel::Debug),
+    );
+    let log_file = plugin_log_files::log_dir()
+        .map(|dir| plugin_log_files::log_path(&dir, &plugin_id))
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string());
+    PluginLogsView {
+        plugin_id,
+        entries,
+        log_file,
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Each plugin directory must contain an `omni-glass.plugin.json` file
 //! describing the plugin's identity, runtime, entry point, and permissions.
+//! Validation lives in `manifest_check`.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,7 +14,8 @@ pub const MANIFEST_FILENAME: &str = "omni-glass.plugin.json";
 /// A user-configurable field declared in a plugin's manifest.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ConfigField {
-    /// Field type: "string", "number", or "boolean".
+    /// Field type: "string", "number", "boolean", or "secret" (stored in the
+    /// OS keychain, never shown back in the settings UI).
     #[serde(rename = "type")]
     pub field_type: String,
     /// Human-readable label for the settings UI.
@@ -29,6 +31,9 @@ pub struct ConfigField {
 /// Parsed and validated plugin manifest.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PluginManifest {
+    /// Manifest format version; see `manifest_check::MANIFEST_VERSION`.
+    #[serde(rename = "manifestVersion", default = "default_manifest_version")]
+    pub manifest_version: u32,
     pub id: String,
     pub name: String,
     pub version: String,
@@ -41,6 +46,45 @@ pub struct PluginManifest {
     /// Optional user-configurable fields (e.g., default_repo, target_language).
     #[serde(default)]
     pub configuration: Option<HashMap<String, ConfigField>>,
+    /// Optional SHA-256 digests of the plugin's files, checked at install.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<crate::mcp::integrity::Integrity>,
+    /// Requested resource limits, capped by the user's policy; see `mcp::limits`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<crate::mcp::limits::ResourceLimits>,
+    /// When the plugin's tools are relevant; see `mcp::relevance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hints: Option<crate::mcp::relevance::PluginHints>,
+    /// Patterns that put a tool in the menu without the classifier; see
+    /// `mcp::triggers`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub triggers: Option<Vec<crate::mcp::triggers::ContentTrigger>>,
+}
+
+fn default_manifest_version() -> u32 {
+    1
+}
+
+/// An empty Node plugin at the current manifest version, for building
+/// manifests field by field (`..Default::default()`).
+impl Default for PluginManifest {
+    fn default() -> Self {
+        Self {
+            manifest_version: default_manifest_version(),
+            id: String::new(),
+            name: String::new(),
+            version: String::new(),
+            description: String::new(),
+            runtime: Runtime::Node,
+            entry: String::new(),
+            permissions: Permissions::default(),
+            configuration: None,
+            integrity: None,
+            limits: None,
+            hints: None,
+            triggers: None,
+        }
+    }
 }
 
 /// Plugin runtime environment.
@@ -48,8 +92,14 @@ pub struct PluginManifest {
 #[serde(rename_all = "lowercase")]
 pub enum Runtime {
     Node,
+    /// Runs from the plugin's `.venv` when the installer created one.
     Python,
     Binary,
+    /// Deno's own permission flags are derived from `Permissions`.
+    Deno,
+    Bun,
+    /// A WASI component run in-process; see `mcp::wasm`.
+    Wasm,
 }
 
 /// Filesystem access declaration: a path and its access level.
@@ -86,68 +136,19 @@ pub struct Permissions {
     /// Shell commands the plugin may spawn.
     #[serde(default)]
     pub shell: Option<ShellPerm>,
+    /// May ask the app's LLM provider for completions (`sampling/createMessage`).
+    /// Left out of the serialized form when false so existing approval
+    /// hashes stay valid.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sampling: bool,
 }
 
 /// Load and validate a plugin manifest from a directory.
+///
+/// All problems are reported in one message; use
+/// `manifest_check::check_manifest` to get them as a list.
 pub fn load_manifest(plugin_dir: &Path) -> Result<PluginManifest, String> {
-    let manifest_path = plugin_dir.join(MANIFEST_FILENAME);
-    if !manifest_path.exists() {
-        return Err(format!(
-            "No {} found in {}",
-            MANIFEST_FILENAME,
-            plugin_dir.display()
-        ));
-    }
-
-    let raw = std::fs::read_to_string(&manifest_path)
-        .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
-
-    let manifest: PluginManifest = serde_json::from_str(&raw)
-        .map_err(|e| format!("Invalid manifest JSON in {}: {}", manifest_path.display(), e))?;
-
-    validate(&manifest, plugin_dir)?;
-    Ok(manifest)
-}
-
-/// Validate manifest fields.
-fn validate(m: &PluginManifest, plugin_dir: &Path) -> Result<(), String> {
-    // ID must be non-empty and look like reverse-domain
-    if m.id.is_empty() || !m.id.contains('.') {
-        return Err(format!(
-            "Plugin id '{}' must be reverse-domain format (e.g. com.example.plugin)",
-            m.id
-        ));
-    }
-
-    // Name must be non-empty
-    if m.name.trim().is_empty() {
-        return Err("Plugin name must not be empty".to_string());
-    }
-
-    // Version must be non-empty
-    if m.version.trim().is_empty() {
-        return Err("Plugin version must not be empty".to_string());
-    }
-
-    // Entry must not contain path traversal
-    if m.entry.contains("..") {
-        return Err(format!(
-            "Plugin entry '{}' must not contain path traversal (..)",
-            m.entry
-        ));
-    }
-
-    // Entry file must exist
-    let entry_path = plugin_dir.join(&m.entry);
-    if !entry_path.exists() {
-        return Err(format!(
-            "Plugin entry file '{}' not found in {}",
-            m.entry,
-            plugin_dir.display()
-        ));
-    }
-
-    Ok(())
+    crate::mcp::manifest_check::check_manifest(plugin_dir).map_err(|errors| errors.join("; "))
 }
 
 #[cfg(test)]
@@ -0,0 +1,212 @@
+//! Manifest validation — every problem at once, each with a JSON path.
+//!
+//! Two passes. The published JSON Schema
+//! (`plugins/omni-glass.plugin.schema.json`) catches structure: unknown
+//! keys, wrong types, unsupported `access` values and config types. The
+//! checks here cover what the schema doesn't express: reverse-domain ids,
+//! semver, hostnames, duplicates, trigger patterns, `manifestVersion`
+//! support and the entry file. `omni-glass-cli validate <DIR>` runs the same code for authors.
+
+use crate::mcp::integrity;
+use crate::mcp::manifest::{PluginManifest, Runtime, MANIFEST_FILENAME};
+use crate::mcp::schema;
+use crate::mcp::triggers;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Newest manifest format this build understands. Manifests without
+/// `manifestVersion` are version 1.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// The published JSON Schema for `omni-glass.plugin.json`.
+pub const MANIFEST_SCHEMA: &str = include_str!("../../../plugins/omni-glass.plugin.schema.json");
+
+static SCHEMA: LazyLock<Value> =
+    LazyLock::new(|| serde_json::from_str(MANIFEST_SCHEMA).expect("manifest schema is valid JSON"));
+
+static SEMVER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$")
+        .unwrap()
+});
+
+static HOSTNAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?)(\.[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*$")
+        .unwrap()
+});
+
+/// Load a plugin's manifest, collecting every problem instead of stopping
+/// at the first.
+pub fn check_manifest(plugin_dir: &Path) -> Result<PluginManifest, Vec<String>> {
+    let path = plugin_dir.join(MANIFEST_FILENAME);
+    if !path.exists() {
+        return Err(vec![format!(
+            "No {} found in {}",
+            MANIFEST_FILENAME,
+            plugin_dir.display()
+        )]);
+    }
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| vec![format!("Failed to read {}: {}", path.display(), e)])?;
+    let value: Value = serde_json::from_str(&raw).map_err(|e| {
+        vec![format!(
+            "Invalid manifest JSON in {}: {}",
+            path.display(),
+            e
+        )]
+    })?;
+
+    let mut errors = schema::validate(&SCHEMA, &value);
+    match serde_json::from_value::<PluginManifest>(value) {
+        Ok(manifest) => {
+            check_fields(&manifest, plugin_dir, &mut errors);
+            if errors.is_empty() {
+                return Ok(manifest);
+            }
+        }
+        // Schema errors already explain why it doesn't deserialize.
+        Err(e) if errors.is_empty() => errors.push(format!("/: {}", e)),
+        Err(_) => {}
+    }
+    Err(errors)
+}
+
+fn check_fields(m: &PluginManifest, plugin_dir: &Path, errors: &mut Vec<String>) {
+    check_identity(m, errors);
+    check_permissions(m, errors);
+    check_triggers(m, errors);
+    check_wasm(m, errors);
+    check_entry(m, plugin_dir, errors);
+}
+
+fn check_identity(m: &PluginManifest, errors: &mut Vec<String>) {
+    if m.manifest_version > MANIFEST_VERSION {
+        errors.push(format!(
+            "/manifestVersion: version {} needs a newer Omni-Glass (this one supports up to {})",
+            m.manifest_version, MANIFEST_VERSION
+        ));
+    }
+
+    let labels_ok = m.id.split('.').all(|label| {
+        !label.is_empty()
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+    });
+    if !m.id.contains('.') || !labels_ok {
+        errors.push(format!(
+            "/id: Plugin id '{}' must be reverse-domain format (e.g. com.example.plugin)",
+            m.id
+        ));
+    }
+    if m.name.trim().is_empty() {
+        errors.push("/name: Plugin name must not be empty".to_string());
+    }
+    if !SEMVER.is_match(&m.version) {
+        errors.push(format!(
+            "/version: '{}' is not a semantic version (e.g. 1.0.0)",
+            m.version
+        ));
+    }
+}
+
+fn check_wasm(m: &PluginManifest, errors: &mut Vec<String>) {
+    if m.runtime == Runtime::Wasm {
+        if m.permissions.shell.is_some() {
+            errors.push(
+                "/permissions/shell: WASM plugins cannot run commands (WASI has no process spawning)"
+                    .to_string(),
+            );
+        }
+        if !m.entry.ends_with(".wasm") {
+            errors.push(format!(
+                "/entry: WASM entry '{}' must be a .wasm component",
+                m.entry
+            ));
+        }
+    }
+}
+
+fn check_entry(m: &PluginManifest, plugin_dir: &Path, errors: &mut Vec<String>) {
+    if m.entry.contains("..") {
+        errors.push(format!(
+            "/entry: Plugin entry '{}' must not contain path traversal (..)",
+            m.entry
+        ));
+    } else if !integrity::is_contained(&m.entry) {
+        errors.push(format!(
+            "/entry: '{}' must be a relative path inside the plugin",
+            m.entry
+        ));
+    } else if !plugin_dir.join(&m.entry).exists() {
+        errors.push(format!(
+            "/entry: Plugin entry file '{}' not found in {}",
+            m.entry,
+            plugin_dir.display()
+        ));
+    }
+
+    if let Some(declared) = &m.integrity {
+        if let Err(e) = integrity::check_declared(declared, &m.entry) {
+            errors.push(format!("/integrity: {}", e));
+        }
+    }
+}
+
+fn check_permissions(m: &PluginManifest, errors: &mut Vec<String>) {
+    let perms = &m.permissions;
+    for (i, domain) in perms.network.iter().flatten().enumerate() {
+        if !HOSTNAME.is_match(domain) || !(domain.contains('.') || domain == "localhost") {
+            errors.push(format!(
+                "/permissions/network/{}: '{}' is not a hostname (no scheme, port or path)",
+                i, domain
+            ));
+        }
+    }
+    let network = perms
+        .network
+        .iter()
+        .flatten()
+        .map(|d| d.to_ascii_lowercase());
+    find_duplicates("/permissions/network", network, errors);
+    let paths = perms.filesystem.iter().flatten().map(|p| p.path.clone());
+    find_duplicates("/permissions/filesystem", paths, errors);
+    find_duplicates(
+        "/permissions/environment",
+        perms.environment.iter().flatten().cloned(),
+        errors,
+    );
+    let commands = perms.shell.iter().flat_map(|s| s.commands.iter().cloned());
+    find_duplicates("/permissions/shell/commands", commands, errors);
+}
+
+fn check_triggers(m: &PluginManifest, errors: &mut Vec<String>) {
+    for (i, trigger) in m.triggers.iter().flatten().enumerate() {
+        match &trigger.pattern {
+            Some(pattern) => {
+                if let Err(e) = triggers::compile_pattern(pattern) {
+                    errors.push(format!("/triggers/{}/pattern: {}", i, e));
+                }
+            }
+            None if trigger.content_types.is_empty() => errors.push(format!(
+                "/triggers/{}: a trigger needs a pattern, contentTypes or both",
+                i
+            )),
+            None => {}
+        }
+    }
+}
+
+fn find_duplicates(path: &str, items: impl Iterator<Item = String>, errors: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    for (i, item) in items.enumerate() {
+        if !seen.insert(item.clone()) {
+            errors.push(format!(
+                "{}/{}: '{}' is listed more than once",
+                path, i, item
+            ));
+        }
+    }
+}
@@ -4,149 +4,286 @@
 //! processes over JSON-RPC 2.0 / NDJSON stdio. It provides:
 //!
 //! - **types**: MCP protocol types (JSON-RPC framing, tool definitions)
-//! - **client**: McpServer — spawn child process, handshake, call tools
+//! - **client**: McpServer — handshake, call tools, read responses within limits
+//! - **spawn**: Start plugin processes for McpServer, with limits and sandbox
 //! - **manifest**: Parse and validate `omni-glass.plugin.json` files
+//! - **manifest_check**: Schema and semantic checks, all problems at once
 //! - **registry**: ToolRegistry — central store for built-in + plugin tools
+//! - **installer**: Install, upgrade and uninstall plugins from archives or directories
+//! - **install_commands**: Tauri commands for the installer; approvals and config cleanup
+//! - **archive**: Safe `.tar.gz` / `.zip` extraction via the system tools
+//! - **integrity**: SHA-256 file digests declared in the manifest
 //! - **loader**: Scan plugins directory, spawn servers, discover tools
+//! - **launch**: Start a plugin from its manifest: env, runtime, limits, sandbox, host
+//! - **runtime**: Interpreter, arguments and environment per plugin runtime
+//! - **limits**: CPU, memory, file, message size and call time limits per plugin
+//! - **line_reader**: Size-limited NDJSON line reads for plugin and peer streams
+//! - **plugin_logs**: Per-plugin stderr and MCP log messages (ring buffer + log file)
+//! - **plugin_log_entry**: `LogEntry` / `LogLevel` and the log file line format
+//! - **plugin_log_files**: Buffered per-plugin log files on a writer thread, with rotation
+//! - **log_commands**: Settings command returning a plugin's recent logs
+//! - **dev**: Developer mode — load plugins from working directories, restart on change
+//! - **dev_commands**: Settings commands for developer mode
+//! - **dev_settings**: Developer mode settings file (`dev-plugins.json`)
+//! - **dev_snapshot**: File snapshots the developer mode watcher compares
+//! - **harness**: Run a plugin's handshake, discovery and one tool call, with timings
+//! - **wasm**: In-process WASI components (`wasm-plugins` feature)
+//! - **args**: Generate, prefill, validate and repair plugin tool arguments
+//! - **config_store**: Per-plugin configuration values and keychain secrets
+//! - **config_commands**: Settings commands to view and edit plugin configuration
+//! - **confirm**: Per-tool argument review preferences (always / never / default)
+//! - **relevance**: BM25 pre-filter choosing the plugin tools sent to the classifier
+//! - **relevance_bm25**: Tokenizer and BM25 scoring for the relevance filter
+//! - **relevance_stats**: Relevance filter counters (`relevance-stats.json`)
+//! - **triggers**: Manifest patterns that put plugin tools in the menu without the classifier
+//! - **tool_prefs**: Disabled and pinned tools for the classify prompt and action menu
+//! - **tool_prefs_commands**: Settings commands for tool preferences
+//! - **host**: Server → client requests (elicitation, sampling, ping)
+//! - **server_requests**: Time and request caps for server requests during a call
+//! - **elicit**: Elicitation prompt windows; the app's `ClientHost`
+//! - **sampling**: Redacted LLM completions for plugins with the sampling permission
 //! - **builtins**: Register the 6 built-in actions as internal tools
+//! - **results**: Map MCP tool results (text, images, resources, links) to ActionResult
+//! - **result_payload**: Mime, size and scheme checks and filenames for result payloads
+//! - **schema**: Minimal JSON Schema validation for plugin I/O
 //! - **sandbox**: OS-level process sandboxing (env filtering, macOS sandbox-exec)
 //! - **approval**: Plugin approval state management (user consent)
+//! - **signing**: Ed25519 plugin signatures and entry-file hashes
+//! - **trust**: Trusted publisher keys and the signed-only policy
+//! - **trust_commands**: Settings commands for the trust store
 
 pub mod approval;
 pub mod approval_commands;
+pub mod archive;
+pub mod args;
 pub mod builtins;
 pub mod client;
+pub mod config_commands;
 pub mod config_store;
+pub mod confirm;
+pub mod confirm_commands;
+pub mod dev;
+pub mod dev_commands;
+pub mod dev_settings;
+pub mod dev_snapshot;
+pub mod elicit;
+pub mod elicit_commands;
+pub mod harness;
+pub mod host;
+pub mod install_commands;
+pub mod installer;
+pub mod integrity;
+pub mod launch;
+pub mod limits;
+pub mod line_reader;
 pub mod loader;
+pub mod log_commands;
 pub mod manifest;
+pub mod manifest_check;
+pub mod plugin_log_entry;
+pub mod plugin_log_files;
+pub mod plugin_logs;
 pub mod registry;
+pub mod relevance;
+pub mod relevance_bm25;
+pub mod relevance_stats;
+pub mod result_payload;
+pub mod results;
+pub mod runtime;
+pub mod sampling;
 pub mod sandbox;
+pub mod schema;
+pub mod server_requests;
+pub mod signing;
+pub mod spawn;
+pub mod tool_prefs;
+pub mod tool_prefs_commands;
+pub mod triggers;
+pub mod trust;
+pub mod trust_commands;
 pub mod types;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
 pub use registry::ToolRegistry;
 
-use crate::llm::execute::{ActionResult, ActionResultBody};
-use crate::safety::{command_check, redact};
+use crate::llm::execute::{ActionResult, ActionResultMetadata};
 
 /// Execute a plugin tool call, converting the MCP result to our ActionResult type.
 ///
 /// Called by the pipeline when the action belongs to a plugin (not builtin).
-/// For non-trivial schemas, an LLM call generates structured arguments from
-/// the OCR text. Safety checks are applied to plugin output before returning.
+/// Arguments are built and validated by `args`; the result is mapped and
+/// safety-checked by `results`. When called from the UI (`app` is set) and
+/// the tool's review preference asks for it, the call stops with a
+/// `tool_args` result instead, and `call_with_arguments` runs it once the
+/// user has approved the arguments.
 pub async fn execute_plugin_tool(
     registry: &ToolRegistry,
     action_id: &str,
     input_text: &str,
-    tool_description: Option<&str>,
-    input_schema: Option<&serde_json::Value>,
-    #[allow(unused_variables)] app: Option<&tauri::AppHandle>,
+    tool: Option<&registry::RegisteredTool>,
+    app: Option<&tauri::AppHandle>,
 ) -> ActionResult {
-    // Generate structured args for non-trivial schemas, fallback to {text} otherwise
-    let arguments = match input_schema {
-        Some(schema) if !crate::llm::plugin_args::is_trivial_schema(schema) => {
-            let args_result = generate_args_for_provider(
-                action_id,
-                tool_description.unwrap_or(""),
-                schema,
-                input_text,
-                app,
-            )
-            .await;
-            match args_result {
-                Ok(args) => args,
-                Err(e) => {
-                    log::warn!("[MCP] Args bridge failed, falling back to {{text}}: {}", e);
-                    serde_json::json!({ "text": input_text })
-                }
-            }
+    let arguments = match args::build_arguments(action_id, tool, input_text, app).await {
+        Ok(arguments) => arguments,
+        Err(e) => {
+            log::warn!("[MCP] Not calling '{}': {}", action_id, e);
+            return ActionResult::error(action_id, &e);
         }
-        _ => serde_json::json!({ "text": input_text }),
     };
+    review_or_call(registry, action_id, arguments, tool, app).await
+}
 
-    match registry.call_plugin_tool(action_id, arguments).await {
-        Ok(result) => {
-            let raw_text = result.text();
-            if result.is_error {
-                return ActionResult::error(action_id, &format!("Plugin error: {}", raw_text));
-            }
-
-            // Safety gate 1: block dangerous commands in plugin output
-            let cmd_check = command_check::is_command_safe(&raw_text);
-            if !cmd_check.safe {
-                let reason = cmd_check.reason.unwrap_or_else(|| "blocked pattern".into());
-                log::warn!(
-                    "[SAFETY] Plugin '{}' output blocked: {}",
-                    action_id,
-                    reason
-                );
-                return ActionResult::error(
-                    action_id,
-                    &format!("Plugin output blocked by safety filter: {}", reason),
-                );
-            }
-
-            // Safety gate 2: redact PII / secrets from plugin output
-            let redaction = redact::redact_sensitive_data(&raw_text);
-            if !redaction.redactions.is_empty() {
-                let labels: Vec<&str> = redaction.redactions.iter()
-                    .map(|r| r.label.as_str())
-                    .collect();
-                log::info!(
-                    "[SAFETY] Redacted {} pattern(s) from plugin '{}' output: {:?}",
-                    redaction.redactions.len(),
-                    action_id,
-                    labels
-                );
-            }
+/// Hold the call for argument review when the tool's preference asks for
+/// it (UI callers only), otherwise call it.
+async fn review_or_call(
+    registry: &ToolRegistry,
+    action_id: &str,
+    arguments: serde_json::Value,
+    tool: Option<&registry::RegisteredTool>,
+    app: Option<&tauri::AppHandle>,
+) -> ActionResult {
+    if let (Some(tool), Some(_)) = (tool, app) {
+        if confirm::needs_review(tool, confirm::pref_for(tool)) {
+            log::info!("[MCP] Holding '{}' for argument review", action_id);
+            return confirm::review_result(action_id, arguments);
+        }
+    }
+    call_with_arguments(registry, action_id, arguments, tool).await
+}
 
-            ActionResult {
-                status: "success".to_string(),
-                action_id: action_id.to_string(),
-                result: ActionResultBody {
-                    result_type: "text".to_string(),
-                    text: Some(redaction.cleaned_text),
-                    file_path: None,
-                    command: None,
-                    clipboard_content: None,
-                    mime_type: None,
-                },
-                metadata: None,
+/// Call a plugin tool with final arguments and map its result.
+pub async fn call_with_arguments(
+    registry: &ToolRegistry,
+    action_id: &str,
+    arguments: serde_json::Value,
+    tool: Option<&registry::RegisteredTool>,
+) -> ActionResult {
+    let output_schema = tool.and_then(|t| t.output_schema.as_ref());
+    match registry.call_plugin_tool(action_id, arguments).await {
+        Ok(result) => results::to_action_result(action_id, &result, output_schema),
+        Err(e) => {
+            let mut result =
+                ActionResult::error(action_id, &format!("Failed to call plugin tool: {}", e));
+            if let Some(violation) = e.violation {
+                if matches!(violation, limits::LimitViolation::CpuTime { .. }) {
+                    let plugin_id = match tool {
+                        Some(tool) => Some(tool.plugin_id.clone()),
+                        None => registry.get_tool(action_id).await.map(|t| t.plugin_id),
+                    };
+                    if let Some(plugin_id) = plugin_id {
+                        restart_out_of_cpu(registry, &plugin_id).await;
+                    }
+                }
+                result.metadata = Some(ActionResultMetadata {
+                    tokens_used: None,
+                    processing_note: None,
+                    limit: Some(violation),
+                    recipe: None,
+                });
             }
+            result
         }
-        Err(e) => ActionResult::error(action_id, &format!("Failed to call plugin tool: {}", e)),
     }
 }
 
-/// Route args generation to the active provider (Anthropic or local).
-async fn generate_args_for_provider(
+/// Run `action_id` through its plugin if it belongs to one.
+///
+/// Returns `None` for built-in actions so the caller can fall through to
+/// LLM execution. Shared by the app pipeline and the headless CLI.
+pub async fn run_plugin_action(
+    registry: &ToolRegistry,
+    action_id: &str,
+    input_text: &str,
+    app: Option<&tauri::AppHandle>,
+) -> Option<ActionResult> {
+    if !registry.is_plugin_action(action_id).await {
+        return None;
+    }
+    log::info!("[EXECUTE] Routing to plugin: {}", action_id);
+    let tool_meta = registry.tool_for_action(action_id).await;
+    let result = execute_plugin_tool(
+        registry,
+        action_id,
+        input_text,
+        tool_meta.as_ref(),
+        app,
+    )
+    .await;
+    Some(result)
+}
+
+/// Run `action_id` with arguments a model produced through native tool
+/// calling, skipping the args bridge.
+///
+/// The arguments get the same config prefill and schema validation as
+/// generated ones; if they are still invalid, the args bridge builds them
+/// from `input_text` instead. Returns `None` for built-in actions.
+pub async fn run_plugin_call(
+    registry: &ToolRegistry,
     action_id: &str,
-    tool_description: &str,
-    schema: &serde_json::Value,
+    arguments: serde_json::Value,
     input_text: &str,
-    #[allow(unused_variables)] app: Option<&tauri::AppHandle>,
-) -> Result<serde_json::Value, String> {
-    #[cfg(feature = "local-llm")]
-    if let Some(app_handle) = app {
-        let provider = crate::settings_commands::resolve_provider();
-        if provider == "local" {
-            use tauri::Manager;
-            let state = app_handle.state::<crate::llm::local_state::LocalLlmState>();
-            return crate::llm::local::generate_plugin_args_local(
+    app: Option<&tauri::AppHandle>,
+) -> Option<ActionResult> {
+    if !registry.is_plugin_action(action_id).await {
+        return None;
+    }
+    let tool_meta = registry.tool_for_action(action_id).await;
+    let Some(tool) = tool_meta.as_ref() else {
+        return run_plugin_action(registry, action_id, input_text, app).await;
+    };
+    log::info!(
+        "[EXECUTE] Routing native tool call to plugin: {}",
+        action_id
+    );
+    let result = match args::accept_arguments(tool, arguments) {
+        Ok(arguments) => review_or_call(registry, action_id, arguments, Some(tool), app).await,
+        Err(errors) => {
+            log::warn!(
+                "[MCP] Native arguments for '{}' invalid ({}), using the args bridge",
                 action_id,
-                tool_description,
-                schema,
-                input_text,
-                &state,
-            )
-            .await;
+                errors.join("; ")
+            );
+            execute_plugin_tool(registry, action_id, input_text, Some(tool), app).await
         }
+    };
+    Some(result)
+}
+
+/// Restart a plugin that was stopped for CPU time.
+///
+/// `cpuSeconds` is a budget over the plugin's life (`RLIMIT_CPU` for a
+/// process, epoch ticks for WASM), so a fresh process gets a fresh budget
+/// and one runaway call doesn't disable the plugin until the app restarts.
+/// The plugin must still be approved for its current entry file; dev-mode
+/// plugins restart on their next change instead.
+async fn restart_out_of_cpu(registry: &ToolRegistry, plugin_id: &str) {
+    registry.remove_plugin(plugin_id).await;
+    match load_installed(registry, plugin_id).await {
+        Ok(Some(_)) => log::info!("[MCP] Restarted '{}' with a fresh CPU budget", plugin_id),
+        Ok(None) => {}
+        Err(e) => log::warn!("[MCP] Failed to restart '{}': {}", plugin_id, e),
     }
+}
 
-    crate::llm::plugin_args::generate_plugin_args(
-        action_id,
-        tool_description,
-        schema,
-        input_text,
-    )
-    .await
+/// Load the installed copy of `plugin_id` if it is still approved for its
+/// current entry file. `Ok(None)` if no such plugin is installed.
+pub async fn load_installed(
+    registry: &ToolRegistry,
+    plugin_id: &str,
+) -> Result<Option<u32>, String> {
+    let Some((manifest, dir)) = loader::find_plugin(plugin_id) else {
+        return Ok(None);
+    };
+    let approved = signing::entry_hash(&manifest, &dir).is_ok_and(|hash| {
+        approval::check_approval(&approval::load_approvals(), &manifest, &hash)
+            == approval::ApprovalStatus::Approved
+    });
+    if !approved {
+        return Err("needs approval".to_string());
+    }
+    loader::load_approved_plugin(&manifest, &dir, registry)
+        .await
+        .map(Some)
 }
@@ -0,0 +1,164 @@
+//! Plugin log entries — one stderr line or MCP log message.
+//!
+//! `plugin_logs` keeps them in memory and `plugin_log_files` writes them
+//! out with `LogEntry::to_line`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Longest stderr line or log message kept.
+pub(crate) const MAX_LINE_BYTES: usize = 8 * 1024;
+
+/// MCP log levels (RFC 5424 severities), least severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+            Self::Alert => "alert",
+            Self::Emergency => "emergency",
+        }
+    }
+}
+
+/// Where an entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    /// A line the process wrote to stderr.
+    Stderr,
+    /// A `notifications/message` from the server.
+    Mcp,
+}
+
+/// One log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub level: LogLevel,
+    pub source: LogSource,
+    /// The `logger` name from an MCP log message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logger: Option<String>,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// A stderr line. stderr carries no severity, so it is logged as info.
+    pub fn stderr(line: &str) -> Self {
+        Self {
+            timestamp: unix_now(),
+            level: LogLevel::Info,
+            source: LogSource::Stderr,
+            logger: None,
+            message: line.trim_end().to_string(),
+        }
+    }
+
+    /// The params of a `notifications/message`. `data` may be any JSON;
+    /// strings are kept as-is, anything else as compact JSON.
+    pub fn from_notification(params: &Value) -> Self {
+        let level = params
+            .get("level")
+            .and_then(|l| serde_json::from_value(l.clone()).ok())
+            .unwrap_or(LogLevel::Info);
+        let message = match params.get("data") {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        Self {
+            timestamp: unix_now(),
+            level,
+            source: LogSource::Mcp,
+            logger: params
+                .get("logger")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            message: truncate(message, MAX_LINE_BYTES),
+        }
+    }
+
+    /// The line written to the log file.
+    pub(crate) fn to_line(&self) -> String {
+        let source = match self.source {
+            LogSource::Stderr => "stderr".to_string(),
+            LogSource::Mcp => match &self.logger {
+                Some(logger) => format!("mcp:{}", logger),
+                None => "mcp".to_string(),
+            },
+        };
+        format!(
+            "{} {:<9} [{}] {}\n",
+            self.timestamp,
+            self.level.as_str(),
+            source,
+            self.message
+        )
+    }
+}
+
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() > max_bytes {
+        let mut end = max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push('…');
+    }
+    text
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications_keep_level_and_logger() {
+        let debug = LogEntry::from_notification(
+            &serde_json::json!({ "level": "debug", "data": "connecting" }),
+        );
+        assert_eq!(debug.level, LogLevel::Debug);
+        assert_eq!(debug.message, "connecting");
+
+        let error = LogEntry::from_notification(&serde_json::json!({
+            "level": "error", "logger": "db", "data": { "code": 7 }
+        }));
+        assert_eq!(error.logger.as_deref(), Some("db"));
+        assert_eq!(error.message, r#"{"code":7}"#);
+        assert_eq!(error.source, LogSource::Mcp);
+        assert!(error
+            .to_line()
+            .ends_with("error     [mcp:db] {\"code\":7}\n"));
+
+        let unknown = LogEntry::from_notification(&serde_json::json!({ "level": "loud" }));
+        assert_eq!(unknown.level, LogLevel::Info);
+    }
+}
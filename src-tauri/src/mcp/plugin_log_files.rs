@@ -0,0 +1,189 @@
+//! Plugin log files — one buffered writer per plugin, on a writer thread.
+//!
+//! `plugin_logs::record` runs on async tasks for every stderr line, so it
+//! only queues the line here. A dedicated thread owns the open files:
+//! it appends, rotates `{plugin_id}.log` to `{plugin_id}.log.1` when it
+//! reaches `MAX_FILE_BYTES`, and flushes once the queue is empty, so a
+//! burst of lines costs one write and never blocks a runtime worker.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::LazyLock;
+
+/// Log file size at which it is rotated.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const LOG_DIR: &str = "plugin-logs";
+
+/// Work for the writer thread, in the order it was queued.
+enum Op {
+    Append { plugin_id: String, line: String },
+    Remove { plugin_id: String },
+}
+
+/// The writer thread's queue; `None` without a config directory.
+static WRITER: LazyLock<Option<Sender<Op>>> = LazyLock::new(|| {
+    let dir = log_dir()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("plugin-logs".to_string())
+        .spawn(move || run(LogFiles::new(dir), rx))
+        .map_err(|e| log::warn!("[PLUGIN] Log writer failed to start: {}", e))
+        .ok()?;
+    Some(tx)
+});
+
+/// Directory holding the plugin log files.
+pub fn log_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(LOG_DIR))
+}
+
+/// A plugin's current log file in `dir`.
+pub fn log_path(dir: &Path, plugin_id: &str) -> PathBuf {
+    dir.join(format!("{}.log", plugin_id))
+}
+
+fn rotated(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+/// Queue `line` for the end of the plugin's log file.
+pub fn append(plugin_id: &str, line: String) {
+    send(Op::Append {
+        plugin_id: plugin_id.to_string(),
+        line,
+    });
+}
+
+/// Close the plugin's log and delete its files, after queued lines.
+pub fn remove(plugin_id: &str) {
+    send(Op::Remove {
+        plugin_id: plugin_id.to_string(),
+    });
+}
+
+fn send(op: Op) {
+    if let Some(tx) = WRITER.as_ref() {
+        let _ = tx.send(op);
+    }
+}
+
+/// Apply queued work until every sender is gone, flushing after each burst.
+fn run(mut files: LogFiles, rx: Receiver<Op>) {
+    while let Ok(op) = rx.recv() {
+        files.apply(op);
+        while let Ok(op) = rx.try_recv() {
+            files.apply(op);
+        }
+        files.flush();
+    }
+}
+
+/// An open log file and its size so far.
+struct OpenLog {
+    writer: BufWriter<File>,
+    len: u64,
+}
+
+/// The open log files in one directory.
+struct LogFiles {
+    dir: PathBuf,
+    open: HashMap<String, OpenLog>,
+}
+
+impl LogFiles {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            open: HashMap::new(),
+        }
+    }
+
+    fn apply(&mut self, op: Op) {
+        match op {
+            Op::Append { plugin_id, line } => {
+                if let Err(e) = self.append(&plugin_id, &line) {
+                    log::warn!("[PLUGIN] Cannot write log for '{}': {}", plugin_id, e);
+                }
+            }
+            Op::Remove { plugin_id } => self.remove(&plugin_id),
+        }
+    }
+
+    /// Append one line, rotating the file first if it is full.
+    fn append(&mut self, plugin_id: &str, line: &str) -> Result<(), String> {
+        if self.log(plugin_id)?.len >= MAX_FILE_BYTES {
+            self.open.remove(plugin_id);
+            let path = log_path(&self.dir, plugin_id);
+            std::fs::rename(&path, rotated(&path))
+                .map_err(|e| format!("Failed to rotate log: {}", e))?;
+        }
+        let log = self.log(plugin_id)?;
+        log.writer
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Failed to append log: {}", e))?;
+        log.len += line.len() as u64;
+        Ok(())
+    }
+
+    /// The plugin's open log, opening it for append if needed.
+    fn log(&mut self, plugin_id: &str) -> Result<&mut OpenLog, String> {
+        if !self.open.contains_key(plugin_id) {
+            std::fs::create_dir_all(&self.dir)
+                .map_err(|e| format!("Failed to create log dir: {}", e))?;
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_path(&self.dir, plugin_id))
+                .map_err(|e| format!("Failed to open log: {}", e))?;
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            let writer = BufWriter::new(file);
+            self.open
+                .insert(plugin_id.to_string(), OpenLog { writer, len });
+        }
+        Ok(self.open.get_mut(plugin_id).expect("opened above"))
+    }
+
+    fn flush(&mut self) {
+        for log in self.open.values_mut() {
+            let _ = log.writer.flush();
+        }
+    }
+
+    fn remove(&mut self, plugin_id: &str) {
+        self.open.remove(plugin_id);
+        let path = log_path(&self.dir, plugin_id);
+        let _ = std::fs::remove_file(rotated(&path));
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_file_rotates_when_full() {
+        let dir = std::env::temp_dir().join("og-plugin-logs-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = log_path(&dir, "com.test.rotate");
+        let mut files = LogFiles::new(dir.clone());
+        files.append("com.test.rotate", "first\n").unwrap();
+        files.flush();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\n");
+
+        files.open.get_mut("com.test.rotate").unwrap().len = MAX_FILE_BYTES;
+        files.append("com.test.rotate", "after\n").unwrap();
+        files.flush();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "after\n");
+        assert_eq!(std::fs::read_to_string(rotated(&path)).unwrap(), "first\n");
+
+        files.remove("com.test.rotate");
+        assert!(!path.exists() && !rotated(&path).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
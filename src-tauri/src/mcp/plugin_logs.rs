@@ -0,0 +1,160 @@
+//! Plugin logs — what plugins write to stderr and send as MCP log messages.
+//!
+//! Each plugin gets a ring buffer of its most recent entries (shown in the
+//! Settings panel) and a log file in `~/.config/omni-glass/plugin-logs/`,
+//! written off the async runtime by `plugin_log_files`.
+//!
+//! stderr is drained by a task per process from the moment it spawns, so a
+//! chatty plugin never blocks on a full pipe. Lines longer than
+//! `MAX_LINE_BYTES` are dropped whole.
+
+use crate::mcp::line_reader::{read_line_limited, LineRead};
+use crate::mcp::plugin_log_entry::MAX_LINE_BYTES;
+use crate::mcp::plugin_log_files;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use tokio::io::{AsyncRead, BufReader};
+
+pub use crate::mcp::plugin_log_entry::{LogEntry, LogLevel, LogSource};
+
+/// Entries kept in memory per plugin.
+pub const RING_CAPACITY: usize = 500;
+
+static RINGS: LazyLock<Mutex<HashMap<String, VecDeque<LogEntry>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Keep an entry in the plugin's ring buffer and queue it for its log file.
+pub fn record(plugin_id: &str, entry: LogEntry) {
+    log::debug!(
+        "[PLUGIN] '{}' {}: {}",
+        plugin_id,
+        entry.level.as_str(),
+        entry.message
+    );
+    plugin_log_files::append(plugin_id, entry.to_line());
+    push(plugin_id, entry);
+}
+
+/// Keep an entry in the plugin's ring buffer only.
+fn push(plugin_id: &str, entry: LogEntry) {
+    let Ok(mut rings) = RINGS.lock() else { return };
+    let ring = rings.entry(plugin_id.to_string()).or_default();
+    if ring.len() == RING_CAPACITY {
+        ring.pop_front();
+    }
+    ring.push_back(entry);
+}
+
+/// The last `limit` entries for a plugin at `min_level` or above, oldest first.
+pub fn recent(plugin_id: &str, limit: usize, min_level: LogLevel) -> Vec<LogEntry> {
+    let Ok(rings) = RINGS.lock() else {
+        return Vec::new();
+    };
+    let Some(ring) = rings.get(plugin_id) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<LogEntry> = ring
+        .iter()
+        .rev()
+        .filter(|e| e.level >= min_level)
+        .take(limit)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
+
+/// Forget a plugin's entries and delete its log files (on uninstall).
+pub fn remove(plugin_id: &str) {
+    if let Ok(mut rings) = RINGS.lock() {
+        rings.remove(plugin_id);
+    }
+    plugin_log_files::remove(plugin_id);
+}
+
+/// Read `reader` (a plugin's stderr) line by line into its log until EOF.
+pub fn drain_stderr<R>(plugin_id: &str, reader: R)
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    let plugin_id = plugin_id.to_string();
+    tokio::spawn(drain(reader, move |entry| record(&plugin_id, entry)));
+}
+
+/// Hand each stderr line in `reader` to `sink` until EOF.
+async fn drain<R, F>(reader: R, sink: F)
+where
+    R: AsyncRead + Unpin,
+    F: Fn(LogEntry),
+{
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match read_line_limited(&mut reader, &mut buf, MAX_LINE_BYTES).await {
+            Ok(LineRead::Line) => {
+                let line = String::from_utf8_lossy(&buf);
+                if !line.trim().is_empty() {
+                    sink(LogEntry::stderr(&line));
+                }
+            }
+            Ok(LineRead::TooLong) => sink(LogEntry::stderr(&format!(
+                "(line longer than {} bytes dropped)",
+                MAX_LINE_BYTES
+            ))),
+            Ok(LineRead::Eof) | Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_keeps_the_newest_entries() {
+        let id = "com.test.logs.ring";
+        for i in 0..RING_CAPACITY + 5 {
+            push(id, LogEntry::stderr(&format!("line {}", i)));
+        }
+        let all = recent(id, usize::MAX, LogLevel::Debug);
+        assert_eq!(all.len(), RING_CAPACITY);
+        assert_eq!(all[0].message, "line 5");
+
+        let last = recent(id, 2, LogLevel::Debug);
+        let messages: Vec<&str> = last.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                format!("line {}", RING_CAPACITY + 3),
+                format!("line {}", RING_CAPACITY + 4)
+            ]
+        );
+        assert!(recent("com.test.logs.none", 10, LogLevel::Debug).is_empty());
+    }
+
+    #[tokio::test]
+    async fn stderr_is_drained_into_the_log() {
+        let id = "com.test.logs.drain";
+        let (mut writer, reader) = tokio::io::duplex(64);
+        // The pipe holds 64 bytes; writing more only finishes if the drain
+        // keeps reading.
+        let drained = tokio::spawn(drain(reader, move |entry| push(id, entry)));
+        let big = "y".repeat(MAX_LINE_BYTES * 2);
+        let input = format!("hello\n{}\n\nbye\n", big);
+        tokio::io::AsyncWriteExt::write_all(&mut writer, input.as_bytes())
+            .await
+            .unwrap();
+        drop(writer);
+        drained.await.unwrap();
+
+        let messages: Vec<String> = recent(id, 10, LogLevel::Debug)
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0], "hello");
+        assert!(messages[1].contains("dropped"));
+        assert_eq!(messages[2], "bye");
+    }
+}
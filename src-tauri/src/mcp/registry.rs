@@ -3,12 +3,61 @@
 //! Holds both built-in tools (dispatched to internal Rust functions)
 //! and plugin tools (dispatched via MCP stdio to child processes).
 //! Registered as Tauri managed state so all commands can query it.
+//!
+//! Each MCP server sits behind its own lock. The registry-wide maps are only
+//! held long enough to look up a handle, so a slow call to one plugin never
+//! blocks calls to another plugin or registry queries.
+//!
+//! The classifier's tool list is built in `registry/prompt.rs`, manifest
+//! triggers live in `registry/triggered.rs` and call audit records in
+//! `registry/call_audit.rs`.
 
 use crate::mcp::client::McpServer;
+use crate::mcp::host::ClientHost;
+use crate::mcp::limits::LimitViolation;
+use crate::mcp::relevance::PluginHints;
+use crate::mcp::triggers::Trigger;
 use crate::mcp::types::McpTool;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::Mutex;
 
+mod call_audit;
+mod prompt;
+mod triggered;
+
+/// How long `shutdown_all` waits for a busy server to finish its call.
+const SHUTDOWN_WAIT_SECS: u64 = 3;
+
+/// Shared handle to one running MCP server.
+///
+/// The mutex serializes requests to that server (its stdio is a single
+/// JSON-RPC channel); different servers are called in parallel.
+pub type ServerHandle = Arc<Mutex<McpServer>>;
+
+/// Why a plugin tool call failed.
+#[derive(Debug, Clone)]
+pub struct PluginCallError {
+    pub message: String,
+    /// Set when the plugin ran into one of its resource limits.
+    pub violation: Option<LimitViolation>,
+}
+
+impl From<String> for PluginCallError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            violation: None,
+        }
+    }
+}
+
+impl std::fmt::Display for PluginCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 /// A tool registered in the system, whether built-in or from a plugin.
 #[derive(Debug, Clone)]
 pub struct RegisteredTool {
@@ -22,6 +71,8 @@ pub struct RegisteredTool {
     pub description: String,
     /// JSON Schema for the tool's input (optional).
     pub input_schema: Option<serde_json::Value>,
+    /// JSON Schema for the tool's `structuredContent` (optional).
+    pub output_schema: Option<serde_json::Value>,
 }
 
 /// Qualified name format: "plugin_id:tool_name".
@@ -32,9 +83,15 @@ pub fn qualified_name(plugin_id: &str, tool_name: &str) -> String {
 /// Central registry for all tools and their MCP server handles.
 pub struct ToolRegistry {
     /// Running MCP server processes, keyed by plugin_id.
-    servers: Mutex<HashMap<String, McpServer>>,
+    servers: Mutex<HashMap<String, ServerHandle>>,
     /// All registered tools, keyed by qualified name ("plugin_id:tool_name").
     tools: Mutex<HashMap<String, RegisteredTool>>,
+    /// Manifest relevance hints, keyed by plugin_id.
+    hints: Mutex<HashMap<String, PluginHints>>,
+    /// Compiled manifest content triggers, keyed by plugin_id.
+    triggers: Mutex<HashMap<String, Vec<Trigger>>>,
+    /// Answers elicitation/sampling requests from servers spawned after it is set.
+    host: std::sync::RwLock<Option<Arc<dyn ClientHost>>>,
 }
 
 impl ToolRegistry {
@@ -42,9 +99,22 @@ impl ToolRegistry {
         Self {
             servers: Mutex::new(HashMap::new()),
             tools: Mutex::new(HashMap::new()),
+            hints: Mutex::new(HashMap::new()),
+            triggers: Mutex::new(HashMap::new()),
+            host: std::sync::RwLock::new(None),
         }
     }
 
+    /// Set the host that plugins loaded from now on talk to (the app's UI).
+    pub fn set_host(&self, host: Arc<dyn ClientHost>) {
+        *self.host.write().unwrap() = Some(host);
+    }
+
+    /// The current host, if any (none in headless use).
+    pub fn host(&self) -> Option<Arc<dyn ClientHost>> {
+        self.host.read().unwrap().clone()
+    }
+
     /// Register tools discovered from an MCP server.
     pub async fn register_plugin_tools(&self, plugin_id: &str, tools: Vec<McpTool>) {
         let mut map = self.tools.lock().await;
@@ -74,11 +144,17 @@ impl ToolRegistry {
                     display_name: display,
                     description: tool.description.unwrap_or_default(),
                     input_schema: tool.input_schema,
+                    output_schema: tool.output_schema,
                 },
             );
         }
     }
 
+    /// Store a plugin's manifest hints for the relevance filter.
+    pub async fn set_plugin_hints(&self, plugin_id: &str, hints: PluginHints) {
+        self.hints.lock().await.insert(plugin_id.to_string(), hints);
+    }
+
     /// Register a single built-in tool (no MCP server needed).
     pub async fn register_builtin(&self, tool: RegisteredTool) {
         let qname = qualified_name(&tool.plugin_id, &tool.name);
@@ -87,7 +163,30 @@ impl ToolRegistry {
 
     /// Store a running MCP server handle.
     pub async fn add_server(&self, plugin_id: String, server: McpServer) {
-        self.servers.lock().await.insert(plugin_id, server);
+        self.servers
+            .lock()
+            .await
+            .insert(plugin_id, Arc::new(Mutex::new(server)));
+    }
+
+    /// Unregister a plugin's tools and shut down its server, if running.
+    pub async fn remove_plugin(&self, plugin_id: &str) {
+        self.tools
+            .lock()
+            .await
+            .retain(|_, tool| tool.plugin_id != plugin_id);
+        self.hints.lock().await.remove(plugin_id);
+        self.triggers.lock().await.remove(plugin_id);
+        let handle = self.servers.lock().await.remove(plugin_id);
+        if let Some(handle) = handle {
+            log::info!("[MCP] Stopping plugin '{}'", plugin_id);
+            handle.lock().await.shutdown().await;
+        }
+    }
+
+    /// Get the handle for a plugin's server without holding the registry lock.
+    pub async fn server_handle(&self, plugin_id: &str) -> Option<ServerHandle> {
+        self.servers.lock().await.get(plugin_id).cloned()
     }
 
     /// Look up a tool by its qualified name.
@@ -123,31 +222,10 @@ impl ToolRegistry {
         None
     }
 
-    /// Format plugin tools as ActionMenu-compatible entries for LLM prompt injection.
-    ///
-    /// Uses the same field names (id, label, description, icon, requiresExecution)
-    /// that the CLASSIFY prompt expects, so the LLM can include them directly
-    /// in its actions array response.
-    pub async fn tools_for_prompt(&self) -> String {
-        let tools = self.tools.lock().await;
-        let plugin_tools: Vec<_> = tools
-            .values()
-            .filter(|t| t.plugin_id != "builtin")
-            .collect();
-
-        if plugin_tools.is_empty() {
-            return String::new();
-        }
-
-        let mut out = String::new();
-        for tool in &plugin_tools {
-            let qname = qualified_name(&tool.plugin_id, &tool.name);
-            out.push_str(&format!(
-                "- id: \"{}\", label: \"{}\", description: \"{}\", icon: \"sparkles\", requiresExecution: true\n",
-                qname, tool.display_name, tool.description
-            ));
-        }
-        out
+    /// Look up the tool an action ID refers to (qualified or bare name).
+    pub async fn tool_for_action(&self, action_id: &str) -> Option<RegisteredTool> {
+        let qname = self.resolve_action(action_id).await?;
+        self.get_tool(&qname).await
     }
 
     /// Get all registered tools (for debugging / settings UI).
@@ -156,11 +234,22 @@ impl ToolRegistry {
     }
 
     /// Shutdown all running MCP servers.
+    ///
+    /// Servers are removed from the registry first, so new calls fail fast.
+    /// A server still busy with a call after `SHUTDOWN_WAIT_SECS` is left to
+    /// finish; it is killed when that call drops the last handle.
     pub async fn shutdown_all(&self) {
-        let mut servers = self.servers.lock().await;
-        for (id, mut server) in servers.drain() {
+        let servers: Vec<(String, ServerHandle)> = self.servers.lock().await.drain().collect();
+        for (id, handle) in servers {
             log::info!("[MCP] Shutting down plugin '{}'", id);
-            server.shutdown().await;
+            let wait = std::time::Duration::from_secs(SHUTDOWN_WAIT_SECS);
+            match tokio::time::timeout(wait, handle.lock()).await {
+                Ok(mut server) => server.shutdown().await,
+                Err(_) => log::warn!(
+                    "[MCP] Plugin '{}' busy — it will be killed when its call finishes",
+                    id
+                ),
+            }
         }
     }
 
@@ -170,7 +259,7 @@ impl ToolRegistry {
         &self,
         action_id: &str,
         arguments: serde_json::Value,
-    ) -> Result<crate::mcp::types::ToolResult, String> {
+    ) -> Result<crate::mcp::types::ToolResult, PluginCallError> {
         // Resolve tool info
         let tool = {
             let tools = self.tools.lock().await;
@@ -179,15 +268,24 @@ impl ToolRegistry {
                 .find(|(qname, t)| *qname == action_id || t.name == action_id);
             match found {
                 Some((_, t)) => t.clone(),
-                None => return Err(format!("Tool '{}' not found in registry", action_id)),
+                None => return Err(format!("Tool '{}' not found in registry", action_id).into()),
             }
         };
 
-        // Call on the plugin's MCP server
-        let mut servers = self.servers.lock().await;
-        let server = servers.get_mut(&tool.plugin_id).ok_or_else(|| {
-            format!("No running server for plugin '{}'", tool.plugin_id)
-        })?;
-        server.call_tool(&tool.name, arguments).await
+        // Call on the plugin's MCP server — only that server is locked
+        let handle = self
+            .server_handle(&tool.plugin_id)
+            .await
+            .ok_or_else(|| format!("No running server for plugin '{}'", tool.plugin_id))?;
+        let result = {
+            let mut server = handle.lock().await;
+            let result = server.call_tool(&tool.name, arguments.clone()).await;
+            result.map_err(|message| PluginCallError {
+                message,
+                violation: server.take_violation(),
+            })
+        };
+        call_audit::record_plugin_call(&tool, arguments, &result).await;
+        result
     }
 }
@@ -0,0 +1,38 @@
+//! Audit records for plugin tool calls.
+
+use super::{qualified_name, PluginCallError, RegisteredTool};
+use crate::mcp::types::{ToolResult, ToolResultContent};
+
+/// Append a plugin tool call and its outcome to the audit log.
+pub(super) async fn record_plugin_call(
+    tool: &RegisteredTool,
+    arguments: serde_json::Value,
+    result: &Result<ToolResult, PluginCallError>,
+) {
+    let (is_error, output) = match result {
+        Ok(r) => {
+            let text: Vec<&str> = r
+                .content
+                .iter()
+                .filter_map(|c| match c {
+                    ToolResultContent::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            (r.is_error, text.join("\n"))
+        }
+        Err(e) => (true, e.message.clone()),
+    };
+    crate::audit::record_async(
+        crate::audit::AuditKind::PluginCall,
+        qualified_name(&tool.plugin_id, &tool.name),
+        serde_json::json!({
+            "pluginId": tool.plugin_id,
+            "tool": tool.name,
+            "arguments": arguments,
+            "isError": is_error,
+            "output": output,
+        }),
+    )
+    .await;
+}
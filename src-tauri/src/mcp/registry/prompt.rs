@@ -0,0 +1,74 @@
+//! The plugin tools offered to the classifier: all enabled tools, or the
+//! relevance filter's pick for one snip.
+
+use super::{RegisteredTool, ToolRegistry};
+use crate::mcp::relevance::{self, Query, Selection};
+use crate::mcp::tool_prefs::ToolPrefs;
+
+/// One `<available_plugins>` line, in the ActionMenu action format.
+fn prompt_line(qname: &str, tool: &RegisteredTool) -> String {
+    format!(
+        "- id: \"{}\", label: \"{}\", description: \"{}\", icon: \"sparkles\", requiresExecution: true\n",
+        qname, tool.display_name, tool.description
+    )
+}
+
+impl ToolRegistry {
+    /// Format plugin tools as ActionMenu-compatible entries for LLM prompt injection.
+    ///
+    /// Uses the same field names (id, label, description, icon, requiresExecution)
+    /// that the CLASSIFY prompt expects, so the LLM can include them directly
+    /// in its actions array response. Tools the user disabled are left out;
+    /// pinned tools come first, the rest in name order.
+    pub async fn tools_for_prompt(&self, prefs: &ToolPrefs) -> String {
+        let tools = self.tools.lock().await;
+        let mut plugin_tools: Vec<(String, &RegisteredTool)> = tools
+            .iter()
+            .filter(|(qname, t)| t.plugin_id != "builtin" && prefs.is_enabled(qname))
+            .map(|(qname, t)| (qname.clone(), t))
+            .collect();
+
+        if plugin_tools.is_empty() {
+            return String::new();
+        }
+        plugin_tools.sort_by(|(a, _), (b, _)| {
+            let rank = |q: &str| prefs.pin_rank(q).unwrap_or(usize::MAX);
+            rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+        });
+
+        plugin_tools
+            .iter()
+            .map(|(qname, tool)| prompt_line(qname, tool))
+            .collect()
+    }
+
+    /// The enabled plugin tools most relevant to a snip, formatted like
+    /// `tools_for_prompt`, and the selection behind them (for metrics).
+    /// See `relevance::select`.
+    pub async fn tools_for_snip(&self, prefs: &ToolPrefs, query: &Query) -> (String, Selection) {
+        let selection = self.select_tools(prefs, query).await;
+        let tools = self.tools.lock().await;
+        let prompt = selection
+            .offered
+            .iter()
+            .filter_map(|qname| tools.get(qname).map(|tool| prompt_line(qname, tool)))
+            .collect();
+        (prompt, selection)
+    }
+
+    /// Run the relevance filter over the enabled plugin tools.
+    pub async fn select_tools(&self, prefs: &ToolPrefs, query: &Query) -> Selection {
+        let tools = self.tools.lock().await;
+        let hints = self.hints.lock().await;
+        let candidates: Vec<relevance::Candidate> = tools
+            .iter()
+            .filter(|(_, t)| t.plugin_id != "builtin")
+            .map(|(qname, tool)| relevance::Candidate {
+                id: qname,
+                tool,
+                hints: hints.get(&tool.plugin_id),
+            })
+            .collect();
+        relevance::select(&candidates, query, prefs, relevance::MAX_PROMPT_TOOLS)
+    }
+}
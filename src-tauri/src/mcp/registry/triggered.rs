@@ -0,0 +1,82 @@
+//! Manifest triggers in the registry: compile and store them per plugin,
+//! and turn the ones that fire on a snip into menu actions.
+
+use super::{qualified_name, ToolRegistry};
+use crate::llm::types::{Action, ActionMenu};
+use crate::mcp::relevance::Query;
+use crate::mcp::tool_prefs::{self, ToolPrefs};
+use crate::mcp::triggers::{self, ContentTrigger};
+
+impl ToolRegistry {
+    /// Compile and store a plugin's manifest triggers. Call after its tools
+    /// are registered, so triggers naming a tool it doesn't have are reported.
+    pub async fn set_plugin_triggers(&self, plugin_id: &str, declared: &[ContentTrigger]) {
+        let compiled = triggers::compile(plugin_id, declared);
+        {
+            let tools = self.tools.lock().await;
+            for trigger in &compiled {
+                if !tools.contains_key(&qualified_name(plugin_id, trigger.tool())) {
+                    log::warn!(
+                        "[MCP] Plugin '{}' has a trigger for unknown tool '{}'",
+                        plugin_id,
+                        trigger.tool()
+                    );
+                }
+            }
+        }
+        self.triggers
+            .lock()
+            .await
+            .insert(plugin_id.to_string(), compiled);
+    }
+
+    /// Plugin tools whose manifest triggers fire for a snip, as menu actions
+    /// (plugins in id order, each tool once). Disabled tools never fire.
+    /// Runs locally, so it works without an LLM provider; see `triggers`.
+    pub async fn triggered_actions(
+        &self,
+        text: &str,
+        query: &Query,
+        prefs: &ToolPrefs,
+    ) -> Vec<Action> {
+        let tools = self.tools.lock().await;
+        let triggers = self.triggers.lock().await;
+        let mut plugin_ids: Vec<&String> = triggers.keys().collect();
+        plugin_ids.sort();
+
+        let mut actions: Vec<Action> = Vec::new();
+        for plugin_id in plugin_ids {
+            for trigger in &triggers[plugin_id] {
+                let qname = qualified_name(plugin_id, trigger.tool());
+                if !prefs.is_enabled(&qname) || actions.iter().any(|a| a.id == qname) {
+                    continue;
+                }
+                let Some(tool) = tools.get(&qname) else {
+                    continue;
+                };
+                if let Some(matched) = trigger.fire(text, query.content_types()) {
+                    actions.push(trigger.action(plugin_id, tool, matched));
+                }
+            }
+        }
+        actions
+    }
+    /// `triggers::merge`, comparing actions by the `tool_prefs::tool_key` of
+    /// the registered tool each id names (qualified or bare).
+    pub async fn merge_triggered(
+        &self,
+        menu: ActionMenu,
+        triggered: &[Action],
+        prefs: &ToolPrefs,
+    ) -> ActionMenu {
+        let tools = self.tools.lock().await;
+        let key = |id: &str| {
+            tools
+                .get(id)
+                .or_else(|| tools.values().find(|t| t.name == id))
+                .map(tool_prefs::tool_key)
+                .unwrap_or_else(|| id.to_string())
+        };
+        triggers::merge(menu, triggered, prefs, key)
+    }
+}
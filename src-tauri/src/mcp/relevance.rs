@@ -0,0 +1,196 @@
+//! Relevance pre-filter — pick the plugin tools worth sending to the classifier.
+//!
+//! With many plugins installed, listing every tool in every classify
+//! request costs tokens and dilutes the model's choice. Before the request,
+//! each enabled plugin tool is scored against the snip with BM25 over its
+//! name, description and the plugin's manifest `hints.keywords`, plus a
+//! boost when one of the plugin's `hints.contentTypes` matches the content
+//! type guessed from the OCR text and heuristics. Only the best
+//! `MAX_PROMPT_TOOLS` go into the prompt. Pinned tools are always offered,
+//! and when no more than `MAX_PROMPT_TOOLS` tools are enabled nothing is
+//! filtered.
+//!
+//! Scoring lives in `relevance_bm25` and the counters that show how well
+//! the filter does in `relevance_stats`.
+
+use crate::mcp::registry::RegisteredTool;
+use crate::mcp::relevance_bm25::{bm25, tokenize};
+use crate::mcp::tool_prefs::ToolPrefs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub use crate::mcp::relevance_stats::{
+    record_launcher_choice, record_menu_choice, record_selection, stats, RelevanceStats,
+};
+
+/// Most plugin tools offered to the classifier per snip (pinned ones excluded).
+pub const MAX_PROMPT_TOOLS: usize = 12;
+
+/// OCR text looked at per snip.
+const MAX_QUERY_CHARS: usize = 4000;
+
+/// Added to the score of a tool whose plugin declares a matching content type.
+const CONTENT_TYPE_BOOST: f64 = 2.0;
+
+/// Words that make a snip look like an error to `guess_content_types`.
+const ERROR_WORDS: &[&str] = &[
+    "error",
+    "exception",
+    "traceback",
+    "panicked",
+    "fatal",
+    "failed",
+];
+
+/// When a plugin's tools are relevant, declared under `hints` in the manifest.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHints {
+    /// Classify content types the tools suit ("error", "code", "table", ...).
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    /// Words that suggest the tools are wanted, matched against the OCR text.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+/// What a snip is scored against.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    terms: HashSet<String>,
+    content_types: Vec<&'static str>,
+}
+
+impl Query {
+    /// Build a query from a snip's OCR text and structure heuristics.
+    pub fn from_snip(text: &str, has_table: bool, has_code: bool) -> Self {
+        let end = text
+            .char_indices()
+            .nth(MAX_QUERY_CHARS)
+            .map_or(text.len(), |(i, _)| i);
+        Self {
+            terms: tokenize(&text[..end]).into_iter().collect(),
+            content_types: guess_content_types(text, has_table, has_code),
+        }
+    }
+
+    /// Content types guessed for the snip.
+    pub fn content_types(&self) -> &[&'static str] {
+        &self.content_types
+    }
+}
+
+/// Content types the classifier is likely to pick, guessed locally.
+pub fn guess_content_types(text: &str, has_table: bool, has_code: bool) -> Vec<&'static str> {
+    let lower = text.to_lowercase();
+    let mut types = Vec::new();
+    if ERROR_WORDS.iter().any(|w| lower.contains(w)) {
+        types.push("error");
+    }
+    if has_code {
+        types.push("code");
+    }
+    if has_table {
+        types.push("table");
+        types.push("kv_pairs");
+    }
+    if lower.contains("http://") || lower.contains("https://") || lower.contains("www.") {
+        types.push("url");
+    }
+    if types.is_empty() {
+        types.push("prose");
+    }
+    types
+}
+
+/// A tool that may be offered, with its plugin's hints.
+pub struct Candidate<'a> {
+    /// Qualified name.
+    pub id: &'a str,
+    pub tool: &'a RegisteredTool,
+    pub hints: Option<&'a PluginHints>,
+}
+
+/// The tools offered for one snip.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Selection {
+    /// Enabled plugin tools there were to choose from.
+    pub available: usize,
+    /// Qualified names offered, in prompt order.
+    pub offered: Vec<String>,
+}
+
+/// Rank enabled candidates for `query` and keep the best `limit`, plus
+/// pinned tools. Disabled tools are skipped. If `limit` covers every
+/// enabled tool, all are offered; otherwise tools that match nothing are
+/// left out.
+pub fn select(
+    candidates: &[Candidate<'_>],
+    query: &Query,
+    prefs: &ToolPrefs,
+    limit: usize,
+) -> Selection {
+    let enabled: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|c| prefs.is_enabled(c.id))
+        .collect();
+    let docs: Vec<Vec<String>> = enabled.iter().map(|c| document(c)).collect();
+    let scores = bm25(&docs, &query.terms);
+
+    let mut ranked: Vec<(Option<usize>, f64, &str)> = enabled
+        .iter()
+        .zip(scores)
+        .map(|(c, score)| {
+            let boost = c.hints.is_some_and(|h| {
+                h.content_types
+                    .iter()
+                    .any(|t| query.content_types.contains(&t.as_str()))
+            });
+            let score = score + if boost { CONTENT_TYPE_BOOST } else { 0.0 };
+            (prefs.pin_rank(c.id), score, c.id)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        let pin = |p: Option<usize>| p.unwrap_or(usize::MAX);
+        pin(a.0)
+            .cmp(&pin(b.0))
+            .then_with(|| b.1.total_cmp(&a.1))
+            .then_with(|| a.2.cmp(b.2))
+    });
+
+    let filter = enabled.len() > limit;
+    let mut unpinned = 0;
+    let offered = ranked
+        .into_iter()
+        .filter(|(pin, score, _)| {
+            if pin.is_some() {
+                return true;
+            }
+            if filter && (*score <= 0.0 || unpinned >= limit) {
+                return false;
+            }
+            unpinned += 1;
+            true
+        })
+        .map(|(_, _, id)| id.to_string())
+        .collect();
+    Selection {
+        available: enabled.len(),
+        offered,
+    }
+}
+
+/// Terms of a tool: name and keywords count twice, description once.
+fn document(candidate: &Candidate<'_>) -> Vec<String> {
+    let tool = candidate.tool;
+    let mut terms = tokenize(&tool.name);
+    terms.extend(tokenize(&tool.display_name));
+    terms.extend(tokenize(&tool.description));
+    if let Some(hints) = candidate.hints {
+        let keywords = tokenize(&hints.keywords.join(" "));
+        terms.extend(keywords.iter().cloned());
+        terms.extend(keywords);
+    }
+    terms
+}
@@ -0,0 +1,79 @@
+//! BM25 scoring and the tokenizer behind the relevance pre-filter.
+//!
+//! Tool documents and snips go through the same `tokenize`, so a tool
+//! named `create_issue` matches a snip that says "issues".
+
+use std::collections::{HashMap, HashSet};
+
+/// BM25 term-frequency saturation and length normalization.
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Words too common to say anything about a tool.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "this", "that", "from", "into", "your", "you", "are", "was",
+    "will", "can", "not", "but", "all", "any", "its", "has", "have", "been", "of", "to", "in",
+    "on", "or", "an", "is", "it", "as", "at", "by", "be", "if", "a",
+];
+
+/// BM25 score of each document for the query terms.
+pub fn bm25(docs: &[Vec<String>], query: &HashSet<String>) -> Vec<f64> {
+    if docs.is_empty() {
+        return Vec::new();
+    }
+    let n = docs.len() as f64;
+    let avg_len = docs.iter().map(Vec::len).sum::<usize>() as f64 / n;
+    let mut doc_freq: HashMap<&str, f64> = HashMap::new();
+    for doc in docs {
+        let unique: HashSet<&str> = doc.iter().map(String::as_str).collect();
+        for term in unique {
+            *doc_freq.entry(term).or_default() += 1.0;
+        }
+    }
+
+    docs.iter()
+        .map(|doc| {
+            let mut tf: HashMap<&str, f64> = HashMap::new();
+            for term in doc {
+                *tf.entry(term.as_str()).or_default() += 1.0;
+            }
+            let len_norm = 1.0 - B + B * doc.len() as f64 / avg_len.max(1.0);
+            query
+                .iter()
+                .filter_map(|term| {
+                    let f = *tf.get(term.as_str())?;
+                    let df = doc_freq[term.as_str()];
+                    let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    Some(idf * f * (K1 + 1.0) / (f + K1 * len_norm))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Lowercase words of two or more letters, stopwords dropped and a plural
+/// "s" trimmed. Splits snake_case and other punctuation.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 2 && !w.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_and_trims() {
+        assert_eq!(
+            tokenize("create_issue: Opens ISSUES for the 2 repos"),
+            ["create", "issue", "open", "issue", "repo"]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Relevance filter counters in `~/.config/omni-glass/relevance-stats.json`.
+//!
+//! They show how well the filter does: how many tools it offers, how often
+//! a plugin action the user ran from the menu had been offered, and how
+//! often a plugin tool picked in the text launcher (which sees every tool)
+//! would have made the cut.
+
+use crate::mcp::relevance::Selection;
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+
+const STATS_FILE: &str = "relevance-stats.json";
+
+/// How well the filter has done so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RelevanceStats {
+    /// Snips classified with the filter.
+    pub snips: u64,
+    /// Snips where the filter left at least one enabled tool out.
+    pub filtered_snips: u64,
+    /// Enabled plugin tools, summed over snips.
+    pub tools_available: u64,
+    /// Plugin tools offered, summed over snips.
+    pub tools_offered: u64,
+    /// Plugin actions the user ran from the action menu.
+    pub menu_choices: u64,
+    /// ...of which the filter had offered for that snip.
+    pub menu_choices_offered: u64,
+    /// Plugin tools the text launcher's router picked.
+    pub launcher_choices: u64,
+    /// ...of which the filter would have offered for the typed text.
+    pub launcher_choices_selected: u64,
+}
+
+static STATS: LazyLock<Mutex<RelevanceStats>> = LazyLock::new(|| Mutex::new(load_stats()));
+
+fn stats_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(STATS_FILE))
+}
+
+fn load_stats() -> RelevanceStats {
+    stats_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Current counters.
+pub fn stats() -> RelevanceStats {
+    STATS.lock().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Count one snip's selection.
+pub fn record_selection(selection: &Selection) {
+    update_stats(|s| {
+        s.snips += 1;
+        if selection.offered.len() < selection.available {
+            s.filtered_snips += 1;
+        }
+        s.tools_available += selection.available as u64;
+        s.tools_offered += selection.offered.len() as u64;
+    });
+}
+
+/// Count a plugin action run from the menu.
+pub fn record_menu_choice(offered: bool) {
+    update_stats(|s| {
+        s.menu_choices += 1;
+        s.menu_choices_offered += offered as u64;
+    });
+}
+
+/// Count a plugin tool picked by the text launcher.
+pub fn record_launcher_choice(selected: bool) {
+    update_stats(|s| {
+        s.launcher_choices += 1;
+        s.launcher_choices_selected += selected as u64;
+    });
+}
+
+fn update_stats(change: impl FnOnce(&mut RelevanceStats)) {
+    let Ok(mut stats) = STATS.lock() else {
+        return;
+    };
+    change(&mut stats);
+    let Some(path) = stats_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&*stats) {
+        if let Err(e) = std::fs::write(&path, json) {
+            log::warn!("[MCP] Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
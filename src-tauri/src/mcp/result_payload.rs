@@ -0,0 +1,90 @@
+//! Checks and naming for binary and link payloads in plugin tool results.
+//!
+//! `results.rs` decides what a result becomes; these helpers reject payloads
+//! the UI can't safely show (malformed mime types, oversized or invalid
+//! base64, non-web links) and pick download filenames.
+
+use base64::Engine;
+
+/// Largest decoded image/blob a plugin may return.
+pub const MAX_BINARY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Check mime type, base64 validity and decoded size of binary content.
+///
+/// The mime type ends up in a `data:` URL, so it must be a plain `type/subtype`.
+pub fn check_binary(data: &str, mime_type: &str, required_prefix: &str) -> Result<(), String> {
+    let well_formed = mime_type.split_once('/').is_some_and(|(kind, sub)| {
+        !kind.is_empty()
+            && !sub.is_empty()
+            && kind.chars().all(|c| c.is_ascii_alphanumeric())
+            && sub
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+    });
+    if !well_formed || !mime_type.starts_with(required_prefix) {
+        return Err(format!(
+            "mime type '{}' is not {}*",
+            mime_type, required_prefix
+        ));
+    }
+    // Cheap upper bound before decoding: 4 base64 chars per 3 bytes.
+    if data.len() / 4 * 3 > MAX_BINARY_BYTES + 3 {
+        return Err(format!(
+            "content larger than {} MB",
+            MAX_BINARY_BYTES / 1024 / 1024
+        ));
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("invalid base64 data: {}", e))?;
+    Ok(())
+}
+
+/// Links open in the user's browser, so only web URLs are allowed.
+pub fn check_link(uri: &str) -> Result<(), String> {
+    let lower = uri.to_ascii_lowercase();
+    if lower.starts_with("https://") || lower.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(format!("link scheme not allowed: {}", uri))
+    }
+}
+
+/// Suggest a safe filename from the last path segment of a resource URI.
+pub fn filename_from_uri(uri: &str, mime_type: Option<&str>) -> String {
+    let path = uri.split(['?', '#']).next().unwrap_or("");
+    let segment = path.rsplit('/').next().unwrap_or("");
+    let segment = segment.rsplit(':').next().unwrap_or("");
+    let name: String = segment
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        .collect();
+    let name = name.trim_start_matches('.');
+    if name.is_empty() {
+        format!(
+            "plugin-output.{}",
+            mime_type.map(extension_for).unwrap_or_else(|| "txt".into())
+        )
+    } else {
+        name.to_string()
+    }
+}
+
+/// File extension for a mime type: "image/svg+xml" → "svg".
+pub fn extension_for(mime_type: &str) -> String {
+    let subtype = mime_type.split('/').nth(1).unwrap_or("bin");
+    let ext: String = subtype
+        .split(['+', ';'])
+        .next()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    match ext.as_str() {
+        "" => "bin".into(),
+        "plain" => "txt".into(),
+        "jpeg" => "jpg".into(),
+        "mpeg" => "mp3".into(),
+        _ => ext,
+    }
+}
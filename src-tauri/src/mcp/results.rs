@@ -0,0 +1,258 @@
+//! Map MCP tool results onto `ActionResult`.
+//!
+//! A tool result is a list of content blocks plus optional
+//! `structuredContent`. Text blocks become the result text (or a caption);
+//! the first non-text block decides the result type:
+//!
+//! | MCP content                          | ActionResult type |
+//! |--------------------------------------|-------------------|
+//! | text only                            | `text`            |
+//! | `image`                              | `image`           |
+//! | `resource` with a `clipboard:` URI   | `clipboard`       |
+//! | `resource` (text or blob), `audio`   | `file`            |
+//! | `resource_link` (http/https)         | `link`            |
+//!
+//! All textual output passes the same safety gates as before: dangerous
+//! command check, secret redaction, and the prompt-injection scan.
+
+use crate::llm::execute::{ActionResult, ActionResultBody};
+use crate::mcp::result_payload::{check_binary, check_link, extension_for, filename_from_uri};
+use crate::mcp::schema;
+use crate::mcp::types::{EmbeddedResource, ToolResult, ToolResultContent};
+use crate::safety::{command_check, injection, redact};
+
+/// Convert a `tools/call` result into the action result shown to the user.
+pub fn to_action_result(
+    action_id: &str,
+    result: &ToolResult,
+    output_schema: Option<&serde_json::Value>,
+) -> ActionResult {
+    if result.is_error {
+        return ActionResult::error(action_id, &format!("Plugin error: {}", result.text()));
+    }
+    if let Err(e) = check_structured_content(result, output_schema) {
+        log::warn!(
+            "[MCP] Plugin '{}' structuredContent rejected: {}",
+            action_id,
+            e
+        );
+        return ActionResult::error(action_id, &format!("Plugin returned invalid output: {}", e));
+    }
+    let body = match build_body(result) {
+        Ok(body) => body,
+        Err(e) => {
+            log::warn!("[MCP] Plugin '{}' output rejected: {}", action_id, e);
+            return ActionResult::error(action_id, &format!("Unsupported plugin output: {}", e));
+        }
+    };
+    apply_safety_gates(action_id, body)
+}
+
+/// Validate `structuredContent` against the tool's declared `outputSchema`.
+fn check_structured_content(
+    result: &ToolResult,
+    output_schema: Option<&serde_json::Value>,
+) -> Result<(), String> {
+    match (output_schema, &result.structured_content) {
+        (Some(_), None) => {
+            Err("tool declares an outputSchema but sent no structuredContent".into())
+        }
+        (Some(schema), Some(value)) => {
+            let errors = schema::validate(schema, value);
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "structuredContent does not match outputSchema: {}",
+                    errors.join("; ")
+                ))
+            }
+        }
+        (None, _) => Ok(()),
+    }
+}
+
+/// The result's text, or its pretty-printed `structuredContent` if it has
+/// no text.
+fn caption_of(result: &ToolResult) -> String {
+    let caption = result.text();
+    match &result.structured_content {
+        Some(structured) if caption.is_empty() => {
+            serde_json::to_string_pretty(structured).unwrap_or_default()
+        }
+        _ => caption,
+    }
+}
+
+/// Content other than text, which decides the result type.
+fn is_rich(content: &ToolResultContent) -> bool {
+    !matches!(
+        content,
+        ToolResultContent::Text { .. } | ToolResultContent::Unknown
+    )
+}
+
+fn build_body(result: &ToolResult) -> Result<ActionResultBody, String> {
+    let caption = caption_of(result);
+    let rich = result.content.iter().find(|c| is_rich(c));
+    let mut body = empty_body("text");
+    body.text = Some(caption.clone());
+    match rich {
+        None | Some(ToolResultContent::Text { .. } | ToolResultContent::Unknown) => {}
+        Some(ToolResultContent::Image { data, mime_type }) => {
+            check_binary(data, mime_type, "image/")?;
+            fill_media(&mut body, "image", "image", data, mime_type);
+        }
+        Some(ToolResultContent::Audio { data, mime_type }) => {
+            check_binary(data, mime_type, "audio/")?;
+            fill_media(&mut body, "file", "audio", data, mime_type);
+        }
+        Some(ToolResultContent::Resource { resource }) => {
+            body = resource_body(resource, caption)?;
+        }
+        Some(ToolResultContent::ResourceLink {
+            uri,
+            name,
+            description,
+            ..
+        }) => {
+            check_link(uri)?;
+            body.result_type = "link".into();
+            body.url = Some(uri.clone());
+            body.text = Some(link_label(&[
+                name.as_deref(),
+                description.as_deref(),
+                Some(&caption),
+            ]));
+        }
+    }
+    Ok(body)
+}
+
+/// A link's name, description and caption, one per line, skipping blanks.
+fn link_label(parts: &[Option<&str>]) -> String {
+    parts
+        .iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Binary image or audio payload, named after its kind and mime type.
+fn fill_media(body: &mut ActionResultBody, result_type: &str, stem: &str, data: &str, mime: &str) {
+    body.result_type = result_type.into();
+    body.file_path = Some(format!("{}.{}", stem, extension_for(mime)));
+    body.data = Some(data.to_string());
+    body.mime_type = Some(mime.to_string());
+}
+
+/// Embedded resources become files, or clipboard payloads for `clipboard:` URIs.
+fn resource_body(resource: &EmbeddedResource, caption: String) -> Result<ActionResultBody, String> {
+    let mime = resource.mime_type.clone();
+    match (&resource.text, &resource.blob) {
+        (Some(text), _) if resource.uri.starts_with("clipboard:") => {
+            let mut body = empty_body("clipboard");
+            body.clipboard_content = Some(text.clone());
+            body.text = Some(caption);
+            body.mime_type = mime;
+            Ok(body)
+        }
+        (Some(text), _) => {
+            let mut body = empty_body("file");
+            body.file_path = Some(filename_from_uri(&resource.uri, mime.as_deref()));
+            body.text = Some(text.clone());
+            body.mime_type = mime;
+            Ok(body)
+        }
+        (None, Some(blob)) => {
+            check_binary(
+                blob,
+                mime.as_deref().unwrap_or("application/octet-stream"),
+                "",
+            )?;
+            let mut body = empty_body("file");
+            body.file_path = Some(filename_from_uri(&resource.uri, mime.as_deref()));
+            body.text = Some(caption);
+            body.data = Some(blob.clone());
+            body.mime_type = mime;
+            Ok(body)
+        }
+        (None, None) => Err(format!(
+            "resource '{}' has neither text nor blob",
+            resource.uri
+        )),
+    }
+}
+
+fn empty_body(result_type: &str) -> ActionResultBody {
+    ActionResultBody {
+        result_type: result_type.to_string(),
+        text: None,
+        file_path: None,
+        command: None,
+        clipboard_content: None,
+        mime_type: None,
+        data: None,
+        url: None,
+        arguments: None,
+    }
+}
+
+/// Command check, redaction and injection scan over all textual fields.
+fn apply_safety_gates(action_id: &str, mut body: ActionResultBody) -> ActionResult {
+    let raw_text = [body.text.as_deref(), body.clipboard_content.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Safety gate 1: block dangerous commands in plugin output
+    let cmd_check = command_check::is_command_safe(&raw_text);
+    if !cmd_check.safe {
+        let reason = cmd_check.reason.unwrap_or_else(|| "blocked pattern".into());
+        log::warn!("[SAFETY] Plugin '{}' output blocked: {}", action_id, reason);
+        return ActionResult::error(
+            action_id,
+            &format!("Plugin output blocked by safety filter: {}", reason),
+        );
+    }
+
+    // Safety gate 2: redact PII / secrets from plugin output
+    let mut labels: Vec<String> = Vec::new();
+    for field in [&mut body.text, &mut body.clipboard_content] {
+        if let Some(value) = field.as_mut() {
+            let redaction = redact::redact_sensitive_data(value);
+            labels.extend(redaction.redactions.into_iter().map(|r| r.label));
+            *value = redaction.cleaned_text;
+        }
+    }
+    if !labels.is_empty() {
+        log::info!(
+            "[SAFETY] Redacted {} pattern(s) from plugin '{}' output: {:?}",
+            labels.len(),
+            action_id,
+            labels
+        );
+    }
+
+    let mut action_result = ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: body,
+        metadata: None,
+    };
+
+    // Safety gate 3: flag output that looks like a prompt-injection attempt
+    let scan = injection::scan_for_injection(&raw_text);
+    if scan.adversarial {
+        log::warn!(
+            "[SAFETY] Plugin '{}' output looks adversarial: {:?}",
+            action_id,
+            scan.signals
+        );
+        action_result.flag_untrusted(injection::INJECTION_WARNING);
+    }
+    action_result
+}
@@ -0,0 +1,288 @@
+//! Plugin runtimes — which program runs a plugin, with what arguments.
+//!
+//! Node, Bun and Python run the entry file with an interpreter from PATH;
+//! Python prefers the plugin's own `.venv`. Deno gets `--allow-*` flags
+//! built from the manifest, so its permission checks enforce the same
+//! grants as the OS sandbox. Binary entries run directly and WASM
+//! components run in-process (`mcp::wasm`), so neither needs an interpreter.
+
+use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::sandbox::env_filter::plugin_temp_dir;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Virtualenv the installer creates for a Python plugin's dependencies.
+pub const PYTHON_VENV_DIR: &str = ".venv";
+
+/// Interpreter names looked up on PATH, in order of preference.
+pub fn binary_names(runtime: &Runtime) -> &'static [&'static str] {
+    match runtime {
+        Runtime::Node => &["node"],
+        Runtime::Python => &["python3", "python"],
+        Runtime::Deno => &["deno"],
+        Runtime::Bun => &["bun"],
+        Runtime::Binary | Runtime::Wasm => &[],
+    }
+}
+
+/// The runtime's interpreter on PATH; `None` if it doesn't need one.
+pub fn find_interpreter(runtime: &Runtime) -> Result<Option<PathBuf>, String> {
+    let names = binary_names(runtime);
+    if names.is_empty() {
+        return Ok(None);
+    }
+    names
+        .iter()
+        .find_map(|name| which::which(name).ok())
+        .map(Some)
+        .ok_or_else(|| format!("{} not found in PATH", display_name(runtime)))
+}
+
+fn display_name(runtime: &Runtime) -> &'static str {
+    match runtime {
+        Runtime::Node => "Node.js",
+        Runtime::Python => "Python",
+        Runtime::Deno => "Deno",
+        Runtime::Bun => "Bun",
+        Runtime::Binary => "Binary",
+        Runtime::Wasm => "WASM",
+    }
+}
+
+/// The Python interpreter inside a plugin's virtualenv, if it has one.
+pub fn venv_python(plugin_dir: &Path) -> Option<PathBuf> {
+    let venv = plugin_dir.join(PYTHON_VENV_DIR);
+    let python = if cfg!(windows) {
+        venv.join("Scripts").join("python.exe")
+    } else {
+        venv.join("bin").join("python")
+    };
+    python.exists().then_some(python)
+}
+
+/// Expand a leading `~` in a manifest path to the user's home directory.
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => format!("{}{}", home.to_string_lossy(), rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Deno permission flags for a manifest: reads of the plugin and its temp
+/// dir, declared paths, hosts, variables and commands. Nothing prompts and
+/// nothing is downloaded; dependencies must be vendored.
+pub fn deno_flags(manifest: &PluginManifest, plugin_dir: &Path) -> Vec<String> {
+    let perms = &manifest.permissions;
+    let tmp = plugin_temp_dir(&manifest.id).to_string_lossy().into_owned();
+    let mut read = vec![plugin_dir.to_string_lossy().into_owned(), tmp.clone()];
+    let mut write = vec![tmp];
+    for perm in perms.filesystem.iter().flatten() {
+        let path = expand_home(&perm.path);
+        if matches!(perm.access.as_str(), "write" | "read-write") {
+            write.push(path.clone());
+        }
+        read.push(path);
+    }
+
+    let mut flags = vec![
+        "run".to_string(),
+        "--no-prompt".to_string(),
+        "--cached-only".to_string(),
+        format!("--allow-read={}", read.join(",")),
+        format!("--allow-write={}", write.join(",")),
+    ];
+    if let Some(domains) = perms.network.as_ref().filter(|d| !d.is_empty()) {
+        flags.push(format!("--allow-net={}", domains.join(",")));
+    }
+    let mut vars = vec!["OMNI_GLASS_PLUGIN_ID".to_string(), "TMPDIR".to_string()];
+    vars.extend(perms.environment.iter().flatten().cloned());
+    flags.push(format!("--allow-env={}", vars.join(",")));
+    if let Some(shell) = perms.shell.as_ref().filter(|s| !s.commands.is_empty()) {
+        flags.push(format!("--allow-run={}", shell.commands.join(",")));
+    }
+    flags
+}
+
+/// Determine the command + args to spawn based on runtime and entry point.
+pub fn resolve_command(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+) -> Result<(String, Vec<String>), String> {
+    let entry_path = plugin_dir.join(&manifest.entry);
+    let entry_str = entry_path.to_string_lossy().to_string();
+
+    match manifest.runtime {
+        Runtime::Node => Ok(("node".to_string(), vec![entry_str])),
+        Runtime::Python => {
+            let python = venv_python(plugin_dir)
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "python3".to_string());
+            Ok((python, vec![entry_str]))
+        }
+        Runtime::Deno => {
+            let mut args = deno_flags(manifest, plugin_dir);
+            args.push(entry_str);
+            Ok(("deno".to_string(), args))
+        }
+        Runtime::Bun => Ok(("bun".to_string(), vec!["run".to_string(), entry_str])),
+        Runtime::Binary => {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if let Ok(meta) = std::fs::metadata(&entry_path) {
+                    let mode = meta.permissions().mode();
+                    if mode & 0o111 == 0 {
+                        return Err(format!(
+                            "Binary entry '{}' is not executable",
+                            manifest.entry
+                        ));
+                    }
+                }
+            }
+            Ok((entry_str, vec![]))
+        }
+        Runtime::Wasm => Err("WASM plugins run in-process, not as a command".to_string()),
+    }
+}
+
+/// Runtime-specific variables added after `env_filter`: the virtualenv for
+/// Python, a cache inside the plugin's temp dir for Deno.
+pub fn add_runtime_env(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    env: &mut HashMap<String, String>,
+) {
+    match manifest.runtime {
+        Runtime::Python if venv_python(plugin_dir).is_some() => {
+            let venv = plugin_dir.join(PYTHON_VENV_DIR);
+            let bin = venv.join(if cfg!(windows) { "Scripts" } else { "bin" });
+            let mut path = bin.to_string_lossy().into_owned();
+            if let Some(existing) = env.get("PATH").filter(|p| !p.is_empty()) {
+                let separator = if cfg!(windows) { ';' } else { ':' };
+                path = format!("{}{}{}", path, separator, existing);
+            }
+            env.insert("PATH".to_string(), path);
+            env.insert(
+                "VIRTUAL_ENV".to_string(),
+                venv.to_string_lossy().into_owned(),
+            );
+        }
+        Runtime::Deno => {
+            env.insert(
+                "DENO_DIR".to_string(),
+                plugin_temp_dir(&manifest.id)
+                    .join("deno")
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            env.insert("DENO_NO_UPDATE_CHECK".to_string(), "1".to_string());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::manifest::{FsPerm, Permissions, ShellPerm};
+    use std::fs;
+
+    fn manifest(runtime: Runtime, permissions: Permissions) -> PluginManifest {
+        PluginManifest {
+            id: "com.test.runtime".to_string(),
+            name: "Test".to_string(),
+            version: "1.0.0".to_string(),
+            runtime,
+            entry: "main.ts".to_string(),
+            permissions,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn deno_flags_follow_permissions() {
+        let perms = Permissions {
+            network: Some(vec!["api.github.com".to_string()]),
+            filesystem: Some(vec![
+                FsPerm {
+                    path: "/data/in".to_string(),
+                    access: "read".to_string(),
+                },
+                FsPerm {
+                    path: "/data/out".to_string(),
+                    access: "write".to_string(),
+                },
+            ]),
+            environment: Some(vec!["GITHUB_TOKEN".to_string()]),
+            shell: Some(ShellPerm {
+                commands: vec!["git".to_string()],
+            }),
+            ..Default::default()
+        };
+        let flags = deno_flags(&manifest(Runtime::Deno, perms), Path::new("/plugins/p"));
+        let tmp = plugin_temp_dir("com.test.runtime");
+        let tmp = tmp.display();
+        assert_eq!(&flags[..3], ["run", "--no-prompt", "--cached-only"]);
+        assert!(flags.contains(&format!(
+            "--allow-read=/plugins/p,{},/data/in,/data/out",
+            tmp
+        )));
+        assert!(flags.contains(&format!("--allow-write={},/data/out", tmp)));
+        assert!(flags.contains(&"--allow-net=api.github.com".to_string()));
+        assert!(flags.contains(&"--allow-env=OMNI_GLASS_PLUGIN_ID,TMPDIR,GITHUB_TOKEN".to_string()));
+        assert!(flags.contains(&"--allow-run=git".to_string()));
+    }
+
+    #[test]
+    fn deno_without_permissions_gets_no_net_or_run() {
+        let flags = deno_flags(
+            &manifest(Runtime::Deno, Permissions::default()),
+            Path::new("/plugins/p"),
+        );
+        assert!(!flags.iter().any(|f| f.starts_with("--allow-net")));
+        assert!(!flags.iter().any(|f| f.starts_with("--allow-run")));
+        assert!(!flags.iter().any(|f| f == "--allow-all" || f == "-A"));
+    }
+
+    #[test]
+    fn python_prefers_the_plugin_venv() {
+        let dir = std::env::temp_dir().join("og-test-runtime-venv");
+        let _ = fs::remove_dir_all(&dir);
+        let m = manifest(Runtime::Python, Permissions::default());
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(resolve_command(&m, &dir).unwrap().0, "python3");
+
+        let python = if cfg!(windows) {
+            "Scripts/python.exe"
+        } else {
+            "bin/python"
+        };
+        let venv_python = dir.join(PYTHON_VENV_DIR).join(python);
+        fs::create_dir_all(venv_python.parent().unwrap()).unwrap();
+        fs::write(&venv_python, "").unwrap();
+        let (command, _) = resolve_command(&m, &dir).unwrap();
+        assert_eq!(command, venv_python.to_string_lossy());
+
+        let mut env = HashMap::new();
+        add_runtime_env(&m, &dir, &mut env);
+        assert!(env["VIRTUAL_ENV"].ends_with(PYTHON_VENV_DIR));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn bun_and_wasm_commands() {
+        let (command, args) = resolve_command(
+            &manifest(Runtime::Bun, Permissions::default()),
+            Path::new("/p"),
+        )
+        .unwrap();
+        assert_eq!(command, "bun");
+        assert_eq!(args[0], "run");
+        assert!(resolve_command(
+            &manifest(Runtime::Wasm, Permissions::default()),
+            Path::new("/p")
+        )
+        .is_err());
+        assert!(binary_names(&Runtime::Wasm).is_empty());
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::mcp::manifest::Permissions;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Essential vars every runtime needs to function.
 const ESSENTIAL_VARS: &[&str] = &[
@@ -14,6 +15,21 @@ const ESSENTIAL_VARS: &[&str] = &[
     "PYTHONPATH",   // Python module resolution
 ];
 
+/// A plugin's own temp directory under the system temp dir. `TMPDIR`
+/// points here, and the Deno, WASI and sandbox-exec grants cover it.
+pub fn plugin_temp_dir(plugin_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("omni-glass-{}", plugin_id))
+}
+
+/// `plugin_temp_dir`, created, with symlinks resolved: on macOS `/tmp` and
+/// `/var` live under `/private`, and sandbox-exec matches resolved paths.
+pub fn create_plugin_temp_dir(plugin_id: &str) -> Result<PathBuf, String> {
+    let dir = plugin_temp_dir(plugin_id);
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| dir.canonicalize())
+        .map_err(|e| format!("Cannot create {}: {}", dir.display(), e))
+}
+
 /// Filter the process environment for a plugin, passing only safe variables.
 ///
 /// 1. Always includes essential runtime vars (PATH, HOME, etc.)
@@ -39,7 +55,7 @@ pub fn filter_environment(
     // Plugin-specific temp directory (isolates temp files per plugin)
     filtered.insert(
         "TMPDIR".to_string(),
-        format!("/tmp/omni-glass-{}", plugin_id),
+        plugin_temp_dir(plugin_id).to_string_lossy().into_owned(),
     );
 
     // Only pass through env vars the plugin explicitly declared
@@ -93,6 +109,7 @@ mod tests {
     fn overrides_tmpdir() {
         let perms = Permissions::default();
         let filtered = filter_environment(&perms, "com.test.plugin");
-        assert_eq!(filtered["TMPDIR"], "/tmp/omni-glass-com.test.plugin");
+        let expected = std::env::temp_dir().join("omni-glass-com.test.plugin");
+        assert_eq!(filtered["TMPDIR"], expected.to_string_lossy());
     }
 }
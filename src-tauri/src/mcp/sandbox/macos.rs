@@ -12,6 +12,7 @@
 //! = exfiltration path, so user files must be default-deny.
 
 use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::runtime;
 use std::path::{Path, PathBuf};
 
 /// Paths for a runtime binary and its installation prefix.
@@ -30,7 +31,9 @@ pub fn generate_profile(
     let runtime_paths = find_runtime_paths(&manifest.runtime)?;
     let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
     let home_str = home.to_string_lossy();
-    let tmp_dir = format!("/private/tmp/omni-glass-{}", manifest.id);
+    let tmp_dir = super::env_filter::create_plugin_temp_dir(&manifest.id)?
+        .display()
+        .to_string();
     let plugin_dir_str = plugin_dir.to_string_lossy();
 
     let mut profile = String::new();
@@ -85,6 +88,13 @@ pub fn generate_profile(
         ";; Runtime binary\n(allow process-exec (literal \"{}\"))\n\n",
         bin_str
     ));
+    // A plugin virtualenv's python links to the base interpreter above
+    if let Some(venv_python) = runtime::venv_python(plugin_dir) {
+        profile.push_str(&format!(
+            "(allow process-exec (literal \"{}\"))\n\n",
+            venv_python.to_string_lossy()
+        ));
+    }
 
     // ── stdio writes (MCP transport) ──
     profile.push_str(";; stdio writes\n");
@@ -145,9 +155,7 @@ pub fn generate_profile(
         profile.push_str("(allow process-fork)\n");
         profile.push_str("(allow process-exec (literal \"/bin/sh\"))\n");
         profile.push_str("(allow process-exec (literal \"/bin/bash\"))\n");
-        profile.push_str(&format!(
-            "(allow file-write* (subpath \"{}\"))\n", tmp_dir
-        ));
+        profile.push_str(&format!("(allow file-write* (subpath \"{tmp_dir}\"))\n"));
         for cmd in &shell.commands {
             if let Ok(cmd_path) = which::which(cmd) {
                 profile.push_str(&format!(
@@ -173,34 +181,19 @@ pub fn write_profile(plugin_id: &str, profile: &str) -> Result<PathBuf, String>
 /// Find the runtime binary and its installation prefix.
 /// The prefix is the parent of `bin/` — e.g., for
 /// `~/.nvm/versions/node/v24/bin/node`, prefix = `~/.nvm/versions/node/v24`.
+/// Runtimes without an interpreter (binary entries) get empty paths.
 pub fn find_runtime_paths(runtime: &Runtime) -> Result<RuntimePaths, String> {
-    match runtime {
-        Runtime::Node => {
-            let binary = which::which("node")
-                .map_err(|_| "Node.js not found in PATH".to_string())?;
-            let prefix = binary.parent()
-                .and_then(|bin| bin.parent())
-                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
-                .to_path_buf();
-            Ok(RuntimePaths { binary, prefix })
-        }
-        Runtime::Python => {
-            let binary = which::which("python3")
-                .or_else(|_| which::which("python"))
-                .map_err(|_| "Python not found in PATH".to_string())?;
-            let prefix = binary.parent()
-                .and_then(|bin| bin.parent())
-                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
-                .to_path_buf();
-            Ok(RuntimePaths { binary, prefix })
-        }
-        Runtime::Binary => {
-            Ok(RuntimePaths {
-                binary: PathBuf::new(),
-                prefix: PathBuf::new(),
-            })
-        }
-    }
+    let Some(binary) = runtime::find_interpreter(runtime)? else {
+        return Ok(RuntimePaths {
+            binary: PathBuf::new(),
+            prefix: PathBuf::new(),
+        });
+    };
+    let prefix = binary.parent()
+        .and_then(|bin| bin.parent())
+        .unwrap_or(binary.parent().unwrap_or(Path::new("")))
+        .to_path_buf();
+    Ok(RuntimePaths { binary, prefix })
 }
 
 #[cfg(test)]
@@ -213,11 +206,9 @@ mod tests {
             id: "com.test.sandbox".to_string(),
             name: "Test".to_string(),
             version: "1.0.0".to_string(),
-            description: String::new(),
-            runtime: Runtime::Node,
             entry: "index.js".to_string(),
             permissions: perms,
-            configuration: None,
+            ..Default::default()
         }
     }
 
@@ -2,11 +2,13 @@
 //!
 //! Submodules:
 //! - env_filter: Environment variable filtering (all platforms)
+//! - process_limits: CPU, memory and file descriptor limits (all platforms)
 //! - macos: sandbox-exec profile generation (macOS only)
 //! - risk: Permission risk level calculation
 //! - windows/linux: stubs with env filtering only
 
 pub mod env_filter;
+pub mod process_limits;
 pub mod risk;
 
 #[cfg(target_os = "macos")]
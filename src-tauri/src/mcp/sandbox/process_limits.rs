@@ -0,0 +1,124 @@
+//! OS resource limits for plugin processes (all platforms).
+//!
+//! - Unix: `setrlimit` in the child between fork and exec, so the
+//!   sandbox-exec wrapper and the runtime inherit them. `RLIMIT_CPU` sends
+//!   SIGXCPU at the limit (SIGKILL a second later). It counts CPU time over
+//!   the process's whole life, not per call, so `cpuSeconds` is a lifetime
+//!   budget: the call that runs it out fails with `CpuTime` and the plugin
+//!   is restarted with a fresh one (`mcp::call_with_arguments`).
+//!   `RLIMIT_DATA` caps the heap, `RLIMIT_NOFILE` caps descriptors. Linux
+//!   enforces all three; macOS does not enforce `RLIMIT_DATA`. Limits never
+//!   exceed the hard limits the app itself runs under.
+//! - Windows: the process is put in a job object with per-process memory
+//!   and user-time limits, right after spawn. There is no handle limit.
+
+use crate::mcp::limits::{LimitViolation, Limits};
+use tokio::process::{Child, Command};
+
+/// Apply limits that must be set before the process starts.
+pub fn limit_command(cmd: &mut Command, limits: &Limits) {
+    #[cfg(unix)]
+    {
+        let memory = limits.memory_mb.saturating_mul(1024 * 1024);
+        let rlimits = [
+            (
+                libc::RLIMIT_CPU,
+                limits.cpu_seconds,
+                limits.cpu_seconds.saturating_add(1),
+            ),
+            (libc::RLIMIT_DATA, memory, memory),
+            (libc::RLIMIT_NOFILE, limits.open_files, limits.open_files),
+        ];
+        // SAFETY: the closure only calls getrlimit/setrlimit, which are
+        // async-signal-safe, and allocates nothing.
+        unsafe {
+            cmd.pre_exec(move || {
+                for (resource, soft, hard) in rlimits {
+                    let mut current = libc::rlimit {
+                        rlim_cur: 0,
+                        rlim_max: 0,
+                    };
+                    if libc::getrlimit(resource, &mut current) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    let hard = (hard as libc::rlim_t).min(current.rlim_max);
+                    let limit = libc::rlimit {
+                        rlim_cur: (soft as libc::rlim_t).min(hard),
+                        rlim_max: hard,
+                    };
+                    if libc::setrlimit(resource, &limit) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (cmd, limits);
+}
+
+/// Apply limits that need the running process.
+pub fn limit_child(child: &Child, limits: &Limits) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        crate::mcp::sandbox::windows::assign_job(child, limits)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (child, limits);
+        Ok(())
+    }
+}
+
+/// The limit that ended a plugin process, if its exit status shows one.
+pub fn exit_violation(
+    status: &std::process::ExitStatus,
+    limits: &Limits,
+) -> Option<LimitViolation> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if status.signal() == Some(libc::SIGXCPU) {
+            return Some(LimitViolation::CpuTime {
+                limit_seconds: limits.cpu_seconds,
+            });
+        }
+    }
+    let _ = (status, limits);
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn child_runs_under_the_limits() {
+        let limits = Limits {
+            open_files: 32,
+            ..Limits::DEFAULT
+        };
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "ulimit -n; ulimit -t"])
+            .stdout(std::process::Stdio::piped());
+        limit_command(&mut cmd, &limits);
+        let output = cmd.output().await.unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        assert_eq!(lines.next(), Some("32"));
+        assert_eq!(lines.next(), Some("300"));
+    }
+
+    #[test]
+    fn sigxcpu_is_a_cpu_violation() {
+        use std::os::unix::process::ExitStatusExt;
+        let killed = std::process::ExitStatus::from_raw(libc::SIGXCPU);
+        assert_eq!(
+            exit_violation(&killed, &Limits::DEFAULT),
+            Some(LimitViolation::CpuTime { limit_seconds: 300 })
+        );
+        let exited = std::process::ExitStatus::from_raw(1 << 8);
+        assert_eq!(exit_violation(&exited, &Limits::DEFAULT), None);
+    }
+}
@@ -19,7 +19,7 @@ pub enum RiskLevel {
 /// Calculate a risk score from the declared permissions.
 ///
 /// Scoring: clipboard=1, network=2, fs-read=2/entry, fs-write=4/entry,
-/// environment=2/var, shell=5. Thresholds: 0-1=Low, 2-4=Medium, 5+=High.
+/// environment=2/var, sampling=2, shell=5. Thresholds: 0-1=Low, 2-4=Medium, 5+=High.
 pub fn calculate_risk(permissions: &Permissions) -> RiskLevel {
     let mut score: u32 = 0;
 
@@ -45,6 +45,10 @@ pub fn calculate_risk(permissions: &Permissions) -> RiskLevel {
         score += env_vars.len() as u32 * 2;
     }
 
+    if permissions.sampling {
+        score += 2;
+    }
+
     if permissions.shell.is_some() {
         score += 5;
     }
@@ -2,8 +2,10 @@
 //!
 //! Full AppContainer implementation is planned for Phase 3.
 //! For now, plugins run with filtered environment variables but
-//! without OS-level process sandboxing.
+//! without OS-level process sandboxing. Resource limits are enforced with
+//! a job object (`assign_job`).
 
+use crate::mcp::limits::Limits;
 use std::collections::HashMap;
 use std::path::Path;
 use tokio::process::Child;
@@ -35,3 +37,42 @@ pub fn spawn_unsandboxed(
         .spawn()
         .map_err(|e| format!("Failed to spawn plugin: {}", e))
 }
+
+/// Put a plugin process in a job object with per-process memory and CPU
+/// time limits. The job lives as long as the process does.
+pub fn assign_job(child: &Child, limits: &Limits) -> Result<(), String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOB_OBJECT_LIMIT_PROCESS_TIME,
+    };
+
+    let Some(process) = child.raw_handle() else {
+        return Err("Plugin process already exited".to_string());
+    };
+    let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+    info.BasicLimitInformation.LimitFlags =
+        JOB_OBJECT_LIMIT_PROCESS_MEMORY | JOB_OBJECT_LIMIT_PROCESS_TIME;
+    // User time is in 100ns units
+    info.BasicLimitInformation.PerProcessUserTimeLimit =
+        (limits.cpu_seconds as i64).saturating_mul(10_000_000);
+    info.ProcessMemoryLimit = (limits.memory_mb as usize).saturating_mul(1024 * 1024);
+
+    // SAFETY: `info` outlives the call and its size is passed alongside;
+    // `process` is the live child's handle.
+    unsafe {
+        let job = CreateJobObjectW(None, PCWSTR::null())
+            .map_err(|e| format!("Failed to create job object: {}", e))?;
+        let assigned = SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &info as *const _ as *const std::ffi::c_void,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        )
+        .and_then(|_| AssignProcessToJobObject(job, HANDLE(process)));
+        let _ = CloseHandle(job);
+        assigned.map_err(|e| format!("Failed to apply job limits: {}", e))
+    }
+}
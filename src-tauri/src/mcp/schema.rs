@@ -0,0 +1,272 @@
+//! JSON Schema validation and config prefill for plugin I/O.
+//!
+//! Covers the keywords MCP tools use in practice: `type` (single or list),
+//! `enum`, `const`, `required`, `properties`, `additionalProperties` (`false`
+//! or a schema for the other values), `items`, string `minLength`/
+//! `maxLength`/`pattern`/`format`, numeric `minimum`/`maximum` (and
+//! exclusive forms), and `minItems`/`maxItems`.
+//! Unknown keywords and formats are ignored, so a schema using more of the
+//! spec is checked leniently rather than rejected. Errors carry a
+//! JSON-pointer-style path so they can be fed back to the LLM for repair.
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Validate `value` against `schema`, returning every violation found.
+///
+/// An empty vector means the value is valid.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", &mut errors);
+    errors
+}
+
+/// Pick saved plugin config values that can fill schema properties.
+///
+/// A config key matches a property by name (`repo`) or with a `default_`
+/// prefix (`default_repo`). Empty strings and values that don't satisfy
+/// the property's own schema are skipped.
+pub fn prefill_values(schema: &Value, config: &HashMap<String, Value>) -> Map<String, Value> {
+    let mut values = Map::new();
+    let Some(props) = schema.get("properties").and_then(Value::as_object) else {
+        return values;
+    };
+    for (name, prop_schema) in props {
+        let candidate = config
+            .get(name)
+            .or_else(|| config.get(&format!("default_{}", name)));
+        let Some(value) = candidate else { continue };
+        if value.as_str() == Some("") || !validate(prop_schema, value).is_empty() {
+            continue;
+        }
+        values.insert(name.clone(), value.clone());
+    }
+    values
+}
+
+/// Insert prefill values for properties missing from `args`.
+///
+/// Values the model produced are kept; prefill only fills gaps.
+pub fn apply_prefill(args: &mut Value, values: &Map<String, Value>) {
+    if let Value::Object(obj) = args {
+        for (name, value) in values {
+            obj.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let at = if path.is_empty() { "/" } else { path };
+
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(expected, value) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                at,
+                expected,
+                type_name(value)
+            ));
+            return;
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed = Value::Array(allowed.clone());
+            errors.push(format!("{}: {} is not one of {}", at, value, allowed));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{}: must equal {}", at, expected));
+        }
+    }
+
+    match value {
+        Value::Object(obj) => check_object(schema, obj, path, errors),
+        Value::Array(items) => check_array(schema, items, path, errors),
+        Value::String(s) => check_string(schema, s, at, errors),
+        Value::Number(_) => check_number(schema, value, at, errors),
+        _ => {}
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    obj: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let at = if path.is_empty() { "/" } else { path };
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !obj.contains_key(key) {
+                errors.push(format!("{}: missing required property '{}'", at, key));
+            }
+        }
+    }
+    let props = schema.get("properties").and_then(Value::as_object);
+    if let Some(props) = props {
+        for (key, sub_schema) in props {
+            if let Some(sub_value) = obj.get(key) {
+                validate_at(sub_schema, sub_value, &format!("{}/{}", path, key), errors);
+            }
+        }
+    }
+    let extras = obj
+        .iter()
+        .filter(|(key, _)| !props.is_some_and(|p| p.contains_key(*key)));
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(false)) => {
+            for (key, _) in extras {
+                errors.push(format!("{}: unexpected property '{}'", at, key));
+            }
+        }
+        Some(extra_schema @ Value::Object(_)) => {
+            for (key, sub_value) in extras {
+                validate_at(
+                    extra_schema,
+                    sub_value,
+                    &format!("{}/{}", path, key),
+                    errors,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_array(schema: &Map<String, Value>, items: &[Value], path: &str, errors: &mut Vec<String>) {
+    let at = if path.is_empty() { "/" } else { path };
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            errors.push(format!("{}: needs at least {} items", at, min));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if items.len() as u64 > max {
+            errors.push(format!("{}: allows at most {} items", at, max));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{}/{}", path, i), errors);
+        }
+    }
+}
+
+fn check_string(schema: &Map<String, Value>, s: &str, at: &str, errors: &mut Vec<String>) {
+    let len = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            errors.push(format!("{}: shorter than {} characters", at, min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            errors.push(format!("{}: longer than {} characters", at, max));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        // An invalid pattern is the schema's fault, not the value's.
+        if Regex::new(pattern).is_ok_and(|re| !re.is_match(s)) {
+            errors.push(format!("{}: does not match pattern {}", at, pattern));
+        }
+    }
+    if let Some(format) = schema.get("format").and_then(Value::as_str) {
+        if !format_matches(format, s) {
+            errors.push(format!("{}: '{}' is not a valid {}", at, s, format));
+        }
+    }
+}
+
+fn check_number(schema: &Map<String, Value>, value: &Value, at: &str, errors: &mut Vec<String>) {
+    let Some(n) = value.as_f64() else { return };
+    let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum").filter(|min| n < *min) {
+        errors.push(format!("{}: must be >= {}", at, min));
+    }
+    if let Some(max) = bound("maximum").filter(|max| n > *max) {
+        errors.push(format!("{}: must be <= {}", at, max));
+    }
+    if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+        errors.push(format!("{}: must be > {}", at, min));
+    }
+    if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+        errors.push(format!("{}: must be < {}", at, max));
+    }
+}
+
+static FORMATS: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    [
+        ("email", r"^[^@\s]+@[^@\s]+\.[^@\s]+$"),
+        ("uri", r"^[A-Za-z][A-Za-z0-9+.-]*:[^\s]+$"),
+        ("date", r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])$"),
+        (
+            "date-time",
+            r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$",
+        ),
+        (
+            "time",
+            r"^\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})?$",
+        ),
+        (
+            "uuid",
+            r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+        ),
+    ]
+    .into_iter()
+    .map(|(name, pattern)| (name, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+fn format_matches(format: &str, s: &str) -> bool {
+    match format {
+        "ipv4" => s.parse::<std::net::Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<std::net::Ipv6Addr>().is_ok(),
+        "uri-reference" | "url" => !s.chars().any(char::is_whitespace),
+        _ => FORMATS
+            .iter()
+            .find(|(name, _)| *name == format)
+            .is_none_or(|(_, re)| re.is_match(s)),
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,159 @@
+//! Server → client requests made while a client request is pending, and
+//! the budget they draw on.
+//!
+//! A plugin may ask for elicitation, sampling or a ping while we wait for
+//! its answer to `tools/call`. None of these may stretch the call without
+//! limit:
+//!
+//! - The call's clock keeps running while we answer, except while an
+//!   elicitation form waits on the user
+//! - Paused time is capped: the call never runs past its budget plus
+//!   `MAX_USER_WAIT_SECS`
+//! - Each call may make at most `MAX_SERVER_REQUESTS` requests, of which at
+//!   most `MAX_SAMPLING_REQUESTS` may be sampling; the rest are refused
+
+use crate::mcp::host::{self, rpc_error, ClientHost, REQUEST_REJECTED};
+use crate::mcp::types::{JsonRpcReply, JsonRpcServerRequest};
+use tokio::time::{Duration, Instant};
+
+/// Longest the user may spend on elicitation forms during one call.
+pub const MAX_USER_WAIT_SECS: u64 = 600;
+/// Server requests answered per call, pings included.
+pub const MAX_SERVER_REQUESTS: usize = 32;
+/// Sampling requests answered per call; each one costs an LLM call.
+pub const MAX_SAMPLING_REQUESTS: usize = 4;
+
+const ELICIT: &str = "elicitation/create";
+const SAMPLE: &str = "sampling/createMessage";
+
+/// Time and request allowance for one client → server request.
+#[derive(Debug)]
+pub struct CallBudget {
+    /// The budget as configured, for reporting a timeout.
+    pub limit_seconds: u64,
+    deadline: Instant,
+    hard_deadline: Instant,
+    requests: usize,
+    samples: usize,
+}
+
+impl CallBudget {
+    /// Start the clock.
+    pub fn new(limit_seconds: u64) -> Self {
+        let deadline = Instant::now() + Duration::from_secs(limit_seconds);
+        Self {
+            limit_seconds,
+            deadline,
+            hard_deadline: deadline + Duration::from_secs(MAX_USER_WAIT_SECS),
+            requests: 0,
+            samples: 0,
+        }
+    }
+
+    /// When the server's answer is due.
+    pub fn deadline(&self) -> Instant {
+        self.deadline.min(self.hard_deadline)
+    }
+
+    /// When answering `method` must be done: a form may use the paused
+    /// allowance, anything else counts against the call.
+    fn answer_deadline(&self, method: &str) -> Instant {
+        if method == ELICIT {
+            self.hard_deadline
+        } else {
+            self.deadline()
+        }
+    }
+
+    /// Count a request; false if it is over the per-call cap.
+    fn admit(&mut self, method: &str) -> bool {
+        self.requests += 1;
+        if method == SAMPLE {
+            self.samples += 1;
+        }
+        self.requests <= MAX_SERVER_REQUESTS && self.samples <= MAX_SAMPLING_REQUESTS
+    }
+}
+
+/// Answer one server request within `budget`. `None` if the budget ran out
+/// while answering: the call has timed out.
+pub async fn answer(
+    host: Option<&dyn ClientHost>,
+    plugin_id: &str,
+    sampling_allowed: bool,
+    request: JsonRpcServerRequest,
+    budget: &mut CallBudget,
+) -> Option<JsonRpcReply> {
+    let started = Instant::now();
+    let outcome = if budget.admit(&request.method) {
+        let handled = host::handle_request(
+            host,
+            plugin_id,
+            sampling_allowed,
+            &request.method,
+            request.params,
+        );
+        tokio::time::timeout_at(budget.answer_deadline(&request.method), handled)
+            .await
+            .ok()?
+    } else {
+        Err(rpc_error(
+            REQUEST_REJECTED,
+            format!("Too many {} requests during one call", request.method),
+        ))
+    };
+    if request.method == ELICIT {
+        budget.deadline += started.elapsed();
+    }
+    if let Err(e) = &outcome {
+        log::info!(
+            "[MCP] '{}' {} refused: {}",
+            plugin_id,
+            request.method,
+            e.message
+        );
+    }
+    Some(JsonRpcReply::new(request.id, outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(method: &str) -> JsonRpcServerRequest {
+        serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 1, "method": method })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn pings_count_against_the_clock_and_the_cap() {
+        let mut budget = CallBudget::new(5);
+        let deadline = budget.deadline();
+        for _ in 0..MAX_SERVER_REQUESTS {
+            let reply = answer(None, "p", false, request("ping"), &mut budget).await;
+            assert!(reply.unwrap().error.is_none());
+        }
+        assert_eq!(budget.deadline(), deadline);
+        let refused = answer(None, "p", false, request("ping"), &mut budget).await;
+        assert!(refused.unwrap().error.unwrap().message.contains("Too many"));
+    }
+
+    #[test]
+    fn sampling_has_its_own_cap() {
+        let mut budget = CallBudget::new(5);
+        for _ in 0..MAX_SAMPLING_REQUESTS {
+            assert!(budget.admit(SAMPLE));
+        }
+        assert!(!budget.admit(SAMPLE));
+        assert!(budget.admit("ping"));
+    }
+
+    #[test]
+    fn paused_time_is_capped() {
+        let mut budget = CallBudget::new(5);
+        let hard = budget.hard_deadline;
+        budget.deadline += Duration::from_secs(MAX_USER_WAIT_SECS * 2);
+        assert_eq!(budget.deadline(), hard);
+        assert_eq!(budget.answer_deadline(ELICIT), hard);
+    }
+}
@@ -0,0 +1,241 @@
+//! Plugin signatures — who published a plugin.
+//!
+//! A publisher signs a plugin with an Ed25519 key and ships the result next
+//! to the manifest as `omni-glass.plugin.sig`:
+//!
+//! ```json
+//! { "publisher": "Acme", "publicKey": "<base64, 32 bytes>", "signature": "<base64, 64 bytes>" }
+//! ```
+//!
+//! The signed message is four lines, each ending in `\n`: the text
+//! `omni-glass-plugin-v1`, the SHA-256 hex of the manifest file, the entry
+//! path as written in the manifest, and the SHA-256 hex of the entry file.
+//! The manifest carries the permissions and any `integrity` digests, so
+//! those are covered too.
+//!
+//! A valid signature only means "signed by this key". It counts as a
+//! verified publisher once the key is in the trust store (`trust`).
+
+use crate::mcp::integrity::sha256_file;
+use crate::mcp::manifest::{PluginManifest, MANIFEST_FILENAME};
+use crate::mcp::trust::{TrustStore, TrustedPublisher};
+use base64::Engine;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// The detached signature file expected next to the manifest.
+pub const SIGNATURE_FILENAME: &str = "omni-glass.plugin.sig";
+
+/// First line of the signed message; bump if the format changes.
+const PAYLOAD_VERSION: &str = "omni-glass-plugin-v1";
+
+/// Contents of `omni-glass.plugin.sig`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSignature {
+    /// Name the publisher claims; only shown as verified for trusted keys.
+    pub publisher: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Who a plugin comes from, as shown in the permission prompt.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum Publisher {
+    /// Valid signature from a key in the trust store.
+    Verified { name: String, fingerprint: String },
+    /// Valid signature from a key the user hasn't trusted.
+    Untrusted { name: String, fingerprint: String },
+    /// No signature file.
+    Unsigned,
+}
+
+/// Short, stable id for a public key: the first 16 hex chars of its SHA-256.
+pub fn fingerprint(public_key: &[u8]) -> String {
+    Sha256::digest(public_key)
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// `sha256:<hex>` of the entry file; approvals pin it to detect code changes.
+pub fn entry_hash(manifest: &PluginManifest, dir: &Path) -> Result<String, String> {
+    sha256_file(&dir.join(&manifest.entry)).map(|h| format!("sha256:{}", h))
+}
+
+/// The exact bytes a publisher signs.
+pub fn signing_payload(manifest: &PluginManifest, dir: &Path) -> Result<String, String> {
+    Ok(format!(
+        "{}\n{}\n{}\n{}\n",
+        PAYLOAD_VERSION,
+        sha256_file(&dir.join(MANIFEST_FILENAME))?,
+        manifest.entry,
+        sha256_file(&dir.join(&manifest.entry))?
+    ))
+}
+
+/// Decode base64 and check the length.
+fn decode(field: &str, value: &str, len: usize) -> Result<Vec<u8>, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|_| format!("Signature {} is not valid base64", field))?;
+    if bytes.len() != len {
+        return Err(format!("Signature {} must be {} bytes", field, len));
+    }
+    Ok(bytes)
+}
+
+/// The trust store entry for `public_key`. The fingerprint is only a
+/// lookup key: a truncated hash can collide, so the stored key must match
+/// byte for byte.
+fn trusted_publisher<'a>(
+    trust: &'a TrustStore,
+    fingerprint: &str,
+    public_key: &[u8],
+) -> Option<&'a TrustedPublisher> {
+    trust.publisher(fingerprint).filter(|trusted| {
+        decode("publicKey", &trusted.public_key, 32).ok().as_deref() == Some(public_key)
+    })
+}
+
+/// Check a plugin's signature and look its key up in the trust store.
+///
+/// A missing signature is `Unsigned`. A signature that is present but
+/// malformed or doesn't match the files is an error: the plugin was
+/// modified after signing.
+pub fn verify(
+    manifest: &PluginManifest,
+    dir: &Path,
+    trust: &TrustStore,
+) -> Result<Publisher, String> {
+    let path = dir.join(SIGNATURE_FILENAME);
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Publisher::Unsigned),
+        Err(e) => return Err(format!("Cannot read {}: {}", SIGNATURE_FILENAME, e)),
+    };
+    let sig: PluginSignature =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid {}: {}", SIGNATURE_FILENAME, e))?;
+    let public_key = decode("publicKey", &sig.public_key, 32)?;
+    let signature = decode("signature", &sig.signature, 64)?;
+    let payload = signing_payload(manifest, dir)?;
+
+    UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(payload.as_bytes(), &signature)
+        .map_err(|_| {
+            format!(
+                "Signature of '{}' does not match its files — it was changed after signing",
+                manifest.id
+            )
+        })?;
+
+    let fingerprint = fingerprint(&public_key);
+    Ok(match trusted_publisher(trust, &fingerprint, &public_key) {
+        Some(trusted) => Publisher::Verified {
+            name: trusted.name.clone(),
+            fingerprint,
+        },
+        None => Publisher::Untrusted {
+            name: sig.publisher,
+            fingerprint,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    /// Write a plugin and sign it with a fresh key; returns (dir, public key).
+    fn signed_plugin(name: &str) -> (std::path::PathBuf, PluginManifest, Vec<u8>) {
+        let dir = std::env::temp_dir().join(format!("og-signing-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.js"), "console.log(1)\n").unwrap();
+        std::fs::write(
+            dir.join(MANIFEST_FILENAME),
+            r#"{"id":"com.test.signed","name":"Signed","version":"1.0.0","runtime":"node","entry":"index.js"}"#,
+        )
+        .unwrap();
+        let manifest = crate::mcp::manifest::load_manifest(&dir).unwrap();
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let payload = signing_payload(&manifest, &dir).unwrap();
+        let sig = PluginSignature {
+            publisher: "Acme".to_string(),
+            public_key: b64(key.public_key().as_ref()),
+            signature: b64(key.sign(payload.as_bytes()).as_ref()),
+        };
+        std::fs::write(
+            dir.join(SIGNATURE_FILENAME),
+            serde_json::to_string(&sig).unwrap(),
+        )
+        .unwrap();
+        (dir, manifest, key.public_key().as_ref().to_vec())
+    }
+
+    #[test]
+    fn trusted_key_is_a_verified_publisher() {
+        let (dir, manifest, public_key) = signed_plugin("trusted");
+        let mut trust = TrustStore::default();
+        let untrusted = verify(&manifest, &dir, &trust).unwrap();
+        assert!(matches!(untrusted, Publisher::Untrusted { ref name, .. } if name == "Acme"));
+
+        trust.publishers.insert(
+            fingerprint(&public_key),
+            TrustedPublisher {
+                name: "Acme Corp".to_string(),
+                public_key: b64(&public_key),
+                added_at: String::new(),
+            },
+        );
+        let verified = verify(&manifest, &dir, &trust).unwrap();
+        assert!(matches!(verified, Publisher::Verified { ref name, .. } if name == "Acme Corp"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn fingerprint_match_with_a_different_key_is_untrusted() {
+        let (dir, manifest, public_key) = signed_plugin("collision");
+        let mut other_key = public_key.clone();
+        other_key[0] ^= 0xff;
+        let mut trust = TrustStore::default();
+        trust.publishers.insert(
+            fingerprint(&public_key),
+            TrustedPublisher {
+                name: "Impostor".to_string(),
+                public_key: b64(&other_key),
+                added_at: String::new(),
+            },
+        );
+        let publisher = verify(&manifest, &dir, &trust).unwrap();
+        assert!(matches!(publisher, Publisher::Untrusted { ref name, .. } if name == "Acme"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn changed_entry_breaks_the_signature() {
+        let (dir, manifest, _) = signed_plugin("tampered");
+        std::fs::write(dir.join("index.js"), "steal()\n").unwrap();
+        let err = verify(&manifest, &dir, &TrustStore::default()).unwrap_err();
+        assert!(err.contains("changed after signing"));
+
+        std::fs::remove_file(dir.join(SIGNATURE_FILENAME)).unwrap();
+        assert_eq!(
+            verify(&manifest, &dir, &TrustStore::default()),
+            Ok(Publisher::Unsigned)
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
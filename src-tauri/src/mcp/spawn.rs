@@ -0,0 +1,146 @@
+//! Starting plugin processes for `McpServer`: plain, under OS resource
+//! limits, or inside the macOS sandbox.
+//!
+//! The child's stdio is piped; stdin/stdout carry MCP and stderr goes to
+//! `plugin_logs`. The child is killed if the server is dropped.
+
+use crate::mcp::client::{McpServer, ServerProcess};
+use crate::mcp::limits::Limits;
+use crate::mcp::plugin_logs;
+use crate::mcp::sandbox::process_limits;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::{Child, Command};
+
+impl McpServer {
+    /// Spawn a child process and prepare stdio pipes.
+    ///
+    /// Does NOT perform the initialize handshake — call `initialize()` after.
+    /// `cwd` sets the working directory (important: sandboxed plugins wall
+    /// off /Users, so CWD must be inside an allowed path).
+    pub fn spawn(
+        plugin_id: &str,
+        command: &str,
+        args: &[&str],
+        env: HashMap<String, String>,
+        cwd: Option<&Path>,
+    ) -> Result<Self, String> {
+        Self::spawn_inner(plugin_id, command, args, env, cwd, None)
+    }
+
+    /// Spawn like `spawn`, with OS resource limits on the process and the
+    /// client-side limits set.
+    pub fn spawn_limited(
+        plugin_id: &str,
+        command: &str,
+        args: &[&str],
+        env: HashMap<String, String>,
+        cwd: Option<&Path>,
+        limits: &Limits,
+    ) -> Result<Self, String> {
+        Self::spawn_inner(plugin_id, command, args, env, cwd, Some(limits))
+    }
+
+    fn spawn_inner(
+        plugin_id: &str,
+        command: &str,
+        args: &[&str],
+        env: HashMap<String, String>,
+        cwd: Option<&Path>,
+        limits: Option<&Limits>,
+    ) -> Result<Self, String> {
+        let mut cmd = piped_command(command, args, env, cwd);
+        if let Some(limits) = limits {
+            process_limits::limit_command(&mut cmd, limits);
+        }
+
+        let child = cmd.spawn().map_err(|e| {
+            format!(
+                "Failed to spawn MCP server '{}' ({}): {}",
+                plugin_id, command, e
+            )
+        })?;
+        if let Some(limits) = limits {
+            process_limits::limit_child(&child, limits)
+                .map_err(|e| format!("MCP server '{}': {}", plugin_id, e))?;
+        }
+
+        let mut server = Self::from_child(plugin_id, child)?;
+        if let Some(limits) = limits {
+            server.set_limits(*limits);
+        }
+        Ok(server)
+    }
+
+    /// Talk to `child` over its stdio; its stderr goes to `plugin_logs`.
+    fn from_child(plugin_id: &str, mut child: Child) -> Result<Self, String> {
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| format!("No stdin for MCP server '{}'", plugin_id))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| format!("No stdout for MCP server '{}'", plugin_id))?;
+        if let Some(stderr) = child.stderr.take() {
+            plugin_logs::drain_stderr(plugin_id, stderr);
+        }
+        Ok(Self::from_io(
+            plugin_id,
+            ServerProcess::Child(child),
+            Box::new(stdin),
+            Box::new(stdout),
+        ))
+    }
+
+    /// Spawn a child process inside a macOS sandbox-exec sandbox.
+    ///
+    /// Wraps the command in `sandbox-exec -f {profile_path}` so the kernel
+    /// enforces the profile's restrictions. CWD is set to `plugin_dir` because
+    /// the sandbox walls off /Users — Node.js calls getcwd() at startup.
+    #[cfg(target_os = "macos")]
+    pub fn spawn_sandboxed(
+        plugin_id: &str,
+        command: &str,
+        args: &[&str],
+        env: HashMap<String, String>,
+        sandbox_profile_path: &Path,
+        plugin_dir: &Path,
+        limits: &Limits,
+    ) -> Result<Self, String> {
+        let profile_str = sandbox_profile_path
+            .to_str()
+            .ok_or("Invalid sandbox profile path")?;
+        let mut sandbox_args = vec!["-f", profile_str, command];
+        sandbox_args.extend(args);
+        Self::spawn_limited(
+            plugin_id,
+            "sandbox-exec",
+            &sandbox_args,
+            env,
+            Some(plugin_dir),
+            limits,
+        )
+    }
+}
+
+/// `command` with piped stdio, killed on drop.
+fn piped_command(
+    command: &str,
+    args: &[&str],
+    env: HashMap<String, String>,
+    cwd: Option<&Path>,
+) -> Command {
+    let mut cmd = Command::new(command);
+    cmd.args(args)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+    cmd
+}
@@ -0,0 +1,239 @@
+//! Per-tool menu preferences: disabled tools and pinned favorites.
+//!
+//! Preferences live in `tool-prefs.json`:
+//!
+//! ```json
+//! { "disabled": ["run_command", "com.example.gh:close_issue"],
+//!   "pinned": ["com.example.gh:create_issue", "copy_text"] }
+//! ```
+//!
+//! Tools are keyed by the id the action menu uses: the bare name for
+//! built-ins, the qualified name ("plugin_id:tool_name") for plugin tools.
+//!
+//! - A disabled tool is left out of the classify prompt and the text
+//!   launcher, never injected by `ensure_required_actions`, and dropped from
+//!   any menu the classifier returns.
+//! - Pinned tools keep their order in `pinned`. When the classifier suggests
+//!   one, it moves ahead of the unpinned actions.
+
+use crate::llm::types::ActionMenu;
+use crate::mcp::registry::{qualified_name, RegisteredTool};
+use serde::{Deserialize, Serialize};
+
+const PREFS_FILE: &str = "tool-prefs.json";
+
+/// Plugin id of the built-in tools.
+const BUILTIN: &str = "builtin";
+
+/// Disabled and pinned tools.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolPrefs {
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Favorites, first one first.
+    #[serde(default)]
+    pub pinned: Vec<String>,
+}
+
+impl ToolPrefs {
+    /// Whether an action or tool id is enabled. Built-ins may be given
+    /// "builtin:"-qualified.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        let id = normalize(id);
+        !self.disabled.iter().any(|key| key == id)
+    }
+
+    /// Position of an action or tool id in the pinned list.
+    pub fn pin_rank(&self, id: &str) -> Option<usize> {
+        let id = normalize(id);
+        self.pinned.iter().position(|key| key == id)
+    }
+
+    /// Enable or disable a tool.
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        let key = normalize(id);
+        self.disabled.retain(|k| k != key);
+        if !enabled {
+            self.disabled.push(key.to_string());
+        }
+    }
+
+    /// Pin a tool at `position` (clamped to the end), or unpin it with `None`.
+    pub fn set_pinned(&mut self, id: &str, position: Option<usize>) {
+        let key = normalize(id);
+        self.pinned.retain(|k| k != key);
+        if let Some(position) = position {
+            let at = position.min(self.pinned.len());
+            self.pinned.insert(at, key.to_string());
+        }
+    }
+}
+
+/// The preference key for a registered tool.
+pub fn tool_key(tool: &RegisteredTool) -> String {
+    if tool.plugin_id == BUILTIN {
+        tool.name.clone()
+    } else {
+        qualified_name(&tool.plugin_id, &tool.name)
+    }
+}
+
+/// Built-ins may arrive as "builtin:name" (text launcher); keys use the bare name.
+fn normalize(id: &str) -> &str {
+    id.strip_prefix("builtin:").unwrap_or(id)
+}
+
+fn prefs_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(PREFS_FILE))
+}
+
+/// Load tool preferences. Nothing disabled or pinned if there is no file.
+pub fn load_prefs() -> ToolPrefs {
+    prefs_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Save tool preferences.
+pub fn save_prefs(prefs: &ToolPrefs) -> Result<(), String> {
+    let path = prefs_path().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(prefs)
+        .map_err(|e| format!("Failed to serialize tool prefs: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Drop disabled actions, move pinned ones to the front in pin order, and
+/// renumber priorities from 1.
+pub fn apply(mut menu: ActionMenu, prefs: &ToolPrefs) -> ActionMenu {
+    let before = menu.actions.len();
+    menu.actions.retain(|a| prefs.is_enabled(&a.id));
+    if menu.actions.len() != before {
+        log::info!(
+            "[MCP] Dropped {} disabled action(s) from the menu",
+            before - menu.actions.len()
+        );
+    }
+    // Stable sort: unpinned actions keep the classifier's ranking
+    menu.actions
+        .sort_by_key(|a| (prefs.pin_rank(&a.id).unwrap_or(usize::MAX), a.priority));
+    for (i, action) in menu.actions.iter_mut().enumerate() {
+        action.priority = (i + 1).min(u8::MAX as usize) as u8;
+    }
+    menu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::types::Action;
+
+    fn action(id: &str, priority: u8) -> Action {
+        Action {
+            id: id.to_string(),
+            label: id.to_string(),
+            icon: "sparkles".to_string(),
+            priority,
+            description: String::new(),
+            requires_execution: true,
+        }
+    }
+
+    fn menu(actions: Vec<Action>) -> ActionMenu {
+        ActionMenu {
+            content_type: "prose".to_string(),
+            confidence: 0.9,
+            summary: String::new(),
+            detected_language: None,
+            actions,
+        }
+    }
+
+    fn ids(menu: &ActionMenu) -> Vec<(&str, u8)> {
+        menu.actions
+            .iter()
+            .map(|a| (a.id.as_str(), a.priority))
+            .collect()
+    }
+
+    #[test]
+    fn builtins_match_with_or_without_prefix() {
+        let mut prefs = ToolPrefs::default();
+        prefs.set_enabled("builtin:run_command", false);
+        prefs.set_enabled("com.example.gh:close_issue", false);
+        assert_eq!(
+            prefs.disabled,
+            ["run_command", "com.example.gh:close_issue"]
+        );
+        assert!(!prefs.is_enabled("run_command"));
+        assert!(!prefs.is_enabled("builtin:run_command"));
+        assert!(!prefs.is_enabled("com.example.gh:close_issue"));
+        assert!(prefs.is_enabled("com.other:close_issue"));
+        assert!(prefs.is_enabled("copy_text"));
+
+        prefs.set_enabled("run_command", true);
+        assert!(prefs.is_enabled("run_command"));
+    }
+
+    #[test]
+    fn pinning_inserts_moves_and_unpins() {
+        let mut prefs = ToolPrefs::default();
+        prefs.set_pinned("copy_text", Some(0));
+        prefs.set_pinned("com.example.gh:create_issue", Some(0));
+        prefs.set_pinned("explain", Some(99));
+        assert_eq!(
+            prefs.pinned,
+            ["com.example.gh:create_issue", "copy_text", "explain"]
+        );
+        prefs.set_pinned("explain", Some(1));
+        assert_eq!(
+            prefs.pinned,
+            ["com.example.gh:create_issue", "explain", "copy_text"]
+        );
+        prefs.set_pinned("com.example.gh:create_issue", None);
+        assert_eq!(prefs.pinned, ["explain", "copy_text"]);
+        assert_eq!(prefs.pin_rank("copy_text"), Some(1));
+    }
+
+    #[test]
+    fn apply_drops_disabled_and_ranks_pinned_first() {
+        let prefs = ToolPrefs {
+            disabled: vec!["search_web".to_string()],
+            pinned: vec![
+                "com.example.gh:create_issue".to_string(),
+                "copy_text".to_string(),
+            ],
+        };
+        let out = apply(
+            menu(vec![
+                action("explain", 1),
+                action("search_web", 2),
+                action("copy_text", 3),
+                action("translate_text", 4),
+                action("com.example.gh:create_issue", 5),
+            ]),
+            &prefs,
+        );
+        assert_eq!(
+            ids(&out),
+            [
+                ("com.example.gh:create_issue", 1),
+                ("copy_text", 2),
+                ("explain", 3),
+                ("translate_text", 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_file_fields_default_to_empty() {
+        let prefs: ToolPrefs = serde_json::from_str(r#"{ "pinned": ["copy_text"] }"#).unwrap();
+        assert!(prefs.disabled.is_empty());
+        assert_eq!(prefs.pinned, ["copy_text"]);
+    }
+}
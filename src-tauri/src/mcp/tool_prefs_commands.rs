@@ -0,0 +1,93 @@
+//! Tauri commands for the Actions section of Settings.
+//!
+//! Lists every registered tool, built-in and plugin, with whether it is
+//! enabled and where it is pinned, and updates `tool-prefs.json`. Also
+//! reports the relevance filter's counters.
+
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::relevance::{self, RelevanceStats};
+use crate::mcp::tool_prefs::{self, ToolPrefs};
+use serde::Serialize;
+
+/// One tool as shown in Settings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolPrefView {
+    /// Preference key: bare name for built-ins, qualified name for plugins.
+    pub tool_id: String,
+    pub plugin_id: String,
+    pub display_name: String,
+    pub description: String,
+    pub enabled: bool,
+    /// Position in the pinned list, if pinned.
+    pub pinned: Option<usize>,
+}
+
+/// Every registered tool: pinned first in pin order, then built-ins, then
+/// plugin tools by id.
+#[tauri::command]
+pub async fn get_tool_prefs(
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<Vec<ToolPrefView>, String> {
+    let prefs = tool_prefs::load_prefs();
+    let mut views: Vec<ToolPrefView> = registry
+        .all_tools()
+        .await
+        .into_iter()
+        .map(|tool| {
+            let tool_id = tool_prefs::tool_key(&tool);
+            ToolPrefView {
+                enabled: prefs.is_enabled(&tool_id),
+                pinned: prefs.pin_rank(&tool_id),
+                tool_id,
+                plugin_id: tool.plugin_id,
+                display_name: tool.display_name,
+                description: tool.description,
+            }
+        })
+        .collect();
+    views.sort_by(|a, b| {
+        let rank = |v: &ToolPrefView| (v.pinned.unwrap_or(usize::MAX), v.plugin_id != "builtin");
+        rank(a)
+            .cmp(&rank(b))
+            .then_with(|| a.tool_id.cmp(&b.tool_id))
+    });
+    Ok(views)
+}
+
+/// Enable or disable a tool.
+#[tauri::command]
+pub fn set_tool_enabled(tool_id: String, enabled: bool) -> Result<(), String> {
+    update(|prefs| prefs.set_enabled(&tool_id, enabled))?;
+    log::info!(
+        "[MCP] Tool '{}' {}",
+        tool_id,
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+/// Pin a tool at `position` (appended if omitted), or unpin it.
+#[tauri::command]
+pub fn set_tool_pinned(
+    tool_id: String,
+    pinned: bool,
+    position: Option<usize>,
+) -> Result<(), String> {
+    update(|prefs| {
+        let position = pinned.then(|| position.unwrap_or(usize::MAX));
+        prefs.set_pinned(&tool_id, position)
+    })
+}
+
+/// Relevance filter counters since they were first recorded.
+#[tauri::command]
+pub fn get_relevance_stats() -> RelevanceStats {
+    relevance::stats()
+}
+
+fn update(change: impl FnOnce(&mut ToolPrefs)) -> Result<(), String> {
+    let mut prefs = tool_prefs::load_prefs();
+    change(&mut prefs);
+    tool_prefs::save_prefs(&prefs)
+}
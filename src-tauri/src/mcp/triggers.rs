@@ -0,0 +1,299 @@
+//! Content triggers — plugin tools offered from local pattern matches.
+//!
+//! Some tools are obviously relevant from the text alone: a JIRA key like
+//! `ABC-123`, a GitHub URL, a tracking number. A manifest declares them
+//! under `triggers`:
+//!
+//! ```json
+//! "triggers": [
+//!   { "tool": "open_issue", "pattern": "\\b[A-Z][A-Z0-9]+-\\d+\\b", "label": "Open {match}" },
+//!   { "tool": "summarize_page", "contentTypes": ["url"] }
+//! ]
+//! ```
+//!
+//! A trigger fires when its `pattern` matches the OCR text and one of its
+//! `contentTypes` was guessed for the snip (see
+//! `relevance::guess_content_types`); a trigger may give either or both.
+//! `ToolRegistry::triggered_actions` evaluates them before the action menu
+//! opens, so the skeleton shows the tools at once, and `merge` (through
+//! `ToolRegistry::merge_triggered`) adds them to whatever menu the
+//! classifier returns — including `ActionMenu::fallback()`
+//! when no provider is available. Disabled tools never fire.
+//!
+//! Patterns use the `regex` crate, which runs in time linear in the text,
+//! so a manifest cannot stall the pipeline with a pathological pattern.
+
+use crate::llm::types::{Action, ActionMenu};
+use crate::mcp::registry::{qualified_name, RegisteredTool};
+use crate::mcp::tool_prefs::{self, ToolPrefs};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Compiled size limit for one pattern.
+const PATTERN_SIZE_LIMIT: usize = 256 * 1024;
+
+/// Longest matched text shown in a label.
+const MAX_MATCH_CHARS: usize = 40;
+
+/// One trigger as declared in the manifest.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentTrigger {
+    /// Tool name, as the plugin lists it.
+    pub tool: String,
+    /// Regular expression the OCR text must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Content types, one of which the snip must look like.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+    /// Menu label; `{match}` is replaced by the matched text. Defaults to
+    /// the tool's display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// A trigger ready to evaluate.
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    tool: String,
+    regex: Option<Regex>,
+    content_types: Vec<String>,
+    label: Option<String>,
+}
+
+/// Compile a pattern the way triggers run it.
+pub fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Compile a plugin's triggers. Invalid ones are logged and skipped
+/// (`manifest_check` has already reported them).
+pub fn compile(plugin_id: &str, triggers: &[ContentTrigger]) -> Vec<Trigger> {
+    triggers
+        .iter()
+        .filter_map(|t| {
+            let regex = match t.pattern.as_deref().map(compile_pattern).transpose() {
+                Ok(regex) => regex,
+                Err(e) => {
+                    log::warn!(
+                        "[MCP] Skipping trigger for '{}:{}': {}",
+                        plugin_id,
+                        t.tool,
+                        e
+                    );
+                    return None;
+                }
+            };
+            if regex.is_none() && t.content_types.is_empty() {
+                return None;
+            }
+            Some(Trigger {
+                tool: t.tool.clone(),
+                regex,
+                content_types: t.content_types.clone(),
+                label: t.label.clone(),
+            })
+        })
+        .collect()
+}
+
+impl Trigger {
+    /// The tool this trigger offers, by name within its plugin.
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    /// Whether the trigger fires for `text`: `Some` with the matched text
+    /// (empty for a content-type-only trigger), `None` if it doesn't.
+    pub fn fire<'t>(&self, text: &'t str, content_types: &[&str]) -> Option<&'t str> {
+        if !self.content_types.is_empty()
+            && !self
+                .content_types
+                .iter()
+                .any(|t| content_types.contains(&t.as_str()))
+        {
+            return None;
+        }
+        match &self.regex {
+            Some(regex) => regex.find(text).map(|m| m.as_str()),
+            None => Some(""),
+        }
+    }
+
+    /// The menu action for a fired trigger.
+    pub fn action(&self, plugin_id: &str, tool: &RegisteredTool, matched: &str) -> Action {
+        let matched = truncate(matched.trim(), MAX_MATCH_CHARS);
+        let label = match &self.label {
+            Some(label) => label.replace("{match}", &matched),
+            None => tool.display_name.clone(),
+        };
+        let description = if matched.is_empty() {
+            tool.description.clone()
+        } else {
+            format!("{} — matched {}", tool.description, matched)
+        };
+        Action {
+            id: qualified_name(plugin_id, &self.tool),
+            label,
+            icon: "sparkles".to_string(),
+            priority: 0,
+            description,
+            requires_execution: true,
+        }
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &text[..i]),
+        None => text.to_string(),
+    }
+}
+
+/// Add triggered actions the menu doesn't already have, ahead of the
+/// classifier's, then apply the user's tool preferences.
+///
+/// Actions are compared by `key`, the `tool_prefs::tool_key` of the tool an
+/// id names, so a bare tool name from the classifier matches the qualified
+/// id a trigger carries. `ToolRegistry::merge_triggered` supplies it.
+pub fn merge(
+    mut menu: ActionMenu,
+    triggered: &[Action],
+    prefs: &ToolPrefs,
+    key: impl Fn(&str) -> String,
+) -> ActionMenu {
+    let mut keys: HashSet<String> = menu.actions.iter().map(|a| key(&a.id)).collect();
+    let mut added = 0;
+    for action in triggered {
+        if !keys.insert(key(&action.id)) {
+            continue;
+        }
+        menu.actions.push(action.clone());
+        added += 1;
+    }
+    if added > 0 {
+        log::info!("[MCP] Added {} triggered action(s) to the menu", added);
+    }
+    tool_prefs::apply(menu, prefs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(json: &str) -> Trigger {
+        let declared: ContentTrigger = serde_json::from_str(json).unwrap();
+        compile("com.example.jira", &[declared]).remove(0)
+    }
+
+    fn tool(name: &str) -> RegisteredTool {
+        RegisteredTool {
+            plugin_id: "com.example.jira".to_string(),
+            name: name.to_string(),
+            display_name: "Open Issue".to_string(),
+            description: "Open a JIRA issue".to_string(),
+            input_schema: None,
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn pattern_and_content_types_must_both_hold() {
+        let t = trigger(
+            r#"{"tool":"open_issue","pattern":"\\b[A-Z][A-Z0-9]+-\\d+\\b","contentTypes":["error","prose"]}"#,
+        );
+        assert_eq!(
+            t.fire("see PROJ-123 for details", &["prose"]),
+            Some("PROJ-123")
+        );
+        assert_eq!(t.fire("see PROJ-123 for details", &["table"]), None);
+        assert_eq!(t.fire("no key here", &["prose"]), None);
+
+        let by_type = trigger(r#"{"tool":"summarize_page","contentTypes":["url"]}"#);
+        assert_eq!(by_type.fire("https://example.com", &["url"]), Some(""));
+        assert_eq!(by_type.fire("plain words", &["prose"]), None);
+    }
+
+    #[test]
+    fn invalid_and_empty_triggers_are_skipped() {
+        let declared: Vec<ContentTrigger> = serde_json::from_str(
+            r#"[{"tool":"a","pattern":"("},{"tool":"b"},{"tool":"c","pattern":"x"}]"#,
+        )
+        .unwrap();
+        let compiled = compile("com.example.jira", &declared);
+        assert_eq!(compiled.len(), 1);
+        assert_eq!(compiled[0].tool(), "c");
+    }
+
+    #[test]
+    fn action_fills_label_and_description_from_match() {
+        let t = trigger(r#"{"tool":"open_issue","pattern":"[A-Z]+-\\d+","label":"Open {match}"}"#);
+        let action = t.action("com.example.jira", &tool("open_issue"), "PROJ-7");
+        assert_eq!(action.id, "com.example.jira:open_issue");
+        assert_eq!(action.label, "Open PROJ-7");
+        assert_eq!(action.description, "Open a JIRA issue — matched PROJ-7");
+
+        let plain = trigger(r#"{"tool":"open_issue","pattern":"[A-Z]+-\\d+"}"#);
+        let long = "A".repeat(60);
+        let action = plain.action("com.example.jira", &tool("open_issue"), &long);
+        assert_eq!(action.label, "Open Issue");
+        assert!(action.description.contains(&format!("{}…", "A".repeat(40))));
+    }
+
+    #[test]
+    fn merge_puts_new_triggered_actions_first_and_respects_prefs() {
+        let t = trigger(r#"{"tool":"open_issue","pattern":"[A-Z]+-\\d+"}"#);
+        let triggered = vec![t.action("com.example.jira", &tool("open_issue"), "PROJ-7")];
+
+        let same = |id: &str| id.to_string();
+        let menu = merge(
+            ActionMenu::fallback(),
+            &triggered,
+            &ToolPrefs::default(),
+            same,
+        );
+        let ids: Vec<&str> = menu.actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "com.example.jira:open_issue",
+                "copy_text",
+                "explain",
+                "search_web"
+            ]
+        );
+        assert_eq!(menu.actions[0].priority, 1);
+
+        // Already in the menu: not added twice
+        let again = merge(menu, &triggered, &ToolPrefs::default(), same);
+        assert_eq!(again.actions.len(), 4);
+
+        let prefs = ToolPrefs {
+            disabled: vec!["com.example.jira:open_issue".to_string()],
+            pinned: Vec::new(),
+        };
+        let menu = merge(ActionMenu::fallback(), &triggered, &prefs, same);
+        assert_eq!(menu.actions.len(), 3);
+    }
+
+    #[test]
+    fn merge_matches_a_bare_tool_name_to_the_qualified_trigger() {
+        let t = trigger(r#"{"tool":"open_issue","pattern":"[A-Z]+-\\d+"}"#);
+        let triggered = vec![t.action("com.example.jira", &tool("open_issue"), "PROJ-7")];
+        let mut classified = ActionMenu::fallback();
+        classified.actions[0].id = "open_issue".to_string();
+
+        let key = |id: &str| match id {
+            "open_issue" => tool_prefs::tool_key(&tool("open_issue")),
+            other => other.to_string(),
+        };
+        let menu = merge(classified, &triggered, &ToolPrefs::default(), key);
+        let ids: Vec<&str> = menu.actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["open_issue", "explain", "search_web"]);
+    }
+}
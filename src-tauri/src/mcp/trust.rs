@@ -0,0 +1,142 @@
+//! Publisher trust store and signing policy.
+//!
+//! Stored at `~/.config/omni-glass/trusted-publishers.json`:
+//!
+//! ```json
+//! { "requireSigned": false,
+//!   "publishers": { "<fingerprint>": { "name": "Acme", "publicKey": "<base64>", "addedAt": "unix:…" } } }
+//! ```
+//!
+//! With `requireSigned` on, only plugins signed by a trusted key are loaded
+//! or installed. A signature from an unknown key proves nothing about who
+//! wrote the plugin, so it counts as unsigned for the policy.
+
+use crate::mcp::signing::{fingerprint, Publisher};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const TRUST_FILE: &str = "trusted-publishers.json";
+
+/// A publisher key the user trusts.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedPublisher {
+    pub name: String,
+    /// Base64 Ed25519 public key (32 bytes).
+    pub public_key: String,
+    pub added_at: String,
+}
+
+/// Trusted publisher keys, keyed by fingerprint, plus the signing policy.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustStore {
+    /// Refuse plugins that aren't signed by a trusted publisher.
+    #[serde(default)]
+    pub require_signed: bool,
+    #[serde(default)]
+    pub publishers: HashMap<String, TrustedPublisher>,
+}
+
+fn trust_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(TRUST_FILE))
+}
+
+/// Load the trust store. Returns an empty store if the file doesn't exist.
+pub fn load_trust() -> TrustStore {
+    trust_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Save the trust store to disk.
+pub fn save_trust(store: &TrustStore) -> Result<(), String> {
+    let path = trust_path().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize trust store: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+impl TrustStore {
+    /// The trusted publisher with this key fingerprint.
+    pub fn publisher(&self, fingerprint: &str) -> Option<&TrustedPublisher> {
+        self.publishers.get(fingerprint)
+    }
+
+    /// Trust a base64 Ed25519 public key. Returns its fingerprint.
+    pub fn add(&mut self, name: &str, public_key: &str) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Publisher name must not be empty".to_string());
+        }
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(public_key.trim())
+            .map_err(|_| "Public key is not valid base64".to_string())?;
+        if key.len() != 32 {
+            return Err("Public key must be a 32-byte Ed25519 key".to_string());
+        }
+        let fp = fingerprint(&key);
+        self.publishers.insert(
+            fp.clone(),
+            TrustedPublisher {
+                name: name.to_string(),
+                public_key: public_key.trim().to_string(),
+                added_at: crate::mcp::approval::chrono_now(),
+            },
+        );
+        Ok(fp)
+    }
+
+    /// Apply the signing policy to a plugin's publisher.
+    pub fn check_policy(&self, plugin_id: &str, publisher: &Publisher) -> Result<(), String> {
+        if self.require_signed && !matches!(publisher, Publisher::Verified { .. }) {
+            return Err(format!(
+                "'{}' is not signed by a trusted publisher, and unsigned plugins are not allowed",
+                plugin_id
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=";
+
+    #[test]
+    fn add_checks_the_key() {
+        let mut store = TrustStore::default();
+        let fp = store.add(" Acme ", KEY).unwrap();
+        assert_eq!(fp.len(), 16);
+        assert_eq!(store.publisher(&fp).unwrap().name, "Acme");
+        assert!(store.add("Acme", "not base64!").is_err());
+        assert!(store.add("Acme", "AAAA").is_err());
+        assert!(store.add("", KEY).is_err());
+    }
+
+    #[test]
+    fn policy_only_accepts_trusted_signatures_when_required() {
+        let verified = Publisher::Verified {
+            name: "Acme".to_string(),
+            fingerprint: "ab".to_string(),
+        };
+        let untrusted = Publisher::Untrusted {
+            name: "Acme".to_string(),
+            fingerprint: "ab".to_string(),
+        };
+        let mut store = TrustStore::default();
+        assert!(store.check_policy("p", &Publisher::Unsigned).is_ok());
+        store.require_signed = true;
+        assert!(store.check_policy("p", &verified).is_ok());
+        assert!(store.check_policy("p", &untrusted).is_err());
+        assert!(store.check_policy("p", &Publisher::Unsigned).is_err());
+    }
+}
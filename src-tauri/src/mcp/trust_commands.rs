@@ -0,0 +1,51 @@
+//! Tauri commands for the trusted publishers section of Settings.
+
+use crate::mcp::trust::{self, TrustStore};
+
+/// The trust store: policy and trusted publisher keys by fingerprint.
+#[tauri::command]
+pub fn get_trust_store() -> TrustStore {
+    trust::load_trust()
+}
+
+/// Trust a publisher's base64 Ed25519 public key. Returns its fingerprint.
+#[tauri::command]
+pub fn trust_publisher(name: String, public_key: String) -> Result<String, String> {
+    let mut store = trust::load_trust();
+    let fingerprint = store.add(&name, &public_key)?;
+    trust::save_trust(&store)?;
+    log::info!(
+        "[TRUST] Trusted publisher '{}' ({})",
+        name.trim(),
+        fingerprint
+    );
+    Ok(fingerprint)
+}
+
+/// Stop trusting a publisher key.
+#[tauri::command]
+pub fn remove_publisher(fingerprint: String) -> Result<(), String> {
+    let mut store = trust::load_trust();
+    if store.publishers.remove(&fingerprint).is_none() {
+        return Err(format!(
+            "No trusted publisher with fingerprint {}",
+            fingerprint
+        ));
+    }
+    trust::save_trust(&store)?;
+    log::info!("[TRUST] Removed publisher {}", fingerprint);
+    Ok(())
+}
+
+/// Turn the "only signed plugins" policy on or off.
+///
+/// Takes effect for installs and approvals right away, and for plugins that
+/// are already running on the next start.
+#[tauri::command]
+pub fn set_require_signed(required: bool) -> Result<(), String> {
+    let mut store = trust::load_trust();
+    store.require_signed = required;
+    trust::save_trust(&store)?;
+    log::info!("[TRUST] Require signed plugins: {}", required);
+    Ok(())
+}
@@ -6,6 +6,7 @@
 //! - Tool discovery (tools/list)
 //! - Tool execution (tools/call)
 //! - Initialize handshake
+//! - Server → client requests (elicitation, sampling) and our replies
 
 use serde::{Deserialize, Serialize};
 
@@ -60,13 +61,51 @@ pub struct JsonRpcResponse {
 }
 
 /// JSON-RPC error object.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct JsonRpcError {
     pub code: i64,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
 }
 
+/// Incoming JSON-RPC request (server → client), e.g. `elicitation/create`.
+///
+/// The id is echoed back as-is; servers may use numbers or strings.
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcServerRequest {
+    pub id: serde_json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// Outgoing JSON-RPC response to a server request (client → server).
+#[derive(Debug, Serialize)]
+pub struct JsonRpcReply {
+    pub jsonrpc: &'static str,
+    pub id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcReply {
+    pub fn new(id: serde_json::Value, outcome: Result<serde_json::Value, JsonRpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
 impl std::fmt::Display for JsonRpcError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "JSON-RPC error {}: {}", self.code, self.message)
@@ -82,9 +121,17 @@ pub struct ClientInfo {
     pub version: String,
 }
 
-/// Capabilities the client declares (currently empty — we're a basic client).
-#[derive(Debug, Serialize)]
-pub struct ClientCapabilities {}
+/// Capabilities the client declares. Each present field is an empty object.
+///
+/// `elicitation` is declared when a UI can answer prompts; `sampling` only
+/// when the plugin's manifest requests the `sampling` permission.
+#[derive(Debug, Default, Serialize)]
+pub struct ClientCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elicitation: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<serde_json::Value>,
+}
 
 /// Parameters for the `initialize` request.
 #[derive(Debug, Serialize)]
@@ -93,10 +140,14 @@ pub struct InitializeParams {
     pub protocol_version: String,
     pub capabilities: ClientCapabilities,
     pub client_info: ClientInfo,
+    /// The plugin's configuration values (secrets included). Not part of
+    /// the MCP spec; servers that don't read it ignore it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// Server info returned from initialize.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ServerInfo {
     pub name: Option<String>,
     pub version: Option<String>,
@@ -113,13 +164,20 @@ pub struct McpTool {
     pub description: Option<String>,
     #[serde(default)]
     pub input_schema: Option<serde_json::Value>,
+    /// JSON Schema that `structuredContent` in results must satisfy.
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
 }
 
 /// Result from a tools/call invocation.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ToolResult {
+    #[serde(default)]
     pub content: Vec<ToolResultContent>,
+    /// Machine-readable result, validated against the tool's `outputSchema`.
+    #[serde(default)]
+    pub structured_content: Option<serde_json::Value>,
     #[serde(default)]
     pub is_error: bool,
 }
@@ -130,10 +188,43 @@ pub struct ToolResult {
 pub enum ToolResultContent {
     #[serde(rename = "text")]
     Text { text: String },
+    /// Base64-encoded image.
+    #[serde(rename = "image", rename_all = "camelCase")]
+    Image { data: String, mime_type: String },
+    /// Base64-encoded audio clip.
+    #[serde(rename = "audio", rename_all = "camelCase")]
+    Audio { data: String, mime_type: String },
+    /// A resource whose contents are included inline.
+    #[serde(rename = "resource")]
+    Resource { resource: EmbeddedResource },
+    /// A pointer to a resource the user can open.
+    #[serde(rename = "resource_link", rename_all = "camelCase")]
+    ResourceLink {
+        uri: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        mime_type: Option<String>,
+    },
     #[serde(other)]
     Unknown,
 }
 
+/// Contents of an embedded resource — either `text` or base64 `blob`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    pub uri: String,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub blob: Option<String>,
+}
+
 impl ToolResult {
     /// Extract the concatenated text from all text content blocks.
     pub fn text(&self) -> String {
@@ -0,0 +1,299 @@
+//! WASM plugins — WASI components run in-process with wasmtime.
+//!
+//! The component is a `wasi:cli/command` that speaks MCP over stdin/stdout
+//! like any other plugin. There is no OS sandbox: WASI is the boundary, and
+//! the guest can only use what is granted here from the manifest.
+//!
+//! - Files: the plugin directory read-only at `/plugin`, its temp dir at
+//!   `/tmp`, declared `filesystem` paths at their own (expanded) path
+//! - Environment: the variables `env_filter` passed, with `TMPDIR=/tmp`
+//! - Network: sockets only to addresses the declared hosts resolve to when
+//!   the plugin loads; none without a `network` permission
+//! - Processes: WASI cannot spawn any (`shell` is rejected by validation)
+//! - Resources: linear memory is capped at the plugin's `memoryMb`. CPU
+//!   time is metered with epoch interruption: a timer thread ticks the
+//!   shared engine every `EPOCH_TICK`, the guest yields back to tokio on
+//!   every tick, and it traps once it has run for `cpuSeconds` over its
+//!   life (the same lifetime budget `RLIMIT_CPU` gives a process plugin).
+//!   Open files are not limited for in-process plugins
+//! - stderr: drained into `plugin_logs`, like a process plugin's
+//!
+//! Built only with the `wasm-plugins` feature.
+
+use crate::mcp::client::{McpServer, ServerProcess};
+use crate::mcp::limits::{LimitViolation, Limits};
+use crate::mcp::manifest::PluginManifest;
+use crate::mcp::plugin_logs;
+use crate::mcp::runtime::expand_home;
+use crate::mcp::sandbox::env_filter::create_plugin_temp_dir;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::Path;
+use std::sync::{Arc, LazyLock, OnceLock};
+use std::time::Duration;
+use tokio::io::DuplexStream;
+use wasmtime::component::{Component, Linker, ResourceTable};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder, UpdateDeadline};
+use wasmtime_wasi::bindings::Command;
+use wasmtime_wasi::pipe::{AsyncReadStream, AsyncWriteStream};
+use wasmtime_wasi::{
+    AsyncStdinStream, AsyncStdoutStream, DirPerms, FilePerms, IoView, WasiCtx, WasiCtxBuilder,
+    WasiView,
+};
+
+/// Bytes buffered in each direction between the guest and the MCP client.
+const PIPE_CAPACITY: usize = 64 * 1024;
+
+/// How often the engine's epoch advances: the guest's time slice and the
+/// unit its CPU budget is counted in.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// One engine for every WASM plugin, with its epoch ticker.
+static ENGINE: LazyLock<Result<Engine, String>> = LazyLock::new(|| {
+    let mut config = Config::new();
+    config.async_support(true).epoch_interruption(true);
+    let engine = Engine::new(&config).map_err(|e| format!("WASM engine failed: {}", e))?;
+    let ticker = engine.weak();
+    std::thread::Builder::new()
+        .name("wasm-epoch".to_string())
+        .spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK);
+            match ticker.upgrade() {
+                Some(engine) => engine.increment_epoch(),
+                None => break,
+            }
+        })
+        .map_err(|e| format!("WASM epoch thread failed: {}", e))?;
+    Ok(engine)
+});
+
+/// Per-plugin store data.
+struct PluginState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl IoView for PluginState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for PluginState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+/// Compile and start a plugin's component; returns a connected server.
+///
+/// Does NOT perform the initialize handshake, same as `McpServer::spawn`.
+pub async fn spawn(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    env: HashMap<String, String>,
+    limits: &Limits,
+) -> Result<McpServer, String> {
+    let engine = ENGINE.clone()?;
+    let component = compile(&engine, manifest, plugin_dir).await?;
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::add_to_linker_async(&mut linker)
+        .map_err(|e| format!("WASI linking failed: {}", e))?;
+
+    let hosts = manifest.permissions.network.clone().unwrap_or_default();
+    let allowed = tokio::task::spawn_blocking(move || resolve_hosts(&hosts))
+        .await
+        .map_err(|e| format!("Host lookup failed: {}", e))?;
+
+    let mut builder = capabilities(manifest, plugin_dir, &env, allowed)?;
+    let (client_in, client_out) = connect_stdio(&mut builder, &manifest.id);
+    let ctx = builder.build();
+    let stopped_by = Arc::new(OnceLock::new());
+    let task = run(
+        &engine,
+        &component,
+        &linker,
+        ctx,
+        manifest,
+        limits,
+        &stopped_by,
+    )
+    .await?;
+    Ok(McpServer::from_io(
+        &manifest.id,
+        ServerProcess::Task(task, stopped_by),
+        Box::new(client_in),
+        Box::new(client_out),
+    ))
+}
+
+/// Give the guest piped stdio; returns the client's ends (its stdin, its
+/// stdout). stderr goes to `plugin_logs`.
+fn connect_stdio(builder: &mut WasiCtxBuilder, plugin_id: &str) -> (DuplexStream, DuplexStream) {
+    let (client_in, guest_in) = tokio::io::duplex(PIPE_CAPACITY);
+    let (guest_out, client_out) = tokio::io::duplex(PIPE_CAPACITY);
+    let (guest_err, host_err) = tokio::io::duplex(PIPE_CAPACITY);
+    plugin_logs::drain_stderr(plugin_id, host_err);
+    builder
+        .stdin(AsyncStdinStream::new(AsyncReadStream::new(guest_in)))
+        .stdout(AsyncStdoutStream::new(AsyncWriteStream::new(
+            PIPE_CAPACITY,
+            guest_out,
+        )))
+        .stderr(AsyncStdoutStream::new(AsyncWriteStream::new(
+            PIPE_CAPACITY,
+            guest_err,
+        )));
+    (client_in, client_out)
+}
+
+/// Compile the entry component. Compiling is CPU-heavy; keep it off the
+/// async workers.
+async fn compile(
+    engine: &Engine,
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+) -> Result<Component, String> {
+    let entry_path = plugin_dir.join(&manifest.entry);
+    let engine = engine.clone();
+    tokio::task::spawn_blocking(move || Component::from_file(&engine, entry_path))
+        .await
+        .map_err(|e| format!("WASM compile task failed: {}", e))?
+        .map_err(|e| format!("Invalid WASM component '{}': {}", manifest.entry, e))
+}
+
+/// Instantiate the component with memory and CPU limits and run it on a
+/// task. `stopped_by` is set if the guest runs out of CPU time.
+async fn run(
+    engine: &Engine,
+    component: &Component,
+    linker: &Linker<PluginState>,
+    ctx: WasiCtx,
+    manifest: &PluginManifest,
+    limits: &Limits,
+    stopped_by: &Arc<OnceLock<LimitViolation>>,
+) -> Result<tokio::task::JoinHandle<()>, String> {
+    let state = PluginState {
+        ctx,
+        table: ResourceTable::new(),
+        limits: StoreLimitsBuilder::new()
+            .memory_size(limits.memory_mb.saturating_mul(1024 * 1024) as usize)
+            .build(),
+    };
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    meter_cpu(&mut store, limits.cpu_seconds, stopped_by.clone());
+    let command = Command::instantiate_async(&mut store, component, linker)
+        .await
+        .map_err(|e| format!("WASM plugin '{}' failed to start: {}", manifest.id, e))?;
+
+    let plugin_id = manifest.id.clone();
+    Ok(tokio::spawn(async move {
+        match command.wasi_cli_run().call_run(&mut store).await {
+            Ok(Ok(())) => log::info!("[MCP] WASM plugin '{}' exited", plugin_id),
+            Ok(Err(())) => log::warn!("[MCP] WASM plugin '{}' exited with an error", plugin_id),
+            Err(e) => log::warn!("[MCP] WASM plugin '{}' trapped: {}", plugin_id, e),
+        }
+    }))
+}
+
+/// Yield to the async runtime on every epoch tick, and trap once the guest
+/// has run for `cpu_seconds` worth of ticks. Idle time (waiting on stdin or
+/// a socket) is not counted: the deadline only fires while guest code runs.
+fn meter_cpu(
+    store: &mut Store<PluginState>,
+    cpu_seconds: u64,
+    stopped_by: Arc<OnceLock<LimitViolation>>,
+) {
+    let budget = cpu_seconds.saturating_mul(1000) / EPOCH_TICK.as_millis() as u64;
+    let mut ticks = 0u64;
+    store.set_epoch_deadline(1);
+    store.epoch_deadline_callback(move |_| {
+        ticks += 1;
+        if ticks > budget {
+            let _ = stopped_by.set(LimitViolation::CpuTime {
+                limit_seconds: cpu_seconds,
+            });
+            return Err(wasmtime::Error::msg("CPU time budget used up"));
+        }
+        Ok(UpdateDeadline::Yield(1))
+    });
+}
+
+/// The WASI context for a manifest, minus stdio.
+fn capabilities(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    env: &HashMap<String, String>,
+    allowed: HashSet<IpAddr>,
+) -> Result<WasiCtxBuilder, String> {
+    let mut builder = WasiCtxBuilder::new();
+    builder.args(&[manifest.entry.as_str()]);
+    for (key, value) in env {
+        builder.env(key, value);
+    }
+    builder.env("TMPDIR", "/tmp");
+
+    builder
+        .preopened_dir(plugin_dir, "/plugin", DirPerms::READ, FilePerms::READ)
+        .map_err(|e| format!("Cannot open plugin directory: {}", e))?;
+    let tmp = create_plugin_temp_dir(&manifest.id)?;
+    builder
+        .preopened_dir(&tmp, "/tmp", DirPerms::all(), FilePerms::all())
+        .map_err(|e| format!("Cannot open {}: {}", tmp.display(), e))?;
+
+    grant_filesystem(&mut builder, manifest);
+
+    if !allowed.is_empty() {
+        let allowed = Arc::new(allowed);
+        builder.allow_ip_name_lookup(true);
+        builder.socket_addr_check(move |addr, _| {
+            let permitted = allowed.contains(&addr.ip());
+            Box::pin(async move { permitted })
+        });
+    }
+    Ok(builder)
+}
+
+/// Preopen each declared `filesystem` path at its own path. Paths that
+/// don't exist are skipped with a warning.
+fn grant_filesystem(builder: &mut WasiCtxBuilder, manifest: &PluginManifest) {
+    for perm in manifest.permissions.filesystem.iter().flatten() {
+        let path = expand_home(&perm.path);
+        let (dir_perms, file_perms) = match perm.access.as_str() {
+            "write" | "read-write" => (DirPerms::all(), FilePerms::all()),
+            _ => (DirPerms::READ, FilePerms::READ),
+        };
+        if let Err(e) = builder.preopened_dir(&path, &path, dir_perms, file_perms) {
+            log::warn!(
+                "[SANDBOX] '{}': declared path {} not available: {}",
+                manifest.id,
+                path,
+                e
+            );
+        }
+    }
+}
+
+/// Addresses the declared hosts resolve to. Unresolvable hosts are skipped.
+fn resolve_hosts(hosts: &[String]) -> HashSet<IpAddr> {
+    hosts
+        .iter()
+        .filter_map(|host| (host.as_str(), 0).to_socket_addrs().ok())
+        .flatten()
+        .map(|addr| addr.ip())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_only_declared_hosts() {
+        let allowed = resolve_hosts(&["localhost".to_string()]);
+        assert!(allowed.iter().all(|ip| ip.is_loopback()));
+        assert!(resolve_hosts(&[]).is_empty());
+    }
+}
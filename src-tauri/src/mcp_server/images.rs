@@ -0,0 +1,171 @@
+//! The image tools: `capture_screen` and `ocr_image`.
+//!
+//! Capture, PNG encoding and OCR block, so both run on blocking tasks.
+//! Recognized text is redacted like text sent to a cloud provider, then
+//! goes back to the host with the injection warning when the scan flags it.
+
+use super::ServerContext;
+use crate::capture;
+use crate::headless;
+use crate::ocr::RecognitionLevel;
+use crate::safety::command_check;
+use crate::safety::injection::{self, InjectionReport};
+use crate::safety::redact::redact_sensitive_data;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::path::Path;
+
+pub async fn capture_screen(ctx: &ServerContext, args: &Value) -> Result<Value, String> {
+    if !ctx.policy.allow_capture {
+        return Err(
+            "Screen capture is off. The user can allow it by starting the server with --allow-capture."
+                .to_string(),
+        );
+    }
+    let window_id = args.get("windowId").and_then(Value::as_u64);
+    let monitor = args.get("monitor").and_then(Value::as_u64).unwrap_or(0) as usize;
+    let with_ocr = args.get("ocr").and_then(Value::as_bool).unwrap_or(true);
+
+    tokio::task::spawn_blocking(move || capture_blocking(window_id, monitor, with_ocr))
+        .await
+        .map_err(|e| format!("Capture task failed: {}", e))?
+}
+
+/// Capture, encode and (optionally) OCR on the calling thread.
+fn capture_blocking(
+    window_id: Option<u64>,
+    monitor: usize,
+    with_ocr: bool,
+) -> Result<Value, String> {
+    let capture = match window_id {
+        Some(id) => capture::capture_window(id as u32).map_err(|e| e.to_string())?,
+        None => capture::capture_all_monitors()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .nth(monitor)
+            .ok_or_else(|| format!("No monitor {}", monitor))?,
+    };
+    let png = headless::encode_png(&capture.image)?;
+    let mut content = vec![json!({
+        "type": "image",
+        "data": base64::engine::general_purpose::STANDARD.encode(png),
+        "mimeType": "image/png",
+    })];
+    let mut structured = json!({ "source": capture.source });
+
+    if with_ocr {
+        let (text, confidence) =
+            headless::ocr_decoded(&capture.image, RecognitionLevel::Fast, &capture.source.name)?;
+        let (text, report) = checked_text(&text);
+        content.push(json!({ "type": "text", "text": untrusted_text(&text, &report) }));
+        structured["ocrText"] = json!(text);
+        structured["ocrConfidence"] = json!(confidence);
+        structured["injection"] = json!(report);
+    }
+    Ok(json!({ "content": content, "structuredContent": structured, "isError": false }))
+}
+
+pub async fn ocr_image(args: &Value) -> Result<Value, String> {
+    let level = if args.get("accurate").and_then(Value::as_bool) == Some(true) {
+        RecognitionLevel::Accurate
+    } else {
+        RecognitionLevel::Fast
+    };
+    let (path, bytes) = image_source(args)?;
+    let (text, confidence) = tokio::task::spawn_blocking(move || match (path, bytes) {
+        (_, Some(bytes)) => headless::ocr_image_bytes(&bytes, level),
+        (Some(path), None) => headless::ocr_image(Path::new(&path), level),
+        (None, None) => unreachable!("image_source returns one of them"),
+    })
+    .await
+    .map_err(|e| format!("OCR task failed: {}", e))??;
+
+    let (text, report) = checked_text(&text);
+    Ok(json!({
+        "content": [{ "type": "text", "text": untrusted_text(&text, &report) }],
+        "structuredContent": { "text": text, "confidence": confidence, "injection": report },
+        "isError": false,
+    }))
+}
+
+/// The image to read: a checked path or decoded base64 data, never both.
+fn image_source(args: &Value) -> Result<(Option<String>, Option<Vec<u8>>), String> {
+    let path = args.get("path").and_then(Value::as_str).map(str::to_string);
+    let data = args.get("data").and_then(Value::as_str);
+    match (path, data) {
+        (Some(path), None) if !command_check::is_path_safe(&path) => Err(format!(
+            "Path not allowed: {} (must be under your home directory)",
+            path
+        )),
+        (Some(path), None) => Ok((Some(path), None)),
+        (None, Some(data)) => base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map(|bytes| (None, Some(bytes)))
+            .map_err(|e| format!("Invalid base64 image data: {}", e)),
+        _ => Err("Give exactly one of path or data".to_string()),
+    }
+}
+
+/// OCR text with secrets and PII redacted, and its injection scan.
+fn checked_text(text: &str) -> (String, InjectionReport) {
+    let cleaned = redact_sensitive_data(text).cleaned_text;
+    let report = injection::scan_for_injection(&cleaned);
+    (cleaned, report)
+}
+
+/// OCR text as shown to the host: prefixed with the injection warning
+/// when the scan flags it.
+fn untrusted_text(text: &str, report: &InjectionReport) -> String {
+    if report.adversarial {
+        format!("{}\n\n{}", injection::INJECTION_WARNING, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::ToolRegistry;
+    use crate::mcp_server::ServerPolicy;
+
+    fn ctx(allow_capture: bool) -> ServerContext {
+        ServerContext {
+            registry: ToolRegistry::new(),
+            policy: ServerPolicy { allow_capture },
+        }
+    }
+
+    #[tokio::test]
+    async fn capture_is_off_by_default() {
+        let err = capture_screen(&ctx(false), &json!({})).await.unwrap_err();
+        assert!(err.contains("--allow-capture"));
+    }
+
+    #[test]
+    fn ocr_text_is_redacted() {
+        let (text, report) = checked_text("SSN: 123-45-6789");
+        assert_eq!(text, "SSN: [REDACTED:ssn]");
+        assert!(!report.adversarial);
+    }
+
+    #[tokio::test]
+    async fn ocr_rejects_unsafe_paths_and_ambiguous_input() {
+        let err = ocr_image(&json!({ "path": "/etc/passwd" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("not allowed"));
+        let err = ocr_image(&json!({ "path": "~/../../etc/passwd" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("not allowed"));
+        assert!(ocr_image(&json!({})).await.is_err());
+        assert!(ocr_image(&json!({ "path": "a.png", "data": "AA==" }))
+            .await
+            .is_err());
+        let err = ocr_image(&json!({ "data": "not base64!" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("base64"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Omni-Glass as an MCP server — lets coding agents and other MCP hosts
+//! use the snip-to-action pipeline.
+//!
+//! Speaks the same NDJSON JSON-RPC 2.0 as the plugin client, over stdio
+//! (`omni-glass-cli mcp`) or a local Unix socket (`--socket <PATH>`).
+//! The tools in `tools.rs` reuse `capture`, `ocr`, `llm` and `ToolRegistry`
+//! through `headless`, and apply the checks the UI applies.
+
+mod images;
+mod schemas;
+mod socket;
+pub mod tools;
+
+pub use socket::serve_socket;
+
+use crate::mcp::line_reader::{read_line_limited, LineRead};
+use crate::mcp::limits::Limits;
+use crate::mcp::types::{JsonRpcError, JsonRpcReply, JsonRpcServerRequest};
+use crate::mcp::ToolRegistry;
+use serde_json::{json, Value};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// MCP protocol version we answer `initialize` with.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Largest request line a host may send; images for `ocr_image` arrive
+/// base64-encoded, so this is the plugin message cap, not the default.
+const MAX_REQUEST_BYTES: usize = Limits::CAP.max_message_bytes as usize;
+
+/// What the connected host is allowed to do beyond the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ServerPolicy {
+    /// Allow `capture_screen`. Off unless the user opts in: a screenshot
+    /// holds whatever is on screen, not just what the host asked about.
+    pub allow_capture: bool,
+}
+
+/// Shared by every connection.
+pub struct ServerContext {
+    /// Built-in actions plus approved plugins, as in the app.
+    pub registry: ToolRegistry,
+    pub policy: ServerPolicy,
+}
+
+/// Answer requests from `reader` on `writer` until the peer disconnects.
+/// A line over `MAX_REQUEST_BYTES` is dropped and answered with an error.
+pub async fn serve<R, W>(ctx: &ServerContext, reader: R, mut writer: W) -> Result<(), String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = read_line_limited(&mut reader, &mut buf, MAX_REQUEST_BYTES)
+            .await
+            .map_err(|e| format!("[MCP-SERVER] read failed: {}", e))?;
+        let reply = match read {
+            LineRead::Eof => return Ok(()),
+            LineRead::TooLong => Some(JsonRpcReply::new(
+                Value::Null,
+                Err(rpc_error(
+                    -32600,
+                    &format!("Request larger than {} bytes", MAX_REQUEST_BYTES),
+                )),
+            )),
+            LineRead::Line => {
+                let line = String::from_utf8_lossy(&buf);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                handle_message(ctx, &line).await
+            }
+        };
+        if let Some(reply) = reply {
+            write_reply(&mut writer, &reply).await?;
+        }
+    }
+}
+
+/// Write one reply as an NDJSON line.
+async fn write_reply<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    reply: &JsonRpcReply,
+) -> Result<(), String> {
+    let mut out = serde_json::to_string(reply)
+        .map_err(|e| format!("[MCP-SERVER] serialize failed: {}", e))?;
+    out.push('\n');
+    writer
+        .write_all(out.as_bytes())
+        .await
+        .map_err(|e| format!("[MCP-SERVER] write failed: {}", e))?;
+    writer
+        .flush()
+        .await
+        .map_err(|e| format!("[MCP-SERVER] flush failed: {}", e))
+}
+
+/// Handle one NDJSON line. Notifications get no reply.
+pub async fn handle_message(ctx: &ServerContext, line: &str) -> Option<JsonRpcReply> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            return Some(JsonRpcReply::new(
+                Value::Null,
+                Err(rpc_error(-32700, &format!("Parse error: {}", e))),
+            ))
+        }
+    };
+    if value.get("id").is_none() {
+        // notifications/initialized, notifications/cancelled, ...
+        return None;
+    }
+    let request: JsonRpcServerRequest = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => {
+            return Some(JsonRpcReply::new(
+                Value::Null,
+                Err(rpc_error(-32600, &format!("Invalid request: {}", e))),
+            ))
+        }
+    };
+    let outcome = match request.method.as_str() {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "omni-glass", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+        "tools/call" => call_tool(ctx, &request.params).await,
+        other => Err(rpc_error(-32601, &format!("Method not found: {}", other))),
+    };
+    Some(JsonRpcReply::new(request.id, outcome))
+}
+
+async fn call_tool(ctx: &ServerContext, params: &Value) -> Result<Value, JsonRpcError> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| rpc_error(-32602, "tools/call needs a tool name"))?;
+    if !tools::definitions().iter().any(|t| t.name == name) {
+        return Err(rpc_error(-32602, &format!("Unknown tool: {}", name)));
+    }
+    let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+    Ok(tools::call(ctx, name, arguments).await)
+}
+
+fn rpc_error(code: i64, message: &str) -> JsonRpcError {
+    JsonRpcError {
+        code,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+/// Serve MCP on stdin/stdout. Logs go to stderr, so stdout stays protocol-only.
+pub async fn serve_stdio(ctx: &ServerContext) -> Result<(), String> {
+    log::info!("[MCP-SERVER] Serving on stdio");
+    serve(ctx, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> ServerContext {
+        ServerContext {
+            registry: ToolRegistry::new(),
+            policy: ServerPolicy::default(),
+        }
+    }
+
+    async fn reply(line: &str) -> Value {
+        let reply = handle_message(&ctx(), line).await.unwrap();
+        serde_json::to_value(reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn initialize_and_list() {
+        let init = reply(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#).await;
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(init["result"]["capabilities"]["tools"].is_object());
+
+        let list = reply(r#"{"jsonrpc":"2.0","id":"a","method":"tools/list"}"#).await;
+        assert_eq!(list["id"], "a");
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["capture_screen", "ocr_image", "classify_text", "run_action"]
+        );
+    }
+
+    #[tokio::test]
+    async fn notifications_and_errors() {
+        let none = handle_message(
+            &ctx(),
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        )
+        .await;
+        assert!(none.is_none());
+        assert_eq!(reply("{not json").await["error"]["code"], -32700);
+        assert_eq!(
+            reply(r#"{"jsonrpc":"2.0","id":2,"method":"resources/list"}"#).await["error"]["code"],
+            -32601
+        );
+        let unknown =
+            reply(r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"rm"}}"#).await;
+        assert_eq!(unknown["error"]["code"], -32602);
+    }
+}
@@ -0,0 +1,91 @@
+//! The server's tool definitions: names, descriptions and input schemas.
+//!
+//! `tools::call` validates every call against these schemas before running
+//! anything, so they are the first check, not just documentation.
+
+use crate::mcp::types::McpTool;
+use serde_json::{json, Value};
+
+/// The server's tools, in the order `tools/list` returns them.
+pub fn definitions() -> Vec<McpTool> {
+    vec![
+        tool(
+            "capture_screen",
+            "Capture a monitor or window as PNG, with the text on it. \
+             Only available when the user started the server with --allow-capture.",
+            capture_screen_schema(),
+        ),
+        tool(
+            "ocr_image",
+            "Extract text from an image file under the user's home directory, or from base64 image data.",
+            ocr_image_schema(),
+        ),
+        tool(
+            "classify_text",
+            "Classify text the way a snip is classified and return the suggested actions.",
+            classify_text_schema(),
+        ),
+        tool(
+            "run_action",
+            "Run an action from classify_text (built-in or plugin) on text and return its result.",
+            run_action_schema(),
+        ),
+    ]
+}
+
+fn tool(name: &str, description: &str, input_schema: Value) -> McpTool {
+    McpTool {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        input_schema: Some(input_schema),
+        output_schema: None,
+    }
+}
+
+fn capture_screen_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "monitor": { "type": "integer", "minimum": 0, "description": "Monitor index, 0 = primary" },
+            "windowId": { "type": "integer", "minimum": 0, "description": "Capture this window instead of a monitor" },
+            "ocr": { "type": "boolean", "description": "Also return the recognized text (default true)" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn ocr_image_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string", "description": "Image file path" },
+            "data": { "type": "string", "description": "Base64-encoded PNG or JPEG" },
+            "accurate": { "type": "boolean", "description": "Slower, more accurate recognition (default false)" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn classify_text_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "text": { "type": "string", "minLength": 1 },
+            "confidence": { "type": "number", "minimum": 0, "maximum": 1, "description": "OCR confidence of the text (default 1.0)" }
+        },
+        "required": ["text"],
+        "additionalProperties": false
+    })
+}
+
+fn run_action_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "actionId": { "type": "string", "minLength": 1 },
+            "text": { "type": "string" }
+        },
+        "required": ["actionId", "text"],
+        "additionalProperties": false
+    })
+}
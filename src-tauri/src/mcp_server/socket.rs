@@ -0,0 +1,98 @@
+//! The Unix socket transport for `omni-glass-cli mcp --socket <PATH>`.
+
+#[cfg(unix)]
+use super::serve;
+use super::ServerContext;
+
+/// Serve MCP on a Unix socket, one task per connection. The socket is
+/// owner-only (0600) from the moment it exists; a stale socket at `path` is
+/// replaced.
+#[cfg(unix)]
+pub async fn serve_socket(
+    ctx: std::sync::Arc<ServerContext>,
+    path: &std::path::Path,
+) -> Result<(), String> {
+    let listener = bind_private(path)?;
+    log::info!("[MCP-SERVER] Serving on {}", path.display());
+
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("[MCP-SERVER] accept failed: {}", e))?;
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let (reader, writer) = stream.into_split();
+            if let Err(e) = serve(&ctx, reader, writer).await {
+                log::warn!("{}", e);
+            }
+            log::info!("[MCP-SERVER] Connection closed");
+        });
+    }
+}
+
+/// Bind `path` without a window where others can connect: the socket is
+/// bound inside a fresh 0700 directory next to `path`, made 0600, then
+/// renamed into place.
+#[cfg(unix)]
+fn bind_private(path: &std::path::Path) -> Result<tokio::net::UnixListener, String> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            return Err(format!("{} exists and is not a socket", path.display()));
+        }
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Cannot remove stale socket {}: {}", path.display(), e))?;
+    }
+    let parent = path.parent().unwrap_or(std::path::Path::new("."));
+    std::fs::create_dir_all(parent)
+        .map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+    let staging = parent.join(format!(".omni-glass-mcp-{}", std::process::id()));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .map_err(|e| format!("Cannot create {}: {}", staging.display(), e))?;
+    let staged = staging.join("mcp.sock");
+    let bound = tokio::net::UnixListener::bind(&staged)
+        .map_err(|e| format!("Cannot listen on {}: {}", path.display(), e))
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))
+                .and_then(|_| std::fs::rename(&staged, path))
+                .map_err(|e| format!("Cannot restrict {}: {}", path.display(), e))?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    bound
+}
+
+/// Local sockets are Unix-only for now.
+#[cfg(not(unix))]
+pub async fn serve_socket(
+    _ctx: std::sync::Arc<ServerContext>,
+    _path: &std::path::Path,
+) -> Result<(), String> {
+    Err("The socket transport needs macOS or Linux; use stdio".to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn socket_is_owner_only_and_replaces_a_stale_one() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("og-mcp-server-socket");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("mcp.sock");
+        drop(bind_private(&path).unwrap());
+        let listener = bind_private(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        drop(listener);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
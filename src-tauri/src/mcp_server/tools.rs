@@ -0,0 +1,192 @@
+//! Tools exposed by Omni-Glass's MCP server, and the checks on each call.
+//!
+//! - Arguments are validated against the tool's `inputSchema` first
+//! - `capture_screen` is refused unless `ServerPolicy::allow_capture`
+//! - `ocr_image` file paths must pass `command_check::is_path_safe`
+//! - Text from OCR or the host is scanned for prompt injection; flagged
+//!   content carries `INJECTION_WARNING`, as in the app
+//! - `run_action` on a tool the user disabled in Settings is refused
+//! - `run_action` on a plugin tool is refused by `headless::execute` when
+//!   the app would hold it for argument review: nobody can review
+//!   arguments from here
+//! - `command` results are returned, never run
+//! - Every call is written to the audit log as `server_call`
+//!
+//! The schemas live in `schemas.rs` and the image tools in `images.rs`.
+
+use super::images::{capture_screen, ocr_image};
+use super::ServerContext;
+use crate::audit::{self, AuditKind};
+use crate::headless;
+use crate::llm::ActionResult;
+use crate::mcp::{schema, tool_prefs};
+use crate::safety::injection;
+use serde_json::{json, Value};
+
+pub use super::schemas::definitions;
+
+/// Run one tool and return an MCP `CallToolResult`. Tool failures are
+/// results with `isError`, not JSON-RPC errors, so the host's model sees them.
+pub async fn call(ctx: &ServerContext, name: &str, arguments: Value) -> Value {
+    let outcome = match check_arguments(name, &arguments) {
+        Err(e) => Err(e),
+        Ok(()) => match name {
+            "capture_screen" => capture_screen(ctx, &arguments).await,
+            "ocr_image" => ocr_image(&arguments).await,
+            "classify_text" => classify_text(ctx, &arguments).await,
+            "run_action" => run_action(ctx, &arguments).await,
+            other => Err(format!("Unknown tool: {}", other)),
+        },
+    };
+
+    let is_error = match &outcome {
+        Ok(result) => result["isError"].as_bool().unwrap_or(false),
+        Err(_) => true,
+    };
+    audit::record_async(
+        AuditKind::ServerCall,
+        name.to_string(),
+        json!({ "arguments": audit_arguments(&arguments), "isError": is_error }),
+    )
+    .await;
+
+    outcome.unwrap_or_else(|e| {
+        log::warn!("[MCP-SERVER] {} refused: {}", name, e);
+        json!({ "content": [{ "type": "text", "text": e }], "isError": true })
+    })
+}
+
+fn check_arguments(name: &str, arguments: &Value) -> Result<(), String> {
+    let schema = definitions()
+        .into_iter()
+        .find(|t| t.name == name)
+        .and_then(|t| t.input_schema)
+        .ok_or_else(|| format!("Unknown tool: {}", name))?;
+    let errors = schema::validate(&schema, arguments);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Invalid arguments: {}", errors.join("; ")))
+    }
+}
+
+/// Arguments as logged: image data is replaced by its length.
+fn audit_arguments(arguments: &Value) -> Value {
+    let mut logged = arguments.clone();
+    if let Some(data) = logged.get_mut("data") {
+        let len = data.as_str().map(str::len).unwrap_or(0);
+        *data = json!(format!("<{} base64 chars>", len));
+    }
+    logged
+}
+
+async fn classify_text(ctx: &ServerContext, args: &Value) -> Result<Value, String> {
+    let text = args["text"].as_str().unwrap_or_default();
+    let confidence = args
+        .get("confidence")
+        .and_then(Value::as_f64)
+        .unwrap_or(1.0);
+    let report = headless::analyze(&ctx.registry, "mcp", text, confidence).await;
+
+    let mut summary = report.menu.summary.clone();
+    if report.injection.adversarial {
+        summary = format!("{} {}", injection::INJECTION_WARNING, summary);
+    }
+    for action in &report.menu.actions {
+        summary.push_str(&format!("\n- {}: {}", action.id, action.label));
+    }
+    Ok(json!({
+        "content": [{ "type": "text", "text": summary }],
+        "structuredContent": report,
+        "isError": false,
+    }))
+}
+
+async fn run_action(ctx: &ServerContext, args: &Value) -> Result<Value, String> {
+    let action_id = args["actionId"].as_str().unwrap_or_default();
+    let text = args["text"].as_str().unwrap_or_default();
+    check_enabled(ctx, action_id).await?;
+
+    let report = injection::scan_for_injection(text);
+    let result = headless::execute(&ctx.registry, action_id, text, report.adversarial).await;
+    Ok(action_result(result))
+}
+
+/// Err when the user disabled `action_id` in Settings.
+async fn check_enabled(ctx: &ServerContext, action_id: &str) -> Result<(), String> {
+    let key = match ctx.registry.tool_for_action(action_id).await {
+        Some(tool) => tool_prefs::tool_key(&tool),
+        None => action_id.to_string(),
+    };
+    let prefs = tokio::task::spawn_blocking(tool_prefs::load_prefs)
+        .await
+        .map_err(|e| format!("Loading tool preferences failed: {}", e))?;
+    if prefs.is_enabled(&key) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' is disabled in Omni-Glass settings",
+            action_id
+        ))
+    }
+}
+
+/// Map an `ActionResult` to a `CallToolResult`.
+fn action_result(result: ActionResult) -> Value {
+    let body = &result.result;
+    let mut content = Vec::new();
+    if let Some(note) = result
+        .metadata
+        .as_ref()
+        .and_then(|m| m.processing_note.as_ref())
+    {
+        content.push(json!({ "type": "text", "text": note }));
+    }
+    let main = body
+        .text
+        .as_ref()
+        .or(body.command.as_ref())
+        .or(body.clipboard_content.as_ref())
+        .or(body.url.as_ref())
+        .or(body.file_path.as_ref());
+    if let Some(main) = main {
+        content.push(json!({ "type": "text", "text": main }));
+    }
+    if let (Some(data), "image") = (&body.data, body.result_type.as_str()) {
+        content.push(json!({
+            "type": "image",
+            "data": data,
+            "mimeType": body.mime_type.as_deref().unwrap_or("image/png"),
+        }));
+    }
+    let is_error = result.status == "error";
+    json!({ "content": content, "structuredContent": result, "isError": is_error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_are_checked_against_the_schema() {
+        assert!(check_arguments("classify_text", &json!({ "text": "hi" })).is_ok());
+        assert!(check_arguments("classify_text", &json!({})).is_err());
+        assert!(check_arguments("run_action", &json!({ "actionId": "x", "text": 1 })).is_err());
+        assert!(check_arguments("capture_screen", &json!({ "shell": "rm" })).is_err());
+        assert!(check_arguments("nope", &json!({})).is_err());
+    }
+
+    #[test]
+    fn audit_omits_image_data() {
+        let logged = audit_arguments(&json!({ "data": "QUJD", "accurate": true }));
+        assert_eq!(logged["data"], "<4 base64 chars>");
+        assert_eq!(logged["accurate"], true);
+    }
+
+    #[test]
+    fn error_results_are_flagged() {
+        let result = action_result(ActionResult::error("explain", "no API key"));
+        assert_eq!(result["isError"], true);
+        assert_eq!(result["structuredContent"]["status"], "error");
+    }
+}
@@ -3,6 +3,7 @@
 //! Dispatches to the appropriate platform backend:
 //! - macOS: Apple Vision Framework via swift-bridge FFI
 //! - Windows: Windows.Media.Ocr via windows-rs (WinRT)
+//! - Linux and others: the Tesseract CLI (tesseract.rs)
 //!
 //! External code uses the public functions here — the platform
 //! backend is selected at compile time via #[cfg(target_os)].
@@ -15,6 +16,9 @@ mod apple_vision;
 #[cfg(target_os = "windows")]
 mod windows_ocr;
 
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod tesseract;
+
 /// Recognition level for text recognition.
 ///
 /// Maps to VNRequestTextRecognitionLevel on macOS.
@@ -46,7 +50,8 @@ pub struct OcrOutput {
 
 /// Run OCR on in-memory PNG bytes. Eliminates disk I/O from the pipeline.
 ///
-/// Dispatches to Apple Vision (macOS) or Windows.Media.Ocr (Windows).
+/// Dispatches to Apple Vision (macOS), Windows.Media.Ocr (Windows),
+/// or Tesseract (everything else).
 pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
     #[cfg(target_os = "macos")]
     {
@@ -57,6 +62,11 @@ pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) ->
     {
         windows_ocr::recognize_text_from_bytes(png_bytes, level)
     }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        tesseract::recognize_text_from_bytes(png_bytes, level)
+    }
 }
 
 /// Run OCR on an image file and return extracted text with metadata.
@@ -77,4 +87,7 @@ pub fn warm_up() {
 
     #[cfg(target_os = "windows")]
     windows_ocr::warm_up();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    tesseract::warm_up();
 }
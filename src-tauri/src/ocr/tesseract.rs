@@ -0,0 +1,137 @@
+//! Linux OCR via the Tesseract CLI.
+//!
+//! Compiled on platforms without a native OCR engine. Shells out to the
+//! `tesseract` binary (PNG on stdin, TSV on stdout) so no native library
+//! has to be linked. If Tesseract isn't installed, OCR returns empty text
+//! and the pipeline falls back to generic actions, same as a blank snip.
+
+use super::{OcrOutput, RecognitionLevel};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+/// Run OCR on in-memory PNG bytes via `tesseract stdin stdout tsv`.
+///
+/// Tesseract has no speed/accuracy switch that maps onto Vision's levels,
+/// so both levels run the same engine.
+pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
+    let start = Instant::now();
+    let level_name = match level {
+        RecognitionLevel::Fast => "fast",
+        RecognitionLevel::Accurate => "accurate",
+    };
+
+    let (text, confidence) = match run_tesseract(&png_bytes) {
+        Ok(tsv) => parse_tsv(&tsv),
+        Err(e) => {
+            log::error!("[OCR] Tesseract failed: {}", e);
+            (String::new(), 0.0)
+        }
+    };
+
+    OcrOutput {
+        char_count: text.chars().count() as i64,
+        text,
+        latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+        confidence,
+        recognition_level: level_name.to_string(),
+    }
+}
+
+/// Check that the `tesseract` binary is on PATH.
+pub fn warm_up() {
+    match which::which("tesseract") {
+        Ok(path) => log::info!("[OCR] Using Tesseract at {}", path.display()),
+        Err(_) => log::warn!("[OCR] tesseract not found on PATH — OCR will return no text"),
+    }
+}
+
+fn run_tesseract(png_bytes: &[u8]) -> Result<String, String> {
+    let mut child = Command::new("tesseract")
+        .args(["stdin", "stdout", "tsv"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start tesseract: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(png_bytes)
+            .map_err(|e| format!("failed to write image: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("tesseract did not finish: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Rebuild text lines from Tesseract TSV and average word confidence.
+///
+/// Word rows (level 5) are grouped by block/paragraph/line number.
+/// Confidence is returned on a 0.0–1.0 scale like the other backends.
+fn parse_tsv(tsv: &str) -> (String, f64) {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_key: Option<(&str, &str, &str)> = None;
+    let mut conf_sum = 0.0;
+    let mut word_count = 0u32;
+
+    for row in tsv.lines().skip(1) {
+        let cols: Vec<&str> = row.splitn(12, '\t').collect();
+        if cols.len() < 12 || cols[0] != "5" {
+            continue;
+        }
+        let word = cols[11].trim();
+        let conf: f64 = cols[10].parse().unwrap_or(-1.0);
+        if word.is_empty() || conf < 0.0 {
+            continue;
+        }
+        let key = (cols[2], cols[3], cols[4]);
+        match lines.last_mut() {
+            Some(line) if current_key == Some(key) => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+        current_key = Some(key);
+        conf_sum += conf;
+        word_count += 1;
+    }
+
+    let confidence = if word_count == 0 {
+        0.0
+    } else {
+        conf_sum / word_count as f64 / 100.0
+    };
+    (lines.join("\n"), confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext
+1\t1\t0\t0\t0\t0\t0\t0\t200\t50\t-1\t
+4\t1\t1\t1\t1\t0\t5\t5\t100\t10\t-1\t
+5\t1\t1\t1\t1\t1\t5\t5\t40\t10\t90.0\tTypeError:
+5\t1\t1\t1\t1\t2\t50\t5\t40\t10\t80.0\tfoo
+5\t1\t1\t1\t2\t1\t5\t20\t40\t10\t70.0\tat
+5\t1\t1\t1\t2\t2\t5\t20\t40\t10\t-1\t
+";
+
+    #[test]
+    fn parse_tsv_groups_words_into_lines() {
+        let (text, confidence) = parse_tsv(TSV);
+        assert_eq!(text, "TypeError: foo\nat");
+        assert!((confidence - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_tsv_empty_output() {
+        assert_eq!(parse_tsv("level\tpage_num\n"), (String::new(), 0.0));
+    }
+}
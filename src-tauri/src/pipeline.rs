@@ -1,16 +1,38 @@
 //! Core snip-to-action pipeline commands.
 //!
 //! These are the multi-step orchestration commands:
-//! - process_snip: crop → OCR → open skeleton menu → stream LLM classify
+//! - process_snip: crop → OCR → content triggers → open skeleton menu →
+//!   stream LLM classify → merge triggered actions and recipes
 //! - execute_action: OCR text + chosen action → LLM execute → ActionResult
+//!   (recipe entries hand off to `recipes::runner`)
+//!
+//! `pipeline/snip_menu.rs` holds the menu window, its plugin actions and
+//! the history entry; `pipeline/action.rs` runs built-in actions.
 
 use crate::capture::CaptureState;
 use crate::llm;
 use crate::mcp;
 use crate::ocr;
+use crate::recipes;
+use crate::safety;
 use crate::settings_commands::resolve_provider;
 use tauri::Manager;
 
+mod action;
+mod snip_menu;
+
+pub(crate) use action::execute_builtin;
+pub(crate) use snip_menu::record_in_history;
+use snip_menu::record_menu_choice;
+
+/// Append one line to the Desktop diagnostics log.
+fn diag_write(path: &std::path::Path, msg: &str) {
+    use std::io::Write;
+    if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(f, "{}", msg);
+    }
+}
+
 /// Tauri command: process a snip through the full pipeline (streaming).
 ///
 /// crop → OCR → open skeleton menu → stream LLM classify → populate actions.
@@ -19,12 +41,7 @@ use tauri::Manager;
 #[tauri::command]
 pub async fn process_snip(
     app: tauri::AppHandle,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
-    menu_x: f64,
-    menu_y: f64,
+    selection: crate::capture::Selection,
 ) -> Result<(), String> {
     let pipeline_start = std::time::Instant::now();
 
@@ -32,30 +49,25 @@ pub async fn process_snip(
     let diag_path = dirs::desktop_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("omni-glass-debug.log");
-    fn diag_write(path: &std::path::Path, msg: &str) {
-        use std::io::Write;
-        if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
-            let _ = writeln!(f, "{}", msg);
-        }
-    }
     // Clear old log and start fresh
     let _ = std::fs::write(&diag_path, "");
-    diag_write(&diag_path, &format!("=== SNIP: {}x{} at ({},{}) ===", width, height, x, y));
-
-    // Stage 2a: Crop the stored screenshot
-    let cropped = {
-        let state = app.state::<CaptureState>();
-        let guard = state.screenshot.lock().map_err(|e| e.to_string())?;
-        let screenshot = guard
-            .as_ref()
-            .ok_or("No screenshot available — capture first")?;
-        screenshot.crop_imm(x, y, width, height)
-    };
+    diag_write(&diag_path, &format!("=== SNIP: {:?} ===", selection));
+
+    // Stage 2a: Crop the capture the selection was drawn on
+    let (cropped, source) =
+        crate::capture_commands::crop_selection(&app.state::<CaptureState>(), &selection)?;
     let crop_ms = pipeline_start.elapsed().as_millis();
     diag_write(&diag_path, &format!("crop: {}ms", crop_ms));
     log::info!(
-        "[CAPTURE] Bounding box received: {{x: {}, y: {}, w: {}, h: {}}}",
-        x, y, width, height
+        "[CAPTURE] Bounding box received: {:?} on {} '{}'",
+        selection, source.kind, source.name
+    );
+
+    // Action menu goes just below the selection, in desktop coordinates.
+    let (origin_x, origin_y) = source.logical_origin();
+    let (menu_x, menu_y) = (
+        origin_x + selection.x,
+        origin_y + selection.y + selection.height,
     );
     log::info!("[CAPTURE] Region crop: {}ms", crop_ms);
 
@@ -105,34 +117,36 @@ pub async fn process_snip(
     *menu_state.menu.lock().unwrap() = None;
     *menu_state.ocr_text.lock().unwrap() = Some(ocr_result.text.clone());
     *menu_state.crop_png.lock().unwrap() = Some(png_bytes_for_reocr);
-
-    // Stage 3a: Close overlay
-    if let Some(window) = app.get_webview_window("overlay") {
-        let _ = window.destroy();
+    let injection = safety::injection::scan_for_injection(&ocr_result.text);
+    if injection.adversarial {
+        log::warn!(
+            "[SAFETY] Snip looks adversarial (score={}, signals={:?})",
+            injection.score,
+            injection.signals
+        );
     }
+    *menu_state.injection.lock().unwrap() = Some(injection);
+
+    let history_id = snip_menu::start_history(&menu_state, &ocr_result.text).await;
+    *menu_state.offered_tools.lock().unwrap() = None;
+    *menu_state.recipe_run.lock().unwrap() = None;
+
+    // Stage 2e: Content triggers, shown by the skeleton
+    let registry = app.state::<mcp::ToolRegistry>();
+    let prefs = mcp::tool_prefs::load_prefs();
+    let query = mcp::relevance::Query::from_snip(&ocr_result.text, has_table, has_code);
+    let text = &ocr_result.text;
+    let triggered =
+        snip_menu::triggered(&registry, &menu_state, text, &query, &prefs, &diag_path).await;
+
+    // Stage 3a: Close every overlay
+    crate::capture_commands::close_overlays(&app);
 
     // Stage 3b: Open action menu window BEFORE LLM call.
     // Shows skeleton immediately — Copy Text is clickable, summary shimmer visible.
     let render_start = std::time::Instant::now();
 
-    if let Some(existing) = app.get_webview_window("action-menu") {
-        let _ = existing.destroy();
-    }
-
-    let _menu_window = tauri::WebviewWindowBuilder::new(
-        &app,
-        "action-menu",
-        tauri::WebviewUrl::App("action-menu.html".into()),
-    )
-    .title("Omni-Glass Actions")
-    .inner_size(300.0, 280.0)
-    .position(menu_x, menu_y + 8.0)
-    .decorations(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .resizable(false)
-    .build()
-    .map_err(|e| format!("Failed to create action menu window: {}", e))?;
+    snip_menu::open_window(&app, menu_x, menu_y + 8.0)?;
 
     let render_ms = render_start.elapsed().as_millis();
     let local_ms = pipeline_start.elapsed().as_millis();
@@ -142,13 +156,10 @@ pub async fn process_snip(
         local_ms, crop_ms, encode_ms, ocr_ms, render_ms
     );
 
-    // Stage 4: Stream LLM classify — emits events to the action menu window.
+    // Stage 4: Stream LLM classify — emits the skeleton event to the action menu window.
     // Get plugin tool descriptions so the LLM knows about installed plugins.
-    let registry = app.state::<mcp::ToolRegistry>();
-    let all_tools = registry.all_tools().await;
-    let plugin_count = all_tools.iter().filter(|t| t.plugin_id != "builtin").count();
-    let plugin_tools = registry.tools_for_prompt().await;
-    diag_write(&diag_path, &format!("registry: {} total tools, {} plugin tools", all_tools.len(), plugin_count));
+    let plugin_tools =
+        snip_menu::offered_tools(&registry, &menu_state, &query, &prefs, &diag_path).await;
 
     let provider = resolve_provider();
     diag_write(&diag_path, &format!("provider: {}", provider));
@@ -163,7 +174,7 @@ pub async fn process_snip(
     let action_menu = match provider.as_str() {
         "gemini" => {
             llm::classify_streaming_gemini(
-                &app,
+                Some(&app),
                 &ocr_result.text,
                 has_table,
                 has_code,
@@ -176,7 +187,7 @@ pub async fn process_snip(
         "local" => {
             let local_state = app.state::<llm::local_state::LocalLlmState>();
             llm::local::classify_local(
-                &app,
+                Some(&app),
                 &ocr_result.text,
                 has_table,
                 has_code,
@@ -199,6 +210,9 @@ pub async fn process_snip(
         }
     };
 
+    // Stage 5: Triggered actions and recipes, then show the finished menu
+    let action_menu = snip_menu::complete(&app, &registry, action_menu, &triggered, &prefs).await;
+
     // Log classify result to diagnostics
     diag_write(&diag_path, &format!("classify_result: content_type={}, summary={}", action_menu.content_type, action_menu.summary));
     diag_write(&diag_path, &format!("actions: {}", action_menu.actions.len()));
@@ -209,6 +223,8 @@ pub async fn process_snip(
     diag_write(&diag_path, &format!("total_pipeline: {}ms", diag_ms));
     eprintln!("[PIPELINE] Diagnostics written to {}", diag_path.display());
 
+    snip_menu::finish_history(history_id, cropped, &action_menu).await;
+
     // Store final ActionMenu in state (fallback for get_action_menu command)
     *menu_state.menu.lock().unwrap() = Some(action_menu);
 
@@ -244,77 +260,37 @@ pub async fn execute_action(
             .ok_or("No OCR text available — snip first".to_string())?
     };
 
+    if let Some(recipe_id) = action_id.strip_prefix(recipes::ACTION_PREFIX) {
+        return Ok(recipes::runner::start(&app, &state, &registry, recipe_id, &fast_text).await);
+    }
+
+    record_menu_choice(&state, &registry, &action_id).await;
+
     // Check if this action belongs to a plugin (non-builtin MCP tool).
     // If so, route to the plugin's MCP server with LLM-generated args.
-    if registry.is_plugin_action(&action_id).await {
-        log::info!("[EXECUTE] Routing to plugin: {}", action_id);
-        let resolved = registry.resolve_action(&action_id).await;
-        let tool_meta = match &resolved {
-            Some(qname) => registry.get_tool(qname).await,
-            None => None,
-        };
-        let result = mcp::execute_plugin_tool(
-            &registry,
-            &action_id,
-            &fast_text,
-            tool_meta.as_ref().map(|t| t.description.as_str()),
-            tool_meta.as_ref().and_then(|t| t.input_schema.as_ref()),
-            Some(&app),
-        )
-        .await;
+    if let Some(result) =
+        mcp::run_plugin_action(&registry, &action_id, &fast_text, Some(&app)).await
+    {
+        record_in_history(&state, &result);
         return Ok(result);
     }
 
-    // For code-fix actions, re-OCR with .accurate for higher fidelity text.
-    // The classify step used .fast (~30ms) which is good enough for action detection,
-    // but code fixes need every bracket and quote to be correct.
-    let needs_accurate = matches!(
-        action_id.as_str(),
-        "suggest_fix" | "fix_error" | "fix_syntax" | "fix_code" | "format_code"
-    );
-    let ocr_text = if needs_accurate {
-        let crop_png = {
-            let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
-            guard.clone()
-        };
-        match crop_png {
-            Some(png_bytes) => {
-                let start = std::time::Instant::now();
-                let result = ocr::recognize_text_from_bytes(
-                    png_bytes,
-                    ocr::RecognitionLevel::Accurate,
-                );
-                let ms = start.elapsed().as_millis();
-                eprintln!(
-                    "[EXECUTE] Re-OCR (.accurate): {} chars in {}ms (was {} chars with .fast)",
-                    result.char_count, ms, fast_text.len()
-                );
-                result.text
-            }
-            None => {
-                eprintln!("[EXECUTE] No crop PNG available, using .fast OCR text");
-                fast_text
-            }
-        }
-    } else {
-        fast_text
-    };
+    let ocr_text = action::action_text(&state, &action_id, fast_text).await?;
 
     log::info!("[EXECUTE] Starting action: {}", action_id);
-    let provider = resolve_provider();
-    let result = match provider.as_str() {
-        #[cfg(feature = "local-llm")]
-        "local" => {
-            let local_state = app.state::<llm::local_state::LocalLlmState>();
-            llm::local::execute_action_local(&action_id, &ocr_text, &local_state).await
-        }
-        _ => llm::execute_action_anthropic(&action_id, &ocr_text).await,
-    };
+    let adversarial = state
+        .injection
+        .lock()
+        .map_err(|e| e.to_string())?
+        .as_ref()
+        .is_some_and(|r| r.adversarial);
+    let result = execute_builtin(&app, &action_id, &ocr_text, adversarial).await;
     log::info!(
         "[EXECUTE] Complete: status={}, type={}",
         result.status,
         result.result.result_type
     );
+    record_in_history(&state, &result);
 
     Ok(result)
 }
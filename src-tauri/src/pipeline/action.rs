@@ -0,0 +1,80 @@
+//! Running a built-in menu action: the text it works on and the provider
+//! that runs it.
+
+use crate::llm;
+use crate::ocr;
+use crate::safety;
+use crate::settings_commands::resolve_provider;
+#[cfg(feature = "local-llm")]
+use tauri::Manager;
+
+/// Actions that re-OCR the snip with `.accurate` before running.
+const ACCURATE_ACTIONS: &[&str] = &[
+    "suggest_fix",
+    "fix_error",
+    "fix_syntax",
+    "fix_code",
+    "format_code",
+];
+
+/// The OCR text `action_id` runs on.
+///
+/// For code-fix actions, re-OCR with .accurate for higher fidelity text.
+/// The classify step used .fast (~30ms) which is good enough for action
+/// detection, but code fixes need every bracket and quote to be correct.
+/// Recognition runs on the blocking pool.
+pub(super) async fn action_text(
+    state: &llm::ActionMenuState,
+    action_id: &str,
+    fast_text: String,
+) -> Result<String, String> {
+    if !ACCURATE_ACTIONS.contains(&action_id) {
+        return Ok(fast_text);
+    }
+    let crop_png = state.crop_png.lock().map_err(|e| e.to_string())?.clone();
+    let Some(png_bytes) = crop_png else {
+        eprintln!("[EXECUTE] No crop PNG available, using .fast OCR text");
+        return Ok(fast_text);
+    };
+    let start = std::time::Instant::now();
+    let result = tokio::task::spawn_blocking(move || {
+        ocr::recognize_text_from_bytes(png_bytes, ocr::RecognitionLevel::Accurate)
+    })
+    .await
+    .map_err(|e| format!("Re-OCR failed: {}", e))?;
+    eprintln!(
+        "[EXECUTE] Re-OCR (.accurate): {} chars in {}ms (was {} chars with .fast)",
+        result.char_count,
+        start.elapsed().as_millis(),
+        fast_text.len()
+    );
+    Ok(result.text)
+}
+
+/// Run a built-in action on `text` with the active provider. Results of an
+/// adversarial snip are flagged untrusted. Shared by menu clicks and recipe
+/// steps.
+pub(crate) async fn execute_builtin(
+    #[allow(unused_variables)] app: &tauri::AppHandle,
+    action_id: &str,
+    text: &str,
+    adversarial: bool,
+) -> llm::ActionResult {
+    let provider = resolve_provider();
+    let mut result = match provider.as_str() {
+        #[cfg(feature = "local-llm")]
+        "local" => {
+            let local_state = app.state::<llm::local_state::LocalLlmState>();
+            llm::local::execute_action_local(action_id, text, &local_state).await
+        }
+        _ => llm::execute_action_anthropic(action_id, text).await,
+    };
+    if adversarial {
+        log::warn!(
+            "[SAFETY] Flagging '{}' result from adversarial snip",
+            action_id
+        );
+        result.flag_untrusted(safety::injection::INJECTION_WARNING);
+    }
+    result
+}
@@ -0,0 +1,179 @@
+//! The action menu for one snip: its window, plugin side and history entry.
+//!
+//! `process_snip` calls these around classification: content triggers and
+//! the relevance filter before it, triggered actions and recipes merged in
+//! after it. The history entry starts before the menu opens, so actions
+//! clicked on the skeleton are recorded, and gets its thumbnail and
+//! classification once the menu is complete. Each action run from the menu
+//! is appended to the entry and counted in the relevance metrics.
+
+use super::diag_write;
+use crate::llm::types::{Action, ActionMenu};
+use crate::llm::{ActionMenuState, ActionResult};
+use crate::mcp::confirm;
+use crate::mcp::relevance::{self, Query};
+use crate::mcp::tool_prefs::ToolPrefs;
+use crate::mcp::ToolRegistry;
+use crate::recipes;
+use image::DynamicImage;
+use std::path::Path;
+use tauri::{Emitter, Manager};
+
+/// Start the snip's history entry (no-op when the user disabled history)
+/// and keep its id for the actions run from this menu.
+pub(super) async fn start_history(menu_state: &ActionMenuState, text: &str) -> Option<String> {
+    let text = text.to_string();
+    let id = tokio::task::spawn_blocking(move || crate::history::start_snip(&text))
+        .await
+        .ok()
+        .flatten();
+    *menu_state.history_id.lock().unwrap() = id.clone();
+    id
+}
+
+/// Complete the history entry with the thumbnail and classification.
+pub(super) async fn finish_history(id: Option<String>, cropped: DynamicImage, menu: &ActionMenu) {
+    let Some(id) = id else {
+        return;
+    };
+    let (content_type, summary) = (menu.content_type.clone(), menu.summary.clone());
+    let _ = tokio::task::spawn_blocking(move || {
+        crate::history::finish_snip(&id, &cropped, &content_type, &summary)
+    })
+    .await;
+}
+
+/// Content triggers — local pattern matches that put plugin tools in the
+/// menu without the classifier. Stored before the window opens so the
+/// skeleton can show them.
+pub(super) async fn triggered(
+    registry: &ToolRegistry,
+    menu_state: &ActionMenuState,
+    text: &str,
+    query: &Query,
+    prefs: &ToolPrefs,
+    diag_path: &Path,
+) -> Vec<Action> {
+    let triggered = registry.triggered_actions(text, query, prefs).await;
+    diag_write(diag_path, &format!("triggers: {} fired", triggered.len()));
+    *menu_state.triggered.lock().unwrap() = triggered.clone();
+    triggered
+}
+
+/// Open the action menu window at (`x`, `y`), replacing any open one.
+pub(super) fn open_window(app: &tauri::AppHandle, x: f64, y: f64) -> Result<(), String> {
+    if let Some(existing) = app.get_webview_window("action-menu") {
+        let _ = existing.destroy();
+    }
+    tauri::WebviewWindowBuilder::new(
+        app,
+        "action-menu",
+        tauri::WebviewUrl::App("action-menu.html".into()),
+    )
+    .title("Omni-Glass Actions")
+    .inner_size(300.0, 280.0)
+    .position(x, y)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .build()
+    .map_err(|e| format!("Failed to create action menu window: {}", e))?;
+    Ok(())
+}
+
+/// The plugin tools the classifier is told about, formatted for the prompt.
+/// The relevance filter picks them; its choice is counted and kept so menu
+/// choices can be attributed to it.
+pub(super) async fn offered_tools(
+    registry: &ToolRegistry,
+    menu_state: &ActionMenuState,
+    query: &Query,
+    prefs: &ToolPrefs,
+    diag_path: &Path,
+) -> String {
+    let all_tools = registry.all_tools().await;
+    let plugin_count = all_tools
+        .iter()
+        .filter(|t| t.plugin_id != "builtin")
+        .count();
+    let (plugin_tools, selection) = registry.tools_for_snip(prefs, query).await;
+    diag_write(
+        diag_path,
+        &format!(
+            "registry: {} total tools, {} plugin tools",
+            all_tools.len(),
+            plugin_count
+        ),
+    );
+    diag_write(
+        diag_path,
+        &format!(
+            "relevance: offered {} of {} enabled plugin tools",
+            selection.offered.len(),
+            selection.available
+        ),
+    );
+    let counted = selection.clone();
+    let _ = tokio::task::spawn_blocking(move || relevance::record_selection(&counted)).await;
+    *menu_state.offered_tools.lock().unwrap() = Some(selection.offered);
+    plugin_tools
+}
+
+/// Add the triggered actions the classifier left out — also when it fell
+/// back — and the recipes that fit, then show the finished menu.
+pub(super) async fn complete(
+    app: &tauri::AppHandle,
+    registry: &ToolRegistry,
+    menu: ActionMenu,
+    triggered: &[Action],
+    prefs: &ToolPrefs,
+) -> ActionMenu {
+    let menu = registry.merge_triggered(menu, triggered, prefs).await;
+    let menu = recipes::add_to_menu(menu, &recipes::load_recipes(), registry, prefs).await;
+    let _ = app.emit("action-menu-complete", &menu);
+    menu
+}
+
+/// Count a plugin action run from the menu in the relevance metrics:
+/// whether the filter had offered it for the current snip. Actions a
+/// content trigger added are left out; the filter didn't decide those.
+pub(super) async fn record_menu_choice(
+    state: &ActionMenuState,
+    registry: &ToolRegistry,
+    action_id: &str,
+) {
+    let offered = state.offered_tools.lock().ok().and_then(|o| o.clone());
+    let Some(offered) = offered else {
+        return;
+    };
+    if !registry.is_plugin_action(action_id).await {
+        return;
+    }
+    let triggered = state
+        .triggered
+        .lock()
+        .is_ok_and(|t| t.iter().any(|a| a.id == action_id));
+    if triggered {
+        return;
+    }
+    let offered = registry
+        .resolve_action(action_id)
+        .await
+        .is_some_and(|q| offered.contains(&q));
+    let _ = tokio::task::spawn_blocking(move || relevance::record_menu_choice(offered)).await;
+}
+
+/// Append an action result to the current snip's history entry, if any.
+///
+/// Results still waiting on argument review are skipped; they are recorded
+/// once the reviewed call completes.
+pub(crate) fn record_in_history(state: &ActionMenuState, result: &ActionResult) {
+    if result.result.result_type == confirm::REVIEW_RESULT_TYPE {
+        return;
+    }
+    let history_id = state.history_id.lock().ok().and_then(|id| id.clone());
+    if let Some(id) = history_id {
+        crate::history::spawn_record_action(id, result.clone());
+    }
+}
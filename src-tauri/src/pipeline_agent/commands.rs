@@ -0,0 +1,128 @@
+//! Tauri commands for agent mode: start a run, answer the call it waits
+//! on, or drop it.
+
+use super::drive;
+use super::state::{AgentReply, AgentRun, AgentState};
+use super::tools::{agent_tools, spec};
+use super::trace::{finish_command, finish_step, observe};
+use crate::mcp::confirm::{self, ConfirmPref};
+use crate::mcp::{self, ToolRegistry};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+
+/// Tauri command: run a typed request in agent mode.
+#[tauri::command]
+pub async fn run_agent_command(
+    app: tauri::AppHandle,
+    text: String,
+    registry: tauri::State<'_, ToolRegistry>,
+    state: tauri::State<'_, AgentState>,
+) -> Result<AgentReply, String> {
+    let provider = crate::settings_commands::resolve_provider();
+    if provider != "anthropic" && provider != "gemini" {
+        return Err(format!(
+            "Agent mode needs native tool calling, which the '{}' provider doesn't have. Switch to Anthropic or Gemini in Settings.",
+            provider
+        ));
+    }
+    log::info!("[AGENT] Input: {} chars", text.len());
+
+    // A new request replaces a run still waiting for the user
+    state.run.lock().map_err(|e| e.to_string())?.take();
+
+    let tools = agent_tools(&registry).await;
+    log::info!("[AGENT] {} tool(s) offered", tools.len());
+    let run = AgentRun {
+        provider,
+        messages: vec![json!({ "role": "user", "content": text })],
+        specs: tools.iter().map(spec).collect(),
+        tools,
+        steps: Vec::new(),
+        queue: VecDeque::new(),
+        results: Vec::new(),
+        waiting: None,
+    };
+    Ok(drive(&app, &registry, &state, run).await)
+}
+
+/// Tauri command: answer the call the agent is waiting on, then go on.
+///
+/// `approve` false declines the call; the model is told and carries on.
+/// For plugin tools, `arguments` are the reviewed arguments — validated
+/// against the schema first, so the form can show errors — and `remember`
+/// updates the tool's review preference as in `confirm_plugin_call`.
+#[tauri::command]
+pub async fn continue_agent(
+    app: tauri::AppHandle,
+    approve: bool,
+    arguments: Option<Value>,
+    remember: Option<ConfirmPref>,
+    registry: tauri::State<'_, ToolRegistry>,
+    state: tauri::State<'_, AgentState>,
+) -> Result<AgentReply, String> {
+    let mut run = state
+        .run
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("No agent step is waiting for confirmation")?;
+    let Some((call, tool, pending)) = run.waiting.take() else {
+        return Err("No agent step is waiting for confirmation".to_string());
+    };
+
+    if !approve {
+        log::info!("[AGENT] User declined step {}", run.steps.len());
+        finish_step(
+            &app,
+            &mut run,
+            &call,
+            "declined",
+            "The user declined this call.",
+            true,
+        );
+        return Ok(drive(&app, &registry, &state, run).await);
+    }
+
+    match tool.tool.clone() {
+        None => {
+            let command = pending.command.clone().unwrap_or_default();
+            log::info!("[AGENT] User approved command for step {}", run.steps.len());
+            match crate::shell_command::run_shell_command(&command).await {
+                Ok(output) if output.trim().is_empty() => finish_step(
+                    &app,
+                    &mut run,
+                    &call,
+                    "ok",
+                    "Command completed with no output.",
+                    false,
+                ),
+                Ok(output) => finish_command(&app, &mut run, &call, "ok", &output, false),
+                Err(e) => finish_command(&app, &mut run, &call, "error", &e, true),
+            }
+        }
+        Some(registered) => {
+            let arguments = arguments.unwrap_or_else(|| call.input.clone());
+            if let Err(e) = confirm::accept_reviewed(&registered, &arguments, remember) {
+                // Keep waiting so the form can be corrected
+                run.waiting = Some((call, tool, pending));
+                *state.run.lock().map_err(|e| e.to_string())? = Some(run);
+                return Err(e);
+            }
+            log::info!("[AGENT] User approved arguments for '{}'", tool.action_id);
+            let result =
+                mcp::call_with_arguments(&registry, &tool.action_id, arguments, Some(&registered))
+                    .await;
+            observe(&app, &mut run, &call, &result);
+        }
+    }
+    Ok(drive(&app, &registry, &state, run).await)
+}
+
+/// Tauri command: drop the agent run waiting for the user.
+#[tauri::command]
+pub fn cancel_agent(state: tauri::State<'_, AgentState>) -> Result<(), String> {
+    if let Some(run) = state.run.lock().map_err(|e| e.to_string())?.take() {
+        log::info!("[AGENT] Cancelled after {} step(s)", run.steps.len());
+    }
+    Ok(())
+}
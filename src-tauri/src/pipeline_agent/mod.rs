@@ -0,0 +1,260 @@
+//! Text launcher agent mode — a tool-use loop.
+//!
+//! `execute_text_command` (pipeline_text.rs) makes one routing decision and
+//! stops. In agent mode the launcher calls `run_agent_command` instead: the
+//! model gets the enabled plugin tools and `run_command` as native tools
+//! (llm/tool_use.rs), calls them one after another, sees each result, and
+//! finishes with an answer. Every call passes the same gates as a single
+//! launcher call:
+//!   - run_command: the command safety check, then the launcher's
+//!     confirmation. Only `continue_agent` with the user's approval runs it,
+//!     through `shell_command::run_shell_command` (checked again, time
+//!     limited and audited). Its output is redacted and fenced as untrusted
+//!     before the model sees it.
+//!   - plugin tools: schema validation, then argument review when the tool's
+//!     review preference asks for it (mcp::confirm).
+//!
+//! This file holds the loop and registers the commands as the "agent"
+//! plugin (`init`). `commands.rs` has the Tauri commands,
+//! `state.rs` the types, `tools.rs` what the model is offered and
+//! `trace.rs` what it and the trace see of each call. A call waiting for
+//! the user parks the run in `AgentState`. Each step is
+//! emitted as "agent-step" for the launcher's trace; after MAX_AGENT_STEPS
+//! calls the model must answer with what it has.
+//!
+//! The loop runs on Anthropic or Gemini native tool calling, whichever
+//! provider is selected; the local model has no tool calling.
+
+mod commands;
+mod state;
+mod tools;
+mod trace;
+
+pub use state::{AgentReply, AgentState, AgentStep, PendingCall};
+
+use crate::llm::prompts_text_command::{AGENT_MAX_TOKENS, AGENT_SYSTEM_PROMPT};
+use crate::llm::tool_use::{self, ModelTurn, ToolCall};
+use crate::mcp::confirm;
+use crate::mcp::{self, ToolRegistry};
+use crate::safety;
+use serde_json::json;
+use state::{AgentRun, AgentTool};
+use tauri::plugin::TauriPlugin;
+use tauri::Manager;
+use trace::{describe_input, emit_step, finish_step, observe, shorten, MAX_TRACE_CHARS};
+
+/// The agent commands and their state as the app-local "agent" plugin, so
+/// lib.rs registers agent mode in one line. The frontend invokes
+/// `plugin:agent|run_agent_command` etc.; build.rs grants the permissions.
+pub fn init() -> TauriPlugin<tauri::Wry> {
+    tauri::plugin::Builder::new("agent")
+        .invoke_handler(tauri::generate_handler![
+            commands::run_agent_command,
+            commands::continue_agent,
+            commands::cancel_agent,
+        ])
+        .setup(|app, _api| {
+            app.manage(AgentState::new());
+            Ok(())
+        })
+        .build()
+}
+
+/// Most tool calls in one run.
+pub const MAX_AGENT_STEPS: usize = 8;
+
+/// How handling one call ended.
+enum Handled {
+    Done,
+    Wait(PendingCall),
+}
+
+/// Handle model turns and tool calls until the model answers or a call
+/// needs the user.
+async fn drive(
+    app: &tauri::AppHandle,
+    registry: &ToolRegistry,
+    state: &AgentState,
+    mut run: AgentRun,
+) -> AgentReply {
+    loop {
+        while let Some(call) = run.queue.pop_front() {
+            if let Handled::Wait(pending) = handle_call(app, registry, &mut run, call).await {
+                return park(state, run, pending);
+            }
+        }
+        let allow_tools = run.steps.len() < MAX_AGENT_STEPS;
+        let turn = match next_turn(&mut run, allow_tools).await {
+            Ok(turn) => turn,
+            Err(e) => {
+                log::error!("[AGENT] Model call failed: {}", e);
+                return finish(run, "error", e);
+            }
+        };
+
+        if turn.calls.is_empty() || !allow_tools {
+            log::info!("[AGENT] Finished after {} step(s)", run.steps.len());
+            let answer = if turn.text.is_empty() {
+                format!("Stopped after {} step(s).", run.steps.len())
+            } else {
+                turn.text
+            };
+            return finish(run, "done", answer);
+        }
+        run.queue.extend(turn.calls);
+    }
+}
+
+/// Send the queued tool results and get the model's next turn.
+async fn next_turn(run: &mut AgentRun, allow_tools: bool) -> Result<ModelTurn, String> {
+    if !run.results.is_empty() {
+        let results = std::mem::take(&mut run.results);
+        run.messages
+            .push(json!({ "role": "user", "content": results }));
+    }
+    let (system, messages, specs) = (AGENT_SYSTEM_PROMPT, &run.messages, &run.specs);
+    let turn = if run.provider == "gemini" {
+        tool_use::complete_with_tools_gemini(system, messages, specs, allow_tools, AGENT_MAX_TOKENS)
+            .await?
+    } else {
+        tool_use::complete_with_tools(system, messages, specs, allow_tools, AGENT_MAX_TOKENS)
+            .await?
+    };
+    run.messages
+        .push(json!({ "role": "assistant", "content": turn.content }));
+    Ok(turn)
+}
+
+/// Keep the run in `state` until the user answers `pending`.
+fn park(state: &AgentState, run: AgentRun, pending: PendingCall) -> AgentReply {
+    let reply = AgentReply {
+        status: "needs_confirmation".to_string(),
+        steps: run.steps.clone(),
+        answer: None,
+        pending: Some(pending),
+    };
+    if let Ok(mut slot) = state.run.lock() {
+        *slot = Some(run);
+    }
+    reply
+}
+
+async fn handle_call(
+    app: &tauri::AppHandle,
+    registry: &ToolRegistry,
+    run: &mut AgentRun,
+    call: ToolCall,
+) -> Handled {
+    let Some(tool) = run.tools.iter().find(|t| t.wire == call.name).cloned() else {
+        let message = format!("Unknown tool '{}'", call.name);
+        run.results
+            .push(tool_use::tool_result(&call.id, &message, true));
+        return Handled::Done;
+    };
+    if run.steps.len() >= MAX_AGENT_STEPS {
+        run.results.push(tool_use::tool_result(
+            &call.id,
+            "Step limit reached. Answer with what you have.",
+            true,
+        ));
+        return Handled::Done;
+    }
+    log::info!("[AGENT] Step {}: {}", run.steps.len() + 1, tool.action_id);
+    run.steps.push(AgentStep {
+        step: run.steps.len() + 1,
+        tool_id: tool.action_id.clone(),
+        label: tool.label.clone(),
+        input: shorten(&describe_input(&call.input), MAX_TRACE_CHARS),
+        status: "running".to_string(),
+        output: String::new(),
+    });
+    emit_step(app, run);
+
+    let Some(registered) = tool.tool.clone() else {
+        return command_call(app, run, call, tool);
+    };
+    let arguments = match mcp::args::accept_arguments(&registered, call.input.clone()) {
+        Ok(arguments) => arguments,
+        Err(errors) => {
+            let message = format!("Invalid arguments:\n{}", errors.join("\n"));
+            finish_step(app, run, &call, "error", &message, true);
+            return Handled::Done;
+        }
+    };
+    if confirm::needs_review(&registered, confirm::pref_for(&registered)) {
+        let pending = PendingCall {
+            kind: "tool_args".to_string(),
+            action_id: tool.action_id.clone(),
+            command: None,
+            explanation: None,
+            arguments: Some(arguments),
+        };
+        return wait(app, run, call, tool, pending);
+    }
+    let result =
+        mcp::call_with_arguments(registry, &tool.action_id, arguments, Some(&registered)).await;
+    observe(app, run, &call, &result);
+    Handled::Done
+}
+
+/// Safety-check a run_command call and hold it for the user's approval.
+fn command_call(
+    app: &tauri::AppHandle,
+    run: &mut AgentRun,
+    call: ToolCall,
+    tool: AgentTool,
+) -> Handled {
+    let field = |name: &str| {
+        call.input
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let Some(command) = field("command") else {
+        finish_step(app, run, &call, "error", "No command given.", true);
+        return Handled::Done;
+    };
+    let check = safety::command_check::is_command_safe(&command);
+    if !check.safe {
+        let reason = check
+            .reason
+            .unwrap_or_else(|| "Unknown safety concern".to_string());
+        log::warn!("[AGENT] Command blocked by safety layer: {}", reason);
+        let message = format!("Command blocked by safety layer: {}", reason);
+        finish_step(app, run, &call, "blocked", &message, true);
+        return Handled::Done;
+    }
+    let pending = PendingCall {
+        kind: "command".to_string(),
+        action_id: tool.action_id.clone(),
+        command: Some(command),
+        explanation: field("explanation"),
+        arguments: None,
+    };
+    wait(app, run, call, tool, pending)
+}
+
+fn wait(
+    app: &tauri::AppHandle,
+    run: &mut AgentRun,
+    call: ToolCall,
+    tool: AgentTool,
+    pending: PendingCall,
+) -> Handled {
+    if let Some(step) = run.steps.last_mut() {
+        step.status = "waiting".to_string();
+    }
+    emit_step(app, run);
+    run.waiting = Some((call, tool, pending.clone()));
+    Handled::Wait(pending)
+}
+
+fn finish(run: AgentRun, status: &str, answer: String) -> AgentReply {
+    AgentReply {
+        status: status.to_string(),
+        steps: run.steps,
+        answer: Some(answer),
+        pending: None,
+    }
+}
@@ -0,0 +1,96 @@
+//! Agent mode types: the launcher's trace and replies, and the run parked
+//! in `AgentState` while a call waits for the user.
+
+use crate::llm::tool_use::{ToolCall, ToolSpec};
+use crate::mcp::registry::RegisteredTool;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// One tool call in the launcher's trace.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStep {
+    /// 1-based.
+    pub step: usize,
+    pub tool_id: String,
+    pub label: String,
+    /// The command or arguments, shortened.
+    pub input: String,
+    /// "running" | "waiting" | "ok" | "error" | "declined" | "blocked"
+    pub status: String,
+    /// The result, shortened.
+    pub output: String,
+}
+
+/// A call waiting for the user.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingCall {
+    /// "command" | "tool_args"
+    pub kind: String,
+    /// `run_command` or the qualified plugin tool name.
+    pub action_id: String,
+    pub command: Option<String>,
+    pub explanation: Option<String>,
+    pub arguments: Option<Value>,
+}
+
+/// Reply to the launcher.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentReply {
+    /// "done" | "needs_confirmation" | "error"
+    pub status: String,
+    pub steps: Vec<AgentStep>,
+    /// The final answer, or the error.
+    pub answer: Option<String>,
+    pub pending: Option<PendingCall>,
+}
+
+/// A tool offered to the model.
+#[derive(Debug, Clone)]
+pub(super) struct AgentTool {
+    /// Name on the wire.
+    pub(super) wire: String,
+    pub(super) action_id: String,
+    pub(super) label: String,
+    /// The plugin tool; `None` for run_command.
+    pub(super) tool: Option<RegisteredTool>,
+}
+
+/// A run in progress.
+pub(super) struct AgentRun {
+    /// "anthropic" | "gemini"
+    pub(super) provider: String,
+    pub(super) messages: Vec<Value>,
+    pub(super) tools: Vec<AgentTool>,
+    pub(super) specs: Vec<ToolSpec>,
+    pub(super) steps: Vec<AgentStep>,
+    /// Calls from the last model turn not handled yet.
+    pub(super) queue: VecDeque<ToolCall>,
+    /// `tool_result` blocks for the next user turn.
+    pub(super) results: Vec<Value>,
+    /// The call waiting for the user.
+    pub(super) waiting: Option<(ToolCall, AgentTool, PendingCall)>,
+}
+
+/// The launcher's agent run waiting for the user. Managed as Tauri state.
+pub struct AgentState {
+    pub(super) run: Mutex<Option<AgentRun>>,
+}
+
+impl AgentState {
+    pub fn new() -> Self {
+        Self {
+            run: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for AgentState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
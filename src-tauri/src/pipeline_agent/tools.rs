@@ -0,0 +1,132 @@
+//! The tools offered to the model: enabled plugin tools plus run_command,
+//! with wire names and input schemas.
+
+use super::state::AgentTool;
+use crate::llm::tool_use::{self, ToolSpec};
+use crate::mcp::registry::RegisteredTool;
+use crate::mcp::{self, ToolRegistry};
+use serde_json::json;
+use std::collections::HashSet;
+
+const RUN_COMMAND: &str = "run_command";
+
+/// Enabled plugin tools, plus run_command, in a stable order.
+pub(super) async fn agent_tools(registry: &ToolRegistry) -> Vec<AgentTool> {
+    let prefs = mcp::tool_prefs::load_prefs();
+    let mut available: Vec<RegisteredTool> = registry
+        .all_tools()
+        .await
+        .into_iter()
+        .filter(|t| prefs.is_enabled(&mcp::tool_prefs::tool_key(t)))
+        .filter(|t| t.plugin_id != "builtin" || t.name == RUN_COMMAND)
+        .collect();
+    available.sort_by_key(mcp::tool_prefs::tool_key);
+
+    let mut taken = HashSet::new();
+    available
+        .into_iter()
+        .map(|t| {
+            let action_id = mcp::tool_prefs::tool_key(&t);
+            let wire = tool_use::wire_name(&action_id, &taken);
+            taken.insert(wire.clone());
+            AgentTool {
+                wire,
+                action_id,
+                label: t.display_name.clone(),
+                tool: (t.plugin_id != "builtin").then_some(t),
+            }
+        })
+        .collect()
+}
+
+/// The host OS as users know it.
+fn host_os() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "macOS",
+        "windows" => "Windows",
+        "linux" => "Linux",
+        other => other,
+    }
+}
+
+pub(super) fn spec(tool: &AgentTool) -> ToolSpec {
+    match &tool.tool {
+        None => ToolSpec {
+            name: tool.wire.clone(),
+            description: format!(
+                "Run a shell command on the user's {} computer and get its output. The user sees and approves the command before it runs.",
+                host_os()
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "command": { "type": "string", "description": "The shell command" },
+                    "explanation": {
+                        "type": "string",
+                        "description": "One sentence on what the command does, shown to the user"
+                    }
+                },
+                "required": ["command", "explanation"]
+            }),
+        },
+        Some(t) => ToolSpec {
+            name: tool.wire.clone(),
+            description: t.description.clone(),
+            input_schema: t
+                .input_schema
+                .clone()
+                .filter(|s| s.get("type") == Some(&json!("object")))
+                .unwrap_or_else(tool_use::text_schema),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn plugin_tool(input_schema: Option<Value>) -> AgentTool {
+        AgentTool {
+            wire: "com_example_gh_get_issue".to_string(),
+            action_id: "com.example.gh:get_issue".to_string(),
+            label: "Get Issue".to_string(),
+            tool: Some(RegisteredTool {
+                plugin_id: "com.example.gh".to_string(),
+                name: "get_issue".to_string(),
+                display_name: "Get Issue".to_string(),
+                description: "Fetch a GitHub issue".to_string(),
+                input_schema,
+                output_schema: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn specs_always_send_object_schemas() {
+        let schema = json!({"type":"object","properties":{"number":{"type":"integer"}}});
+        assert_eq!(
+            spec(&plugin_tool(Some(schema.clone()))).input_schema,
+            schema
+        );
+        assert_eq!(
+            spec(&plugin_tool(None)).input_schema,
+            tool_use::text_schema()
+        );
+        assert_eq!(
+            spec(&plugin_tool(Some(json!({"type":"string"})))).input_schema,
+            tool_use::text_schema()
+        );
+
+        let run_command = AgentTool {
+            wire: RUN_COMMAND.to_string(),
+            action_id: RUN_COMMAND.to_string(),
+            label: "Run Command".to_string(),
+            tool: None,
+        };
+        assert_eq!(
+            spec(&run_command).input_schema["required"],
+            json!(["command", "explanation"])
+        );
+    }
+}
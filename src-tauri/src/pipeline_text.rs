@@ -4,6 +4,11 @@
 //! The user types text in the launcher (Cmd+Shift+Space) instead of
 //! snipping a screen region. The LLM decides: respond directly or
 //! route to a tool (built-in or plugin).
+//!
+//! Anthropic and Gemini get the enabled registry tools natively
+//! (`native.rs`), so a plugin call arrives with its arguments and skips
+//! the args bridge. The local model routes with a JSON decision
+//! constrained by its grammar.
 
 use crate::llm;
 use crate::mcp;
@@ -11,8 +16,10 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "local-llm")]
 use tauri::Manager;
 
-use llm::prompts_text_command::{self, TEXT_COMMAND_MAX_TOKENS, TEXT_COMMAND_SYSTEM_PROMPT};
-use llm::streaming;
+use llm::prompts_text_command::{TEXT_COMMAND_MAX_TOKENS, TEXT_COMMAND_SYSTEM_PROMPT};
+use mcp::registry::RegisteredTool;
+
+mod native;
 
 /// Result returned to the text launcher frontend.
 ///
@@ -24,15 +31,22 @@ pub struct TextCommandResult {
     pub status: String,
     pub text: String,
     pub action_id: Option<String>,
-    /// "text" | "command" | "clipboard" | "file"
+    /// "text" | "command" | "clipboard" | "file" | "image" | "link" | "tool_args"
     pub result_type: String,
     pub command: Option<String>,
     pub file_path: Option<String>,
     pub file_content: Option<String>,
     pub clipboard_content: Option<String>,
+    /// Base64 payload for image and binary file results.
+    pub data: Option<String>,
+    pub mime_type: Option<String>,
+    pub url: Option<String>,
+    /// Plugin arguments awaiting review ("tool_args" results).
+    pub arguments: Option<serde_json::Value>,
 }
 
-/// LLM routing decision — parsed from the LLM response.
+/// LLM routing decision — parsed from the local model's response.
+#[cfg(feature = "local-llm")]
 #[derive(Debug, Deserialize)]
 struct RouteDecision {
     #[serde(rename = "type")]
@@ -42,6 +56,18 @@ struct RouteDecision {
     input_text: Option<String>,
 }
 
+#[cfg(feature = "local-llm")]
+impl RouteDecision {
+    /// Local models may use non-standard type names (e.g. "run_command" instead
+    /// of "tool"). Normalize: if there's a tool_id, treat as tool; otherwise direct.
+    fn is_tool(&self) -> bool {
+        self.decision_type == "tool"
+            || self.tool_id.is_some()
+            || self.decision_type.contains("command")
+            || self.decision_type.contains("tool")
+    }
+}
+
 /// Tauri command: execute a typed text command.
 ///
 /// Routes through LLM to decide: direct response or tool dispatch.
@@ -53,29 +79,35 @@ pub async fn execute_text_command(
 ) -> Result<TextCommandResult, String> {
     log::info!("[TEXT_CMD] Input: {} chars", text.len());
 
-    // Get all available tools for the LLM prompt
-    let all_tools = registry.all_tools().await;
-    let tool_descriptions: Vec<String> = all_tools
-        .iter()
-        .map(|t| {
-            let qname = mcp::registry::qualified_name(&t.plugin_id, &t.name);
-            format!("- {} ({}): {}", t.display_name, qname, t.description)
-        })
+    // The enabled tools are the ones the router may pick
+    let prefs = mcp::tool_prefs::load_prefs();
+    let tools: Vec<RegisteredTool> = registry
+        .all_tools()
+        .await
+        .into_iter()
+        .filter(|t| prefs.is_enabled(&mcp::tool_prefs::tool_key(t)))
         .collect();
-    let tools_prompt = tool_descriptions.join("\n");
 
-    // Route to the active provider for the routing decision
     let provider = crate::settings_commands::resolve_provider();
-    let json_text = match provider.as_str() {
-        #[cfg(feature = "local-llm")]
-        "local" => {
-            let local_state = app.state::<llm::local_state::LocalLlmState>();
-            llm::local::execute_text_command_local(&text, &tools_prompt, &local_state).await?
-        }
-        _ => {
-            route_via_anthropic(&text, &tools_prompt).await?
-        }
-    };
+    #[cfg(feature = "local-llm")]
+    if provider == "local" {
+        return route_via_local(&app, &registry, &prefs, &tools, &text).await;
+    }
+    native::route_natively(&app, &registry, &prefs, &tools, &text, &provider).await
+}
+
+/// Route with the local model's JSON decision (tools described in the prompt).
+#[cfg(feature = "local-llm")]
+async fn route_via_local(
+    app: &tauri::AppHandle,
+    registry: &mcp::ToolRegistry,
+    prefs: &mcp::tool_prefs::ToolPrefs,
+    tools: &[RegisteredTool],
+    text: &str,
+) -> Result<TextCommandResult, String> {
+    let local_state = app.state::<llm::local_state::LocalLlmState>();
+    let json_text =
+        llm::local::execute_text_command_local(text, &tools_prompt(tools), &local_state).await?;
 
     let decision: RouteDecision = serde_json::from_str(&json_text)
         .map_err(|e| format!("Failed to parse LLM routing decision: {}", e))?;
@@ -87,36 +119,79 @@ pub async fn execute_text_command(
         decision.text.as_deref().map(|t| &t[..80.min(t.len())])
     );
 
-    // Local models may use non-standard type names (e.g. "run_command" instead
-    // of "tool"). Normalize: if there's a tool_id, treat as tool; otherwise direct.
-    let is_tool = decision.decision_type == "tool"
-        || decision.tool_id.is_some()
-        || decision.decision_type.contains("command")
-        || decision.decision_type.contains("tool");
-
-    if is_tool {
+    if decision.is_tool() {
         let tool_id = decision.tool_id.unwrap_or_else(|| {
             // Local model may put the tool name in the type field
             decision.decision_type.clone()
         });
-        let input = decision.input_text.or(decision.text).unwrap_or_else(|| text.clone());
+        let input = decision
+            .input_text
+            .or(decision.text)
+            .unwrap_or_else(|| text.to_string());
         log::info!("[TEXT_CMD] Routing to tool: {}", tool_id);
-        route_to_tool(&app, &registry, &tool_id, &input).await
+        record_launcher_choice(registry, prefs, text, &tool_id).await;
+        route_to_tool(app, registry, &tool_id, &input).await
     } else {
         log::info!("[TEXT_CMD] Direct response");
-        Ok(TextCommandResult {
-            status: "success".to_string(),
-            text: decision.text.unwrap_or_default(),
-            action_id: None,
-            result_type: "text".to_string(),
-            command: None,
-            file_path: None,
-            file_content: None,
-            clipboard_content: None,
+        Ok(direct_result(decision.text.unwrap_or_default()))
+    }
+}
+
+/// The enabled tools as prompt lines for the local router.
+#[cfg(feature = "local-llm")]
+fn tools_prompt(tools: &[RegisteredTool]) -> String {
+    tools
+        .iter()
+        .map(|t| {
+            let qname = mcp::registry::qualified_name(&t.plugin_id, &t.name);
+            format!("- {} ({}): {}", t.display_name, qname, t.description)
         })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A direct answer, no tool involved.
+fn direct_result(text: String) -> TextCommandResult {
+    TextCommandResult {
+        status: "success".to_string(),
+        text,
+        action_id: None,
+        result_type: "text".to_string(),
+        command: None,
+        file_path: None,
+        file_content: None,
+        clipboard_content: None,
+        data: None,
+        mime_type: None,
+        url: None,
+        arguments: None,
     }
 }
 
+/// Count a plugin tool the router picked in the relevance metrics: whether
+/// the snip filter, given the typed text, would have offered it. The
+/// launcher sees every tool, so this is where the filter's misses show.
+async fn record_launcher_choice(
+    registry: &mcp::ToolRegistry,
+    prefs: &mcp::tool_prefs::ToolPrefs,
+    text: &str,
+    tool_id: &str,
+) {
+    if !registry.is_plugin_action(tool_id).await {
+        return;
+    }
+    let Some(qname) = registry.resolve_action(tool_id).await else {
+        return;
+    };
+    let query = mcp::relevance::Query::from_snip(
+        text,
+        crate::ocr::heuristics::detect_table_structure(text),
+        crate::ocr::heuristics::detect_code_structure(text),
+    );
+    let selection = registry.select_tools(prefs, &query).await;
+    mcp::relevance::record_launcher_choice(selection.offered.contains(&qname));
+}
+
 /// Dispatch to a tool — built-in (LLM execute) or plugin (MCP).
 async fn route_to_tool(
     app: &tauri::AppHandle,
@@ -124,29 +199,16 @@ async fn route_to_tool(
     tool_id: &str,
     input_text: &str,
 ) -> Result<TextCommandResult, String> {
-    // Strip plugin prefix (e.g. "builtin::run_command" or "builtin:run_command" → "run_command")
-    let bare_id = tool_id
-        .rsplit_once("::")
-        .map(|(_, name)| name)
-        .unwrap_or_else(|| tool_id.rsplit_once(':').map(|(_, name)| name).unwrap_or(tool_id));
-    eprintln!("[TEXT_CMD] Dispatching tool: raw={}, bare={}", tool_id, bare_id);
-
-    let result = if registry.is_plugin_action(tool_id).await {
-        // Plugin tool — use MCP dispatch with args bridge
-        let resolved = registry.resolve_action(tool_id).await;
-        let tool_meta = match &resolved {
-            Some(qname) => registry.get_tool(qname).await,
-            None => None,
-        };
-        mcp::execute_plugin_tool(
-            registry,
-            tool_id,
-            input_text,
-            tool_meta.as_ref().map(|t| t.description.as_str()),
-            tool_meta.as_ref().and_then(|t| t.input_schema.as_ref()),
-            Some(app),
-        )
-        .await
+    let bare_id = bare_tool_id(tool_id);
+    eprintln!(
+        "[TEXT_CMD] Dispatching tool: raw={}, bare={}",
+        tool_id, bare_id
+    );
+
+    // Plugin tool — use MCP dispatch with args bridge
+    let plugin_result = mcp::run_plugin_action(registry, tool_id, input_text, Some(app)).await;
+    let result = if let Some(result) = plugin_result {
+        result
     } else {
         // Built-in tool — dispatch to active provider
         let provider = crate::settings_commands::resolve_provider();
@@ -160,69 +222,37 @@ async fn route_to_tool(
         }
     };
 
-    Ok(TextCommandResult {
+    Ok(to_text_result(tool_id, result))
+}
+
+/// Strip the plugin prefix (e.g. "builtin::run_command" or
+/// "builtin:run_command" → "run_command").
+fn bare_tool_id(tool_id: &str) -> &str {
+    tool_id
+        .rsplit_once("::")
+        .map(|(_, name)| name)
+        .unwrap_or_else(|| {
+            tool_id
+                .rsplit_once(':')
+                .map(|(_, name)| name)
+                .unwrap_or(tool_id)
+        })
+}
+
+/// Convert a tool's ActionResult into the launcher's result.
+fn to_text_result(tool_id: &str, result: llm::ActionResult) -> TextCommandResult {
+    TextCommandResult {
         status: result.status,
         text: result.result.text.unwrap_or_default(),
-        action_id: Some(bare_id.to_string()),
+        action_id: Some(bare_tool_id(tool_id).to_string()),
         result_type: result.result.result_type,
         command: result.result.command,
         file_path: result.result.file_path,
         file_content: None, // File content handled via file_path
         clipboard_content: result.result.clipboard_content,
-    })
-}
-
-/// Call Anthropic API for the text command routing decision.
-async fn route_via_anthropic(text: &str, tools_prompt: &str) -> Result<String, String> {
-    let user_message = prompts_text_command::build_text_command_message(text, tools_prompt);
-
-    let api_key = std::env::var("ANTHROPIC_API_KEY")
-        .map_err(|_| "No API key configured".to_string())?;
-    if api_key.is_empty() {
-        return Err("No API key configured".to_string());
-    }
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .post("https://api.anthropic.com/v1/messages")
-        .header("x-api-key", &api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&serde_json::json!({
-            "model": llm::prompts::MODEL,
-            "max_tokens": TEXT_COMMAND_MAX_TOKENS,
-            "system": TEXT_COMMAND_SYSTEM_PROMPT,
-            "messages": [{"role": "user", "content": user_message}]
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("API call failed: {}", e))?;
-
-    if !resp.status().is_success() {
-        let body = resp.text().await.unwrap_or_default();
-        return Err(format!("API error: {}", &body[..200.min(body.len())]));
-    }
-
-    let body = resp.text().await.map_err(|e| e.to_string())?;
-    let response_text = extract_text(&body)?;
-    eprintln!("[TEXT_CMD] Raw router response: {}", &response_text[..300.min(response_text.len())]);
-    Ok(streaming::strip_code_fences(&response_text))
-}
-
-/// Extract text content from an Anthropic Messages API response.
-fn extract_text(body: &str) -> Result<String, String> {
-    let parsed: serde_json::Value =
-        serde_json::from_str(body).map_err(|e| format!("Invalid response: {}", e))?;
-    let content = parsed
-        .get("content")
-        .and_then(|c| c.as_array())
-        .ok_or("No content in response")?;
-    for block in content {
-        if block.get("type").and_then(|t| t.as_str()) == Some("text") {
-            if let Some(t) = block.get("text").and_then(|t| t.as_str()) {
-                return Ok(t.to_string());
-            }
-        }
+        data: result.result.data,
+        mime_type: result.result.mime_type,
+        url: result.result.url,
+        arguments: result.result.arguments,
     }
-    Err("No text in response".to_string())
 }
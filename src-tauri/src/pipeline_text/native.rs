@@ -0,0 +1,92 @@
+//! Native tool routing for the text launcher (Anthropic, Gemini).
+//!
+//! Each enabled registry tool becomes a `ToolSpec` under a provider-safe
+//! wire name. The model answers in text or calls one tool; plugin calls
+//! run with the model's arguments, so they skip the args bridge.
+
+use super::{direct_result, record_launcher_choice, route_to_tool, to_text_result};
+use super::{TextCommandResult, TEXT_COMMAND_MAX_TOKENS, TEXT_COMMAND_SYSTEM_PROMPT};
+use crate::llm::tool_use::{self, ModelTurn, ToolSpec};
+use crate::mcp;
+use crate::mcp::registry::RegisteredTool;
+use std::collections::HashSet;
+
+/// Route `text` with native tool calling through `provider`.
+pub(super) async fn route_natively(
+    app: &tauri::AppHandle,
+    registry: &mcp::ToolRegistry,
+    prefs: &mcp::tool_prefs::ToolPrefs,
+    tools: &[RegisteredTool],
+    text: &str,
+    provider: &str,
+) -> Result<TextCommandResult, String> {
+    let (specs, action_ids) = tool_specs(tools);
+    let turn = complete(provider, text, &specs).await?;
+
+    let Some(call) = turn.calls.into_iter().next() else {
+        log::info!("[TEXT_CMD] Direct response");
+        return Ok(direct_result(turn.text));
+    };
+    let action_id = specs
+        .iter()
+        .position(|s| s.name == call.name)
+        .map(|i| action_ids[i].clone())
+        .ok_or_else(|| format!("Model called an unknown tool: {}", call.name))?;
+    log::info!("[TEXT_CMD] Routing to tool: {}", action_id);
+    record_launcher_choice(registry, prefs, text, &action_id).await;
+
+    if let Some(result) =
+        mcp::run_plugin_call(registry, &action_id, call.input.clone(), text, Some(app)).await
+    {
+        return Ok(to_text_result(&action_id, result));
+    }
+    let input = call
+        .input
+        .get("text")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(text);
+    route_to_tool(app, registry, &action_id, input).await
+}
+
+/// One spec per tool, and the action id behind each spec, by index.
+fn tool_specs(tools: &[RegisteredTool]) -> (Vec<ToolSpec>, Vec<String>) {
+    let mut taken = HashSet::new();
+    let mut action_ids = Vec::with_capacity(tools.len());
+    let specs = tools
+        .iter()
+        .map(|t| {
+            let action_id = mcp::tool_prefs::tool_key(t);
+            let wire = tool_use::wire_name(&action_id, &taken);
+            taken.insert(wire.clone());
+            action_ids.push(action_id);
+            ToolSpec {
+                name: wire,
+                description: t.description.clone(),
+                input_schema: t
+                    .input_schema
+                    .clone()
+                    .filter(|s| s.get("type") == Some(&serde_json::json!("object")))
+                    .unwrap_or_else(tool_use::text_schema),
+            }
+        })
+        .collect();
+    (specs, action_ids)
+}
+
+/// One model turn with the tools offered.
+async fn complete(provider: &str, text: &str, specs: &[ToolSpec]) -> Result<ModelTurn, String> {
+    let messages = [serde_json::json!({ "role": "user", "content": text })];
+    let system = TEXT_COMMAND_SYSTEM_PROMPT;
+    if provider == "gemini" {
+        return tool_use::complete_with_tools_gemini(
+            system,
+            &messages,
+            specs,
+            true,
+            TEXT_COMMAND_MAX_TOKENS,
+        )
+        .await;
+    }
+    tool_use::complete_with_tools(system, &messages, specs, true, TEXT_COMMAND_MAX_TOKENS).await
+}
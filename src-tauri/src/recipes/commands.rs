@@ -0,0 +1,58 @@
+//! Tauri commands for recipes: editing them in Settings, and confirming or
+//! cancelling a step that waits for review in the action menu.
+
+use crate::llm::{self, ActionResult};
+use crate::mcp::confirm::{self, ConfirmPref};
+use crate::mcp::ToolRegistry;
+use crate::recipes::{self, RecipeBook};
+
+/// Every saved recipe.
+#[tauri::command]
+pub fn get_recipes() -> RecipeBook {
+    recipes::load_recipes()
+}
+
+/// Replace the saved recipes. Nothing is written if any recipe is invalid;
+/// the error lists every problem.
+#[tauri::command]
+pub fn save_recipes(book: RecipeBook) -> Result<(), String> {
+    recipes::save_recipes(&book)?;
+    log::info!("[RECIPES] Saved {} recipe(s)", book.recipes.len());
+    Ok(())
+}
+
+/// Run the recipe step waiting for review with the user's arguments, then
+/// the rest of the recipe. Arguments are validated against the tool's
+/// schema first, so the form can show errors; `remember` updates the tool's
+/// review preference as in `confirm_plugin_call`.
+#[tauri::command]
+pub async fn continue_recipe(
+    app: tauri::AppHandle,
+    arguments: serde_json::Value,
+    remember: Option<ConfirmPref>,
+    state: tauri::State<'_, llm::ActionMenuState>,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<ActionResult, String> {
+    let action = state
+        .recipe_run
+        .lock()
+        .map_err(|e| e.to_string())?
+        .as_ref()
+        .map(|run| run.waiting_action().to_string())
+        .ok_or("No recipe is waiting for confirmation")?;
+    let tool = registry
+        .tool_for_action(&action)
+        .await
+        .ok_or_else(|| format!("Unknown plugin action: {}", action))?;
+    confirm::accept_reviewed(&tool, &arguments, remember)?;
+    recipes::runner::resume(&app, &state, &registry, arguments).await
+}
+
+/// Drop the recipe waiting for review; its remaining steps don't run.
+#[tauri::command]
+pub fn cancel_recipe(state: tauri::State<'_, llm::ActionMenuState>) -> Result<(), String> {
+    if let Some(run) = state.recipe_run.lock().map_err(|e| e.to_string())?.take() {
+        log::info!("[RECIPES] '{}' cancelled", run.recipe_id());
+    }
+    Ok(())
+}
@@ -0,0 +1,284 @@
+//! Recipes — user-defined chains of actions offered as one menu entry.
+//!
+//! A recipe lists steps, each a built-in action or a plugin tool. The
+//! chain runner (`runner.rs`) passes each step's result on as the next
+//! step's input, so "explain_error → create a GitHub issue" runs as one
+//! click. Plugin steps stop for the user to review their arguments before
+//! they run. Recipes live in `recipes.json`:
+//!
+//! ```json
+//! { "recipes": [{
+//!     "id": "report-error",
+//!     "label": "Explain & File Issue",
+//!     "contentTypes": ["error"],
+//!     "steps": [
+//!       { "action": "explain_error" },
+//!       { "action": "com.example.gh:create_issue",
+//!         "input": "{previous}\n\nOriginal error:\n{text}" }
+//!     ]
+//! }] }
+//! ```
+
+pub mod commands;
+pub mod runner;
+mod store;
+mod validate;
+
+pub use store::{load_recipes, save_recipes};
+pub use validate::validate;
+
+use crate::llm::execute::{ActionResult, ActionResultMetadata};
+use crate::llm::types::{Action, ActionMenu};
+use crate::mcp::tool_prefs::{self, ToolPrefs};
+use crate::mcp::ToolRegistry;
+use serde::{Deserialize, Serialize};
+
+/// Action id prefix of recipe menu entries ("recipe:<id>").
+pub const ACTION_PREFIX: &str = "recipe:";
+
+/// Most steps in one recipe.
+pub const MAX_STEPS: usize = 8;
+
+/// Placeholder for the previous step's output (the OCR text for step 1).
+const PREVIOUS: &str = "{previous}";
+
+/// Placeholder for the snip's OCR text.
+const TEXT: &str = "{text}";
+
+/// Copies the piped text to the clipboard; only as the last step.
+const COPY_ACTION: &str = "copy_text";
+
+/// Every saved recipe.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecipeBook {
+    #[serde(default)]
+    pub recipes: Vec<Recipe>,
+}
+
+/// A named chain of actions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recipe {
+    /// Letters, digits, '-' and '_'; unique.
+    pub id: String,
+    pub label: String,
+    #[serde(default = "default_icon")]
+    pub icon: String,
+    /// Content types the recipe is offered for; empty offers it for every snip.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+    pub steps: Vec<RecipeStep>,
+}
+
+fn default_icon() -> String {
+    "sparkles".to_string()
+}
+
+/// One action in a recipe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeStep {
+    /// A built-in action ("explain_error") or plugin tool ("plugin_id:tool_name").
+    pub action: String,
+    /// What the step gets as its text. `{previous}` is the previous step's
+    /// output, `{text}` the snip's OCR text. Defaults to `{previous}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// `true` makes a plugin step always wait for the user to review its
+    /// arguments. Otherwise the tool's review preference decides, as in the
+    /// action menu; a step can't skip a review the user asked for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm: Option<bool>,
+}
+
+/// Where a running recipe is, attached to each result it returns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeProgress {
+    pub recipe_id: String,
+    pub label: String,
+    /// 1-based step the result belongs to.
+    pub step: usize,
+    pub steps: usize,
+    pub action: String,
+}
+
+impl RecipeStep {
+    /// The text this step runs on.
+    pub fn input_for(&self, text: &str, previous: &str) -> String {
+        match &self.input {
+            Some(template) => template.replace(PREVIOUS, previous).replace(TEXT, text),
+            None => previous.to_string(),
+        }
+    }
+}
+
+impl Recipe {
+    /// Whether the recipe fits a snip of `content_type`.
+    pub fn offered_for(&self, content_type: &str) -> bool {
+        self.content_types.is_empty() || self.content_types.iter().any(|t| t == content_type)
+    }
+
+    /// The recipe's single action menu entry.
+    pub fn menu_action(&self) -> Action {
+        let chain: Vec<&str> = self.steps.iter().map(|s| s.action.as_str()).collect();
+        Action {
+            id: format!("{}{}", ACTION_PREFIX, self.id),
+            label: self.label.clone(),
+            icon: self.icon.clone(),
+            priority: u8::MAX,
+            description: format!("Recipe: {}", chain.join(" → ")),
+            requires_execution: true,
+        }
+    }
+
+    /// Progress metadata for step `index` (0-based).
+    pub fn progress(&self, index: usize) -> RecipeProgress {
+        RecipeProgress {
+            recipe_id: self.id.clone(),
+            label: self.label.clone(),
+            step: index + 1,
+            steps: self.steps.len(),
+            action: self.steps[index].action.clone(),
+        }
+    }
+}
+
+/// The text a result passes to the next step, if it has any.
+pub fn piped_text(result: &ActionResult) -> Option<String> {
+    let body = &result.result;
+    match body.result_type.as_str() {
+        "text" | "file" if body.data.is_none() => body.text.clone(),
+        "clipboard" => body.clipboard_content.clone(),
+        "link" => body.url.clone(),
+        _ => None,
+    }
+    .filter(|t| !t.trim().is_empty())
+}
+
+/// Tag a result with the recipe step it came from.
+pub fn attach_progress(result: &mut ActionResult, progress: RecipeProgress) {
+    result
+        .metadata
+        .get_or_insert(ActionResultMetadata {
+            tokens_used: None,
+            processing_note: None,
+            limit: None,
+            recipe: None,
+        })
+        .recipe = Some(progress);
+}
+
+/// Add an entry for each recipe that fits the menu's content type and whose
+/// plugin steps are all installed and enabled, then reapply tool
+/// preferences (recipes can be pinned like any action).
+pub async fn add_to_menu(
+    mut menu: ActionMenu,
+    book: &RecipeBook,
+    registry: &ToolRegistry,
+    prefs: &ToolPrefs,
+) -> ActionMenu {
+    let mut added = 0;
+    for recipe in &book.recipes {
+        if !recipe.offered_for(&menu.content_type) || !recipe.problems().is_empty() {
+            continue;
+        }
+        if !steps_available(recipe, registry, prefs).await {
+            log::info!(
+                "[RECIPES] '{}' not offered: a step's tool is missing or disabled",
+                recipe.id
+            );
+            continue;
+        }
+        menu.actions.push(recipe.menu_action());
+        added += 1;
+    }
+    if added == 0 {
+        return menu;
+    }
+    tool_prefs::apply(menu, prefs)
+}
+
+async fn steps_available(recipe: &Recipe, registry: &ToolRegistry, prefs: &ToolPrefs) -> bool {
+    for step in &recipe.steps {
+        if !prefs.is_enabled(&step.action) {
+            return false;
+        }
+        if step.action.contains(':') && registry.resolve_action(&step.action).await.is_none() {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(json: &str) -> Recipe {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn report_error() -> Recipe {
+        recipe(
+            r#"{"id":"report-error","label":"Explain & File Issue","contentTypes":["error"],
+                "steps":[{"action":"explain_error"},
+                         {"action":"com.example.gh:create_issue",
+                          "input":"{previous}\n\nOriginal error:\n{text}"}]}"#,
+        )
+    }
+
+    #[test]
+    fn inputs_fill_placeholders_and_default_to_previous() {
+        let r = report_error();
+        assert_eq!(r.steps[0].input_for("boom", "boom"), "boom");
+        assert_eq!(
+            r.steps[1].input_for("boom", "It exploded."),
+            "It exploded.\n\nOriginal error:\nboom"
+        );
+    }
+
+    #[test]
+    fn menu_entry_and_content_types() {
+        let r = report_error();
+        let action = r.menu_action();
+        assert_eq!(action.id, "recipe:report-error");
+        assert_eq!(
+            action.description,
+            "Recipe: explain_error → com.example.gh:create_issue"
+        );
+        assert!(r.offered_for("error"));
+        assert!(!r.offered_for("table"));
+        let anywhere = recipe(r#"{"id":"any","label":"Any","steps":[{"action":"explain"}]}"#);
+        assert!(anywhere.offered_for("unknown"));
+        assert_eq!(anywhere.icon, "sparkles");
+    }
+
+    #[test]
+    fn piped_text_takes_what_the_next_step_can_read() {
+        let text = ActionResult::text("explain", "Meaning");
+        assert_eq!(piped_text(&text).as_deref(), Some("Meaning"));
+
+        let mut csv = ActionResult::text("export_csv", "a,b\n1,2");
+        csv.result.result_type = "file".to_string();
+        assert_eq!(piped_text(&csv).as_deref(), Some("a,b\n1,2"));
+        csv.result.data = Some("AAAA".to_string());
+        assert_eq!(piped_text(&csv), None);
+
+        let mut command = ActionResult::text("run_command", "");
+        command.result.result_type = "command".to_string();
+        command.result.command = Some("ls".to_string());
+        assert_eq!(piped_text(&command), None);
+        assert_eq!(piped_text(&ActionResult::text("explain", "  ")), None);
+    }
+
+    #[test]
+    fn progress_is_attached_to_results() {
+        let r = report_error();
+        let mut result = ActionResult::text("explain_error", "x");
+        attach_progress(&mut result, r.progress(1));
+        let progress = result.metadata.unwrap().recipe.unwrap();
+        assert_eq!((progress.step, progress.steps), (2, 2));
+        assert_eq!(progress.action, "com.example.gh:create_issue");
+    }
+}
@@ -0,0 +1,245 @@
+//! Chain runner — runs a recipe's steps in order, piping results along.
+//!
+//! Each step runs on its input (see `RecipeStep::input_for`) and its result
+//! becomes `{previous}` for the next. Built-in steps go through the active
+//! LLM provider like a menu click; plugin steps build their arguments with
+//! the args bridge. A plugin step that needs confirmation returns a
+//! `tool_args` review instead of running, and the run waits in
+//! `ActionMenuState::recipe_run` until `continue_recipe` brings the
+//! reviewed arguments (or `cancel_recipe` drops it).
+//!
+//! The chain stops at the first error, and at a result with nothing to pass
+//! on (a command, an image) — which is shown as is, so a suggested command
+//! still goes through the usual confirmation dialog. Every step's result is
+//! added to the snip's history.
+
+use crate::llm::execute::ActionResult;
+use crate::llm::ActionMenuState;
+use crate::mcp::confirm;
+use crate::mcp::{self, args, ToolRegistry};
+use crate::recipes::{self, Recipe, RecipeStep};
+
+/// A recipe in progress.
+#[derive(Debug, Clone)]
+pub struct PendingRun {
+    recipe: Recipe,
+    /// Index of the step to run next (or waiting for review).
+    next: usize,
+    /// The snip's OCR text, for `{text}`.
+    text: String,
+    /// The previous step's output, for `{previous}`.
+    previous: String,
+    adversarial: bool,
+}
+
+impl PendingRun {
+    /// Id of the recipe, for log lines.
+    pub fn recipe_id(&self) -> &str {
+        &self.recipe.id
+    }
+
+    /// Action of the step to run next (the one waiting for review).
+    pub fn waiting_action(&self) -> &str {
+        &self.recipe.steps[self.next].action
+    }
+}
+
+/// How one step ended.
+enum Step {
+    Done(ActionResult),
+    /// Arguments awaiting review; the run is parked.
+    Held(ActionResult),
+}
+
+/// Start recipe `recipe_id` on the current snip's text.
+pub async fn start(
+    app: &tauri::AppHandle,
+    state: &ActionMenuState,
+    registry: &ToolRegistry,
+    recipe_id: &str,
+    text: &str,
+) -> ActionResult {
+    let action_id = format!("{}{}", recipes::ACTION_PREFIX, recipe_id);
+    let Some(recipe) = recipes::load_recipes()
+        .recipes
+        .into_iter()
+        .find(|r| r.id == recipe_id)
+    else {
+        return ActionResult::error(&action_id, &format!("No recipe '{}'", recipe_id));
+    };
+    let problems = recipe.problems();
+    if !problems.is_empty() {
+        return ActionResult::error(&action_id, &problems.join("\n"));
+    }
+    let adversarial = state
+        .injection
+        .lock()
+        .ok()
+        .and_then(|r| r.as_ref().map(|r| r.adversarial))
+        .unwrap_or(false);
+    log::info!(
+        "[RECIPES] Running '{}' ({} steps)",
+        recipe.id,
+        recipe.steps.len()
+    );
+    let run = PendingRun {
+        recipe,
+        next: 0,
+        text: text.to_string(),
+        previous: text.to_string(),
+        adversarial,
+    };
+    advance(app, state, registry, run).await
+}
+
+/// Run the parked step with the user's reviewed arguments, then go on.
+pub async fn resume(
+    app: &tauri::AppHandle,
+    state: &ActionMenuState,
+    registry: &ToolRegistry,
+    arguments: serde_json::Value,
+) -> Result<ActionResult, String> {
+    let run = state
+        .recipe_run
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("No recipe is waiting for confirmation")?;
+    let action = run.waiting_action().to_string();
+    let tool = registry
+        .tool_for_action(&action)
+        .await
+        .ok_or_else(|| format!("Unknown plugin action: {}", action))?;
+    log::info!(
+        "[RECIPES] '{}' step {} confirmed",
+        run.recipe.id,
+        run.next + 1
+    );
+    let result = mcp::call_with_arguments(registry, &action, arguments, Some(&tool)).await;
+    Ok(match after_step(state, run, result) {
+        Ok(run) => advance(app, state, registry, run).await,
+        Err(result) => result,
+    })
+}
+
+/// Run steps from `run.next` until the recipe ends or a step is held.
+async fn advance(
+    app: &tauri::AppHandle,
+    state: &ActionMenuState,
+    registry: &ToolRegistry,
+    mut run: PendingRun,
+) -> ActionResult {
+    loop {
+        let step = run.recipe.steps[run.next].clone();
+        let input = step.input_for(&run.text, &run.previous);
+        match run_step(app, registry, &step, &input, run.adversarial).await {
+            Step::Held(mut review) => {
+                recipes::attach_progress(&mut review, run.recipe.progress(run.next));
+                log::info!(
+                    "[RECIPES] '{}' step {} waiting for review",
+                    run.recipe.id,
+                    run.next + 1
+                );
+                if let Ok(mut slot) = state.recipe_run.lock() {
+                    *slot = Some(run);
+                }
+                return review;
+            }
+            Step::Done(result) => match after_step(state, run, result) {
+                Ok(next) => run = next,
+                Err(result) => return result,
+            },
+        }
+    }
+}
+
+async fn run_step(
+    app: &tauri::AppHandle,
+    registry: &ToolRegistry,
+    step: &RecipeStep,
+    input: &str,
+    adversarial: bool,
+) -> Step {
+    if step.action == recipes::COPY_ACTION {
+        let mut result = ActionResult::text(&step.action, "");
+        result.result.result_type = "clipboard".to_string();
+        result.result.text = None;
+        result.result.clipboard_content = Some(input.to_string());
+        return Step::Done(result);
+    }
+    if !registry.is_plugin_action(&step.action).await {
+        return Step::Done(
+            crate::pipeline::execute_builtin(app, &step.action, input, adversarial).await,
+        );
+    }
+
+    let tool = registry.tool_for_action(&step.action).await;
+    let arguments = match args::build_arguments(&step.action, tool.as_ref(), input, Some(app)).await
+    {
+        Ok(arguments) => arguments,
+        Err(e) => return Step::Done(ActionResult::error(&step.action, &e)),
+    };
+    let pref = tool.as_ref().and_then(confirm::pref_for);
+    let review = match &tool {
+        Some(tool) => confirm::needs_review(tool, pref),
+        None => true,
+    };
+    if step.confirm == Some(true) || review {
+        return Step::Held(confirm::review_result(&step.action, arguments));
+    }
+    Step::Done(mcp::call_with_arguments(registry, &step.action, arguments, tool.as_ref()).await)
+}
+
+/// Record a finished step and decide what's next: `Ok` with the run moved
+/// on, or `Err` with the result to show when the chain ends here.
+fn after_step(
+    state: &ActionMenuState,
+    mut run: PendingRun,
+    mut result: ActionResult,
+) -> Result<PendingRun, ActionResult> {
+    recipes::attach_progress(&mut result, run.recipe.progress(run.next));
+    crate::pipeline::record_in_history(state, &result);
+    let step = run.next + 1;
+    let steps = run.recipe.steps.len();
+
+    if result.status == "error" {
+        log::warn!("[RECIPES] '{}' failed at step {}", run.recipe.id, step);
+        return Err(result);
+    }
+    if step == steps {
+        log::info!("[RECIPES] '{}' finished", run.recipe.id);
+        return Err(result);
+    }
+    match recipes::piped_text(&result) {
+        Some(text) => {
+            run.previous = text;
+            run.next += 1;
+            Ok(run)
+        }
+        None => {
+            log::info!(
+                "[RECIPES] '{}' stopped at step {}: a '{}' result has no text to pass on",
+                run.recipe.id,
+                step,
+                result.result.result_type
+            );
+            note(
+                &mut result,
+                &format!(
+                    "Recipe stopped after step {} of {}: this result can't be passed on.",
+                    step, steps
+                ),
+            );
+            Err(result)
+        }
+    }
+}
+
+fn note(result: &mut ActionResult, text: &str) {
+    if let Some(metadata) = result.metadata.as_mut() {
+        metadata.processing_note = Some(match metadata.processing_note.take() {
+            Some(existing) => format!("{} {}", existing, text),
+            None => text.to_string(),
+        });
+    }
+}
@@ -0,0 +1,34 @@
+//! Recipe persistence in `~/.config/omni-glass/recipes.json`.
+
+use super::{validate, RecipeBook};
+
+const RECIPES_FILE: &str = "recipes.json";
+
+fn recipes_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join(RECIPES_FILE))
+}
+
+/// Load saved recipes. Empty if there is no file; a file that doesn't
+/// parse is logged and treated as empty.
+pub fn load_recipes() -> RecipeBook {
+    let Some(json) = recipes_path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return RecipeBook::default();
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| {
+        log::warn!("[RECIPES] Ignoring {}: {}", RECIPES_FILE, e);
+        RecipeBook::default()
+    })
+}
+
+/// Validate and save recipes.
+pub fn save_recipes(book: &RecipeBook) -> Result<(), String> {
+    validate(book)?;
+    let path = recipes_path().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(book)
+        .map_err(|e| format!("Failed to serialize recipes: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,119 @@
+//! Recipe checks, run when recipes are saved and again before one runs.
+
+use super::{Recipe, RecipeBook, ACTION_PREFIX, COPY_ACTION, MAX_STEPS};
+use std::collections::HashSet;
+
+/// Built-in actions handled only by the action menu UI, never by the runner.
+const UI_ONLY_ACTIONS: &[&str] = &[
+    "search_web",
+    "search_error",
+    "search_command",
+    "search_online",
+    "search_docs",
+];
+
+impl Recipe {
+    /// Every problem with the recipe, in order.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let id_ok = !self.id.is_empty()
+            && self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+        if !id_ok {
+            problems.push(format!(
+                "Recipe id '{}' may only use letters, digits, '-' and '_'",
+                self.id
+            ));
+        }
+        if self.label.trim().is_empty() {
+            problems.push(format!("Recipe '{}' needs a label", self.id));
+        }
+        if self.steps.is_empty() || self.steps.len() > MAX_STEPS {
+            problems.push(format!(
+                "Recipe '{}' must have 1 to {} steps",
+                self.id, MAX_STEPS
+            ));
+        }
+        let last = self.steps.len().saturating_sub(1);
+        for (i, step) in self.steps.iter().enumerate() {
+            let action = step.action.as_str();
+            if action.trim().is_empty() {
+                problems.push(format!("Recipe '{}' step {} has no action", self.id, i + 1));
+            } else if UI_ONLY_ACTIONS.contains(&action) || action.starts_with(ACTION_PREFIX) {
+                problems.push(format!(
+                    "Recipe '{}' step {}: '{}' can't be used in a recipe",
+                    self.id,
+                    i + 1,
+                    action
+                ));
+            } else if action == COPY_ACTION && i != last {
+                problems.push(format!(
+                    "Recipe '{}' step {}: copy_text can only be the last step",
+                    self.id,
+                    i + 1
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Check every recipe and that ids are unique.
+pub fn validate(book: &RecipeBook) -> Result<(), String> {
+    let mut problems: Vec<String> = book.recipes.iter().flat_map(Recipe::problems).collect();
+    let mut seen = HashSet::new();
+    for recipe in &book.recipes {
+        if !seen.insert(recipe.id.as_str()) {
+            problems.push(format!("Recipe id '{}' is used more than once", recipe.id));
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(json: &str) -> Recipe {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn report_error() -> Recipe {
+        recipe(
+            r#"{"id":"report-error","label":"Explain & File Issue",
+                "steps":[{"action":"explain_error"},{"action":"com.example.gh:create_issue"}]}"#,
+        )
+    }
+
+    #[test]
+    fn problems_cover_ids_steps_and_placement() {
+        assert!(report_error().problems().is_empty());
+        let bad = recipe(
+            r#"{"id":"bad id","label":" ","steps":[{"action":"copy_text"},
+                {"action":"search_web"},{"action":"recipe:other"},{"action":""}]}"#,
+        );
+        let problems = bad.problems();
+        assert_eq!(problems.len(), 6, "{:?}", problems);
+        assert!(problems[0].contains("letters, digits"));
+        assert!(problems[2].contains("copy_text can only be the last step"));
+        assert!(problems[3].contains("'search_web' can't be used"));
+
+        let empty = recipe(r#"{"id":"empty","label":"Empty","steps":[]}"#);
+        assert!(empty.problems()[0].contains("1 to 8 steps"));
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let book = RecipeBook {
+            recipes: vec![report_error(), report_error()],
+        };
+        let err = validate(&book).unwrap_err();
+        assert!(err.contains("used more than once"));
+    }
+}
@@ -1,8 +1,11 @@
-//! Safety layer — sensitive data redaction and command validation.
+//! Safety layer — sensitive data redaction, command validation, and
+//! prompt-injection detection.
 //!
 //! All OCR text passes through redaction before reaching cloud LLMs.
 //! All LLM-suggested commands pass through the blocklist before
-//! being shown to the user.
+//! being shown to the user. Untrusted text is scored for injection
+//! markers and fenced before it enters a prompt.
 
 pub mod command_check;
+pub mod injection;
 pub mod redact;
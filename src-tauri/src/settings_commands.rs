@@ -149,7 +149,7 @@ pub async fn test_provider(provider_id: String) -> Result<bool, String> {
             let key = std::env::var("ANTHROPIC_API_KEY")
                 .map_err(|_| "No ANTHROPIC_API_KEY set".to_string())?;
             (
-                "https://api.anthropic.com/v1/messages".to_string(),
+                crate::llm::provider::anthropic_messages_url(),
                 vec![
                     ("x-api-key".to_string(), key),
                     ("anthropic-version".to_string(), "2023-06-01".to_string()),
@@ -0,0 +1,81 @@
+//! Running a shell command the user approved.
+//!
+//! Shared by the confirmation dialog (`commands::run_confirmed_command`)
+//! and the launcher's agent mode (pipeline_agent/). The command is
+//! checked against the safety layer again, run through `sh -c` as a tokio
+//! process so it never blocks a runtime worker, killed once it has run for
+//! `COMMAND_TIMEOUT`, and audited either way.
+
+use crate::audit;
+use crate::safety;
+use std::time::Duration;
+
+/// Longest an approved command may run before it is killed.
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Run an approved command: safety check, shell, audit record. Returns
+/// stdout, or stderr as the error when the command failed without output.
+pub(crate) async fn run_shell_command(command: &str) -> Result<String, String> {
+    // Double-check safety before executing
+    let check = safety::command_check::is_command_safe(command);
+    if !check.safe {
+        let reason = check.reason.unwrap_or_else(|| "Unknown".to_string());
+        audit::record_async(
+            audit::AuditKind::Command,
+            command.to_string(),
+            serde_json::json!({"status": "blocked", "reason": reason}),
+        )
+        .await;
+        return Err(format!("Command blocked by safety layer: {}", reason));
+    }
+
+    log::info!("[EXECUTE] Running confirmed command: {}", command);
+
+    // Dropping the future on timeout kills the shell
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, child).await;
+    audit::record_async(
+        audit::AuditKind::Command,
+        command.to_string(),
+        match &output {
+            Ok(Ok(o)) => serde_json::json!({"status": "executed", "exitCode": o.status.code()}),
+            Ok(Err(e)) => serde_json::json!({"status": "spawn_failed", "error": e.to_string()}),
+            Err(_) => serde_json::json!({
+                "status": "timed_out",
+                "seconds": COMMAND_TIMEOUT.as_secs()
+            }),
+        },
+    )
+    .await;
+    let output = output
+        .map_err(|_| {
+            format!(
+                "Command timed out after {} seconds",
+                COMMAND_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Failed to run command: {}", e))?;
+    command_outcome(output)
+}
+
+fn command_outcome(output: std::process::Output) -> Result<String, String> {
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    if output.status.success() {
+        log::info!("[EXECUTE] Command succeeded");
+        Ok(stdout)
+    } else if !stdout.trim().is_empty() {
+        // Non-zero exit but stdout has content (e.g. `find` with permission errors).
+        // Return the valid output rather than discarding it.
+        log::info!("[EXECUTE] Command exited non-zero but produced output");
+        Ok(stdout)
+    } else {
+        log::warn!("[EXECUTE] Command failed: {}", stderr);
+        Err(format!("Command failed:\n{}", stderr))
+    }
+}
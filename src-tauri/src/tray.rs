@@ -1,8 +1,8 @@
 //! System tray setup and click handler.
 //!
 //! The tray icon is the primary entry point for Omni-Glass.
-//! Left/right-click opens a native menu with Snip Screen, Type Command,
-//! Settings, and Quit.
+//! Left/right-click opens a native menu with Snip Screen, Snip Window,
+//! Type Command, History, Settings, and Quit.
 
 use tauri::{
     image::Image as TauriImage,
@@ -14,20 +14,26 @@ use tauri::{
 /// Sets up the system tray icon with a native menu.
 ///
 /// Both left-click and right-click open the same menu:
-///   - Snip Screen  → capture flow
+///   - Snip Screen  → capture flow (every monitor)
+///   - Snip Window  → window picker → capture a single window
 ///   - Type Command → text launcher
+///   - History...   → searchable snip history
 ///   - Settings...  → settings window
 ///   - Quit         → exit
 pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let snip_item = MenuItemBuilder::with_id("snip", "Snip Screen").build(app)?;
+    let snip_window_item = MenuItemBuilder::with_id("snip_window", "Snip Window...").build(app)?;
     let type_item = MenuItemBuilder::with_id("type_command", "Type Command").build(app)?;
+    let history_item = MenuItemBuilder::with_id("history", "History...").build(app)?;
     let settings_item = MenuItemBuilder::with_id("settings", "Settings...").build(app)?;
     let quit_item = MenuItemBuilder::with_id("quit", "Quit Omni-Glass").build(app)?;
 
     let menu = MenuBuilder::new(app)
         .item(&snip_item)
+        .item(&snip_window_item)
         .item(&type_item)
         .separator()
+        .item(&history_item)
         .item(&settings_item)
         .separator()
         .item(&quit_item)
@@ -59,10 +65,18 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
                         log::error!("Failed to start snip mode: {}", e);
                     }
                 }
+                "snip_window" => {
+                    log::info!("[TRAY] Snip Window selected");
+                    crate::capture_commands::open_window_picker(app);
+                }
                 "type_command" => {
                     log::info!("[TRAY] Type Command selected");
                     crate::show_text_launcher(app);
                 }
+                "history" => {
+                    log::info!("[TRAY] History selected");
+                    crate::history::commands::open_history_window(app);
+                }
                 "settings" => {
                     log::info!("[TRAY] Settings selected");
                     if let Some(window) = app.get_webview_window("settings") {
@@ -91,89 +105,36 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// Initiates snip mode: captures the screen, then opens the overlay window.
+/// Initiates snip mode: captures every monitor, then opens an overlay on each.
 ///
-/// The screenshot is saved to a temp PNG file and loaded by the webview
-/// via Tauri's asset protocol.
+/// Each screenshot is saved to a temp PNG file and loaded by its overlay
+/// webview via Tauri's asset protocol.
 pub fn start_snip_mode(
     app: &AppHandle,
     click_epoch_ms: f64,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use crate::capture::{self, CaptureState};
-
     let start = std::time::Instant::now();
 
-    // Guard: if the overlay window already exists, close it first.
-    if let Some(existing) = app.get_webview_window("overlay") {
-        log::info!("Closing existing overlay window before starting new snip");
-        let _ = existing.destroy();
-    }
-
-    // Step 1: Capture the full screen
-    let screenshot = capture::capture_primary_monitor()
+    // Step 1: Capture every monitor
+    let captures = crate::capture::capture_all_monitors()
         .map_err(|e| format!("Screen capture failed: {}", e))?;
 
     let capture_us = start.elapsed().as_micros();
     log::info!(
-        "[LATENCY] xcap_capture={:.2}ms",
-        capture_us as f64 / 1000.0
-    );
-
-    // Step 2: Save screenshot to temp PNG file for overlay display.
-    let temp_path = std::env::temp_dir().join("omni-glass-capture.png");
-    screenshot
-        .save(&temp_path)
-        .map_err(|e| format!("PNG save failed: {}", e))?;
-    // Canonicalize to resolve /var → /private/var symlink on macOS.
-    let temp_path = std::fs::canonicalize(&temp_path)
-        .unwrap_or(temp_path);
-
-    let save_us = start.elapsed().as_micros() - capture_us;
-    let file_size = std::fs::metadata(&temp_path)
-        .map(|m| m.len())
-        .unwrap_or(0);
-    log::info!(
-        "[LATENCY] png_save={:.2}ms ({} bytes, {})",
-        save_us as f64 / 1000.0,
-        file_size,
-        temp_path.display()
+        "[LATENCY] xcap_capture={:.2}ms ({} monitor(s))",
+        capture_us as f64 / 1000.0,
+        captures.len()
     );
 
-    // Step 3: Store the screenshot + capture info for the overlay to fetch.
-    let state = app.state::<CaptureState>();
-    *state.screenshot.lock().unwrap() = Some(screenshot);
-    *state.capture_info.lock().unwrap() = Some(capture::CaptureInfo {
-        image_path: temp_path.to_string_lossy().to_string(),
-        click_epoch_ms,
-    });
-
-    // Step 4: Create the overlay window.
-    let _overlay_window = tauri::WebviewWindowBuilder::new(
-        app,
-        "overlay",
-        tauri::WebviewUrl::App("index.html".into()),
-    )
-    .fullscreen(true)
-    .transparent(true)
-    .decorations(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .title("Omni-Glass Overlay")
-    .build()?;
-
-    let window_us = start.elapsed().as_micros() - capture_us - save_us;
-    log::info!(
-        "[LATENCY] window_create={:.2}ms",
-        window_us as f64 / 1000.0
-    );
+    // Step 2: Save screenshots, store capture state, create overlay windows.
+    crate::capture_commands::open_overlays(app, captures, click_epoch_ms)?;
 
     let total_us = start.elapsed().as_micros();
     log::info!(
-        "[LATENCY] rust_total={:.2}ms (capture={:.2} + save={:.2} + window={:.2})",
+        "[LATENCY] rust_total={:.2}ms (capture={:.2} + save+windows={:.2})",
         total_us as f64 / 1000.0,
         capture_us as f64 / 1000.0,
-        save_us as f64 / 1000.0,
-        window_us as f64 / 1000.0,
+        (total_us - capture_us) as f64 / 1000.0,
     );
 
     Ok(())
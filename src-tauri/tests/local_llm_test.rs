@@ -152,6 +152,7 @@ fn args_prompt_includes_schema() {
         "Translates text between languages",
         r#"{"type":"object","properties":{"text":{"type":"string"},"target_lang":{"type":"string"}}}"#,
         "Hello world",
+        "",
     );
     assert!(prompt.contains("translate"));
     assert!(prompt.contains("target_lang"));
@@ -0,0 +1,202 @@
+//! Tests for manifest validation: schema and semantic passes together.
+//!
+//! Run with: cargo test --test manifest_check
+
+use omni_glass_lib::mcp::manifest::{PluginManifest, MANIFEST_FILENAME};
+use omni_glass_lib::mcp::manifest_check::{check_manifest, MANIFEST_SCHEMA};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn check(name: &str, manifest: &str) -> Result<PluginManifest, Vec<String>> {
+    let dir = std::env::temp_dir().join(format!("og-manifest-check-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(MANIFEST_FILENAME), manifest).unwrap();
+    fs::write(dir.join("index.js"), "// test").unwrap();
+    let result = check_manifest(&dir);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+#[test]
+fn reports_every_problem_with_its_path() {
+    let errors = check(
+        "many",
+        r#"{
+            "id": "com.example.bad", "name": "Bad", "version": "1.0",
+            "runtime": "node", "entry": "index.js", "author": "me",
+            "permissions": {
+                "netwrok": ["x.com"],
+                "network": ["https://api.example.com", "a.io", "A.io"],
+                "filesystem": [
+                    {"path": "~/Documents", "access": "rw"},
+                    {"path": "~/Documents", "access": "read"}
+                ]
+            }
+        }"#,
+    )
+    .unwrap_err();
+    let has = |prefix: &str| errors.iter().any(|e| e.starts_with(prefix));
+    assert!(has("/: unexpected property 'author'"), "{:?}", errors);
+    assert!(has("/permissions: unexpected property 'netwrok'"));
+    assert!(has("/permissions/filesystem/0/access"));
+    // Both passes report: the wrong fields still deserialize.
+    assert!(has("/version"));
+    assert!(has("/permissions/network/2"));
+    assert!(has(
+        "/permissions/filesystem/1: '~/Documents' is listed more than once"
+    ));
+}
+
+#[test]
+fn semantic_checks_collect_together() {
+    let errors = check(
+        "semantic",
+        r#"{
+            "manifestVersion": 2, "id": "com..bad", "name": "Bad", "version": "1.0",
+            "runtime": "node", "entry": "missing.js",
+            "permissions": {
+                "network": ["https://api.example.com", "a.io", "A.io"],
+                "environment": ["TOKEN", "TOKEN"]
+            }
+        }"#,
+    )
+    .unwrap_err();
+    let has = |prefix: &str| errors.iter().any(|e| e.starts_with(prefix));
+    assert!(has("/manifestVersion"), "{:?}", errors);
+    assert!(has("/id"));
+    assert!(has("/version"));
+    assert!(has("/permissions/network/0"));
+    assert!(has("/permissions/network/2"));
+    assert!(has("/permissions/environment/1"));
+    assert!(has("/entry"));
+    assert_eq!(errors.len(), 7, "{:?}", errors);
+}
+
+#[test]
+fn accepts_current_and_legacy_manifests() {
+    let legacy = check(
+        "legacy",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.2.3-beta.1",
+            "runtime":"python","entry":"index.js",
+            "permissions":{"network":["localhost","api.example.com"]}}"#,
+    )
+    .unwrap();
+    assert_eq!(legacy.manifest_version, 1);
+    let current = check(
+        "current",
+        r#"{"$schema":"../omni-glass.plugin.schema.json","manifestVersion":1,
+            "id":"com.example.ok","name":"Ok","version":"0.1.0",
+            "runtime":"node","entry":"index.js"}"#,
+    );
+    assert!(current.is_ok(), "{:?}", current);
+}
+
+#[test]
+fn limits_must_be_positive_and_known() {
+    let ok = check(
+        "limits-ok",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "limits":{"cpuSeconds":30,"callTimeoutSecs":60}}"#,
+    )
+    .unwrap();
+    assert_eq!(ok.limits.unwrap().call_timeout_secs, Some(60));
+    let errors = check(
+        "limits-bad",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "limits":{"memoryMb":0,"threads":4}}"#,
+    )
+    .unwrap_err();
+    assert!(errors.iter().any(|e| e.starts_with("/limits/memoryMb")));
+    assert!(errors.iter().any(|e| e.contains("threads")), "{:?}", errors);
+}
+
+#[test]
+fn hints_use_known_content_types() {
+    let ok = check(
+        "hints-ok",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "hints":{"contentTypes":["error","code"],"keywords":["github"]}}"#,
+    )
+    .unwrap();
+    assert_eq!(ok.hints.unwrap().content_types, ["error", "code"]);
+    let errors = check(
+        "hints-bad",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "hints":{"contentTypes":["spreadsheet"]}}"#,
+    )
+    .unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("/hints/contentTypes/0")));
+}
+
+#[test]
+fn triggers_need_a_valid_pattern_or_content_types() {
+    let ok = check(
+        "triggers-ok",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "triggers":[{"tool":"open_issue","pattern":"\\b[A-Z]+-\\d+\\b"},
+                        {"tool":"summarize","contentTypes":["url"]}]}"#,
+    )
+    .unwrap();
+    assert_eq!(ok.triggers.unwrap().len(), 2);
+    let errors = check(
+        "triggers-bad",
+        r#"{"id":"com.example.ok","name":"Ok","version":"1.0.0",
+            "runtime":"node","entry":"index.js",
+            "triggers":[{"tool":"a","pattern":"([A-Z]"},{"tool":"b"},
+                        {"tool":"c","contentTypes":["spreadsheet"]}]}"#,
+    )
+    .unwrap_err();
+    let has = |prefix: &str| errors.iter().any(|e| e.starts_with(prefix));
+    assert!(has("/triggers/0/pattern"), "{:?}", errors);
+    assert!(has("/triggers/1: a trigger needs"));
+    assert!(has("/triggers/2/contentTypes/0"));
+}
+
+#[test]
+fn wasm_plugins_cannot_declare_shell() {
+    let errors = check(
+        "wasm",
+        r#"{"id":"com.example.wasm","name":"Wasm","version":"1.0.0",
+            "runtime":"wasm","entry":"index.js",
+            "permissions":{"shell":{"commands":["git"]}}}"#,
+    )
+    .unwrap_err();
+    assert!(
+        errors.iter().any(|e| e.starts_with("/permissions/shell")),
+        "{:?}",
+        errors
+    );
+    assert!(errors.iter().any(|e| e.starts_with("/entry")));
+}
+
+#[test]
+fn bundled_plugins_pass() {
+    let plugins = Path::new(env!("CARGO_MANIFEST_DIR")).join("../plugins");
+    for entry in fs::read_dir(plugins).unwrap().flatten() {
+        if entry.path().join(MANIFEST_FILENAME).exists() {
+            let result = check_manifest(&entry.path());
+            assert!(result.is_ok(), "{}: {:?}", entry.path().display(), result);
+        }
+    }
+}
+
+#[test]
+fn schema_config_types_match_the_store() {
+    let schema: Value = serde_json::from_str(MANIFEST_SCHEMA).unwrap();
+    let types = &schema["properties"]["configuration"]["additionalProperties"]["properties"]
+        ["type"]["enum"];
+    let expected: Vec<Value> = omni_glass_lib::mcp::config_store::FIELD_TYPES
+        .iter()
+        .map(|t| Value::from(*t))
+        .collect();
+    assert_eq!(types, &Value::Array(expected));
+}
@@ -0,0 +1,77 @@
+//! Plugin configuration delivery at `initialize`.
+//!
+//! Spawns a tiny Node.js MCP server that remembers `params.configuration`
+//! from the initialize request and returns it from its `config` tool.
+//!
+//! Requires: Node.js on PATH (skipped otherwise).
+
+use omni_glass_lib::mcp::client::McpServer;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const CONFIG_SERVER_JS: &str = r#"
+const rl = require("readline").createInterface({ input: process.stdin });
+const send = (msg) => process.stdout.write(JSON.stringify(msg) + "\n");
+let configuration = "absent";
+rl.on("line", (line) => {
+  const msg = JSON.parse(line);
+  if (msg.id === undefined) return;
+  if (msg.method === "initialize") {
+    if ("configuration" in msg.params) configuration = msg.params.configuration;
+    send({ jsonrpc: "2.0", id: msg.id, result: { serverInfo: { name: "config", version: "0.0.1" } } });
+  } else if (msg.method === "tools/call") {
+    send({ jsonrpc: "2.0", id: msg.id,
+      result: { content: [{ type: "text", text: JSON.stringify(configuration) }] } });
+  }
+});
+"#;
+
+fn node_available() -> bool {
+    std::process::Command::new("node")
+        .arg("--version")
+        .output()
+        .is_ok()
+}
+
+async fn configuration_seen(configuration: Option<Map<String, Value>>) -> Value {
+    let script = std::env::temp_dir().join("og-config-mcp-server.js");
+    std::fs::write(&script, CONFIG_SERVER_JS).unwrap();
+    let mut server = McpServer::spawn(
+        "com.test.config",
+        "node",
+        &[&script.to_string_lossy()],
+        HashMap::new(),
+        None,
+    )
+    .expect("spawn config server");
+    if let Some(configuration) = configuration {
+        server.set_configuration(configuration);
+    }
+    server.initialize().await.expect("initialize");
+    let result = server
+        .call_tool("config", json!({}))
+        .await
+        .expect("tools/call");
+    server.shutdown().await;
+    serde_json::from_str(&result.text()).expect("configuration JSON")
+}
+
+#[tokio::test]
+async fn configuration_is_sent_in_initialize() {
+    if !node_available() {
+        eprintln!("SKIP: node not installed");
+        return;
+    }
+    let values = json!({ "repo": "owner/repo", "token": "ghp_secret", "limit": 5 });
+    let seen = configuration_seen(values.as_object().cloned()).await;
+    assert_eq!(seen, values);
+}
+
+#[tokio::test]
+async fn configuration_is_omitted_when_not_set() {
+    if !node_available() {
+        eprintln!("SKIP: node not installed");
+        return;
+    }
+    assert_eq!(configuration_seen(None).await, json!("absent"));
+}
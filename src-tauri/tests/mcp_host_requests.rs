@@ -0,0 +1,144 @@
+//! Server → client requests during `tools/call`: elicitation and sampling.
+//!
+//! Spawns a tiny Node.js MCP server whose `ask` tool sends a request back to
+//! the client mid-call and returns whatever it got as the tool result. A
+//! fake `ClientHost` stands in for the app's UI and LLM provider.
+//!
+//! Requires: Node.js on PATH (skipped otherwise).
+
+use omni_glass_lib::mcp::client::McpServer;
+use omni_glass_lib::mcp::host::{ClientHost, HostFuture};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// `ask` sends `arguments.method` with `arguments.params` to the client and
+/// returns the JSON-RPC reply (result or error) as text.
+const ASKING_SERVER_JS: &str = r#"
+const rl = require("readline").createInterface({ input: process.stdin });
+const send = (msg) => process.stdout.write(JSON.stringify(msg) + "\n");
+let pendingCall = null;
+rl.on("line", (line) => {
+  const msg = JSON.parse(line);
+  if (msg.method === undefined && msg.id === "srv-1" && pendingCall) {
+    send({ jsonrpc: "2.0", id: pendingCall,
+      result: { content: [{ type: "text", text: JSON.stringify(msg.error || msg.result) }] } });
+    pendingCall = null;
+    return;
+  }
+  if (msg.id === undefined) return;
+  if (msg.method === "initialize") {
+    send({ jsonrpc: "2.0", id: msg.id, result: {
+      serverInfo: { name: "asker", version: "0.0.1" },
+      clientCapabilities: msg.params.capabilities } });
+  } else if (msg.method === "tools/list") {
+    send({ jsonrpc: "2.0", id: msg.id, result: { tools: [{ name: "ask" }] } });
+  } else if (msg.method === "tools/call") {
+    pendingCall = msg.id;
+    console.log("log line the client should skip");
+    send({ jsonrpc: "2.0", method: "notifications/progress", params: {} });
+    send({ jsonrpc: "2.0", id: "srv-1", method: msg.params.arguments.method,
+      params: msg.params.arguments.params });
+  }
+});
+"#;
+
+/// Accepts every elicitation and echoes sampling requests back.
+struct FakeHost;
+
+impl ClientHost for FakeHost {
+    fn elicit<'a>(&'a self, plugin_id: &'a str, params: Value) -> HostFuture<'a> {
+        Box::pin(async move {
+            Ok(json!({ "action": "accept", "content": {
+                "from": plugin_id, "message": params["message"] } }))
+        })
+    }
+
+    fn sample<'a>(&'a self, _plugin_id: &'a str, params: Value) -> HostFuture<'a> {
+        Box::pin(async move {
+            let text = params["messages"][0]["content"]["text"].clone();
+            Ok(json!({ "role": "assistant", "model": "fake",
+                "content": { "type": "text", "text": text } }))
+        })
+    }
+}
+
+fn node_available() -> bool {
+    std::process::Command::new("node")
+        .arg("--version")
+        .output()
+        .is_ok()
+}
+
+async fn spawn(host: Option<Arc<dyn ClientHost>>, sampling: bool) -> McpServer {
+    let script = std::env::temp_dir().join("og-asking-mcp-server.js");
+    std::fs::write(&script, ASKING_SERVER_JS).unwrap();
+    let mut server = McpServer::spawn(
+        "com.test.asker",
+        "node",
+        &[&script.to_string_lossy()],
+        HashMap::new(),
+        None,
+    )
+    .expect("spawn asker");
+    server.set_host(host, sampling);
+    server.initialize().await.expect("initialize");
+    server
+}
+
+/// Call `ask` and parse the reply the server got back from us.
+async fn ask(server: &mut McpServer, method: &str, params: Value) -> Value {
+    let result = server
+        .call_tool("ask", json!({ "method": method, "params": params }))
+        .await
+        .expect("tools/call");
+    serde_json::from_str(&result.text()).expect("reply JSON")
+}
+
+#[tokio::test]
+async fn elicitation_is_answered_mid_call() {
+    if !node_available() {
+        eprintln!("SKIP: node not installed");
+        return;
+    }
+    let mut server = spawn(Some(Arc::new(FakeHost)), false).await;
+    let params = json!({
+        "message": "Which repo?",
+        "requestedSchema": { "type": "object", "properties": { "repo": { "type": "string" } } }
+    });
+    let reply = ask(&mut server, "elicitation/create", params).await;
+    assert_eq!(reply["action"], "accept");
+    assert_eq!(reply["content"]["from"], "com.test.asker");
+    assert_eq!(reply["content"]["message"], "Which repo?");
+
+    let nested = json!({ "message": "?", "requestedSchema": { "type": "object",
+        "properties": { "a": { "type": "object" } } } });
+    let reply = ask(&mut server, "elicitation/create", nested).await;
+    assert_eq!(reply["code"], -32602);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn sampling_requires_the_manifest_permission() {
+    if !node_available() {
+        eprintln!("SKIP: node not installed");
+        return;
+    }
+    let params = json!({ "maxTokens": 50, "messages": [
+        { "role": "user", "content": { "type": "text", "text": "ping" } }] });
+
+    let mut allowed = spawn(Some(Arc::new(FakeHost)), true).await;
+    let reply = ask(&mut allowed, "sampling/createMessage", params.clone()).await;
+    assert_eq!(reply["content"]["text"], "ping");
+    allowed.shutdown().await;
+
+    let mut denied = spawn(Some(Arc::new(FakeHost)), false).await;
+    let reply = ask(&mut denied, "sampling/createMessage", params.clone()).await;
+    assert_eq!(reply["code"], -1);
+    denied.shutdown().await;
+
+    let mut headless = spawn(None, true).await;
+    let reply = ask(&mut headless, "sampling/createMessage", params).await;
+    assert_eq!(reply["code"], -32601);
+    headless.shutdown().await;
+}
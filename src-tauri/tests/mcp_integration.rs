@@ -173,3 +173,34 @@ async fn registry_plugin_tool_dispatch() {
     // Cleanup
     registry.shutdown_all().await;
 }
+
+#[tokio::test]
+async fn harness_runs_the_test_plugin() {
+    if !plugin_available() {
+        eprintln!("SKIP: test plugin not installed");
+        return;
+    }
+
+    use omni_glass_lib::mcp::harness::{self, ArgumentsSource, HarnessRequest};
+
+    let entry = test_plugin_entry();
+    let request = HarnessRequest {
+        plugin_dir: std::path::Path::new(&entry).parent().unwrap().to_path_buf(),
+        tool: Some("echo_text".to_string()),
+        arguments: Some(serde_json::json!({"text": "Harness test"})),
+        text: None,
+    };
+    let report = harness::run(&request).await.expect("Harness run failed");
+
+    assert_eq!(report.plugin_id, "com.omni-glass.test");
+    assert_eq!(report.tools.len(), 1);
+    assert!(report.passed());
+    let call = report.call.expect("No call report");
+    assert_eq!(call.arguments_from, ArgumentsSource::Given);
+    assert!(call.argument_errors.is_empty());
+    assert_eq!(
+        call.result.result.text.as_deref(),
+        Some("[Echo] Harness test")
+    );
+    assert!(report.timings.call_ms.is_some());
+}
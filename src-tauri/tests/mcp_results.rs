@@ -0,0 +1,138 @@
+//! Tests for mapping MCP tool results onto ActionResult.
+//!
+//! Feeds raw `tools/call` result JSON through `mcp::results::to_action_result`
+//! and checks the result type, payload fields and safety gates. No plugin
+//! process is needed.
+//!
+//! Run with: cargo test --test mcp_results
+
+use omni_glass_lib::mcp::results::to_action_result;
+use omni_glass_lib::mcp::types::ToolResult;
+use serde_json::json;
+
+fn parse(value: serde_json::Value) -> ToolResult {
+    serde_json::from_value(value).expect("valid ToolResult")
+}
+
+const PNG_B64: &str = "iVBORw0KGgo=";
+
+#[test]
+fn text_only_stays_text() {
+    let result = parse(json!({ "content": [{ "type": "text", "text": "hello" }] }));
+    let action = to_action_result("p:t", &result, None);
+    assert_eq!(action.status, "success");
+    assert_eq!(action.result.result_type, "text");
+    assert_eq!(action.result.text.as_deref(), Some("hello"));
+}
+
+#[test]
+fn image_becomes_image_result_with_caption() {
+    let result = parse(json!({ "content": [
+        { "type": "text", "text": "A chart" },
+        { "type": "image", "data": PNG_B64, "mimeType": "image/png" }
+    ]}));
+    let action = to_action_result("p:t", &result, None);
+    assert_eq!(action.result.result_type, "image");
+    assert_eq!(action.result.data.as_deref(), Some(PNG_B64));
+    assert_eq!(action.result.mime_type.as_deref(), Some("image/png"));
+    assert_eq!(action.result.file_path.as_deref(), Some("image.png"));
+    assert_eq!(action.result.text.as_deref(), Some("A chart"));
+}
+
+#[test]
+fn image_with_bad_mime_or_data_is_rejected() {
+    let wrong_mime = parse(json!({ "content": [
+        { "type": "image", "data": PNG_B64, "mimeType": "text/html" }
+    ]}));
+    assert_eq!(to_action_result("p:t", &wrong_mime, None).status, "error");
+    let injected_mime = parse(json!({ "content": [
+        { "type": "image", "data": PNG_B64, "mimeType": "image/png\" onerror=\"x" }
+    ]}));
+    assert_eq!(to_action_result("p:t", &injected_mime, None).status, "error");
+    let bad_data = parse(json!({ "content": [
+        { "type": "image", "data": "not base64!", "mimeType": "image/png" }
+    ]}));
+    assert_eq!(to_action_result("p:t", &bad_data, None).status, "error");
+}
+
+#[test]
+fn embedded_resources_map_to_file_and_clipboard() {
+    let file = parse(json!({ "content": [{ "type": "resource", "resource": {
+        "uri": "file:///tmp/out/report.csv?v=1", "mimeType": "text/csv", "text": "a,b\n1,2"
+    }}]}));
+    let action = to_action_result("p:t", &file, None);
+    assert_eq!(action.result.result_type, "file");
+    assert_eq!(action.result.file_path.as_deref(), Some("report.csv"));
+    assert_eq!(action.result.text.as_deref(), Some("a,b\n1,2"));
+
+    let blob = parse(json!({ "content": [{ "type": "resource", "resource": {
+        "uri": "data:", "mimeType": "image/png", "blob": PNG_B64
+    }}]}));
+    let action = to_action_result("p:t", &blob, None);
+    assert_eq!(
+        action.result.file_path.as_deref(),
+        Some("plugin-output.png")
+    );
+    assert_eq!(action.result.data.as_deref(), Some(PNG_B64));
+
+    let clip = parse(json!({ "content": [{ "type": "resource", "resource": {
+        "uri": "clipboard:", "text": "copied"
+    }}]}));
+    let action = to_action_result("p:t", &clip, None);
+    assert_eq!(action.result.result_type, "clipboard");
+    assert_eq!(action.result.clipboard_content.as_deref(), Some("copied"));
+}
+
+#[test]
+fn links_must_be_web_urls() {
+    let ok = parse(json!({ "content": [{ "type": "resource_link",
+        "uri": "https://example.com/issue/1", "name": "Issue #1" }]}));
+    let action = to_action_result("p:t", &ok, None);
+    assert_eq!(action.result.result_type, "link");
+    assert_eq!(
+        action.result.url.as_deref(),
+        Some("https://example.com/issue/1")
+    );
+    assert_eq!(action.result.text.as_deref(), Some("Issue #1"));
+
+    let bad =
+        parse(json!({ "content": [{ "type": "resource_link", "uri": "file:///etc/passwd" }]}));
+    assert_eq!(to_action_result("p:t", &bad, None).status, "error");
+}
+
+#[test]
+fn structured_content_is_checked_against_output_schema() {
+    let schema = json!({ "type": "object", "required": ["count"],
+        "properties": { "count": { "type": "integer" } } });
+    let good = parse(json!({ "content": [], "structuredContent": { "count": 3 } }));
+    let action = to_action_result("p:t", &good, Some(&schema));
+    assert_eq!(action.status, "success");
+    assert!(action.result.text.unwrap().contains("\"count\": 3"));
+
+    let bad = parse(json!({ "content": [], "structuredContent": { "count": "3" } }));
+    assert_eq!(to_action_result("p:t", &bad, Some(&schema)).status, "error");
+    let missing = parse(json!({ "content": [{ "type": "text", "text": "3" }] }));
+    assert_eq!(
+        to_action_result("p:t", &missing, Some(&schema)).status,
+        "error"
+    );
+}
+
+#[test]
+fn safety_gates_cover_clipboard_payloads() {
+    let clip = parse(json!({ "content": [{ "type": "resource", "resource": {
+        "uri": "clipboard:", "text": "rm -rf /"
+    }}]}));
+    assert_eq!(to_action_result("p:t", &clip, None).status, "error");
+}
+
+#[test]
+fn unknown_blocks_are_ignored() {
+    let result = parse(json!({ "content": [
+        { "type": "hologram", "data": "x" },
+        { "type": "text", "text": "still here" }
+    ]}));
+    let action = to_action_result("p:t", &result, None);
+    assert_eq!(action.result.result_type, "text");
+    assert_eq!(action.result.text.as_deref(), Some("still here"));
+}
@@ -0,0 +1,112 @@
+//! Tests for the plugin JSON Schema validator and config prefill.
+//!
+//! Run with: cargo test --test mcp_schema
+
+use omni_glass_lib::mcp::schema::{apply_prefill, prefill_values, validate};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["title", "tags"],
+        "properties": {
+            "title": { "type": "string" },
+            "priority": { "enum": ["low", "high"] },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        }
+    })
+}
+
+#[test]
+fn accepts_valid_value() {
+    let value = json!({ "title": "Bug", "priority": "high", "tags": ["ui"] });
+    assert!(validate(&schema(), &value).is_empty());
+}
+
+#[test]
+fn reports_each_violation_with_path() {
+    let value = json!({ "priority": "urgent", "tags": ["ui", 3] });
+    let errors = validate(&schema(), &value);
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("'title'")));
+    assert!(errors.iter().any(|e| e.starts_with("/priority")));
+    assert!(errors.iter().any(|e| e.starts_with("/tags/1")));
+}
+
+#[test]
+fn type_lists_and_integers() {
+    let schema = json!({ "type": ["integer", "null"] });
+    assert!(validate(&schema, &json!(3)).is_empty());
+    assert!(validate(&schema, &Value::Null).is_empty());
+    assert_eq!(validate(&schema, &json!(1.5)).len(), 1);
+}
+
+#[test]
+fn string_formats_and_bounds() {
+    let schema = json!({ "type": "object", "properties": {
+        "email": { "type": "string", "format": "email" },
+        "due": { "type": "string", "format": "date" },
+        "repo": { "type": "string", "pattern": "^[\\w.-]+/[\\w.-]+$" },
+        "title": { "type": "string", "minLength": 3, "maxLength": 10 },
+        "count": { "type": "integer", "minimum": 1, "maximum": 5 }
+    }});
+    let good = json!({ "email": "a@b.io", "due": "2026-03-01", "repo": "acme/app",
+        "title": "Crash", "count": 2 });
+    assert!(validate(&schema, &good).is_empty());
+
+    let bad = json!({ "email": "nope", "due": "2026-13-01", "repo": "acme",
+        "title": "Hi", "count": 9 });
+    assert_eq!(
+        validate(&schema, &bad).len(),
+        5,
+        "{:?}",
+        validate(&schema, &bad)
+    );
+}
+
+#[test]
+fn additional_properties_false_rejects_extras() {
+    let schema = json!({ "type": "object", "additionalProperties": false,
+        "properties": { "a": {} } });
+    let errors = validate(&schema, &json!({ "a": 1, "b": 2 }));
+    assert_eq!(errors, vec!["/: unexpected property 'b'"]);
+}
+
+#[test]
+fn additional_properties_schema_checks_map_values() {
+    let schema = json!({ "type": "object", "properties": { "a": {} },
+        "additionalProperties": { "type": "string" } });
+    let errors = validate(&schema, &json!({ "a": 1, "b": "ok", "c": 2 }));
+    assert_eq!(errors, vec!["/c: expected \"string\", got number"]);
+}
+
+#[test]
+fn unknown_formats_are_ignored() {
+    let schema = json!({ "type": "string", "format": "color-hex" });
+    assert!(validate(&schema, &json!("not checked")).is_empty());
+}
+
+#[test]
+fn prefill_matches_names_and_default_prefix() {
+    let schema = json!({ "type": "object", "properties": {
+        "repo": { "type": "string" },
+        "labels": { "type": "array" },
+        "count": { "type": "integer" }
+    }});
+    let config = HashMap::from([
+        ("default_repo".to_string(), json!("acme/app")),
+        ("labels".to_string(), json!("")),
+        ("count".to_string(), json!("three")),
+    ]);
+    let values = prefill_values(&schema, &config);
+    assert_eq!(values.len(), 1, "empty and mistyped values are skipped");
+
+    let mut args = json!({ "title": "Bug" });
+    apply_prefill(&mut args, &values);
+    assert_eq!(args["repo"], "acme/app");
+
+    let mut args = json!({ "repo": "other/repo" });
+    apply_prefill(&mut args, &values);
+    assert_eq!(args["repo"], "other/repo", "model output wins");
+}
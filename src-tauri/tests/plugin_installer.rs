@@ -0,0 +1,198 @@
+//! Plugin installer: staging, integrity checks, install / upgrade / uninstall.
+//!
+//! Works on throwaway plugins and plugin directories under the temp dir.
+//! Archive tests use the system `tar` (skipped when it is missing).
+
+use omni_glass_lib::mcp::installer::{self, InstallMode};
+use omni_glass_lib::mcp::integrity::sha256_file;
+use omni_glass_lib::mcp::trust::TrustStore;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const ID: &str = "com.test.installable";
+
+/// A fresh, empty directory under the temp dir.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("og-installer-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write a Node plugin into `dir`; digests are declared when `pinned`.
+fn write_plugin(dir: &Path, version: &str, pinned: bool) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("index.js"), format!("// v{}\n", version)).unwrap();
+    std::fs::create_dir_all(dir.join(".git")).unwrap();
+    std::fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    let integrity = if pinned {
+        let digest = sha256_file(&dir.join("index.js")).unwrap();
+        format!(
+            r#", "integrity": {{ "files": {{ "index.js": "{}" }} }}"#,
+            digest
+        )
+    } else {
+        String::new()
+    };
+    let manifest = format!(
+        r#"{{ "id": "{}", "name": "Installable", "version": "{}",
+            "runtime": "node", "entry": "index.js"{} }}"#,
+        ID, version, integrity
+    );
+    std::fs::write(dir.join("omni-glass.plugin.json"), manifest).unwrap();
+}
+
+fn trust() -> TrustStore {
+    TrustStore::default()
+}
+
+fn tar_available() -> bool {
+    Command::new("tar").arg("--version").output().is_ok()
+}
+
+#[test]
+fn installs_from_a_directory_and_upgrades_in_place() {
+    let root = scratch("dir");
+    let plugins = root.join("plugins");
+    write_plugin(&root.join("src-v1"), "1.0.0", true);
+
+    let installed = installer::install(
+        &root.join("src-v1"),
+        &plugins,
+        InstallMode::Install,
+        &trust(),
+    )
+    .unwrap();
+    assert_eq!(installed.dir, plugins.join(ID));
+    assert_eq!(installed.verified_files, 1);
+    assert!(installed.previous.is_none());
+    assert!(installed.dir.join("index.js").exists());
+    assert!(!installed.dir.join(".git").exists());
+
+    let again = installer::install(
+        &root.join("src-v1"),
+        &plugins,
+        InstallMode::Install,
+        &trust(),
+    );
+    assert!(again.unwrap_err().contains("already installed"));
+
+    write_plugin(&root.join("src-v2"), "2.0.0", false);
+    let upgraded = installer::install(
+        &root.join("src-v2"),
+        &plugins,
+        InstallMode::Upgrade,
+        &trust(),
+    )
+    .unwrap();
+    assert_eq!(upgraded.previous.unwrap().version, "1.0.0");
+    assert_eq!(upgraded.verified_files, 0);
+    let entry = std::fs::read_to_string(plugins.join(ID).join("index.js")).unwrap();
+    assert_eq!(entry, "// v2.0.0\n");
+
+    assert_eq!(installer::uninstall(ID, &plugins).unwrap().version, "2.0.0");
+    assert!(!plugins.join(ID).exists());
+    assert!(installer::uninstall(ID, &plugins).is_err());
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tampered_files_and_missing_plugins_are_refused() {
+    let root = scratch("tampered");
+    let plugins = root.join("plugins");
+    write_plugin(&root.join("src"), "1.0.0", true);
+    std::fs::write(root.join("src/index.js"), "// patched\n").unwrap();
+
+    let err = installer::install(&root.join("src"), &plugins, InstallMode::Install, &trust())
+        .unwrap_err();
+    assert!(err.contains("Integrity check failed"));
+    assert!(!plugins.join(ID).exists());
+
+    write_plugin(&root.join("src"), "1.0.0", true);
+    let err = installer::install(&root.join("src"), &plugins, InstallMode::Upgrade, &trust())
+        .unwrap_err();
+    assert!(err.contains("not installed"));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn python_requirements_must_be_vendored() {
+    let root = scratch("python");
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(src.join("main.py"), "").unwrap();
+    std::fs::write(src.join("requirements.txt"), "requests==2.32.0\n").unwrap();
+    std::fs::write(
+        src.join("omni-glass.plugin.json"),
+        r#"{ "id": "com.test.py", "name": "Py", "version": "1.0.0",
+             "runtime": "python", "entry": "main.py" }"#,
+    )
+    .unwrap();
+
+    let err = installer::install(&src, &root.join("plugins"), InstallMode::Install, &trust())
+        .unwrap_err();
+    assert!(err.contains("wheels/"));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn installs_from_a_wrapped_tarball() {
+    if !tar_available() {
+        eprintln!("SKIP: tar not installed");
+        return;
+    }
+    let root = scratch("tarball");
+    write_plugin(&root.join("build/installable-1.0.0"), "1.0.0", true);
+    let status = Command::new("tar")
+        .args([
+            "-czf",
+            "installable.tar.gz",
+            "-C",
+            "build",
+            "installable-1.0.0",
+        ])
+        .current_dir(&root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let plugins = root.join("plugins");
+    let installed = installer::install(
+        &root.join("installable.tar.gz"),
+        &plugins,
+        InstallMode::Install,
+        &trust(),
+    )
+    .unwrap();
+    assert_eq!(installed.manifest.id, ID);
+    assert!(plugins.join(ID).join("omni-glass.plugin.json").exists());
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn archive_entries_outside_the_plugin_are_refused() {
+    if !tar_available() {
+        eprintln!("SKIP: tar not installed");
+        return;
+    }
+    let root = scratch("traversal");
+    write_plugin(&root.join("a/b"), "1.0.0", false);
+    // Paths are stored as given, so "b/../../x" lands outside the staging dir.
+    std::fs::write(root.join("x"), "").unwrap();
+    let status = Command::new("tar")
+        .args(["-czf", "evil.tar.gz", "-P", "a/b/../../x", "a/b"])
+        .current_dir(&root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let err = installer::install(
+        &root.join("evil.tar.gz"),
+        &root.join("plugins"),
+        InstallMode::Install,
+        &trust(),
+    )
+    .unwrap_err();
+    assert!(err.contains("escapes"));
+    let _ = std::fs::remove_dir_all(&root);
+}
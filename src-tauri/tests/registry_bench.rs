@@ -4,20 +4,21 @@ use std::time::Instant;
 async fn benchmark_registry_overhead() {
     let registry = omni_glass_lib::mcp::ToolRegistry::new();
     omni_glass_lib::mcp::builtins::register_builtins(&registry).await;
+    let prefs = omni_glass_lib::mcp::tool_prefs::ToolPrefs::default();
 
     let mut times = Vec::new();
     for _ in 0..1000 {
         let start = Instant::now();
         let all = registry.all_tools().await;
         let _ = all.iter().filter(|t| t.plugin_id != "builtin").count();
-        let _ = registry.tools_for_prompt().await;
+        let _ = registry.tools_for_prompt(&prefs).await;
         times.push(start.elapsed());
     }
     times.sort();
     let median = times[times.len() / 2];
     let p99 = times[(times.len() as f64 * 0.99) as usize];
     let tools_count = registry.all_tools().await.len();
-    let prompt = registry.tools_for_prompt().await;
+    let prompt = registry.tools_for_prompt(&prefs).await;
 
     eprintln!("=== Phase 2 Registry Hot-Path Benchmark ===");
     eprintln!("Builtin tools: {}", tools_count);
@@ -30,17 +31,14 @@ async fn benchmark_registry_overhead() {
 #[test]
 fn benchmark_approval_check() {
     use omni_glass_lib::mcp::approval;
-    use omni_glass_lib::mcp::manifest::{Permissions, PluginManifest, Runtime};
+    use omni_glass_lib::mcp::manifest::PluginManifest;
 
     let manifest = PluginManifest {
         id: "com.benchmark.test".to_string(),
         name: "Bench".to_string(),
         version: "1.0.0".to_string(),
-        description: String::new(),
-        runtime: Runtime::Node,
         entry: "index.js".to_string(),
-        permissions: Permissions::default(),
-        configuration: None,
+        ..Default::default()
     };
 
     let store = approval::load_approvals();
@@ -48,7 +46,7 @@ fn benchmark_approval_check() {
     let mut times = Vec::new();
     for _ in 0..10000 {
         let start = Instant::now();
-        let _ = approval::check_approval(&store, &manifest);
+        let _ = approval::check_approval(&store, &manifest, "sha256:00");
         times.push(start.elapsed());
     }
     times.sort();
@@ -88,17 +86,14 @@ fn benchmark_env_filter() {
 #[cfg(target_os = "macos")]
 fn benchmark_profile_generation() {
     use omni_glass_lib::mcp::sandbox::macos;
-    use omni_glass_lib::mcp::manifest::{Permissions, PluginManifest, Runtime};
+    use omni_glass_lib::mcp::manifest::PluginManifest;
 
     let manifest = PluginManifest {
         id: "com.benchmark.test".to_string(),
         name: "Bench".to_string(),
         version: "1.0.0".to_string(),
-        description: String::new(),
-        runtime: Runtime::Node,
         entry: "index.js".to_string(),
-        permissions: Permissions::default(),
-        configuration: None,
+        ..Default::default()
     };
     let dir = std::env::temp_dir().join("og-bench");
     let _ = std::fs::create_dir_all(&dir);
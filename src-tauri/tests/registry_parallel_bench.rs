@@ -0,0 +1,123 @@
+//! Benchmark: plugin calls to different servers run in parallel.
+//!
+//! Spawns two copies of a tiny Node.js MCP server whose only tool sleeps
+//! for `DELAY_MS`, registers them as separate plugins, and times concurrent
+//! calls. With per-server locking, two plugins finish in about one delay;
+//! two calls to the same plugin still take two (its stdio is one channel).
+//!
+//! Requires: Node.js on PATH (skipped otherwise).
+//! Run with: cargo test --test registry_parallel_bench -- --nocapture
+
+use omni_glass_lib::mcp::client::McpServer;
+use omni_glass_lib::mcp::ToolRegistry;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const DELAY_MS: u64 = 400;
+
+/// Minimal NDJSON MCP server with one `slow_echo` tool.
+const SLOW_SERVER_JS: &str = r#"
+const rl = require("readline").createInterface({ input: process.stdin });
+const send = (msg) => process.stdout.write(JSON.stringify(msg) + "\n");
+rl.on("line", (line) => {
+  const req = JSON.parse(line);
+  if (req.id === undefined) return;
+  if (req.method === "initialize") {
+    send({ jsonrpc: "2.0", id: req.id, result: { serverInfo: { name: "slow", version: "0.0.1" } } });
+  } else if (req.method === "tools/list") {
+    send({ jsonrpc: "2.0", id: req.id, result: { tools: [{ name: "slow_echo", description: "Sleep then echo" }] } });
+  } else if (req.method === "tools/call") {
+    setTimeout(() => send({ jsonrpc: "2.0", id: req.id,
+      result: { content: [{ type: "text", text: req.params.arguments.text }] } }), DELAY_MS);
+  }
+});
+"#;
+
+fn node_available() -> bool {
+    std::process::Command::new("node")
+        .arg("--version")
+        .output()
+        .is_ok()
+}
+
+async fn spawn_plugin(registry: &ToolRegistry, plugin_id: &str, script: &std::path::Path) {
+    let mut server = McpServer::spawn(
+        plugin_id,
+        "node",
+        &[&script.to_string_lossy()],
+        HashMap::new(),
+        None,
+    )
+    .expect("spawn slow server");
+    server.initialize().await.expect("initialize");
+    let tools = server.list_tools().await.expect("tools/list");
+    registry.register_plugin_tools(plugin_id, tools).await;
+    registry.add_server(plugin_id.to_string(), server).await;
+}
+
+async fn call(registry: &ToolRegistry, action_id: &str) -> Duration {
+    let start = Instant::now();
+    let result = registry
+        .call_plugin_tool(action_id, serde_json::json!({ "text": action_id }))
+        .await
+        .expect("tools/call");
+    assert_eq!(result.text(), action_id);
+    start.elapsed()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn benchmark_concurrent_calls_to_two_plugins_overlap() {
+    if !node_available() {
+        eprintln!("SKIP: node not on PATH");
+        return;
+    }
+    let script = std::env::temp_dir().join("og-slow-mcp-server.js");
+    std::fs::write(
+        &script,
+        SLOW_SERVER_JS.replace("DELAY_MS", &DELAY_MS.to_string()),
+    )
+    .unwrap();
+
+    let registry = ToolRegistry::new();
+    spawn_plugin(&registry, "com.bench.a", &script).await;
+    spawn_plugin(&registry, "com.bench.b", &script).await;
+
+    let start = Instant::now();
+    tokio::join!(
+        call(&registry, "com.bench.a:slow_echo"),
+        call(&registry, "com.bench.b:slow_echo")
+    );
+    let two_plugins = start.elapsed();
+
+    let start = Instant::now();
+    tokio::join!(
+        call(&registry, "com.bench.a:slow_echo"),
+        call(&registry, "com.bench.a:slow_echo")
+    );
+    let same_plugin = start.elapsed();
+
+    let start = Instant::now();
+    let _ = registry.all_tools().await;
+    let query_during_idle = start.elapsed();
+
+    registry.shutdown_all().await;
+
+    eprintln!("=== Registry Parallelism Benchmark ===");
+    eprintln!("Tool delay:                 {}ms", DELAY_MS);
+    eprintln!("2 calls, 2 plugins (join):  {:?}", two_plugins);
+    eprintln!("2 calls, same plugin:       {:?}", same_plugin);
+    eprintln!("Registry query:             {:?}", query_during_idle);
+    eprintln!("======================================");
+
+    let delay = Duration::from_millis(DELAY_MS);
+    assert!(
+        two_plugins < delay * 3 / 2,
+        "calls to different plugins should overlap: took {:?}",
+        two_plugins
+    );
+    assert!(
+        same_plugin >= delay * 2,
+        "calls to one plugin share its stdio channel: took {:?}",
+        same_plugin
+    );
+}
@@ -0,0 +1,131 @@
+//! Tests for the relevance pre-filter: ranking, pins and small installs.
+//!
+//! Run with: cargo test --test relevance
+
+use omni_glass_lib::mcp::registry::RegisteredTool;
+use omni_glass_lib::mcp::relevance::{
+    guess_content_types, select, Candidate, PluginHints, Query, Selection, MAX_PROMPT_TOOLS,
+};
+use omni_glass_lib::mcp::tool_prefs::ToolPrefs;
+
+fn tool(plugin_id: &str, name: &str, description: &str) -> RegisteredTool {
+    RegisteredTool {
+        plugin_id: plugin_id.to_string(),
+        name: name.to_string(),
+        display_name: name.replace('_', " "),
+        description: description.to_string(),
+        input_schema: None,
+        output_schema: None,
+    }
+}
+
+struct Fixture {
+    ids: Vec<String>,
+    tools: Vec<RegisteredTool>,
+    hints: Vec<Option<PluginHints>>,
+}
+
+impl Fixture {
+    fn new(tools: Vec<(RegisteredTool, Option<PluginHints>)>) -> Self {
+        let (tools, hints): (Vec<_>, Vec<_>) = tools.into_iter().unzip();
+        let ids = tools
+            .iter()
+            .map(|t| format!("{}:{}", t.plugin_id, t.name))
+            .collect();
+        Self { ids, tools, hints }
+    }
+
+    fn select(&self, query: &Query, prefs: &ToolPrefs, limit: usize) -> Selection {
+        let candidates: Vec<Candidate> = (0..self.tools.len())
+            .map(|i| Candidate {
+                id: &self.ids[i],
+                tool: &self.tools[i],
+                hints: self.hints[i].as_ref(),
+            })
+            .collect();
+        select(&candidates, query, prefs, limit)
+    }
+}
+
+fn fixture() -> Fixture {
+    Fixture::new(vec![
+        (
+            tool(
+                "com.gh",
+                "create_issue",
+                "Create a GitHub issue from the text",
+            ),
+            Some(PluginHints {
+                content_types: vec!["error".to_string()],
+                keywords: vec!["bug".to_string(), "github".to_string()],
+            }),
+        ),
+        (
+            tool(
+                "com.cal",
+                "add_event",
+                "Add a calendar event for a date and time",
+            ),
+            None,
+        ),
+        (
+            tool(
+                "com.units",
+                "convert_units",
+                "Convert measurements between units",
+            ),
+            None,
+        ),
+        (tool("com.jira", "log_ticket", "Open a Jira ticket"), None),
+    ])
+}
+
+#[test]
+fn content_types_come_from_text_and_heuristics() {
+    assert_eq!(
+        guess_content_types("Traceback (most recent call last)", false, true),
+        ["error", "code"]
+    );
+    assert_eq!(
+        guess_content_types("see https://example.com", false, false),
+        ["url"]
+    );
+    assert_eq!(guess_content_types("Dear team,", false, false), ["prose"]);
+}
+
+#[test]
+fn keywords_and_content_types_rank_tools() {
+    let f = fixture();
+    let query = Query::from_snip("TypeError: x is undefined", false, true);
+    let selection = f.select(&query, &ToolPrefs::default(), 1);
+    assert_eq!(selection.available, 4);
+    assert_eq!(selection.offered, ["com.gh:create_issue"]);
+
+    let query = Query::from_snip("Team sync: add to calendar, Tuesday 10am", false, false);
+    let selection = f.select(&query, &ToolPrefs::default(), 2);
+    assert_eq!(selection.offered, ["com.cal:add_event"]);
+}
+
+#[test]
+fn small_installs_are_not_filtered() {
+    let f = fixture();
+    let query = Query::from_snip("Dear team,", false, false);
+    let selection = f.select(&query, &ToolPrefs::default(), MAX_PROMPT_TOOLS);
+    assert_eq!(selection.offered.len(), 4);
+}
+
+#[test]
+fn pinned_tools_always_offered_and_disabled_never() {
+    let f = fixture();
+    let prefs = ToolPrefs {
+        disabled: vec!["com.gh:create_issue".to_string()],
+        pinned: vec!["com.jira:log_ticket".to_string()],
+    };
+    let query = Query::from_snip("Fatal error: bug in calendar sync", false, false);
+    let selection = f.select(&query, &prefs, 1);
+    assert_eq!(selection.available, 3);
+    assert_eq!(
+        selection.offered,
+        ["com.jira:log_ticket", "com.cal:add_event"]
+    );
+}
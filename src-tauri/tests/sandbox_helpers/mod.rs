@@ -1,6 +1,6 @@
 //! Shared test helpers for sandbox escape tests.
 
-use omni_glass_lib::mcp::manifest::{Permissions, PluginManifest, Runtime};
+use omni_glass_lib::mcp::manifest::{Permissions, PluginManifest};
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -19,11 +19,9 @@ pub fn test_manifest(id: &str, perms: Permissions) -> PluginManifest {
         id: id.to_string(),
         name: "Sandbox Test".to_string(),
         version: "1.0.0".to_string(),
-        description: String::new(),
-        runtime: Runtime::Node,
         entry: "index.js".to_string(),
         permissions: perms,
-        configuration: None,
+        ..Default::default()
     }
 }
 
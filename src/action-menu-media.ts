@@ -0,0 +1,79 @@
+/**
+ * Action menu image results — preview with caption and Save button.
+ *
+ * Plugins can return MCP image content; Rust validates the mime type and
+ * base64 payload before it reaches here, and Save writes the decoded
+ * bytes through the native save dialog (handleFileResult).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { escapeAttr, escapeHtml } from "./action-menu-render";
+import { ActionResult, handleFileResult } from "./action-menu-results";
+
+const buttonStyle = `
+  background: transparent;
+  border: 1px solid rgba(255,255,255,0.2);
+  color: rgba(255,255,255,0.8);
+  padding: 4px 12px;
+  border-radius: 4px;
+  cursor: pointer;
+  font-size: 12px;
+`;
+
+export async function handleImageResult(result: ActionResult): Promise<void> {
+  const mimeType = result.result.mimeType || "image/png";
+  const caption = result.result.text || "";
+  const src = `data:${mimeType};base64,${result.result.data || ""}`;
+
+  const container = document.getElementById("action-menu")!;
+  const wrapper = container.querySelector("div")!;
+  wrapper.style.width = "380px";
+
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  actionsEl.innerHTML = `
+    <div style="padding: 12px 14px; text-align: center;">
+      <img id="result-image" src="${escapeAttr(src)}" alt="${escapeAttr(caption)}" style="
+        max-width: 100%;
+        max-height: 320px;
+        border-radius: 4px;
+        background: rgba(0,0,0,0.3);
+      " />
+      ${caption ? `<div style="
+        margin-top: 8px;
+        font-size: 12px;
+        color: rgba(255,255,255,0.7);
+        line-height: 1.4;
+        text-align: left;
+      ">${escapeHtml(caption)}</div>` : ""}
+    </div>
+    <div style="
+      padding: 6px 14px 8px;
+      display: flex;
+      gap: 8px;
+      justify-content: flex-end;
+      border-top: 1px solid rgba(255,255,255,0.1);
+    ">
+      <button id="btn-save-image" style="${buttonStyle}">Save Image</button>
+      <button id="btn-close-image" style="${buttonStyle}">Close</button>
+    </div>
+  `;
+
+  document.getElementById("btn-save-image")?.addEventListener("click", async () => {
+    await handleFileResult(result);
+  });
+  document.getElementById("btn-close-image")?.addEventListener("click", async () => {
+    try { await invoke("close_action_menu"); } catch { /* closing */ }
+  });
+
+  const image = document.getElementById("result-image") as HTMLImageElement | null;
+  image?.addEventListener("load", async () => {
+    const totalHeight = Math.min(actionsEl.scrollHeight + 60, 520);
+    try {
+      await getCurrentWebviewWindow().setSize(new LogicalSize(400, totalHeight));
+    } catch { /* resize not critical */ }
+  });
+}
@@ -64,6 +64,11 @@ export function escapeHtml(text: string): string {
   return div.innerHTML;
 }
 
+/** Escape for use inside a quoted HTML attribute (escapeHtml leaves quotes). */
+export function escapeAttr(text: string): string {
+  return escapeHtml(text).replace(/"/g, "&quot;").replace(/'/g, "&#39;");
+}
+
 // ── Drag handle ─────────────────────────────────────────────────────
 
 const DRAG_HANDLE = `
@@ -189,6 +194,33 @@ export function renderSkeleton(): void {
 
 // ── Skeleton update ─────────────────────────────────────────────────
 
+/** Show plugin actions from content triggers below Copy Text, before classify ends. */
+export function renderTriggeredActions(actions: Action[]): void {
+  const copyRow = document.querySelector('#menu-actions .action-row[data-action-id="copy_text"]');
+  if (!copyRow || actions.length === 0) return;
+  copyRow.insertAdjacentHTML(
+    "afterend",
+    actions
+      .map(
+        (action) => `
+        <div class="action-row" data-action-id="${escapeAttr(action.id)}" style="
+          padding: 0 14px;
+          height: 36px;
+          display: flex;
+          align-items: center;
+          gap: 10px;
+          cursor: pointer;
+          transition: background 0.1s;
+        " title="${escapeAttr(action.description)}">
+          <span style="font-size: 16px; width: 20px; text-align: center;">${getIcon(action.icon)}</span>
+          <span style="flex: 1; font-size: 14px;">${escapeHtml(action.label)}</span>
+        </div>`
+      )
+      .join("")
+  );
+  console.log(`[RENDER] Skeleton: ${actions.length} triggered action(s)`);
+}
+
 export function updateSummary(skeleton: ActionMenuSkeleton): void {
   const summaryEl = document.getElementById("menu-summary");
   if (summaryEl) {
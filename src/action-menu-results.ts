@@ -2,7 +2,8 @@
  * Action menu result handlers — displays results from LLM execute.
  *
  * Handles text results (with code block extraction), file export
- * (native save dialog), and command confirmation (opens dialog window).
+ * (native save dialog, text or base64 binary), and command confirmation
+ * (opens dialog window). Image and link results live in action-menu-media.ts.
  */
 
 import { invoke } from "@tauri-apps/api/core";
@@ -22,11 +23,44 @@ export interface ActionResultBody {
   command?: string;
   clipboardContent?: string;
   mimeType?: string;
+  data?: string;
+  url?: string;
+  arguments?: Record<string, unknown>;
+}
+
+/** A resource limit a plugin ran into (see src-tauri/src/mcp/limits.rs). */
+export interface LimitViolation {
+  kind: "cpuTime" | "messageSize" | "callTimeout";
+  limitSeconds?: number;
+  limitBytes?: number;
+}
+
+/** Where a recipe was when it returned this result. */
+export interface RecipeProgress {
+  recipeId: string;
+  label: string;
+  step: number;
+  steps: number;
+  action: string;
 }
 
 export interface ActionResultMeta {
   tokensUsed?: number;
   processingNote?: string;
+  limit?: LimitViolation;
+  recipe?: RecipeProgress;
+}
+
+/** One-line explanation of a limit error and what can be changed. */
+export function describeLimit(limit: LimitViolation): string {
+  switch (limit.kind) {
+    case "cpuTime":
+      return `The plugin used its ${limit.limitSeconds}s CPU budget and was stopped. Restart Omni-Glass to reload it.`;
+    case "messageSize":
+      return `The plugin's reply was over ${Math.round((limit.limitBytes ?? 0) / 1024)} KB.`;
+    case "callTimeout":
+      return `The plugin took longer than ${limit.limitSeconds}s.`;
+  }
 }
 
 export interface ActionResult {
@@ -38,7 +72,7 @@ export interface ActionResult {
 
 // ── Text result ──────────────────────────────────────────────────────
 
-export async function showTextResult(text: string): Promise<void> {
+export async function showTextResult(text: string, warning?: string): Promise<void> {
   const container = document.getElementById("action-menu")!;
   const wrapper = container.querySelector("div")!;
   wrapper.style.width = "380px";
@@ -50,6 +84,7 @@ export async function showTextResult(text: string): Promise<void> {
   const actionsEl = document.getElementById("menu-actions");
   if (actionsEl) {
     actionsEl.innerHTML = `
+      ${warning ? renderWarning(warning) : ""}
       <div style="
         padding: 12px 14px;
         font-size: 13px;
@@ -159,7 +194,11 @@ export async function handleFileResult(result: ActionResult): Promise<void> {
 
     if (!chosenPath) return;
 
-    await invoke<string>("write_file_to_path", { filePath: chosenPath, content });
+    if (result.result.data) {
+      await invoke<string>("write_binary_file_to_path", { filePath: chosenPath, data: result.result.data });
+    } else {
+      await invoke<string>("write_file_to_path", { filePath: chosenPath, content });
+    }
     console.log(`[ACTION] File written to: ${chosenPath}`);
 
     const savedName = chosenPath.split("/").pop() || filename;
@@ -244,6 +283,7 @@ export async function handleCommandResult(result: ActionResult): Promise<void> {
           command,
           explanation,
           actionId: result.actionId,
+          warning: result.metadata?.processingNote,
         });
       }, 200);
     });
@@ -257,6 +297,17 @@ export async function handleCommandResult(result: ActionResult): Promise<void> {
 
 // ── Internal helpers ─────────────────────────────────────────────────
 
+/** Amber banner for results flagged by the prompt-injection scan. */
+function renderWarning(warning: string): string {
+  return `<div style="
+    padding: 8px 14px;
+    font-size: 12px;
+    color: #f59e0b;
+    background: rgba(245,158,11,0.1);
+    border-bottom: 1px solid rgba(245,158,11,0.3);
+  ">${escapeHtml(warning)}</div>`;
+}
+
 /** Extract content from the first ``` code block, or null if none found. */
 function extractCodeBlock(text: string): string | null {
   const match = text.match(/```[\w]*\n([\s\S]*?)```/);
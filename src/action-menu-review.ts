@@ -0,0 +1,87 @@
+/**
+ * Action menu argument review — shown for "tool_args" results.
+ *
+ * A plugin tool with side effects comes back from execute_action with the
+ * generated arguments instead of running. The user edits/approves them in
+ * the shared form (tool-args-form.ts); confirm_plugin_call then validates
+ * and runs the call, and its result is shown like any other.
+ *
+ * A recipe step waiting for review looks the same but carries
+ * `metadata.recipe`: the form is headed with the recipe's progress, Run
+ * goes to continue_recipe (which runs the remaining steps) and Cancel
+ * drops the recipe.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { escapeHtml, showFeedback } from "./action-menu-render";
+import { ActionResult } from "./action-menu-results";
+import { renderArgsForm, ToolReview } from "./tool-args-form";
+
+export async function handleToolArgsResult(
+  result: ActionResult,
+  showResult: (result: ActionResult) => Promise<void>,
+): Promise<void> {
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  let review: ToolReview;
+  try {
+    review = await invoke<ToolReview>("get_tool_review", { actionId: result.actionId });
+  } catch (err) {
+    showFeedback(`Error: ${err}`, true);
+    return;
+  }
+
+  const container = document.getElementById("action-menu")!;
+  container.querySelector("div")!.style.width = "380px";
+
+  const recipe = result.metadata?.recipe;
+  renderArgsForm(
+    actionsEl,
+    review,
+    result.result.arguments || {},
+    async (decision) => {
+      try {
+        const final = recipe
+          ? await invoke<ActionResult>("continue_recipe", {
+              arguments: decision.arguments,
+              remember: decision.remember,
+            })
+          : await invoke<ActionResult>("confirm_plugin_call", {
+              actionId: result.actionId,
+              arguments: decision.arguments,
+              remember: decision.remember,
+              recordHistory: true,
+            });
+        await showResult(final);
+        return null;
+      } catch (err) {
+        return String(err);
+      }
+    },
+    async () => {
+      if (recipe) {
+        try { await invoke("cancel_recipe"); } catch { /* closing anyway */ }
+      }
+      try { await invoke("close_action_menu"); } catch { /* closing */ }
+    },
+  );
+
+  if (recipe) {
+    actionsEl.insertAdjacentHTML(
+      "afterbegin",
+      `<div style="padding: 8px 14px 0; font-size: 11px; color: #93c5fd;">
+        Step ${recipe.step} of ${recipe.steps} · ${escapeHtml(recipe.label)}
+      </div>`,
+    );
+  }
+
+  requestAnimationFrame(async () => {
+    const totalHeight = Math.min(actionsEl.scrollHeight + 60, 560);
+    try {
+      await getCurrentWebviewWindow().setSize(new LogicalSize(400, totalHeight));
+    } catch { /* resize not critical */ }
+  });
+}
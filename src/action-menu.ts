@@ -4,6 +4,7 @@
  * State 1 (Skeleton): Shows immediately when the window opens.
  *   - Shimmer placeholder for summary
  *   - Copy Text button (always available — OCR text is already stored)
+ *   - Plugin actions whose content triggers fired (matched locally)
  *   - 3 shimmer placeholders for loading actions
  *
  * State 2 (Complete): Fills in when the streaming LLM response finishes.
@@ -22,7 +23,9 @@ import { open } from "@tauri-apps/plugin-shell";
 import {
   ActionMenu,
   ActionMenuSkeleton,
+  Action,
   renderSkeleton,
+  renderTriggeredActions,
   updateSummary,
   renderMenu,
   showLoading,
@@ -36,7 +39,10 @@ import {
   showTextResult,
   handleFileResult,
   handleCommandResult,
+  describeLimit,
 } from "./action-menu-results";
+import { handleImageResult } from "./action-menu-media";
+import { handleToolArgsResult } from "./action-menu-review";
 
 // ── State ───────────────────────────────────────────────────────────
 
@@ -73,38 +79,58 @@ async function executeAction(actionId: string): Promise<void> {
     const result = await invoke<ActionResult>("execute_action", { actionId });
     console.log(`[ACTION] Result: status=${result.status}, type=${result.result.type}`);
 
-    if (result.status === "error") {
-      console.error(`[ACTION] Execute error: ${result.result.text}`);
-      showFeedback(result.result.text || "Action failed", true);
-      return;
-    }
-
-    switch (result.result.type) {
-      case "text":
-        showTextResult(result.result.text || "No content returned.");
-        break;
-      case "clipboard":
-        if (result.result.clipboardContent) {
-          await invoke("copy_to_clipboard", { text: result.result.clipboardContent });
-          showFeedback("Copied to clipboard");
-          closeAfterDelay(800);
-        }
-        break;
-      case "file":
-        await handleFileResult(result);
-        break;
-      case "command":
-        await handleCommandResult(result);
-        break;
-      default:
-        showFeedback(`Unknown result type: ${result.result.type}`, true);
-    }
+    await showResult(result);
   } catch (err) {
     console.error(`[ACTION] Failed to execute ${actionId}:`, err);
     showFeedback(`Error: ${err}`, true);
   }
 }
 
+// ── Result display ───────────────────────────────────────────────────
+
+/** Show an action result; also used after a reviewed plugin call. */
+async function showResult(result: ActionResult): Promise<void> {
+  if (result.status === "error") {
+    console.error(`[ACTION] Execute error: ${result.result.text}`);
+    const limit = result.metadata?.limit;
+    showFeedback(limit ? describeLimit(limit) : result.result.text || "Action failed", true);
+    return;
+  }
+
+  switch (result.result.type) {
+    case "text":
+      showTextResult(result.result.text || "No content returned.", result.metadata?.processingNote);
+      break;
+    case "clipboard":
+      if (result.result.clipboardContent) {
+        await invoke("copy_to_clipboard", { text: result.result.clipboardContent });
+        showFeedback("Copied to clipboard");
+        closeAfterDelay(800);
+      }
+      break;
+    case "file":
+      await handleFileResult(result);
+      break;
+    case "command":
+      await handleCommandResult(result);
+      break;
+    case "image":
+      await handleImageResult(result);
+      break;
+    case "link":
+      showTextResult(
+        [result.result.text, result.result.url].filter(Boolean).join("\n\n"),
+        result.metadata?.processingNote,
+      );
+      break;
+    case "tool_args":
+      await handleToolArgsResult(result, showResult);
+      break;
+    default:
+      showFeedback(`Unknown result type: ${result.result.type}`, true);
+  }
+}
+
 // ── Init ─────────────────────────────────────────────────────────────
 
 async function init(): Promise<void> {
@@ -136,6 +162,12 @@ async function init(): Promise<void> {
     renderMenu(event.payload);
   });
 
+  invoke<Action[]>("get_triggered_actions")
+    .then((actions) => {
+      if (!menuRendered) renderTriggeredActions(actions);
+    })
+    .catch(() => { /* none for this snip */ });
+
   pollForMenu();
 }
 
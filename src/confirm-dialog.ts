@@ -18,6 +18,7 @@ interface ConfirmPayload {
   command: string;
   explanation: string;
   actionId: string;
+  warning?: string;
 }
 
 function escapeHtml(text: string): string {
@@ -47,6 +48,17 @@ function renderDialog(payload: ConfirmPayload): void {
         Confirm Command
       </div>
 
+      ${payload.warning ? `<div style="
+        background: rgba(245,158,11,0.1);
+        border: 1px solid rgba(245,158,11,0.4);
+        border-radius: 6px;
+        padding: 8px 12px;
+        font-size: 12px;
+        color: #f59e0b;
+        margin-bottom: 12px;
+        line-height: 1.4;
+      ">${escapeHtml(payload.warning)}</div>` : ""}
+
       <div style="
         background: #0d1117;
         border: 1px solid rgba(255,255,255,0.1);
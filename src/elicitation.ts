@@ -0,0 +1,109 @@
+/**
+ * Plugin question window — answers an MCP `elicitation/create` request.
+ *
+ * A plugin can ask the user for input while one of its tools runs. Rust
+ * opens this window with the label `elicitation-<id>` and holds the plugin
+ * call until the user submits, declines, or closes the window.
+ *
+ * Flow:
+ * 1. Read the prompt id from the window label, load it via get_elicitation
+ * 2. Render the plugin's message and a form for its requestedSchema
+ * 3. Submit → respond_elicitation("accept", values); Rust validates them
+ *    and closes the window. Decline / Cancel send no values.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { escapeHtml } from "./action-menu-render";
+import { buttonStyle, JsonSchema, readField, renderField } from "./tool-args-form";
+
+interface ElicitationPrompt {
+  id: number;
+  pluginId: string;
+  message: string;
+  requestedSchema: JsonSchema;
+}
+
+const appWindow = getCurrentWebviewWindow();
+const promptId = Number(appWindow.label.replace("elicitation-", ""));
+
+async function respond(action: string, content: Record<string, unknown> | null): Promise<string | null> {
+  try {
+    await invoke("respond_elicitation", { id: promptId, action, content });
+    return null;
+  } catch (err) {
+    return String(err);
+  }
+}
+
+function render(prompt: ElicitationPrompt): void {
+  const container = document.getElementById("elicitation")!;
+  const props = prompt.requestedSchema.properties || {};
+  const required = new Set(prompt.requestedSchema.required || []);
+  const names = Object.keys(props);
+
+  container.innerHTML = `
+    <div style="padding: 14px 16px 6px;">
+      <div style="font-size: 11px; color: rgba(255,255,255,0.5); margin-bottom: 4px;">
+        ${escapeHtml(prompt.pluginId)} asks:
+      </div>
+      <div style="font-size: 14px; color: #f1f5f9; line-height: 1.4; margin-bottom: 12px; white-space: pre-wrap;">${escapeHtml(prompt.message)}</div>
+      <div style="max-height: 180px; overflow-y: auto;">
+        ${names.map((n) => renderField(n, props[n], props[n]?.default, required.has(n))).join("")}
+      </div>
+      <div id="elicit-error" style="display: none; color: #fca5a5; font-size: 12px; white-space: pre-wrap; margin: 6px 0;"></div>
+    </div>
+    <div style="
+      padding: 8px 16px 10px;
+      display: flex;
+      gap: 8px;
+      justify-content: flex-end;
+      border-top: 1px solid rgba(255,255,255,0.1);
+    ">
+      <button id="btn-decline" style="${buttonStyle}">Decline</button>
+      <button id="btn-cancel" style="${buttonStyle}">Cancel</button>
+      <button id="btn-submit" style="${buttonStyle} background: #3b82f6; border-color: #3b82f6; color: white;">Submit</button>
+    </div>
+  `;
+
+  const errorEl = document.getElementById("elicit-error")!;
+  const showError = (message: string) => {
+    errorEl.textContent = message;
+    errorEl.style.display = "block";
+  };
+
+  document.getElementById("btn-decline")?.addEventListener("click", async () => {
+    const error = await respond("decline", null);
+    if (error) showError(error);
+  });
+  document.getElementById("btn-cancel")?.addEventListener("click", async () => {
+    const error = await respond("cancel", null);
+    if (error) showError(error);
+  });
+  document.getElementById("btn-submit")?.addEventListener("click", async () => {
+    const values: Record<string, unknown> = {};
+    try {
+      for (const name of names) {
+        const value = readField(name, props[name]);
+        if (value !== undefined) values[name] = value;
+      }
+    } catch (err) {
+      showError(String((err as Error).message || err));
+      return;
+    }
+    const error = await respond("accept", values);
+    if (error) showError(error);
+  });
+}
+
+async function init(): Promise<void> {
+  try {
+    const prompt = await invoke<ElicitationPrompt>("get_elicitation", { id: promptId });
+    render(prompt);
+  } catch (err) {
+    console.error("[ELICIT] Failed to load prompt:", err);
+    await appWindow.close();
+  }
+}
+
+init();
@@ -0,0 +1,203 @@
+/**
+ * History browser — search past snips and re-run actions on them.
+ *
+ * Sections:
+ *   1. Privacy — history on/off switch and "Clear History"
+ *   2. Search — full-text search over OCR text, summaries and results
+ *   3. Results — thumbnail, summary, OCR preview, executed actions
+ *
+ * Re-running an action sends the stored OCR text through the normal
+ * execute pipeline; the new result is appended to the same entry.
+ */
+
+import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+
+interface ActionRecord {
+  actionId: string;
+  timestamp: number;
+  status: string;
+  resultType: string;
+  text: string | null;
+  command: string | null;
+  filePath: string | null;
+}
+
+interface SnipRecord {
+  id: string;
+  timestamp: number;
+  thumbnailPath: string | null;
+  ocrText: string;
+  contentType: string;
+  summary: string;
+  actions: ActionRecord[];
+}
+
+interface HistorySettings {
+  enabled: boolean;
+  maxEntries: number;
+  maxAgeDays: number;
+}
+
+interface ActionResult {
+  status: string;
+  result: { text?: string; command?: string; filePath?: string };
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function formatTime(secs: number): string {
+  return new Date(secs * 1000).toLocaleString();
+}
+
+// ── Render ───────────────────────────────────────────────────────────
+
+const buttonStyle = `
+  background: transparent;
+  border: 1px solid rgba(255,255,255,0.2);
+  color: rgba(255,255,255,0.8);
+  padding: 2px 10px;
+  border-radius: 4px;
+  cursor: pointer;
+  font-size: 12px;`;
+
+function renderAction(snipId: string, a: ActionRecord): string {
+  const detail = a.command ?? a.filePath ?? a.text ?? "";
+  return `
+    <div style="display:flex; gap:8px; align-items:center; margin-top:4px; font-size:12px;">
+      <span style="color:${a.status === "error" ? "#f87171" : "#94a3b8"};">${escapeHtml(a.actionId)}</span>
+      <span style="flex:1; color:rgba(255,255,255,0.5); overflow:hidden; text-overflow:ellipsis; white-space:nowrap;">${escapeHtml(detail.slice(0, 120))}</span>
+      <button class="btn-rerun" data-id="${snipId}" data-action="${escapeHtml(a.actionId)}" style="${buttonStyle}">Re-run</button>
+    </div>`;
+}
+
+function renderEntry(r: SnipRecord): string {
+  const thumb = r.thumbnailPath
+    ? `<img src="${convertFileSrc(r.thumbnailPath)}" style="width:96px; border-radius:4px; flex-shrink:0;" />`
+    : "";
+  // Latest result per action, newest first.
+  const seen = new Set<string>();
+  const actions = [...r.actions]
+    .reverse()
+    .filter((a) => !seen.has(a.actionId) && seen.add(a.actionId))
+    .map((a) => renderAction(r.id, a))
+    .join("");
+  return `
+    <div style="padding:12px 0; border-bottom:1px solid rgba(255,255,255,0.08);">
+      <div style="display:flex; gap:12px;">
+        ${thumb}
+        <div style="flex:1; min-width:0;">
+          <div style="font-size:12px; color:rgba(255,255,255,0.5);">
+            ${formatTime(r.timestamp)} · ${escapeHtml(r.contentType)}
+            <button class="btn-delete" data-id="${r.id}" style="${buttonStyle} float:right;">Delete</button>
+          </div>
+          <div style="margin:4px 0; color:#e2e8f0;">${escapeHtml(r.summary)}</div>
+          <div style="font-size:12px; color:rgba(255,255,255,0.6); white-space:pre-wrap; max-height:60px; overflow:hidden;">${escapeHtml(r.ocrText.slice(0, 300))}</div>
+        </div>
+      </div>
+      ${actions}
+    </div>`;
+}
+
+function renderShell(settings: HistorySettings): void {
+  document.getElementById("history")!.innerHTML = `
+    <div style="padding: 20px; max-width: 520px; margin: 0 auto;">
+      <h1 style="font-size: 18px; font-weight: 600; margin-bottom: 12px; color: #e2e8f0;">History</h1>
+      <div style="display:flex; gap:12px; align-items:center; margin-bottom:12px; font-size:13px;">
+        <label style="flex:1;">
+          <input type="checkbox" id="toggle-enabled" ${settings.enabled ? "checked" : ""} />
+          Keep history (last ${settings.maxEntries} snips, ${settings.maxAgeDays} days)
+        </label>
+        <button id="btn-purge" style="${buttonStyle}">Clear History</button>
+      </div>
+      <input id="search" type="search" placeholder="Search OCR text, summaries, results..." style="
+        width: 100%; padding: 8px 10px; border-radius: 6px; font-size: 13px;
+        background: rgba(255,255,255,0.06); color: #fff;
+        border: 1px solid rgba(255,255,255,0.15);" />
+      <div id="rerun-result"></div>
+      <div id="entries"></div>
+    </div>`;
+}
+
+async function refresh(): Promise<void> {
+  const query = (document.getElementById("search") as HTMLInputElement).value;
+  const entries = document.getElementById("entries")!;
+  try {
+    const records = await invoke<SnipRecord[]>("search_history", { query });
+    entries.innerHTML = records.length
+      ? records.map(renderEntry).join("")
+      : `<div style="padding:16px 0; color:rgba(255,255,255,0.5); font-size:13px;">No snips found</div>`;
+  } catch (err) {
+    entries.innerHTML = `<div style="padding:16px 0; color:#f87171;">${escapeHtml(String(err))}</div>`;
+  }
+}
+
+function showRerunResult(text: string, isError: boolean): void {
+  document.getElementById("rerun-result")!.innerHTML = `
+    <div style="margin-top:12px; padding:10px; border-radius:6px; font-size:12px; white-space:pre-wrap;
+      background:rgba(255,255,255,0.06); color:${isError ? "#f87171" : "#e2e8f0"};">${escapeHtml(text)}</div>`;
+}
+
+// ── Actions ──────────────────────────────────────────────────────────
+
+async function rerun(id: string, actionId: string): Promise<void> {
+  showRerunResult(`Running ${actionId}...`, false);
+  try {
+    const res = await invoke<ActionResult>("rerun_history_action", { id, actionId });
+    const body = res.result.command ?? res.result.filePath ?? res.result.text ?? "";
+    showRerunResult(body, res.status === "error");
+  } catch (err) {
+    showRerunResult(String(err), true);
+  }
+  await refresh();
+}
+
+async function init(): Promise<void> {
+  const settings = await invoke<HistorySettings>("get_history_settings");
+  renderShell(settings);
+
+  let debounce: number | undefined;
+  document.getElementById("search")!.addEventListener("input", () => {
+    clearTimeout(debounce);
+    debounce = window.setTimeout(refresh, 200);
+  });
+
+  document.getElementById("toggle-enabled")!.addEventListener("change", async (e) => {
+    const enabled = (e.target as HTMLInputElement).checked;
+    if (!enabled && !confirm("Turning history off deletes all saved snips. Continue?")) {
+      (e.target as HTMLInputElement).checked = true;
+      return;
+    }
+    await invoke("set_history_settings", { settings: { ...settings, enabled } });
+    settings.enabled = enabled;
+    await refresh();
+  });
+
+  document.getElementById("btn-purge")!.addEventListener("click", async () => {
+    if (!confirm("Delete all saved snips?")) return;
+    await invoke("purge_history");
+    await refresh();
+  });
+
+  document.getElementById("entries")!.addEventListener("click", async (e) => {
+    const target = e.target as HTMLElement;
+    const id = target.dataset.id;
+    if (!id) return;
+    if (target.classList.contains("btn-rerun")) {
+      await rerun(id, target.dataset.action!);
+    } else if (target.classList.contains("btn-delete")) {
+      await invoke("delete_history_entry", { id });
+      await refresh();
+    }
+  });
+
+  await refresh();
+}
+
+init().catch((err) => {
+  document.getElementById("history")!.innerHTML =
+    `<div style="padding:20px;color:#f87171;">Failed to load history: ${escapeHtml(String(err))}</div>`;
+});
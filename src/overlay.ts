@@ -1,15 +1,19 @@
 /**
  * Overlay module — handles the fullscreen snip interaction.
  *
+ * One overlay opens per monitor ("overlay", "overlay-1", ...), or a single
+ * overlay over a captured window. Each shows its own capture.
+ *
  * Flow:
- * 1. Fetches screenshot path from Rust via get_capture_info command.
+ * 1. Fetches its screenshot path from Rust via get_capture_info(index).
  * 2. Draws it on a canvas with a 50% dark overlay.
  * 3. User drags a rectangle to select a region.
- * 4. On mouseup, sends the rectangle coordinates to Rust via process_snip.
- * 5. Rust crops → OCR → LLM → opens action menu.
+ * 4. On mouseup, sends the CSS rectangle + viewport size to process_snip.
+ * 5. Rust maps to capture pixels → crops → OCR → LLM → opens action menu.
  */
 
 import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 
 interface SelectionRect {
   startX: number;
@@ -21,6 +25,13 @@ interface SelectionRect {
 interface CaptureInfo {
   image_path: string;
   click_epoch_ms: number;
+  index: number;
+}
+
+/** Capture index encoded in this overlay's window label ("overlay-2" → 2). */
+function captureIndex(): number {
+  const match = getCurrentWebviewWindow().label.match(/^overlay-(\d+)$/);
+  return match ? parseInt(match[1], 10) : 0;
 }
 
 export function setupOverlay(): void {
@@ -133,25 +144,19 @@ export function setupOverlay(): void {
     console.log(`Selection: ${w}×${h} at (${x}, ${y})`);
 
     try {
-      // Map CSS pixel coordinates to screenshot pixel coordinates.
-      // Use actual image dimensions — devicePixelRatio doesn't match the
-      // screenshot resolution on macOS scaled displays (e.g. "Looks like
-      // 1440x900" on a 2560x1600 panel gives dpr=2 but xcap captures at
-      // 2560x1600, not 2880x1800).
-      const cssW = window.innerWidth;
-      const cssH = window.innerHeight;
-      const imgW = screenshotImage?.width || cssW;
-      const imgH = screenshotImage?.height || cssH;
-      const scaleX = imgW / cssW;
-      const scaleY = imgH / cssH;
-      console.log(`[PIPELINE] Starting process_snip... scale=${scaleX.toFixed(2)}x${scaleY.toFixed(2)} img=${imgW}x${imgH} css=${cssW}x${cssH}`);
+      // Rust maps CSS pixels to capture pixels using this overlay's viewport,
+      // so each monitor's own scale factor applies on mixed-DPI setups.
+      console.log(`[PIPELINE] Starting process_snip... css=${window.innerWidth}x${window.innerHeight}`);
       await invoke("process_snip", {
-        x: Math.round(x * scaleX),
-        y: Math.round(y * scaleY),
-        width: Math.round(w * scaleX),
-        height: Math.round(h * scaleY),
-        menuX: x,       // CSS pixels for action menu window position
-        menuY: y + h,    // Bottom edge of bounding box
+        selection: {
+          captureIndex: captureIndex(),
+          x,
+          y,
+          width: w,
+          height: h,
+          viewportWidth: window.innerWidth,
+          viewportHeight: window.innerHeight,
+        },
       });
       // Overlay is closed by Rust after pipeline completes
     } catch (err) {
@@ -174,7 +179,7 @@ export function setupOverlay(): void {
   async function loadScreenshot(): Promise<void> {
     try {
       const fetchStartMs = Date.now();
-      const info = await invoke<CaptureInfo>("get_capture_info");
+      const info = await invoke<CaptureInfo>("get_capture_info", { index: captureIndex() });
       const clickEpochMs = info.click_epoch_ms;
       const commandMs = Date.now() - fetchStartMs;
       console.log(`[LATENCY] get_capture_info: ${commandMs}ms`);
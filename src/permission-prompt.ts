@@ -29,6 +29,13 @@ interface Permissions {
   filesystem: FsPerm[] | null;
   environment: string[] | null;
   shell: ShellPerm | null;
+  sampling?: boolean;
+}
+
+interface Publisher {
+  status: "verified" | "untrusted" | "unsigned";
+  name?: string;
+  fingerprint?: string;
 }
 
 interface PendingPlugin {
@@ -39,6 +46,7 @@ interface PendingPlugin {
   permissions: Permissions;
   riskLevel: string; // "Low" | "Medium" | "High"
   isUpdate: boolean;
+  publisher: Publisher;
 }
 
 function escapeHtml(text: string): string {
@@ -65,6 +73,18 @@ function riskBg(level: string): string {
   }
 }
 
+function renderPublisher(publisher: Publisher): string {
+  switch (publisher.status) {
+    case "verified":
+      return `<span style="color:#22c55e;">&#10003; Signed by ${escapeHtml(publisher.name ?? "")}</span>`;
+    case "untrusted":
+      return `<span style="color:#f59e0b;">Signed as "${escapeHtml(publisher.name ?? "")}" by an untrusted key
+        <code>${escapeHtml(publisher.fingerprint ?? "")}</code></span>`;
+    default:
+      return `<span style="color:rgba(255,255,255,0.5);">Unsigned — publisher not verified</span>`;
+  }
+}
+
 function renderPermissionList(perms: Permissions): string {
   const items: string[] = [];
 
@@ -93,6 +113,10 @@ function renderPermissionList(perms: Permissions): string {
     items.push(`<li>Shell commands: ${cmds}</li>`);
   }
 
+  if (perms.sampling) {
+    items.push(`<li>Use your AI provider (prompts are redacted first)</li>`);
+  }
+
   if (items.length === 0) {
     items.push(`<li style="color:rgba(255,255,255,0.5)">No special permissions</li>`);
   }
@@ -104,7 +128,7 @@ function renderPlugin(plugin: PendingPlugin): void {
   const container = document.getElementById("permission-prompt")!;
   const color = riskColor(plugin.riskLevel);
   const bg = riskBg(plugin.riskLevel);
-  const title = plugin.isUpdate ? "Updated Permissions" : "New Plugin";
+  const title = plugin.isUpdate ? "Plugin Changed" : "New Plugin";
 
   container.innerHTML = `
     <div style="
@@ -140,6 +164,9 @@ function renderPlugin(plugin: PendingPlugin): void {
         <div style="font-size:12px;color:rgba(255,255,255,0.5);margin-top:2px;">
           ${escapeHtml(plugin.id)}
         </div>
+        <div style="font-size:12px;margin-top:4px;">
+          ${renderPublisher(plugin.publisher)}
+        </div>
       </div>
 
       ${plugin.description ? `
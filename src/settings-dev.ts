@@ -0,0 +1,116 @@
+/**
+ * Plugin developer mode UI — rendered inside the Settings panel.
+ *
+ * Lists plugin folders loaded straight from disk, with a toggle for
+ * developer mode. A listed folder runs without the permission prompt and
+ * restarts whenever its files change; each row shows the running plugin,
+ * how many times it has loaded, and the last load error.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+
+interface DevPluginView {
+  path: string;
+  pluginId: string | null;
+  toolCount: number;
+  error: string | null;
+  loads: number;
+  loadedAt: number | null;
+}
+
+interface DevModeView {
+  enabled: boolean;
+  plugins: DevPluginView[];
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+const smallButton = `
+  padding:5px 10px;background:#1e3a5f;border:1px solid #3b82f6;
+  border-radius:4px;color:#93c5fd;font-size:12px;cursor:pointer;`;
+
+function describeStatus(p: DevPluginView, enabled: boolean): string {
+  if (!enabled) return "Not running — developer mode is off";
+  if (p.error) return `Load failed: ${p.error}`;
+  if (!p.pluginId) return "Starting...";
+  const time = p.loadedAt ? new Date(p.loadedAt * 1000).toLocaleTimeString() : "";
+  const restarts = p.loads > 1 ? `, restarted ${p.loads - 1}×` : "";
+  return `Running ${p.pluginId} — ${p.toolCount} tool(s), loaded ${time}${restarts}`;
+}
+
+function renderDevPlugin(p: DevPluginView, enabled: boolean): string {
+  const color = p.error && enabled ? "#f87171" : "rgba(255,255,255,0.45)";
+  return `
+    <div style="display:flex;justify-content:space-between;align-items:center;gap:8px;
+      background:#0f1629;border:1px solid rgba(255,255,255,0.1);border-radius:8px;
+      padding:8px 12px;margin-bottom:6px;">
+      <span style="min-width:0;">
+        <code style="font-size:12px;word-break:break-all;">${escapeHtml(p.path)}</code>
+        <div style="font-size:11px;color:${color};margin-top:2px;white-space:pre-wrap;">${escapeHtml(describeStatus(p, enabled))}</div>
+      </span>
+      <button class="dev-remove" data-path="${escapeHtml(p.path)}" style="
+        padding:4px 10px;background:#7f1d1d;border:1px solid #dc2626;border-radius:4px;
+        color:#fca5a5;font-size:12px;cursor:pointer;">Remove</button>
+    </div>`;
+}
+
+/** Render the developer mode section HTML. */
+export async function renderDevSection(): Promise<string> {
+  let view: DevModeView;
+  try {
+    view = await invoke<DevModeView>("get_dev_plugins");
+  } catch {
+    view = { enabled: false, plugins: [] };
+  }
+
+  return `
+    <section style="margin-bottom:24px;">
+      <h2 style="font-size:14px;font-weight:500;color:rgba(255,255,255,0.5);
+                  text-transform:uppercase;letter-spacing:0.05em;margin-bottom:12px;">
+        Plugin Development
+      </h2>
+      <label style="display:flex;align-items:center;gap:8px;cursor:pointer;font-size:13px;margin-bottom:10px;">
+        <input type="checkbox" id="dev-mode" ${view.enabled ? "checked" : ""} style="accent-color:#3b82f6;" />
+        Developer mode: run plugins from these folders and restart them on change
+      </label>
+      ${view.plugins.map((p) => renderDevPlugin(p, view.enabled)).join("")}
+      <button id="dev-add" style="${smallButton}">Add plugin folder…</button>
+      <div id="dev-status" style="font-size:12px;color:#f87171;margin-top:6px;white-space:pre-wrap;"></div>
+      <div style="font-size:11px;color:rgba(255,255,255,0.4);padding-top:4px;">
+        Folders listed here skip the permission prompt. Only add your own code.
+      </div>
+    </section>`;
+}
+
+/** Attach toggle, add and remove handlers. */
+export function attachDevHandlers(reloadSettings: () => Promise<void>): void {
+  const status = document.getElementById("dev-status");
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      await reloadSettings();
+    } catch (e) {
+      if (status) status.textContent = String(e);
+    }
+  };
+
+  document.getElementById("dev-mode")?.addEventListener("change", (e) => {
+    run("set_dev_mode", { enabled: (e.target as HTMLInputElement).checked });
+  });
+
+  document.getElementById("dev-add")?.addEventListener("click", async () => {
+    const path = await open({ directory: true });
+    if (typeof path === "string") run("add_dev_plugin", { path });
+  });
+
+  document.querySelectorAll(".dev-remove").forEach((btn) => {
+    btn.addEventListener("click", () => {
+      run("remove_dev_plugin", { path: (btn as HTMLElement).dataset.path! });
+    });
+  });
+}
@@ -0,0 +1,83 @@
+/**
+ * Plugin log viewer — the Logs panel on each plugin card in Settings.
+ *
+ * Shows what the plugin wrote to stderr or sent as MCP log messages since
+ * the app started, fetched fresh each time the panel opens, with the path
+ * of the full log file when the plugin has one.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface PluginLogEntry {
+  timestamp: number;
+  level: string;
+  source: "stderr" | "mcp";
+  logger?: string;
+  message: string;
+}
+
+interface PluginLogsView {
+  pluginId: string;
+  entries: PluginLogEntry[];
+  logFile: string | null;
+}
+
+const LOG_LEVEL_COLORS: Record<string, string> = {
+  debug: "rgba(255,255,255,0.4)",
+  info: "rgba(255,255,255,0.75)",
+  notice: "#93c5fd",
+  warning: "#fbbf24",
+};
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function renderLogEntry(entry: PluginLogEntry): string {
+  const time = new Date(entry.timestamp * 1000).toLocaleTimeString();
+  const color = LOG_LEVEL_COLORS[entry.level] ?? "#f87171";
+  const source = entry.source === "mcp" && entry.logger ? `mcp:${entry.logger}` : entry.source;
+  return `<div style="color:${color};">${escapeHtml(time)} ${escapeHtml(entry.level)} [${escapeHtml(source)}] ${escapeHtml(entry.message)}</div>`;
+}
+
+function renderLogs(view: PluginLogsView): string {
+  const entries = view.entries.length === 0
+    ? `<div style="color:rgba(255,255,255,0.4);">Nothing logged since the app started.</div>`
+    : view.entries.map(renderLogEntry).join("");
+  const file = view.logFile
+    ? `<div style="font-size:11px;color:rgba(255,255,255,0.4);margin-top:4px;">Full log: ${escapeHtml(view.logFile)}</div>`
+    : "";
+  return `
+    <div style="max-height:200px;overflow:auto;background:#0a0f1e;border:1px solid rgba(255,255,255,0.1);
+      border-radius:6px;padding:8px;font:11px/1.5 ui-monospace,monospace;white-space:pre-wrap;word-break:break-all;">
+      ${entries}
+    </div>
+    ${file}`;
+}
+
+/** The hidden logs panel for a plugin card; `toggleLogs` fills it. */
+export function renderLogsPanel(pluginId: string): string {
+  return `<div class="plugin-logs" data-plugin="${escapeHtml(pluginId)}" style="display:none;margin-top:8px;"></div>`;
+}
+
+/** Show or hide a plugin's recent logs, fetching them fresh when shown. */
+export async function toggleLogs(pluginId: string): Promise<void> {
+  const panel = document.querySelector(`.plugin-logs[data-plugin="${CSS.escape(pluginId)}"]`) as HTMLElement | null;
+  if (!panel) return;
+  if (panel.style.display !== "none") {
+    panel.style.display = "none";
+    return;
+  }
+  panel.style.display = "block";
+  panel.textContent = "Loading...";
+  try {
+    const view = await invoke<PluginLogsView>("get_plugin_logs", { pluginId, limit: 200 });
+    panel.innerHTML = renderLogs(view);
+    const list = panel.firstElementChild as HTMLElement | null;
+    if (list) list.scrollTop = list.scrollHeight;
+  } catch (e) {
+    panel.textContent = String(e);
+  }
+}
@@ -0,0 +1,281 @@
+/**
+ * Plugin management UI — rendered inside the Settings panel.
+ *
+ * Install buttons take a `.tar.gz` / `.zip` archive or a plugin folder; the
+ * permission prompt opens once it is installed. One card per installed
+ * plugin offers Upgrade / Uninstall and a form for the `configuration`
+ * fields its manifest declares. Secret fields are write-only: the backend
+ * stores them in the OS keychain and only reports whether a value is set.
+ * Saving restarts a running plugin so it receives the new values at
+ * initialize. The Logs panel lives in settings-plugin-logs.ts.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { renderLogsPanel, toggleLogs } from "./settings-plugin-logs";
+
+interface PluginConfigField {
+  key: string;
+  type: "string" | "number" | "boolean" | "secret";
+  label: string;
+  placeholder: string | null;
+  description: string | null;
+  value: unknown;
+  hasValue: boolean;
+}
+
+interface PluginConfigView {
+  pluginId: string;
+  name: string;
+  version: string;
+  fields: PluginConfigField[];
+}
+
+interface InstallReport {
+  pluginId: string;
+  name: string;
+  version: string;
+  previousVersion: string | null;
+  verifiedFiles: number;
+  dependencies: string[];
+}
+
+const ARCHIVE_FILTERS = [{ name: "Plugin archive", extensions: ["zip", "gz", "tgz"] }];
+
+let plugins: PluginConfigView[] = [];
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+const smallButton = `
+  padding:5px 10px;background:#1e3a5f;border:1px solid #3b82f6;
+  border-radius:4px;color:#93c5fd;font-size:12px;cursor:pointer;`;
+
+const dangerButton = `
+  padding:5px 10px;background:#7f1d1d;border:1px solid #dc2626;
+  border-radius:4px;color:#fca5a5;font-size:12px;cursor:pointer;`;
+
+const inputStyle = `
+  width:100%;padding:7px 10px;background:#16213e;border:1px solid rgba(255,255,255,0.15);
+  border-radius:6px;color:#fff;font-size:13px;outline:none;box-sizing:border-box;`;
+
+function fieldId(pluginId: string, key: string): string {
+  return `plugin-cfg-${pluginId}-${key}`.replace(/[^A-Za-z0-9_-]/g, "_");
+}
+
+function renderField(pluginId: string, field: PluginConfigField): string {
+  const id = fieldId(pluginId, field.key);
+  const help = field.description
+    ? `<div style="font-size:11px;color:rgba(255,255,255,0.45);margin-top:4px;">${escapeHtml(field.description)}</div>`
+    : "";
+
+  if (field.type === "boolean") {
+    return `
+      <label style="display:flex;align-items:center;gap:8px;cursor:pointer;margin-bottom:10px;">
+        <input type="checkbox" id="${id}" ${field.value === true ? "checked" : ""} style="accent-color:#3b82f6;" />
+        <span style="font-size:13px;">${escapeHtml(field.label)}</span>
+      </label>${help}`;
+  }
+
+  const isSecret = field.type === "secret";
+  const placeholder = isSecret && field.hasValue
+    ? "Saved — enter a new value to replace"
+    : field.placeholder || "";
+  const value = isSecret || field.value == null ? "" : String(field.value);
+  const clearBtn = isSecret && field.hasValue
+    ? `<button class="plugin-secret-clear" data-plugin="${escapeHtml(pluginId)}" data-key="${escapeHtml(field.key)}" style="
+        padding:6px 10px;background:#7f1d1d;border:1px solid #dc2626;border-radius:6px;
+        color:#fca5a5;font-size:12px;cursor:pointer;white-space:nowrap;">Clear</button>`
+    : "";
+
+  return `
+    <div style="margin-bottom:10px;">
+      <label for="${id}" style="font-size:12px;color:rgba(255,255,255,0.7);display:block;margin-bottom:4px;">
+        ${escapeHtml(field.label)}
+      </label>
+      <div style="display:flex;gap:6px;">
+        <input id="${id}" type="${isSecret ? "password" : field.type === "number" ? "number" : "text"}"
+          value="${escapeHtml(value)}" placeholder="${escapeHtml(placeholder)}" style="${inputStyle}" />
+        ${clearBtn}
+      </div>
+      ${help}
+    </div>`;
+}
+
+/** Read a plugin's form. Empty secret inputs are left out (keep the stored value). */
+function readValues(plugin: PluginConfigView): Record<string, unknown> {
+  const values: Record<string, unknown> = {};
+  for (const field of plugin.fields) {
+    const el = document.getElementById(fieldId(plugin.pluginId, field.key)) as HTMLInputElement | null;
+    if (!el) continue;
+    if (field.type === "boolean") {
+      values[field.key] = el.checked;
+    } else if (field.type === "secret") {
+      if (el.value) values[field.key] = el.value;
+    } else if (field.type === "number") {
+      values[field.key] = el.value.trim() === "" ? null : Number(el.value);
+    } else {
+      values[field.key] = el.value === "" ? null : el.value;
+    }
+  }
+  return values;
+}
+
+function renderCard(p: PluginConfigView): string {
+  const id = escapeHtml(p.pluginId);
+  const form = p.fields.length === 0 ? "" : `
+    ${p.fields.map((f) => renderField(p.pluginId, f)).join("")}
+    <button class="plugin-config-save" data-plugin="${id}" style="${smallButton}">Save</button>`;
+  return `
+    <div style="background:#0f1629;border:1px solid rgba(255,255,255,0.1);
+      border-radius:8px;padding:12px;margin-bottom:8px;">
+      <div style="display:flex;justify-content:space-between;align-items:center;margin-bottom:10px;">
+        <span style="font-weight:500;font-size:13px;">${escapeHtml(p.name)}
+          <span style="font-weight:400;color:rgba(255,255,255,0.45);">v${escapeHtml(p.version)}</span></span>
+        <span style="display:flex;gap:6px;">
+          <button class="plugin-logs-toggle" data-plugin="${id}" style="${smallButton}">Logs</button>
+          <button class="plugin-upgrade" data-plugin="${id}" style="${smallButton}">Upgrade</button>
+          <button class="plugin-uninstall" data-plugin="${id}" style="${dangerButton}">Uninstall</button>
+        </span>
+      </div>
+      ${form}
+      <div class="plugin-config-status" data-plugin="${id}"
+        style="font-size:12px;color:rgba(255,255,255,0.6);margin-top:6px;"></div>
+      ${renderLogsPanel(p.pluginId)}
+    </div>`;
+}
+
+/** Render the plugins section HTML: install buttons and one card per plugin. */
+export async function renderPluginConfigSection(): Promise<string> {
+  try {
+    plugins = await invoke<PluginConfigView[]>("get_plugin_configs");
+  } catch {
+    plugins = [];
+  }
+
+  return `
+    <section style="margin-bottom:24px;">
+      <h2 style="font-size:14px;font-weight:500;color:rgba(255,255,255,0.5);
+                  text-transform:uppercase;letter-spacing:0.05em;margin-bottom:12px;">
+        Plugins
+      </h2>
+      <div style="display:flex;gap:8px;align-items:center;margin-bottom:10px;">
+        <button id="plugin-install-archive" style="${smallButton}">Install from archive…</button>
+        <button id="plugin-install-folder" style="${smallButton}">Install from folder…</button>
+      </div>
+      <div id="plugin-install-status" style="font-size:12px;color:rgba(255,255,255,0.6);margin-bottom:8px;white-space:pre-wrap;"></div>
+      ${plugins.map(renderCard).join("")}
+      <div style="font-size:11px;color:rgba(255,255,255,0.4);padding-top:4px;">
+        Secret fields are stored in your OS keychain. Saving restarts the plugin.
+        Installed and upgraded plugins ask for permission before they run.
+      </div>
+    </section>`;
+}
+
+function describeInstall(report: InstallReport): string {
+  const action = report.previousVersion
+    ? `Upgraded ${report.name} from v${report.previousVersion} to v${report.version}.`
+    : `Installed ${report.name} v${report.version}.`;
+  const integrity = report.verifiedFiles > 0
+    ? ` ${report.verifiedFiles} file(s) matched their declared SHA-256.`
+    : " No file digests were declared.";
+  return action + integrity;
+}
+
+/** Ask for an archive or folder, then install (or upgrade) from it. */
+async function pickAndInstall(
+  command: "install_plugin" | "upgrade_plugin",
+  directory: boolean,
+  status: HTMLElement,
+  reloadSettings: () => Promise<void>,
+): Promise<void> {
+  const path = await open(directory ? { directory: true } : { filters: ARCHIVE_FILTERS });
+  if (typeof path !== "string") return;
+  status.style.color = "rgba(255,255,255,0.6)";
+  status.textContent = "Installing...";
+  try {
+    const report = await invoke<InstallReport>(command, { path });
+    await reloadSettings();
+    const fresh = document.getElementById("plugin-install-status");
+    if (fresh) fresh.textContent = describeInstall(report);
+  } catch (e) {
+    status.textContent = String(e);
+    status.style.color = "#f87171";
+  }
+}
+
+/** Attach install, logs, upgrade, uninstall, Save and Clear handlers. */
+export function attachPluginConfigHandlers(reloadSettings: () => Promise<void>): void {
+  const statusFor = (pluginId: string) =>
+    document.querySelector(`.plugin-config-status[data-plugin="${CSS.escape(pluginId)}"]`) as HTMLElement | null;
+  const installStatus = document.getElementById("plugin-install-status")!;
+
+  document.getElementById("plugin-install-archive")?.addEventListener("click", () =>
+    pickAndInstall("install_plugin", false, installStatus, reloadSettings));
+  document.getElementById("plugin-install-folder")?.addEventListener("click", () =>
+    pickAndInstall("install_plugin", true, installStatus, reloadSettings));
+
+  document.querySelectorAll(".plugin-logs-toggle").forEach((btn) => {
+    btn.addEventListener("click", () => toggleLogs((btn as HTMLElement).dataset.plugin!));
+  });
+
+  document.querySelectorAll(".plugin-upgrade").forEach((btn) => {
+    btn.addEventListener("click", () => {
+      const status = statusFor((btn as HTMLElement).dataset.plugin!);
+      if (status) pickAndInstall("upgrade_plugin", false, status, reloadSettings);
+    });
+  });
+
+  document.querySelectorAll(".plugin-uninstall").forEach((btn) => {
+    btn.addEventListener("click", async () => {
+      const pluginId = (btn as HTMLElement).dataset.plugin!;
+      if (!confirm(`Uninstall ${pluginId}? Its settings and saved secrets are removed too.`)) return;
+      try {
+        await invoke("uninstall_plugin", { pluginId });
+        await reloadSettings();
+      } catch (e) {
+        const status = statusFor(pluginId);
+        if (status) {
+          status.textContent = String(e);
+          status.style.color = "#f87171";
+        }
+      }
+    });
+  });
+
+  document.querySelectorAll(".plugin-config-save").forEach((btn) => {
+    btn.addEventListener("click", async () => {
+      const pluginId = (btn as HTMLElement).dataset.plugin!;
+      const plugin = plugins.find((p) => p.pluginId === pluginId);
+      const status = statusFor(pluginId);
+      if (!plugin) return;
+      (btn as HTMLButtonElement).disabled = true;
+      if (status) status.textContent = "Saving...";
+      try {
+        await invoke("set_plugin_config", { pluginId, values: readValues(plugin) });
+        await reloadSettings();
+      } catch (e) {
+        if (status) {
+          status.textContent = String(e);
+          status.style.color = "#f87171";
+        }
+        (btn as HTMLButtonElement).disabled = false;
+      }
+    });
+  });
+
+  document.querySelectorAll(".plugin-secret-clear").forEach((btn) => {
+    btn.addEventListener("click", async () => {
+      const { plugin: pluginId, key } = (btn as HTMLElement).dataset;
+      try {
+        await invoke("set_plugin_config", { pluginId, values: { [key!]: null } });
+        await reloadSettings();
+      } catch (e) {
+        console.error("Clear secret failed:", e);
+      }
+    });
+  });
+}
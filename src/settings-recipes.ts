@@ -0,0 +1,116 @@
+/**
+ * Recipes editor — rendered inside the Settings panel.
+ *
+ * A recipe chains actions into one menu entry, e.g. "Translate → Summarize
+ * → Copy". Recipes are edited as JSON; save_recipes validates every recipe
+ * and nothing is written if one is wrong, so the errors show under the
+ * editor. Step actions are built-in action ids ("translate") or plugin
+ * tools ("com.example.jira:create_issue"); a step's `input` may use
+ * {previous} (the step before's output, the default) and {text} (the snip).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface RecipeStep {
+  action: string;
+  input?: string;
+  confirm?: boolean;
+}
+
+interface Recipe {
+  id: string;
+  label: string;
+  icon?: string;
+  contentTypes?: string[];
+  steps: RecipeStep[];
+}
+
+interface RecipeBook {
+  recipes: Recipe[];
+}
+
+const EXAMPLE: RecipeBook = {
+  recipes: [
+    {
+      id: "translate-summarize",
+      label: "Translate and summarize",
+      steps: [
+        { action: "translate" },
+        { action: "explain", input: "Summarize in three bullet points:\n{previous}" },
+        { action: "copy_text" },
+      ],
+    },
+  ],
+};
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+const buttonStyle = `
+  padding:4px 12px;background:transparent;border:1px solid rgba(255,255,255,0.2);
+  border-radius:4px;color:rgba(255,255,255,0.8);font-size:12px;cursor:pointer;`;
+
+/** Render the recipes section HTML. */
+export async function renderRecipesSection(): Promise<string> {
+  let book: RecipeBook;
+  try {
+    book = await invoke<RecipeBook>("get_recipes");
+  } catch {
+    book = { recipes: [] };
+  }
+  const json = JSON.stringify(book.recipes.length > 0 ? book : EXAMPLE, null, 2);
+  const hint = book.recipes.length > 0
+    ? `${book.recipes.length} recipe(s). Each appears in the action menu when its content types match.`
+    : "No recipes yet — the example above is not saved until you press Save.";
+
+  return `
+    <section style="margin-bottom:24px;">
+      <h2 style="font-size:14px;font-weight:500;color:rgba(255,255,255,0.5);
+                  text-transform:uppercase;letter-spacing:0.05em;margin-bottom:12px;">
+        Recipes
+      </h2>
+      <textarea id="recipes-json" spellcheck="false" rows="14"
+        style="width:100%;box-sizing:border-box;background:#0f1629;color:#e2e8f0;
+               border:1px solid rgba(255,255,255,0.1);border-radius:8px;padding:8px 12px;
+               font-family:ui-monospace,monospace;font-size:12px;">${escapeHtml(json)}</textarea>
+      <div style="display:flex;gap:8px;align-items:center;margin-top:6px;">
+        <button id="btn-recipes-save" style="${buttonStyle}">Save</button>
+        <span id="recipes-status" style="font-size:12px;color:#4ade80;"></span>
+      </div>
+      <div id="recipes-error" style="font-size:12px;color:#f87171;white-space:pre-wrap;margin-top:6px;"></div>
+      <div style="font-size:11px;color:rgba(255,255,255,0.4);padding-top:4px;">
+        ${escapeHtml(hint)} Plugin steps ask for review as they do in the action menu, and
+        always when the step says "confirm": true.
+      </div>
+    </section>`;
+}
+
+/** Attach the save handler. */
+export function attachRecipesHandlers(reloadSettings: () => Promise<void>): void {
+  const error = document.getElementById("recipes-error");
+  const status = document.getElementById("recipes-status");
+
+  document.getElementById("btn-recipes-save")?.addEventListener("click", async () => {
+    const text = (document.getElementById("recipes-json") as HTMLTextAreaElement).value;
+    if (error) error.textContent = "";
+    if (status) status.textContent = "";
+    let book: RecipeBook;
+    try {
+      book = JSON.parse(text);
+    } catch (e) {
+      if (error) error.textContent = `Invalid JSON: ${(e as Error).message}`;
+      return;
+    }
+    try {
+      await invoke("save_recipes", { book });
+      await reloadSettings();
+      const saved = document.getElementById("recipes-status");
+      if (saved) saved.textContent = "Saved";
+    } catch (e) {
+      if (error) error.textContent = String(e);
+    }
+  });
+}
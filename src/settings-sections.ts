@@ -0,0 +1,44 @@
+/**
+ * The Settings sections that live in their own modules: plugins, actions,
+ * recipes, trusted publishers and plugin development.
+ *
+ * settings.ts leaves one placeholder per section in its page and mounts
+ * them after the main render; each module renders its HTML and attaches
+ * its handlers, reloading the whole panel after a change.
+ */
+
+import { renderPluginConfigSection, attachPluginConfigHandlers } from "./settings-plugins";
+import { renderToolPrefsSection, attachToolPrefsHandlers } from "./settings-tools";
+import { renderRecipesSection, attachRecipesHandlers } from "./settings-recipes";
+import { renderTrustSection, attachTrustHandlers } from "./settings-trust";
+import { renderDevSection, attachDevHandlers } from "./settings-dev";
+
+interface ModuleSection {
+  id: string;
+  render: () => Promise<string>;
+  attach: (reloadSettings: () => Promise<void>) => void;
+}
+
+/** In page order. */
+const SECTIONS: ModuleSection[] = [
+  { id: "plugin-config-section", render: renderPluginConfigSection, attach: attachPluginConfigHandlers },
+  { id: "tool-prefs-section", render: renderToolPrefsSection, attach: attachToolPrefsHandlers },
+  { id: "recipes-section", render: renderRecipesSection, attach: attachRecipesHandlers },
+  { id: "trust-section", render: renderTrustSection, attach: attachTrustHandlers },
+  { id: "dev-section", render: renderDevSection, attach: attachDevHandlers },
+];
+
+/** Empty placeholders for the sections, filled by `mountModuleSections`. */
+export function moduleSectionPlaceholders(): string {
+  return SECTIONS.map((s) => `<div id="${s.id}"></div>`).join("\n");
+}
+
+/** Render each section into its placeholder and attach its handlers. */
+export async function mountModuleSections(reloadSettings: () => Promise<void>): Promise<void> {
+  for (const section of SECTIONS) {
+    const el = document.getElementById(section.id);
+    if (!el) continue;
+    el.innerHTML = await section.render();
+    section.attach(reloadSettings);
+  }
+}
@@ -0,0 +1,159 @@
+/**
+ * Action preferences UI — rendered inside the Settings panel.
+ *
+ * Lists every tool, built-in and plugin. Unchecking a tool keeps it out of
+ * the classify prompt, the action menu and the text launcher. Pinned tools
+ * rank first whenever the classifier suggests them, in the order shown;
+ * the arrow moves a pinned tool up one place. Below the list, the relevance
+ * filter's counters show how many plugin tools each snip offers and how
+ * often the tool the user wanted was among them.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface ToolPrefView {
+  toolId: string;
+  pluginId: string;
+  displayName: string;
+  description: string;
+  enabled: boolean;
+  pinned: number | null;
+}
+
+interface RelevanceStats {
+  snips: number;
+  filteredSnips: number;
+  toolsAvailable: number;
+  toolsOffered: number;
+  menuChoices: number;
+  menuChoicesOffered: number;
+  launcherChoices: number;
+  launcherChoicesSelected: number;
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+const iconButton = `
+  padding:2px 8px;background:transparent;border:1px solid rgba(255,255,255,0.15);
+  border-radius:4px;font-size:12px;cursor:pointer;`;
+
+function renderTool(t: ToolPrefView): string {
+  const source = t.pluginId === "builtin" ? "Built-in" : t.pluginId;
+  const pinned = t.pinned !== null;
+  const up = pinned && t.pinned! > 0
+    ? `<button class="tool-up" data-tool="${escapeHtml(t.toolId)}" data-position="${t.pinned! - 1}"
+         title="Move up" style="${iconButton}color:rgba(255,255,255,0.6);">↑</button>`
+    : "";
+  return `
+    <div style="display:flex;justify-content:space-between;align-items:center;gap:8px;
+      background:#0f1629;border:1px solid rgba(255,255,255,0.1);border-radius:8px;
+      padding:6px 12px;margin-bottom:4px;opacity:${t.enabled ? 1 : 0.5};">
+      <label style="display:flex;align-items:center;gap:8px;cursor:pointer;min-width:0;"
+        title="${escapeHtml(t.description)}">
+        <input type="checkbox" class="tool-enabled" data-tool="${escapeHtml(t.toolId)}"
+          ${t.enabled ? "checked" : ""} style="accent-color:#3b82f6;" />
+        <span style="font-size:13px;">${escapeHtml(t.displayName)}</span>
+        <span style="font-size:11px;color:rgba(255,255,255,0.4);">${escapeHtml(source)}</span>
+      </label>
+      <span style="display:flex;gap:4px;flex-shrink:0;">
+        ${up}
+        <button class="tool-pin" data-tool="${escapeHtml(t.toolId)}" data-pinned="${pinned}"
+          title="${pinned ? "Unpin" : "Pin to the top of the menu"}"
+          style="${iconButton}color:${pinned ? "#fbbf24" : "rgba(255,255,255,0.4)"};">
+          ${pinned ? "★" : "☆"}</button>
+      </span>
+    </div>`;
+}
+
+function percent(part: number, whole: number): string {
+  return `${Math.round((100 * part) / whole)}%`;
+}
+
+function describeStats(s: RelevanceStats): string {
+  if (s.snips === 0) return "";
+  const lines = [
+    `Relevance filter: ${(s.toolsOffered / s.snips).toFixed(1)} of ` +
+      `${(s.toolsAvailable / s.snips).toFixed(1)} plugin actions offered per snip ` +
+      `(filtered on ${s.filteredSnips} of ${s.snips} snips).`,
+  ];
+  if (s.menuChoices > 0) {
+    lines.push(`Plugin actions run from the menu that were offered: ${percent(s.menuChoicesOffered, s.menuChoices)} of ${s.menuChoices}.`);
+  }
+  if (s.launcherChoices > 0) {
+    lines.push(`Plugin actions picked in the launcher the filter would have offered: ${percent(s.launcherChoicesSelected, s.launcherChoices)} of ${s.launcherChoices}.`);
+  }
+  return lines.map(escapeHtml).join("<br>");
+}
+
+/** Render the action preferences section HTML. */
+export async function renderToolPrefsSection(): Promise<string> {
+  let tools: ToolPrefView[];
+  try {
+    tools = await invoke<ToolPrefView[]>("get_tool_prefs");
+  } catch {
+    tools = [];
+  }
+  if (tools.length === 0) return "";
+  let stats = "";
+  try {
+    stats = describeStats(await invoke<RelevanceStats>("get_relevance_stats"));
+  } catch {
+    // Counters are informational only
+  }
+
+  return `
+    <section style="margin-bottom:24px;">
+      <h2 style="font-size:14px;font-weight:500;color:rgba(255,255,255,0.5);
+                  text-transform:uppercase;letter-spacing:0.05em;margin-bottom:12px;">
+        Actions
+      </h2>
+      ${tools.map(renderTool).join("")}
+      <div id="tool-prefs-status" style="font-size:12px;color:#f87171;margin-top:6px;"></div>
+      <div style="font-size:11px;color:rgba(255,255,255,0.4);padding-top:4px;">
+        Unchecked actions are never offered. Pinned actions come first when suggested.
+      </div>
+      ${stats ? `<div style="font-size:11px;color:rgba(255,255,255,0.4);padding-top:6px;">${stats}</div>` : ""}
+    </section>`;
+}
+
+/** Attach enable, pin and reorder handlers. */
+export function attachToolPrefsHandlers(reloadSettings: () => Promise<void>): void {
+  const status = document.getElementById("tool-prefs-status");
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      await reloadSettings();
+    } catch (e) {
+      if (status) status.textContent = String(e);
+    }
+  };
+
+  document.querySelectorAll(".tool-enabled").forEach((box) => {
+    box.addEventListener("change", () => {
+      const input = box as HTMLInputElement;
+      run("set_tool_enabled", { toolId: input.dataset.tool!, enabled: input.checked });
+    });
+  });
+
+  document.querySelectorAll(".tool-pin").forEach((btn) => {
+    btn.addEventListener("click", () => {
+      const el = btn as HTMLElement;
+      run("set_tool_pinned", { toolId: el.dataset.tool!, pinned: el.dataset.pinned !== "true" });
+    });
+  });
+
+  document.querySelectorAll(".tool-up").forEach((btn) => {
+    btn.addEventListener("click", () => {
+      const el = btn as HTMLElement;
+      run("set_tool_pinned", {
+        toolId: el.dataset.tool!,
+        pinned: true,
+        position: Number(el.dataset.position),
+      });
+    });
+  });
+}
@@ -0,0 +1,110 @@
+/**
+ * Trusted publishers UI — rendered inside the Settings panel.
+ *
+ * Lists the Ed25519 keys the user trusts, with a form to add one
+ * (name + base64 public key) and a toggle that refuses plugins not signed
+ * by a trusted key. Plugins signed by a listed key show as a verified
+ * publisher in the permission prompt.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface TrustedPublisher {
+  name: string;
+  publicKey: string;
+  addedAt: string;
+}
+
+interface TrustStore {
+  requireSigned: boolean;
+  publishers: Record<string, TrustedPublisher>;
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+const inputStyle = `
+  flex:1;padding:7px 10px;background:#16213e;border:1px solid rgba(255,255,255,0.15);
+  border-radius:6px;color:#fff;font-size:13px;outline:none;min-width:0;`;
+
+function renderPublisher(fingerprint: string, p: TrustedPublisher): string {
+  return `
+    <div style="display:flex;justify-content:space-between;align-items:center;
+      background:#0f1629;border:1px solid rgba(255,255,255,0.1);border-radius:8px;
+      padding:8px 12px;margin-bottom:6px;">
+      <span style="font-size:13px;">${escapeHtml(p.name)}
+        <code style="color:rgba(255,255,255,0.45);font-size:11px;">${escapeHtml(fingerprint)}</code></span>
+      <button class="trust-remove" data-fingerprint="${escapeHtml(fingerprint)}" style="
+        padding:4px 10px;background:#7f1d1d;border:1px solid #dc2626;border-radius:4px;
+        color:#fca5a5;font-size:12px;cursor:pointer;">Remove</button>
+    </div>`;
+}
+
+/** Render the trusted publishers section HTML. */
+export async function renderTrustSection(): Promise<string> {
+  let store: TrustStore;
+  try {
+    store = await invoke<TrustStore>("get_trust_store");
+  } catch {
+    store = { requireSigned: false, publishers: {} };
+  }
+  const entries = Object.entries(store.publishers)
+    .sort(([, a], [, b]) => a.name.localeCompare(b.name));
+
+  return `
+    <section style="margin-bottom:24px;">
+      <h2 style="font-size:14px;font-weight:500;color:rgba(255,255,255,0.5);
+                  text-transform:uppercase;letter-spacing:0.05em;margin-bottom:12px;">
+        Trusted Publishers
+      </h2>
+      ${entries.length === 0
+        ? `<div style="font-size:12px;color:rgba(255,255,255,0.45);margin-bottom:8px;">No trusted publishers.</div>`
+        : entries.map(([fp, p]) => renderPublisher(fp, p)).join("")}
+      <div style="display:flex;gap:6px;margin:8px 0;">
+        <input id="trust-name" placeholder="Publisher name" style="${inputStyle}max-width:140px;" />
+        <input id="trust-key" placeholder="Base64 Ed25519 public key" style="${inputStyle}" />
+        <button id="trust-add" style="
+          padding:5px 10px;background:#1e3a5f;border:1px solid #3b82f6;
+          border-radius:4px;color:#93c5fd;font-size:12px;cursor:pointer;">Add</button>
+      </div>
+      <label style="display:flex;align-items:center;gap:8px;cursor:pointer;font-size:13px;">
+        <input type="checkbox" id="trust-require-signed" ${store.requireSigned ? "checked" : ""}
+          style="accent-color:#3b82f6;" />
+        Only run plugins signed by a trusted publisher
+      </label>
+      <div id="trust-status" style="font-size:12px;color:#f87171;margin-top:6px;"></div>
+    </section>`;
+}
+
+/** Attach add, remove and policy handlers. */
+export function attachTrustHandlers(reloadSettings: () => Promise<void>): void {
+  const status = document.getElementById("trust-status");
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      await reloadSettings();
+    } catch (e) {
+      if (status) status.textContent = String(e);
+    }
+  };
+
+  document.getElementById("trust-add")?.addEventListener("click", () => {
+    const name = (document.getElementById("trust-name") as HTMLInputElement).value;
+    const publicKey = (document.getElementById("trust-key") as HTMLInputElement).value;
+    run("trust_publisher", { name, publicKey });
+  });
+
+  document.querySelectorAll(".trust-remove").forEach((btn) => {
+    btn.addEventListener("click", () => {
+      const fingerprint = (btn as HTMLElement).dataset.fingerprint!;
+      run("remove_publisher", { fingerprint });
+    });
+  });
+
+  document.getElementById("trust-require-signed")?.addEventListener("change", (e) => {
+    run("set_require_signed", { required: (e.target as HTMLInputElement).checked });
+  });
+}
@@ -3,8 +3,11 @@
  *
  * Sections:
  *   1. AI Provider — dropdown, API key inputs, Test buttons
- *   2. Recognition — OCR mode toggle (fast/accurate)
- *   3. About — version info
+ *   2. Local Models — download / delete (settings-local.ts)
+ *   3. Plugins, Actions, Recipes, Trusted Publishers, Plugin Development
+ *      — mounted by settings-sections.ts
+ *   4. Recognition — OCR mode toggle (fast/accurate)
+ *   5. About — version info
  *
  * API keys are stored in the OS keychain via Rust (keyring crate).
  * Falls back to environment variables for development.
@@ -12,6 +15,7 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { renderLocalModelsSection, attachLocalModelHandlers } from "./settings-local";
+import { moduleSectionPlaceholders, mountModuleSections } from "./settings-sections";
 
 interface ProviderInfo {
   id: string;
@@ -97,6 +101,9 @@ async function loadSettings(): Promise<void> {
       <!-- Local Models Section (injected dynamically) -->
       <div id="local-models-section"></div>
 
+      <!-- Plugins, Actions, Recipes, Trust, Dev (injected dynamically) -->
+      ${moduleSectionPlaceholders()}
+
       <!-- Recognition Mode Section -->
       <section style="margin-bottom: 24px;">
         <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
@@ -176,6 +183,8 @@ async function loadSettings(): Promise<void> {
     attachLocalModelHandlers(loadSettings);
   }
 
+  await mountModuleSections(loadSettings);
+
   // Wire up event handlers
   attachHandlers(config);
 }
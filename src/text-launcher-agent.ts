@@ -0,0 +1,150 @@
+/**
+ * Text launcher agent mode — the step trace and the agent's pauses.
+ *
+ * run_agent_command and continue_agent return an AgentReply; each
+ * "agent-step" event updates the trace live. A pause for a command or
+ * plugin arguments shows the usual confirmation and sends the answer
+ * back through continue_agent.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { renderArgsForm, ToolReview } from "./tool-args-form";
+import {
+  escapeHtml, renderCommandConfirmation, resizeToContent, showStatus, showTextResult,
+} from "./text-launcher-view";
+
+interface AgentStep {
+  step: number;
+  toolId: string;
+  label: string;
+  input: string;
+  status: "running" | "waiting" | "ok" | "error" | "declined" | "blocked";
+  output: string;
+}
+
+export interface AgentReply {
+  status: "done" | "needs_confirmation" | "error";
+  steps: AgentStep[];
+  answer: string | null;
+  pending: {
+    kind: "command" | "tool_args";
+    actionId: string;
+    command: string | null;
+    explanation: string | null;
+    arguments: Record<string, unknown> | null;
+  } | null;
+}
+
+let traceSteps: AgentStep[] = [];
+
+const STEP_MARKS: Record<AgentStep["status"], [string, string]> = {
+  running: ["…", "rgba(255,255,255,0.5)"],
+  waiting: ["?", "#fbbf24"],
+  ok: ["✓", "#4ade80"],
+  error: ["✗", "#f87171"],
+  declined: ["–", "rgba(255,255,255,0.4)"],
+  blocked: ["⊘", "#f87171"],
+};
+
+/** Clear the trace before a new request. */
+export function resetTrace(): void {
+  traceSteps = [];
+  renderTrace();
+}
+
+function renderTrace(): void {
+  const trace = document.getElementById("agent-trace");
+  if (!trace) return;
+  trace.innerHTML = traceSteps.length === 0 ? "" : `
+    <div style="
+      padding: 6px 14px;
+      border-top: 1px solid rgba(255,255,255,0.08);
+      font-size: 12px;
+      max-height: 140px;
+      overflow-y: auto;
+    ">
+      ${traceSteps.map((s) => {
+        const [mark, color] = STEP_MARKS[s.status] || ["·", "rgba(255,255,255,0.5)"];
+        return `<div style="display: flex; gap: 6px; padding: 2px 0;" title="${escapeHtml(s.output)}">
+          <span style="color: ${color}; width: 12px; flex-shrink: 0;">${mark}</span>
+          <span style="color: rgba(255,255,255,0.4); flex-shrink: 0;">${s.step}.</span>
+          <span style="color: rgba(255,255,255,0.85); flex-shrink: 0;">${escapeHtml(s.label)}</span>
+          <span style="color: rgba(255,255,255,0.4); overflow: hidden; text-overflow: ellipsis; white-space: nowrap;">${escapeHtml(s.input)}</span>
+        </div>`;
+      }).join("")}
+    </div>
+  `;
+}
+
+/** Live trace: the backend emits each step as it starts and ends. */
+listen<AgentStep>("agent-step", (event) => {
+  const step = event.payload;
+  const i = traceSteps.findIndex((s) => s.step === step.step);
+  if (i >= 0) traceSteps[i] = step;
+  else traceSteps.push(step);
+  renderTrace();
+  resizeToContent();
+});
+
+export async function handleAgentReply(reply: AgentReply): Promise<void> {
+  traceSteps = reply.steps;
+  renderTrace();
+
+  if (reply.status !== "needs_confirmation" || !reply.pending) {
+    showTextResult(reply.answer || "Done.", reply.status === "error");
+    return;
+  }
+
+  const pending = reply.pending;
+  const answer = async (args: Record<string, unknown>): Promise<void> => {
+    showStatus("Working...");
+    await resizeToContent();
+    try {
+      await handleAgentReply(await invoke<AgentReply>("plugin:agent|continue_agent", args));
+    } catch (err) {
+      showTextResult(`Error: ${err}`, true);
+    }
+    await resizeToContent();
+  };
+
+  if (pending.kind === "command") {
+    renderCommandConfirmation(
+      pending.command || "",
+      pending.explanation || "",
+      () => { answer({ approve: false }); },
+      () => answer({ approve: true }),
+    );
+    await resizeToContent();
+    return;
+  }
+
+  let review: ToolReview;
+  try {
+    review = await invoke<ToolReview>("get_tool_review", { actionId: pending.actionId });
+  } catch (err) {
+    showTextResult(`Error: ${err}`, true);
+    return;
+  }
+  renderArgsForm(
+    document.getElementById("result-area")!,
+    review,
+    pending.arguments || {},
+    async (decision) => {
+      try {
+        const next = await invoke<AgentReply>("plugin:agent|continue_agent", {
+          approve: true,
+          arguments: decision.arguments,
+          remember: decision.remember,
+        });
+        await handleAgentReply(next);
+        await resizeToContent();
+        return null;
+      } catch (err) {
+        return String(err);
+      }
+    },
+    () => { answer({ approve: false }); },
+  );
+  await resizeToContent();
+}
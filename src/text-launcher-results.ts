@@ -0,0 +1,177 @@
+/**
+ * Text launcher results — routes a TextCommandResult by type.
+ *
+ * Commands wait for confirmation and their output is summarized against
+ * the question that produced them; clipboard and file results run at
+ * once; plugin arguments open the review form first.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { renderArgsForm, ToolReview } from "./tool-args-form";
+import {
+  closeLauncher, renderCommandConfirmation, resizeToContent, showStatus, showTextResult,
+} from "./text-launcher-view";
+
+export interface TextCommandResult {
+  status: string;
+  text: string;
+  actionId: string | null;
+  resultType: string;
+  command: string | null;
+  filePath: string | null;
+  fileContent: string | null;
+  clipboardContent: string | null;
+  data: string | null;
+  mimeType: string | null;
+  url: string | null;
+  arguments: Record<string, unknown> | null;
+}
+
+// ── Result routing ──────────────────────────────────────────────────
+
+/** Act on a result; `question` is the text the user typed. */
+export async function handleResult(result: TextCommandResult, question: string): Promise<void> {
+  switch (result.resultType) {
+    case "command":
+      showCommandConfirmation(result, question);
+      break;
+    case "clipboard":
+      await handleClipboard(result);
+      break;
+    case "file":
+    case "image":
+      await handleFile(result);
+      break;
+    case "link":
+      showTextResult([result.text, result.url].filter(Boolean).join("\n\n"), false);
+      break;
+    case "tool_args":
+      await showArgsReview(result, question);
+      break;
+    default:
+      // "text" or unknown — check for URLs in the response
+      showTextResult(result.text || "Done.", false);
+      break;
+  }
+}
+
+// ── Plugin argument review ──────────────────────────────────────────
+
+interface ConfirmedResult {
+  status: string;
+  actionId: string;
+  result: {
+    type: string;
+    text?: string;
+    filePath?: string;
+    command?: string;
+    clipboardContent?: string;
+    mimeType?: string;
+    data?: string;
+    url?: string;
+  };
+}
+
+async function showArgsReview(result: TextCommandResult, question: string): Promise<void> {
+  let review: ToolReview;
+  try {
+    review = await invoke<ToolReview>("get_tool_review", { actionId: result.actionId });
+  } catch (err) {
+    showTextResult(`Error: ${err}`, true);
+    return;
+  }
+
+  renderArgsForm(
+    document.getElementById("result-area")!,
+    review,
+    result.arguments || {},
+    async (decision) => {
+      try {
+        const final = await invoke<ConfirmedResult>("confirm_plugin_call", {
+          actionId: result.actionId,
+          arguments: decision.arguments,
+          remember: decision.remember,
+          recordHistory: false,
+        });
+        await handleResult({
+          status: final.status,
+          text: final.result.text || "",
+          actionId: final.actionId,
+          resultType: final.result.type,
+          command: final.result.command || null,
+          filePath: final.result.filePath || null,
+          fileContent: null,
+          clipboardContent: final.result.clipboardContent || null,
+          data: final.result.data || null,
+          mimeType: final.result.mimeType || null,
+          url: final.result.url || null,
+          arguments: null,
+        }, question);
+        await resizeToContent();
+        return null;
+      } catch (err) {
+        return String(err);
+      }
+    },
+    () => { closeLauncher(); },
+  );
+  await resizeToContent();
+}
+
+// ── Command execution (with confirmation) ───────────────────────────
+
+function showCommandConfirmation(result: TextCommandResult, question: string): void {
+  const cmd = result.command || "";
+  renderCommandConfirmation(cmd, result.text || "", () => closeLauncher(), async () => {
+    try {
+      const rawOutput = await invoke<string>("run_confirmed_command", { command: cmd });
+      if (!rawOutput) {
+        showTextResult("Command completed successfully.", false);
+      } else {
+        // Summarize multi-line output through LLM for human-readable answer
+        showStatus("Summarizing...");
+        await resizeToContent();
+        try {
+          const summary = await invoke<string>("summarize_command_output", {
+            userQuestion: question,
+            command: cmd,
+            rawOutput,
+          });
+          showTextResult(summary, false);
+        } catch {
+          showTextResult(rawOutput, false); // Fallback to raw output
+        }
+      }
+    } catch (err) {
+      showTextResult(`Command failed: ${err}`, true);
+    }
+    await resizeToContent();
+  });
+}
+
+// ── Clipboard auto-copy ─────────────────────────────────────────────
+
+async function handleClipboard(result: TextCommandResult): Promise<void> {
+  const content = result.clipboardContent || result.text || "";
+  try {
+    await invoke("copy_to_clipboard", { text: content });
+    showTextResult("Copied to clipboard.", false);
+    setTimeout(() => closeLauncher(), 1000);
+  } catch (err) {
+    showTextResult(`Failed to copy: ${err}`, true);
+  }
+}
+
+// ── File save ───────────────────────────────────────────────────────
+
+async function handleFile(result: TextCommandResult): Promise<void> {
+  const filename = result.filePath || "output.txt";
+  const content = result.fileContent || result.text || "";
+  try {
+    // Images and binary files arrive as base64 `data`
+    const path = await invoke<string>("write_to_desktop", { filename, content, data: result.data });
+    showTextResult(`Saved to: ${path}`, false);
+  } catch (err) {
+    showTextResult(`Failed to save file: ${err}`, true);
+  }
+}
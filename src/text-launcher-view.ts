@@ -0,0 +1,241 @@
+/**
+ * Text launcher view — window sizing, result display and the command
+ * confirmation panel, shared by the direct and agent paths.
+ *
+ * Everything here renders into #result-area; nothing routes results.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-shell";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+
+const appWindow = getCurrentWindow();
+const WIDTH = 600;
+const INPUT_HEIGHT = 72;
+const MAX_HEIGHT = 500;
+
+// ── Window sizing ────────────────────────────────────────────────────
+
+export async function resizeToContent(): Promise<void> {
+  await new Promise(r => setTimeout(r, 20));
+  const outer = document.getElementById("launcher")!;
+  const h = Math.min(Math.max(outer.scrollHeight + 16, INPUT_HEIGHT), MAX_HEIGHT);
+  try {
+    await appWindow.setSize(new LogicalSize(WIDTH, h));
+  } catch { /* window closing */ }
+}
+
+async function resetSize(): Promise<void> {
+  try {
+    await appWindow.setSize(new LogicalSize(WIDTH, INPUT_HEIGHT));
+  } catch { /* window closing */ }
+}
+
+// ── Command confirmation ────────────────────────────────────────────
+
+/** Show a command for approval; `onRun` runs after the user clicks Run. */
+export function renderCommandConfirmation(
+  cmd: string,
+  explanation: string,
+  onCancel: () => void,
+  onRun: () => Promise<void>,
+): void {
+  const area = document.getElementById("result-area")!;
+
+  area.innerHTML = `
+    ${explanation ? `<div style="
+      padding: 10px 14px;
+      font-size: 13px;
+      color: rgba(255,255,255,0.7);
+      border-top: 1px solid rgba(255,255,255,0.08);
+      line-height: 1.4;
+    ">${renderLight(explanation)}</div>` : ""}
+    <div style="
+      padding: 8px 14px;
+      border-top: 1px solid rgba(255,255,255,0.08);
+    ">
+      <div style="
+        font-size: 11px;
+        color: rgba(255,255,255,0.4);
+        margin-bottom: 4px;
+      ">Command to run:</div>
+      <pre style="
+        background: rgba(0,0,0,0.4);
+        border: 1px solid rgba(255,255,255,0.1);
+        border-radius: 4px;
+        padding: 8px 10px;
+        font-family: 'SF Mono', Menlo, monospace;
+        font-size: 12px;
+        color: #e2e8f0;
+        white-space: pre-wrap;
+        word-break: break-all;
+        margin: 0;
+      ">${escapeHtml(cmd)}</pre>
+    </div>
+    <div style="
+      padding: 6px 14px 8px;
+      display: flex;
+      gap: 8px;
+      justify-content: flex-end;
+      border-top: 1px solid rgba(255,255,255,0.08);
+    ">
+      <button id="btn-cancel" style="
+        background: transparent;
+        border: 1px solid rgba(255,255,255,0.2);
+        color: rgba(255,255,255,0.7);
+        padding: 4px 12px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 12px;
+      ">Cancel</button>
+      <button id="btn-run" style="
+        background: #3b82f6;
+        border: 1px solid #3b82f6;
+        color: white;
+        padding: 4px 14px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 12px;
+        font-weight: 500;
+      ">Run</button>
+    </div>
+  `;
+
+  document.getElementById("btn-cancel")?.addEventListener("click", () => onCancel());
+  document.getElementById("btn-run")?.addEventListener("click", async () => {
+    const btn = document.getElementById("btn-run") as HTMLButtonElement;
+    btn.disabled = true;
+    btn.textContent = "Running...";
+    btn.style.opacity = "0.6";
+    await onRun();
+  });
+}
+
+// ── Result display ───────────────────────────────────────────────────
+
+export function showStatus(message: string): void {
+  const area = document.getElementById("result-area")!;
+  area.innerHTML = `
+    <div style="
+      padding: 10px 14px;
+      font-size: 13px;
+      color: rgba(255,255,255,0.5);
+      border-top: 1px solid rgba(255,255,255,0.08);
+    ">${escapeHtml(message)}</div>
+  `;
+}
+
+export function showTextResult(text: string, isError: boolean): void {
+  const area = document.getElementById("result-area")!;
+  const color = isError ? "#fca5a5" : "rgba(255,255,255,0.85)";
+
+  // Extract URLs from the text for clickable links
+  const urls = extractUrls(text);
+
+  area.innerHTML = `
+    <div style="
+      padding: 12px 14px;
+      font-size: 13px;
+      color: ${color};
+      line-height: 1.5;
+      max-height: 340px;
+      overflow-y: auto;
+      border-top: 1px solid rgba(255,255,255,0.08);
+      word-wrap: break-word;
+    ">${renderLight(text)}</div>
+    <div style="
+      padding: 6px 14px 8px;
+      display: flex;
+      gap: 8px;
+      justify-content: flex-end;
+      border-top: 1px solid rgba(255,255,255,0.08);
+    ">
+      ${urls.length > 0 ? `<button id="btn-open-url" style="
+        background: #3b82f6;
+        border: 1px solid #3b82f6;
+        color: white;
+        padding: 4px 12px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 12px;
+      ">Open Link</button>` : ""}
+      <button id="btn-copy" style="
+        background: transparent;
+        border: 1px solid rgba(255,255,255,0.2);
+        color: rgba(255,255,255,0.7);
+        padding: 4px 12px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 12px;
+      ">Copy</button>
+      <button id="btn-close" style="
+        background: transparent;
+        border: 1px solid rgba(255,255,255,0.2);
+        color: rgba(255,255,255,0.7);
+        padding: 4px 12px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 12px;
+      ">Close</button>
+    </div>
+  `;
+
+  if (urls.length > 0) {
+    document.getElementById("btn-open-url")?.addEventListener("click", async () => {
+      try { await open(urls[0]); } catch { /* ignore */ }
+      setTimeout(() => closeLauncher(), 500);
+    });
+  }
+
+  document.getElementById("btn-copy")?.addEventListener("click", async () => {
+    await invoke("copy_to_clipboard", { text });
+    const btn = document.getElementById("btn-copy")!;
+    btn.textContent = "Copied";
+    setTimeout(() => closeLauncher(), 500);
+  });
+
+  document.getElementById("btn-close")?.addEventListener("click", () => closeLauncher());
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────
+
+export function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function extractUrls(text: string): string[] {
+  const urlPattern = /https?:\/\/[^\s<>"')\]]+/g;
+  return text.match(urlPattern) || [];
+}
+
+/** Minimal markdown: code blocks, inline code, bold, line breaks. */
+export function renderLight(text: string): string {
+  let html = escapeHtml(text);
+  html = html.replace(
+    /```(\w*)\n([\s\S]*?)```/g,
+    (_m, _lang, code) => `<pre style="
+      background:rgba(0,0,0,0.4);border:1px solid rgba(255,255,255,0.1);
+      border-radius:4px;padding:8px 10px;margin:6px 0;
+      font-family:'SF Mono',Menlo,monospace;font-size:12px;
+      line-height:1.4;overflow-x:auto;white-space:pre;color:#e2e8f0;
+    ">${code.trim()}</pre>`
+  );
+  html = html.replace(/`([^`]+)`/g,
+    `<code style="background:rgba(0,0,0,0.3);padding:1px 4px;border-radius:3px;font-family:'SF Mono',Menlo,monospace;font-size:12px;">$1</code>`
+  );
+  html = html.replace(/\*\*([^*]+)\*\*/g, "<strong>$1</strong>");
+  html = html.replace(/\n/g, "<br>");
+  html = html.replace(/<pre([^>]*)>([\s\S]*?)<\/pre>/g, (_m, a, c) =>
+    `<pre${a}>${c.replace(/<br>/g, "\n")}</pre>`
+  );
+  return html;
+}
+
+export async function closeLauncher(): Promise<void> {
+  try { await invoke("plugin:agent|cancel_agent"); } catch { /* nothing waiting */ }
+  await resetSize();
+  try { await invoke("close_text_launcher"); } catch { /* closing */ }
+}
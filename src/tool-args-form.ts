@@ -0,0 +1,202 @@
+/**
+ * Plugin argument review form — shared by the action menu and text launcher.
+ * The field helpers are also used by the plugin question window (elicitation.ts).
+ *
+ * Renders the arguments the args bridge generated as an editable form,
+ * driven by the tool's input schema:
+ *   - string → text input (textarea for long values), enum → select
+ *   - number / integer → number input, boolean → checkbox
+ *   - arrays, objects, and anything without a schema → JSON textarea
+ *
+ * Nothing is sent to the plugin until the user presses Run. The footer
+ * also sets the per-tool review preference (always / never confirm).
+ */
+
+import { escapeAttr, escapeHtml } from "./action-menu-render";
+
+export interface ToolReview {
+  toolId: string;
+  displayName: string;
+  description: string;
+  inputSchema: JsonSchema | null;
+  pref: "always" | "never" | null;
+}
+
+export interface JsonSchema {
+  type?: string | string[];
+  properties?: Record<string, JsonSchema>;
+  required?: string[];
+  enum?: unknown[];
+  description?: string;
+  default?: unknown;
+}
+
+export interface ReviewDecision {
+  arguments: Record<string, unknown>;
+  remember: "always" | "never" | null;
+}
+
+/** Submit handler: returns an error message to show, or null when done. */
+export type SubmitHandler = (decision: ReviewDecision) => Promise<string | null>;
+
+export const inputStyle = `
+  width: 100%;
+  box-sizing: border-box;
+  background: rgba(0,0,0,0.3);
+  border: 1px solid rgba(255,255,255,0.15);
+  border-radius: 4px;
+  color: #e2e8f0;
+  font-size: 12px;
+  padding: 5px 8px;
+  font-family: inherit;
+`;
+
+export const buttonStyle = `
+  background: transparent;
+  border: 1px solid rgba(255,255,255,0.2);
+  color: rgba(255,255,255,0.8);
+  padding: 4px 12px;
+  border-radius: 4px;
+  cursor: pointer;
+  font-size: 12px;
+`;
+
+function fieldKind(schema: JsonSchema | undefined): string {
+  if (!schema) return "json";
+  if (schema.enum) return "enum";
+  const type = Array.isArray(schema.type) ? schema.type.find((t) => t !== "null") : schema.type;
+  if (type === "string" || type === "number" || type === "integer" || type === "boolean") return type;
+  return "json";
+}
+
+export function renderField(name: string, schema: JsonSchema | undefined, value: unknown, required: boolean): string {
+  const id = `arg-${escapeAttr(name)}`;
+  const label = `${escapeHtml(name)}${required ? ' <span style="color:#f87171">*</span>' : ""}`;
+  const hint = schema?.description
+    ? `<div style="font-size: 11px; color: rgba(255,255,255,0.4); margin-top: 2px;">${escapeHtml(schema.description)}</div>`
+    : "";
+  const kind = fieldKind(schema);
+  let control: string;
+
+  if (kind === "enum") {
+    const options = (schema!.enum || []).map((opt) => {
+      const selected = JSON.stringify(opt) === JSON.stringify(value) ? " selected" : "";
+      return `<option value="${escapeAttr(JSON.stringify(opt))}"${selected}>${escapeHtml(String(opt))}</option>`;
+    });
+    control = `<select id="${id}" style="${inputStyle}">${required ? "" : '<option value=""></option>'}${options.join("")}</select>`;
+  } else if (kind === "boolean") {
+    control = `<input id="${id}" type="checkbox"${value === true ? " checked" : ""} />`;
+  } else if (kind === "number" || kind === "integer") {
+    const step = kind === "integer" ? "1" : "any";
+    control = `<input id="${id}" type="number" step="${step}" value="${escapeAttr(String(value ?? ""))}" style="${inputStyle}" />`;
+  } else if (kind === "string") {
+    const text = typeof value === "string" ? value : "";
+    control = text.length > 60 || text.includes("\n")
+      ? `<textarea id="${id}" rows="4" style="${inputStyle} resize: vertical;">${escapeHtml(text)}</textarea>`
+      : `<input id="${id}" type="text" value="${escapeAttr(text)}" style="${inputStyle}" />`;
+  } else {
+    const json = value === undefined ? "" : JSON.stringify(value, null, 2);
+    control = `<textarea id="${id}" rows="3" style="${inputStyle} font-family: 'SF Mono', Menlo, monospace; resize: vertical;">${escapeHtml(json)}</textarea>`;
+  }
+
+  return `
+    <label style="display: block; margin-bottom: 8px; font-size: 12px; color: rgba(255,255,255,0.7);">
+      <div style="margin-bottom: 3px;">${label}</div>
+      ${control}
+      ${hint}
+    </label>`;
+}
+
+/** Read one field back; `undefined` means "leave out". Throws on bad JSON. */
+export function readField(name: string, schema: JsonSchema | undefined): unknown {
+  const el = document.getElementById(`arg-${name}`) as HTMLInputElement | null;
+  if (!el) return undefined;
+  const kind = fieldKind(schema);
+  if (kind === "boolean") return el.checked;
+  if (el.value === "") return undefined;
+  if (kind === "enum") return JSON.parse(el.value);
+  if (kind === "number" || kind === "integer") return Number(el.value);
+  if (kind === "string") return el.value;
+  try {
+    return JSON.parse(el.value);
+  } catch {
+    throw new Error(`${name}: not valid JSON`);
+  }
+}
+
+/**
+ * Render the review form into `container` and call `onSubmit` on Run.
+ *
+ * `onCancel` is called when the user dismisses the form.
+ */
+export function renderArgsForm(
+  container: HTMLElement,
+  review: ToolReview,
+  args: Record<string, unknown>,
+  onSubmit: SubmitHandler,
+  onCancel: () => void,
+): void {
+  const props = review.inputSchema?.properties || {};
+  const required = new Set(review.inputSchema?.required || []);
+  // Schema fields first, then anything extra the model produced.
+  const names = [...Object.keys(props), ...Object.keys(args).filter((k) => !(k in props))];
+
+  container.innerHTML = `
+    <div style="padding: 10px 14px 4px; font-size: 13px; color: rgba(255,255,255,0.9);">
+      <div style="font-weight: 600; margin-bottom: 2px;">Review: ${escapeHtml(review.displayName)}</div>
+      <div style="font-size: 11px; color: rgba(255,255,255,0.5); margin-bottom: 10px;">
+        Check the arguments before they are sent to the plugin.
+      </div>
+      <div style="max-height: 300px; overflow-y: auto;">
+        ${names.map((n) => renderField(n, props[n], args[n], required.has(n))).join("")}
+      </div>
+      <div id="args-error" style="display: none; color: #fca5a5; font-size: 12px; white-space: pre-wrap; margin: 6px 0;"></div>
+    </div>
+    <div style="
+      padding: 6px 14px 8px;
+      display: flex;
+      gap: 8px;
+      align-items: center;
+      border-top: 1px solid rgba(255,255,255,0.1);
+    ">
+      <select id="args-remember" style="${inputStyle} width: auto; flex: 1;">
+        <option value="">Ask for this tool as usual</option>
+        <option value="always"${review.pref === "always" ? " selected" : ""}>Always review this tool</option>
+        <option value="never"${review.pref === "never" ? " selected" : ""}>Never review this tool</option>
+      </select>
+      <button id="btn-args-cancel" style="${buttonStyle}">Cancel</button>
+      <button id="btn-args-run" style="${buttonStyle} background: #3b82f6; border-color: #3b82f6; color: white;">Run</button>
+    </div>
+  `;
+
+  const errorEl = document.getElementById("args-error")!;
+  const showError = (message: string) => {
+    errorEl.textContent = message;
+    errorEl.style.display = "block";
+  };
+
+  document.getElementById("btn-args-cancel")?.addEventListener("click", () => onCancel());
+  document.getElementById("btn-args-run")?.addEventListener("click", async () => {
+    const runButton = document.getElementById("btn-args-run") as HTMLButtonElement;
+    const values: Record<string, unknown> = {};
+    try {
+      for (const name of names) {
+        const value = readField(name, props[name]);
+        if (value !== undefined) values[name] = value;
+      }
+    } catch (err) {
+      showError(String((err as Error).message || err));
+      return;
+    }
+    const remember = (document.getElementById("args-remember") as HTMLSelectElement).value;
+    runButton.disabled = true;
+    const error = await onSubmit({
+      arguments: values,
+      remember: remember === "always" || remember === "never" ? remember : null,
+    });
+    if (error) {
+      showError(error);
+      runButton.disabled = false;
+    }
+  });
+}
@@ -0,0 +1,115 @@
+/**
+ * Window picker — lists open windows for "Snip Window...".
+ *
+ * Clicking a window captures it directly (so parts hidden behind other
+ * windows are included) and opens the snip overlay over it.
+ * Escape or Cancel dismisses the picker.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface CaptureSource {
+  kind: string;
+  id: number;
+  name: string;
+  width: number;
+  height: number;
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+// ── Render ───────────────────────────────────────────────────────────
+
+function render(windows: CaptureSource[], error?: string): void {
+  const container = document.getElementById("window-picker")!;
+  const rows = windows
+    .map(
+      (w) => `
+    <div class="row" data-window-id="${w.id}" style="
+      padding: 8px 14px;
+      color: #e2e8f0;
+      font-size: 13px;
+      cursor: pointer;
+      white-space: nowrap;
+      overflow: hidden;
+      text-overflow: ellipsis;
+    ">${escapeHtml(w.name)}</div>`
+    )
+    .join("");
+
+  container.innerHTML = `
+    <div style="
+      background: #1a1a2e;
+      border: 1px solid rgba(255,255,255,0.15);
+      border-radius: 8px;
+      box-shadow: 0 4px 16px rgba(0,0,0,0.4);
+      overflow: hidden;
+      user-select: none;
+    ">
+      <div style="
+        padding: 10px 14px;
+        font-size: 13px;
+        font-weight: 600;
+        color: rgba(255,255,255,0.9);
+        border-bottom: 1px solid rgba(255,255,255,0.08);
+      ">Snip Window</div>
+      <div style="max-height: 330px; overflow-y: auto;">
+        ${error ? `<div style="padding: 10px 14px; color: #f87171; font-size: 12px;">${escapeHtml(error)}</div>` : ""}
+        ${!error && windows.length === 0 ? `<div style="padding: 10px 14px; color: rgba(255,255,255,0.5); font-size: 12px;">No windows found</div>` : ""}
+        ${rows}
+      </div>
+      <div style="
+        padding: 6px 14px 8px;
+        display: flex;
+        justify-content: flex-end;
+        border-top: 1px solid rgba(255,255,255,0.08);
+      ">
+        <button id="btn-cancel" style="
+          background: transparent;
+          border: 1px solid rgba(255,255,255,0.2);
+          color: rgba(255,255,255,0.8);
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        ">Cancel</button>
+      </div>
+    </div>
+  `;
+
+  document.getElementById("btn-cancel")?.addEventListener("click", closePicker);
+}
+
+// ── Actions ──────────────────────────────────────────────────────────
+
+async function closePicker(): Promise<void> {
+  try { await invoke("close_window_picker"); } catch { /* closing */ }
+}
+
+document.getElementById("window-picker")!.addEventListener("click", async (e) => {
+  const row = (e.target as HTMLElement).closest(".row") as HTMLElement | null;
+  if (!row?.dataset.windowId) return;
+  const windowId = parseInt(row.dataset.windowId, 10);
+  try {
+    await invoke("start_window_snip", { windowId });
+  } catch (err) {
+    console.error("[CAPTURE] Window snip failed:", err);
+    render([], `Capture failed: ${err}`);
+  }
+});
+
+document.addEventListener("keydown", (e: KeyboardEvent) => {
+  if (e.key === "Escape") closePicker();
+});
+
+const style = document.createElement("style");
+style.textContent = `.row:hover { background: rgba(255,255,255,0.08); }`;
+document.head.appendChild(style);
+
+invoke<CaptureSource[]>("list_capture_windows")
+  .then((windows) => render(windows))
+  .catch((err) => render([], `Could not list windows: ${err}`));
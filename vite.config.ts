@@ -15,6 +15,9 @@ export default defineConfig({
         "permission-prompt": resolve(__dirname, "permission-prompt.html"),
         "text-launcher": resolve(__dirname, "text-launcher.html"),
         "tray-menu": resolve(__dirname, "tray-menu.html"),
+        "window-picker": resolve(__dirname, "window-picker.html"),
+        history: resolve(__dirname, "history.html"),
+        elicitation: resolve(__dirname, "elicitation.html"),
       },
     },
   },